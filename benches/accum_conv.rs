@@ -2,9 +2,9 @@ use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Through
 use ezkl::circuit::poly::PolyOp;
 use ezkl::circuit::*;
 use ezkl::pfsys::create_keys;
-use ezkl::pfsys::create_proof_circuit_kzg;
 use ezkl::pfsys::srs::gen_srs;
 use ezkl::pfsys::TranscriptType;
+use ezkl::pfsys::{create_proof_circuit_kzg, MultiOpenScheme};
 use ezkl::tensor::*;
 use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
 use halo2_proofs::poly::kzg::strategy::SingleStrategy;
@@ -143,6 +143,7 @@ fn runcnvrl(c: &mut Criterion) {
                         SingleStrategy::new(&params),
                         CheckMode::UNSAFE,
                         None,
+                        MultiOpenScheme::SHPLONK,
                     );
                     prover.unwrap();
                 });
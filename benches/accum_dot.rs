@@ -1,9 +1,9 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use ezkl::circuit::poly::PolyOp;
 use ezkl::circuit::*;
-use ezkl::pfsys::create_proof_circuit_kzg;
 use ezkl::pfsys::TranscriptType;
 use ezkl::pfsys::{create_keys, srs::gen_srs};
+use ezkl::pfsys::{create_proof_circuit_kzg, MultiOpenScheme};
 use ezkl::tensor::*;
 use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
 use halo2_proofs::poly::kzg::strategy::SingleStrategy;
@@ -110,6 +110,7 @@ fn rundot(c: &mut Criterion) {
                     SingleStrategy::new(&params),
                     CheckMode::UNSAFE,
                     None,
+                    MultiOpenScheme::SHPLONK,
                 );
                 prover.unwrap();
             });
@@ -3,9 +3,9 @@ use ezkl::circuit::*;
 
 use ezkl::circuit::lookup::LookupOp;
 use ezkl::circuit::poly::PolyOp;
-use ezkl::pfsys::create_proof_circuit_kzg;
 use ezkl::pfsys::TranscriptType;
 use ezkl::pfsys::{create_keys, srs::gen_srs};
+use ezkl::pfsys::{create_proof_circuit_kzg, MultiOpenScheme};
 use ezkl::tensor::*;
 use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
 use halo2_proofs::poly::kzg::strategy::SingleStrategy;
@@ -134,6 +134,7 @@ fn runmatmul(c: &mut Criterion) {
                     SingleStrategy::new(&params),
                     CheckMode::SAFE,
                     None,
+                    MultiOpenScheme::SHPLONK,
                 );
                 prover.unwrap();
             });
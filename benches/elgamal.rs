@@ -4,9 +4,9 @@ use ezkl::circuit::modules::elgamal::{ElGamalConfig, ElGamalGadget, ElGamalVaria
 use ezkl::circuit::modules::Module;
 use ezkl::circuit::*;
 use ezkl::pfsys::create_keys;
-use ezkl::pfsys::create_proof_circuit_kzg;
 use ezkl::pfsys::srs::gen_srs;
 use ezkl::pfsys::TranscriptType;
+use ezkl::pfsys::{create_proof_circuit_kzg, MultiOpenScheme};
 use ezkl::tensor::*;
 use halo2_proofs::circuit::Value;
 use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
@@ -45,9 +45,9 @@ impl Circuit<Fr> for EncryptytionCircuit {
     ) -> Result<(), Error> {
         let mut chip = ElGamalGadget::new(config);
         chip.load_variables(self.variables.clone());
-        let sk: Tensor<ValType<Fr>> =
-            Tensor::new(Some(&[Value::known(self.variables.sk).into()]), &[1]).unwrap();
-        chip.layout(&mut layouter, &[self.message.clone(), sk.into()], 0)?;
+        let r: Tensor<ValType<Fr>> =
+            Tensor::new(Some(&[Value::known(self.variables.r).into()]), &[1]).unwrap();
+        chip.layout(&mut layouter, &[self.message.clone(), r.into()], 0)?;
         Ok(())
     }
 }
@@ -103,6 +103,7 @@ fn runelgamal(c: &mut Criterion) {
                     SingleStrategy::new(&params),
                     CheckMode::UNSAFE,
                     None,
+                    MultiOpenScheme::SHPLONK,
                 );
                 prover.unwrap();
             });
@@ -12,8 +12,14 @@ import './LoadInstances.sol';
 // 3. Static Calls: Makes static calls to fetch data from other contracts. See the `staticCall` method.
 // 4. Field Element Conversion: The fixed-point representation is then converted into a field element modulo P using the `toFieldElement` method.
 // 5. Data Attestation: The `attestData` method validates that the public instances match the data fetched and processed by the contract.
+//    For hashed inputs (outputs) the data is either the precomputed hash, or if HASH_INPUTS (HASH_OUTPUTS) is set the data is hashed on-chain
+//    with Poseidon and compared against the hash in the public instances. The PoseidonHash library is appended to this contract when it is
+//    rendered, as its constants are generated from the Poseidon parameters used by EZKL.
 // 6. Proof Verification: The `verifyWithDataAttestation` method parses the instances out of the encoded calldata and calls the `attestData` method to validate the public instances,
 //  then calls the `verifyProof` method to verify the proof on the verifier.
+// 7. Block Commitment: If COMMITS_BLOCK is set the last three public instances hold the number and the (high and low 128 bits of the) hash of the block
+//  the proof's data was read at, which `verifyWithDataAttestation` checks with `blockhash`. As that is only available for the 256 most recent blocks,
+//  only fresh proofs verify. The data itself is still read at the current block, so the proof only verifies if it hasn't changed since.
 
 contract DataAttestation is LoadInstances {
     /**
@@ -44,6 +50,12 @@ contract DataAttestation is LoadInstances {
 
     uint256 constant OUTPUT_CALLS = 0;
 
+    bool constant HASH_INPUTS = false;
+
+    bool constant HASH_OUTPUTS = false;
+
+    bool constant COMMITS_BLOCK = false;
+
     uint8 public instanceOffset;
 
     /**
@@ -222,10 +234,7 @@ contract DataAttestation is LoadInstances {
      * @param instances - The public instances to the proof (the data in the proof that publicly accessible to the verifier).
      */
     function attestData(uint256[] memory instances) internal view {
-        require(
-            instances.length >= INPUT_CALLS + OUTPUT_CALLS,
-            "Invalid public inputs length"
-        );
+        uint256[] memory fieldElements = new uint256[](INPUT_CALLS + OUTPUT_CALLS);
         uint256 _accountCount = accountCalls.length;
         uint counter = 0;
         for (uint8 i = 0; i < _accountCount; ++i) {
@@ -241,23 +250,102 @@ contract DataAttestation is LoadInstances {
                     accountCalls[i].decimals[j],
                     scale
                 );
-                uint256 field_element = toFieldElement(quantized_data);
-                require(
-                    field_element == instances[counter + instanceOffset],
-                    "Public input does not match"
-                );
+                fieldElements[counter] = toFieldElement(quantized_data);
                 counter++;
             }
         }
+        uint256 instanceIndex = attestInstances(
+            instances,
+            fieldElements,
+            0,
+            INPUT_CALLS,
+            HASH_INPUTS,
+            instanceOffset
+        );
+        attestInstances(
+            instances,
+            fieldElements,
+            INPUT_CALLS,
+            OUTPUT_CALLS,
+            HASH_OUTPUTS,
+            instanceIndex
+        );
     }
 
+    /**
+     * @dev Check a range of the fetched data against the public instances, or its Poseidon hash against a single instance.
+     * @param instances - The public instances to the proof.
+     * @param fieldElements - The fetched data, as field elements.
+     * @param start - The index in `fieldElements` of the first element of the range.
+     * @param length - The number of elements in the range.
+     * @param hashed - Whether the public instances contain the hash of the range rather than the range itself.
+     * @param instanceIndex - The index in `instances` the range (or its hash) is checked against.
+     * @return The index in `instances` following the checked instances.
+     */
+    function attestInstances(
+        uint256[] memory instances,
+        uint256[] memory fieldElements,
+        uint256 start,
+        uint256 length,
+        bool hashed,
+        uint256 instanceIndex
+    ) internal pure returns (uint256) {
+        if (length == 0) {
+            return instanceIndex;
+        }
+        if (hashed) {
+            uint256[] memory message = new uint256[](length);
+            for (uint i = 0; i < length; i++) {
+                message[i] = fieldElements[start + i];
+            }
+            require(instances.length > instanceIndex, "Invalid public inputs length");
+            require(
+                PoseidonHash.hash(message) == instances[instanceIndex],
+                "Public input hash does not match"
+            );
+            return instanceIndex + 1;
+        }
+        require(
+            instances.length >= instanceIndex + length,
+            "Invalid public inputs length"
+        );
+        for (uint i = 0; i < length; i++) {
+            require(
+                fieldElements[start + i] == instances[instanceIndex + i],
+                "Public input does not match"
+            );
+        }
+        return instanceIndex + length;
+    }
+
+    /**
+     * @dev Check the block committed to in the last three public instances against the hash of that block.
+     * @param instances - The public instances to the proof.
+     */
+    function attestBlock(uint256[] memory instances) internal view {
+        uint256 len = instances.length;
+        require(len >= 3, "Invalid public inputs length");
+        uint256 hashHigh = instances[len - 2];
+        uint256 hashLow = instances[len - 1];
+        require(hashHigh < (1 << 128) && hashLow < (1 << 128), "Invalid block hash");
+        bytes32 blockHash = blockhash(instances[len - 3]);
+        require(blockHash != bytes32(0), "Block is too old or in the future");
+        require(
+            blockHash == bytes32((hashHigh << 128) | hashLow),
+            "Invalid block hash"
+        );
+    }
 
     function verifyWithDataAttestation(
         address verifier,
         bytes calldata encoded
     ) public view returns (bool) {
         require(verifier.code.length > 0,"Address: call to non-contract");
-        attestData(getInstancesCalldata(encoded));
+        uint256[] memory instances = getInstancesCalldata(encoded);
+        attestData(instances);
+        if (COMMITS_BLOCK) {
+            attestBlock(instances);
+        }
         // static call the verifier contract to verify the proof
         (bool success, bytes memory returndata) = verifier.staticcall(encoded);
 
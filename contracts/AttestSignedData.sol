@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+import './LoadInstances.sol';
+
+// This contract serves as a Data Attestation Verifier for the EZKL model, for data that is signed off-chain (e.g. by an oracle).
+// It is designed to check that instances of proofs generated from a specified circuit were signed by a configured signer.
+
+// Overview of the contract functionality:
+// 1. Initialization: Through the constructor, it sets up the signer and the ranges of the public instances that are signed.
+// 2. Signed Digest: Each range of instances is signed as an EIP-712 `SignedData(uint256 nonce,bytes32 dataHash)` struct, with dataHash = keccak256(abi.encodePacked(instances[offset:offset + length])).
+//    The domain is bound to the chain id and the verifier contract the proof is checked by, and the nonce must be the current nonce of this contract, so signatures can't be replayed elsewhere or after the nonce is bumped.
+//    The instances are the quantized data, as field elements, ie. exactly what `ezkl sign-data` signs.
+// 3. Data Attestation: The `attestSignedData` method recovers the signer of each range of the public instances and checks it against the configured signer.
+// 4. Proof Verification: The `verifyWithSignedDataAttestation` method parses the instances out of the encoded calldata and calls the `attestSignedData` method to validate the public instances,
+//  then calls the `verifyProof` method to verify the proof on the verifier.
+
+contract DataAttestationSigned is LoadInstances {
+    /**
+     * @notice The address that signs the attested data
+     */
+    address public signer;
+
+    address public admin;
+
+    /**
+     * @notice The offsets and lengths of the signed ranges of the public instances, one per signature
+     */
+    uint256[] public instanceOffsets;
+
+    uint256[] public instanceLengths;
+
+    /**
+     * @notice Signatures are only accepted over the current nonce, bumping it revokes all the earlier signatures
+     */
+    uint256 public nonce;
+
+    bytes32 constant EIP712_DOMAIN_TYPEHASH =
+        keccak256(
+            "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+        );
+
+    bytes32 constant SIGNED_DATA_TYPEHASH =
+        keccak256("SignedData(uint256 nonce,bytes32 dataHash)");
+
+    /**
+     * @dev Upper bound of the s value of a non-malleable signature (see EIP-2)
+     */
+    uint256 constant HALF_ORDER_SECP256K1 =
+        0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF5D576E7357A4501DDFE92F46681B20A0;
+
+    /**
+     * @dev Initialize the contract with the signer of the data and the signed ranges of the instances.
+     * @param _signer - The address that signs the data.
+     * @param _instanceOffsets - The offset in the public instances of each signed range.
+     * @param _instanceLengths - The number of public instances in each signed range.
+     */
+    constructor(
+        address _signer,
+        uint256[] memory _instanceOffsets,
+        uint256[] memory _instanceLengths,
+        address _admin
+    ) {
+        require(
+            _instanceOffsets.length == _instanceLengths.length,
+            "Invalid input length"
+        );
+        signer = _signer;
+        instanceOffsets = _instanceOffsets;
+        instanceLengths = _instanceLengths;
+        admin = _admin;
+    }
+
+    function updateAdmin(address _admin) external {
+        require(msg.sender == admin, "Only admin can update admin");
+        if (_admin == address(0)) {
+            revert();
+        }
+        admin = _admin;
+    }
+
+    function updateSigner(address _signer) external {
+        require(msg.sender == admin, "Only admin can update signer");
+        if (_signer == address(0)) {
+            revert();
+        }
+        signer = _signer;
+    }
+
+    function incrementNonce() external {
+        require(
+            msg.sender == admin || msg.sender == signer,
+            "Only admin or signer can increment nonce"
+        );
+        nonce += 1;
+    }
+
+    /**
+     * @dev The EIP-712 domain separator of data attested for `verifier` on this chain.
+     * @param verifier - The verifier contract the proof is checked by.
+     */
+    function domainSeparator(address verifier) public view returns (bytes32) {
+        return
+            keccak256(
+                abi.encode(
+                    EIP712_DOMAIN_TYPEHASH,
+                    keccak256(bytes("EZKL Data Attestation")),
+                    keccak256(bytes("1")),
+                    block.chainid,
+                    verifier
+                )
+            );
+    }
+
+    /**
+     * @dev Recover the address that signed the EIP-712 `digest`.
+     * @param digest - The signed digest.
+     * @param signature - The 65 byte (r, s, v) signature.
+     */
+    function recoverSigner(
+        bytes32 digest,
+        bytes calldata signature
+    ) internal pure returns (address) {
+        require(signature.length == 65, "Invalid signature length");
+        bytes32 r;
+        bytes32 s;
+        uint8 v;
+        assembly {
+            r := calldataload(signature.offset)
+            s := calldataload(add(signature.offset, 0x20))
+            v := byte(0, calldataload(add(signature.offset, 0x40)))
+        }
+        require(uint256(s) <= HALF_ORDER_SECP256K1, "Invalid signature s value");
+        address recovered = ecrecover(digest, v, r, s);
+        require(recovered != address(0), "Invalid signature");
+        return recovered;
+    }
+
+    /**
+     * @dev Check that each signed range of the public instances was signed by the signer.
+     * @param instances - The public instances to the proof (the data in the proof that publicly accessible to the verifier).
+     * @param signatures - One signature per signed range of the instances.
+     * @param verifier - The verifier contract the proof is checked by.
+     */
+    function attestSignedData(
+        uint256[] memory instances,
+        bytes[] calldata signatures,
+        address verifier
+    ) internal view {
+        require(
+            signatures.length == instanceOffsets.length,
+            "Invalid number of signatures"
+        );
+        bytes32 separator = domainSeparator(verifier);
+        for (uint i = 0; i < signatures.length; i++) {
+            uint256 offset = instanceOffsets[i];
+            uint256 length = instanceLengths[i];
+            require(
+                instances.length >= offset + length,
+                "Invalid public inputs length"
+            );
+            bytes memory packed = new bytes(length * 0x20);
+            for (uint j = 0; j < length; j++) {
+                uint256 instance = instances[offset + j];
+                assembly {
+                    mstore(add(add(packed, 0x20), mul(j, 0x20)), instance)
+                }
+            }
+            bytes32 digest = keccak256(
+                abi.encodePacked(
+                    "\x19\x01",
+                    separator,
+                    keccak256(
+                        abi.encode(SIGNED_DATA_TYPEHASH, nonce, keccak256(packed))
+                    )
+                )
+            );
+            require(
+                recoverSigner(digest, signatures[i]) == signer,
+                "Public input was not signed by the signer"
+            );
+        }
+    }
+
+    function verifyWithSignedDataAttestation(
+        address verifier,
+        bytes calldata encoded,
+        bytes[] calldata signatures
+    ) public view returns (bool) {
+        require(verifier.code.length > 0, "Address: call to non-contract");
+        attestSignedData(getInstancesCalldata(encoded), signatures, verifier);
+        // static call the verifier contract to verify the proof
+        (bool success, bytes memory returndata) = verifier.staticcall(encoded);
+
+        if (success) {
+            return abi.decode(returndata, (bool));
+        } else {
+            revert("low-level call to verifier failed");
+        }
+    }
+}
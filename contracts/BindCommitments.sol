@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+// This contract extends the Halo2Verifier of a circuit with KZG committed inputs, params or outputs (`kzgcommit` visibility),
+// to check that the committed values are equal to externally supplied commitments (e.g. a dataset commitment stored in a registry contract,
+// which can then be reused across proofs).
+// It is appended to a verifier rendered with `--bind-commitments`, which renames the rendered verifier contract to Halo2VerifierBase.
+
+// Overview of the contract functionality:
+// 1. Commitments: The KZG commitments to the committed values are the first witness commitments in the proof (see `swap-proof-commitments`),
+//    each written as the (x, y) coordinates of a G1 point.
+// 2. Binding: The `verifyProofWithCommitments` method checks that the proof starts with the supplied commitments,
+//    given as a flattened array of (x, y) coordinates, then verifies the proof.
+// 3. Registry: The `verifyProofWithRegisteredCommitments` method does the same with the commitments stored under `id` in a registry contract.
+// Proofs are still verified without any binding through `verifyProof`.
+
+interface ICommitmentRegistry {
+    function getCommitments(bytes32 id) external view returns (uint256[] memory);
+}
+
+contract Halo2Verifier is Halo2VerifierBase {
+    /**
+     * @dev Check that the proof starts with the commitments.
+     * @param proof - The proof passed to `verifyProof`.
+     * @param commitments - The flattened (x, y) coordinates of the commitments.
+     */
+    function checkCommitments(
+        bytes calldata proof,
+        uint256[] memory commitments
+    ) internal pure {
+        require(
+            commitments.length % 2 == 0,
+            "Commitments must be (x, y) coordinates"
+        );
+        require(
+            proof.length >= commitments.length * 0x20,
+            "Proof is shorter than the commitments"
+        );
+        for (uint i = 0; i < commitments.length; i++) {
+            uint256 word;
+            assembly {
+                word := calldataload(add(proof.offset, mul(i, 0x20)))
+            }
+            require(
+                word == commitments[i],
+                "Proof commitment does not match the supplied commitment"
+            );
+        }
+    }
+
+    function verifyProofWithCommitments(
+        bytes calldata proof,
+        uint256[] calldata instances,
+        uint256[] calldata commitments
+    ) public returns (bool) {
+        checkCommitments(proof, commitments);
+        // the verifier reads the proof from the calldata so it has to be called externally
+        return this.verifyProof(proof, instances);
+    }
+
+    function verifyProofWithRegisteredCommitments(
+        bytes calldata proof,
+        uint256[] calldata instances,
+        address registry,
+        bytes32 id
+    ) public returns (bool) {
+        checkCommitments(
+            proof,
+            ICommitmentRegistry(registry).getCommitments(id)
+        );
+        return this.verifyProof(proof, instances);
+    }
+}
@@ -0,0 +1,1148 @@
+/*
+EdDSA signature verification over BabyJubJub (the twisted Edwards curve embedded in the bn254 scalar field),
+in the form of a Halo2 Chip. The signed message is the Poseidon hash of the input (the same hash as the `hashed`
+visibility) and the challenge is the Poseidon hash of the nonce point, the public key and the message, so a data
+provider can sign a record once and the only public instance of the proof is their public key.
+
+This is an ezkl specific scheme rather than circomlib's: the hashes are halo2's Poseidon sponge (see
+[super::poseidon::PoseidonParams]) and the verification equation is `s * G = r + h * pk` without cofactor
+clearing, so signatures made by circomlib or zk-kit signers don't verify, sign with [EddsaSignature::sign].
+The public key must lie in the prime order subgroup (and not be the identity) and `s` must be below the
+subgroup order, which makes signatures non-malleable.
+*/
+
+use super::poseidon::spec::{PoseidonSpec, POSEIDON_RATE, POSEIDON_WIDTH};
+use super::poseidon::{PoseidonChip, PoseidonConfig};
+use super::Module;
+use crate::tensor::{Tensor, ValTensor, ValType};
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::{AssignedCell, Layouter, Value};
+use halo2_proofs::plonk::{
+    Advice, Column, ConstraintSystem, Constraints, Error, Expression, Fixed, Instance, Selector,
+};
+use halo2_proofs::poly::Rotation;
+use halo2curves::bn256::Fr as Fp;
+use halo2curves::ff::PrimeField;
+use num::BigUint;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// The number of instance columns used by the EdDSA module.
+pub const NUM_INSTANCE_COLUMNS: usize = 1;
+
+/// The number of elements hashed per block of the message hash.
+pub const EDDSA_MESSAGE_LEN: usize = 32;
+
+/// The number of bits scalars are decomposed into in the circuit.
+const SCALAR_BITS: usize = Fp::NUM_BITS as usize;
+
+/// The `a` coefficient of BabyJubJub, `a x^2 + y^2 = 1 + d x^2 y^2`
+const EDWARDS_A: u64 = 168700;
+/// The `d` coefficient of BabyJubJub
+const EDWARDS_D: u64 = 168696;
+/// The generator of the prime order subgroup (circomlib's `Base8`)
+const GENERATOR_X: &str =
+    "5299619240641551281634865583518297030282874472190772894086521144482721001553";
+const GENERATOR_Y: &str =
+    "16950150798460657717958625567821834550301663161624707787222815936182638968203";
+/// The order of the prime order subgroup
+const SUBGROUP_ORDER: &str =
+    "2736030358979909402780800718157159386076813972158567259200215660948447373041";
+
+// column indices of the curve gates
+const ACC_X: usize = 0;
+const ACC_Y: usize = 1;
+const BIT: usize = 2;
+const SUM: usize = 3;
+const DBL_X: usize = 4;
+const DBL_Y: usize = 5;
+const DBL_T: usize = 6;
+const ADD_X: usize = 7;
+const ADD_Y: usize = 8;
+const ADD_T: usize = 9;
+const BASE_X: usize = 10;
+const BASE_Y: usize = 11;
+const EQ: usize = 12;
+const LT: usize = 13;
+
+/// Hashes the message, this is the same (tree) hash as the `hashed` visibility
+type MessageHasher = PoseidonChip<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, EDDSA_MESSAGE_LEN>;
+/// Hashes the nonce point, the public key and the message into the challenge
+type ChallengeHasher = PoseidonChip<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 5>;
+
+/// A pair of assigned coordinates
+type AssignedPoint = (AssignedCell<Fp, Fp>, AssignedCell<Fp, Fp>);
+
+/// Hashes a message into the element that is signed.
+pub fn hash_message(message: Vec<Fp>) -> Result<Fp, Box<dyn std::error::Error>> {
+    Ok(MessageHasher::run(message)?[0][0])
+}
+
+/// The challenge a signature with nonce point `r` by `public_key` over the (hashed) `message` answers.
+fn challenge(
+    r: &EdwardsPoint,
+    public_key: &EdwardsPoint,
+    message: Fp,
+) -> Result<Fp, Box<dyn std::error::Error>> {
+    Ok(ChallengeHasher::run(vec![r.x, r.y, public_key.x, public_key.y, message])?[0][0])
+}
+
+/// The bits of a scalar, most significant first.
+fn to_bits_be(scalar: &Fp) -> Vec<bool> {
+    let repr = scalar.to_repr();
+    (0..SCALAR_BITS)
+        .rev()
+        .map(|i| (repr.as_ref()[i / 8] >> (i % 8)) & 1 == 1)
+        .collect()
+}
+
+/// The bits of an integer below 2^254, most significant first.
+fn biguint_bits_be(x: &BigUint) -> Vec<bool> {
+    (0..SCALAR_BITS).rev().map(|i| x.bit(i as u64)).collect()
+}
+
+/// The bits of the field modulus, most significant first.
+fn modulus_bits_be() -> Vec<bool> {
+    biguint_bits_be(&(to_biguint(&-Fp::ONE) + 1u32))
+}
+
+fn subgroup_order() -> BigUint {
+    BigUint::parse_bytes(SUBGROUP_ORDER.as_bytes(), 10).unwrap()
+}
+
+fn to_biguint(x: &Fp) -> BigUint {
+    BigUint::from_bytes_le(x.to_repr().as_ref())
+}
+
+/// Converts an integer smaller than the field modulus (eg. reduced modulo the subgroup order).
+fn from_biguint(x: &BigUint) -> Fp {
+    let mut repr = [0u8; 32];
+    let bytes = x.to_bytes_le();
+    repr[..bytes.len()].copy_from_slice(&bytes);
+    Fp::from_repr(repr).unwrap()
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+/// An affine point of BabyJubJub.
+pub struct EdwardsPoint {
+    ///
+    pub x: Fp,
+    ///
+    pub y: Fp,
+}
+
+impl Default for EdwardsPoint {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl std::ops::Add for EdwardsPoint {
+    type Output = Self;
+
+    /// The (complete) twisted Edwards addition law.
+    fn add(self, other: Self) -> Self {
+        let (a, d) = (Fp::from(EDWARDS_A), Fp::from(EDWARDS_D));
+        let t = d * self.x * other.x * self.y * other.y;
+        // the denominators never vanish for points on the curve
+        let x = (self.x * other.y + self.y * other.x) * (Fp::ONE + t).invert().unwrap_or(Fp::ZERO);
+        let y =
+            (self.y * other.y - a * self.x * other.x) * (Fp::ONE - t).invert().unwrap_or(Fp::ZERO);
+        Self { x, y }
+    }
+}
+
+impl EdwardsPoint {
+    /// The identity (neutral) point.
+    pub fn identity() -> Self {
+        Self {
+            x: Fp::ZERO,
+            y: Fp::ONE,
+        }
+    }
+
+    /// The generator of the prime order subgroup.
+    pub fn generator() -> Self {
+        Self {
+            x: Fp::from_str_vartime(GENERATOR_X).unwrap(),
+            y: Fp::from_str_vartime(GENERATOR_Y).unwrap(),
+        }
+    }
+
+    /// Whether the point satisfies the curve equation.
+    pub fn is_on_curve(&self) -> bool {
+        let (x2, y2) = (self.x.square(), self.y.square());
+        Fp::from(EDWARDS_A) * x2 + y2 == Fp::ONE + Fp::from(EDWARDS_D) * x2 * y2
+    }
+
+    /// Whether the point is in the prime order subgroup, other than the identity.
+    pub fn is_in_subgroup(&self) -> bool {
+        self.is_on_curve()
+            && *self != Self::identity()
+            && self.scalar_mul(&from_biguint(&subgroup_order())) == Self::identity()
+    }
+
+    /// Double-and-add over the bits of the scalar, as in the circuit.
+    pub fn scalar_mul(&self, scalar: &Fp) -> Self {
+        to_bits_be(scalar)
+            .into_iter()
+            .fold(Self::identity(), |acc, bit| {
+                let acc = acc + acc;
+                if bit {
+                    acc + *self
+                } else {
+                    acc
+                }
+            })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// An EdDSA signature over a message, along with the public key it verifies against.
+/// The default is a signature by the identity key (the public key of the zero secret key), which
+/// the circuit rejects, used to lay out the circuit without a witness.
+pub struct EddsaSignature {
+    /// The signer's public key.
+    pub public_key: EdwardsPoint,
+    /// The nonce point.
+    pub r: EdwardsPoint,
+    /// The scalar, reduced modulo the subgroup order.
+    pub s: Fp,
+}
+
+impl Default for EddsaSignature {
+    fn default() -> Self {
+        Self {
+            public_key: EdwardsPoint::identity(),
+            r: EdwardsPoint::identity(),
+            s: Fp::ZERO,
+        }
+    }
+}
+
+impl EddsaSignature {
+    /// The public key of a secret key.
+    pub fn public_key(secret_key: &Fp) -> EdwardsPoint {
+        EdwardsPoint::generator().scalar_mul(secret_key)
+    }
+
+    /// Generate a random secret key.
+    pub fn gen_secret_key<R: CryptoRng + RngCore>(rng: &mut R) -> Fp {
+        from_biguint(&(to_biguint(&Fp::random(rng)) % subgroup_order()))
+    }
+
+    /// Sign a message with a secret key.
+    pub fn sign<R: CryptoRng + RngCore>(
+        secret_key: &Fp,
+        message: Vec<Fp>,
+        rng: &mut R,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let nonce = Self::gen_secret_key(rng);
+        Self::sign_with_nonce(secret_key, message, &nonce)
+    }
+
+    /// Sign a message with a secret key and a given nonce (which must be random and never reused).
+    fn sign_with_nonce(
+        secret_key: &Fp,
+        message: Vec<Fp>,
+        nonce: &Fp,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let order = subgroup_order();
+        let public_key = Self::public_key(secret_key);
+
+        let r = EdwardsPoint::generator().scalar_mul(nonce);
+        let h = challenge(&r, &public_key, hash_message(message)?)?;
+        // s = nonce + h * sk (mod order)
+        let s = (to_biguint(nonce) + to_biguint(&h) * to_biguint(secret_key)) % &order;
+
+        Ok(Self {
+            public_key,
+            r,
+            s: from_biguint(&s),
+        })
+    }
+
+    /// Whether the signature verifies over a message, ie. `s * G = r + h * pk`, for a public key in
+    /// the prime order subgroup and `s` below the subgroup order.
+    pub fn verify(&self, message: Vec<Fp>) -> Result<bool, Box<dyn std::error::Error>> {
+        if !self.public_key.is_in_subgroup()
+            || !self.r.is_on_curve()
+            || to_biguint(&self.s) >= subgroup_order()
+        {
+            return Ok(false);
+        }
+        let h = challenge(&self.r, &self.public_key, hash_message(message)?)?;
+        Ok(
+            EdwardsPoint::generator().scalar_mul(&self.s)
+                == self.r + self.public_key.scalar_mul(&h),
+        )
+    }
+}
+
+/// Constraints for `(x3, y3) = (x1, y1) + (x2, y2)`, given `t = x1 x2 y1 y2`.
+fn edwards_add(
+    (x1, y1): (Expression<Fp>, Expression<Fp>),
+    (x2, y2): (Expression<Fp>, Expression<Fp>),
+    t: Expression<Fp>,
+    (x3, y3): (Expression<Fp>, Expression<Fp>),
+) -> [Expression<Fp>; 3] {
+    let one = Expression::Constant(Fp::ONE);
+    let a = Expression::Constant(Fp::from(EDWARDS_A));
+    let d = Expression::Constant(Fp::from(EDWARDS_D));
+    [
+        t.clone() - x1.clone() * x2.clone() * y1.clone() * y2.clone(),
+        x3 * (one.clone() + d.clone() * t.clone())
+            - (x1.clone() * y2.clone() + y1.clone() * x2.clone()),
+        y3 * (one - d * t) - (y1 * y2 - a * x1 * x2),
+    ]
+}
+
+#[derive(Debug, Clone)]
+/// Configuration for the EdDSA chip.
+pub struct EddsaConfig {
+    poseidon: PoseidonConfig<POSEIDON_WIDTH, POSEIDON_RATE>,
+    /// The columns of the curve gates: the accumulated point, the scalar bit and its running sum,
+    /// the doubled point, the addend, the base point and the running comparison of the bits with
+    /// the bound (whether they are equal, or less, so far).
+    cols: [Column<Advice>; 14],
+    /// The bits of the bound (the modulus, or the subgroup order), alongside the bits of the scalars.
+    bound_bits: Column<Fixed>,
+    q_mul: Selector,
+    q_add: Selector,
+    q_on_curve: Selector,
+    q_not_identity: Selector,
+    /// The column used for the instance.
+    pub instance: Option<Column<Instance>>,
+}
+
+#[derive(Debug, Clone)]
+/// A chip verifying an EdDSA signature over a message.
+pub struct EddsaChip {
+    config: EddsaConfig,
+    /// The signature to verify.
+    signature: EddsaSignature,
+}
+
+impl EddsaChip {
+    /// Configure the chip with an optional (shared) instance column.
+    pub fn configure_with_optional_instance(
+        meta: &mut ConstraintSystem<Fp>,
+        instance: Option<Column<Instance>>,
+    ) -> EddsaConfig {
+        let poseidon = MessageHasher::configure_with_optional_instance(meta, None);
+
+        let cols = [(); 14].map(|_| meta.advice_column());
+        for col in cols.iter() {
+            meta.enable_equality(*col);
+        }
+        let bound_bits = meta.fixed_column();
+
+        let q_mul = meta.selector();
+        meta.create_gate(
+            "EdDSA scalar mul: double the accumulator and add the base if the bit is set",
+            |meta| {
+                let q_mul = meta.query_selector(q_mul);
+                let [acc_x, acc_y, bit, sum, dbl_x, dbl_y, dbl_t, add_x, add_y, add_t, base_x, base_y] =
+                    [
+                        ACC_X, ACC_Y, BIT, SUM, DBL_X, DBL_Y, DBL_T, ADD_X, ADD_Y, ADD_T, BASE_X,
+                        BASE_Y,
+                    ]
+                    .map(|i| meta.query_advice(cols[i], Rotation::cur()));
+                let [acc_x_next, acc_y_next, sum_next, base_x_next, base_y_next] =
+                    [ACC_X, ACC_Y, SUM, BASE_X, BASE_Y]
+                        .map(|i| meta.query_advice(cols[i], Rotation::next()));
+                let one = Expression::Constant(Fp::ONE);
+                let two = Expression::Constant(Fp::from(2));
+
+                let [dbl_t_check, dbl_x_check, dbl_y_check] = edwards_add(
+                    (acc_x.clone(), acc_y.clone()),
+                    (acc_x, acc_y),
+                    dbl_t,
+                    (dbl_x.clone(), dbl_y.clone()),
+                );
+                let [add_t_check, add_x_check, add_y_check] = edwards_add(
+                    (dbl_x, dbl_y),
+                    (add_x.clone(), add_y.clone()),
+                    add_t,
+                    (acc_x_next, acc_y_next),
+                );
+
+                Constraints::with_selector(
+                    q_mul,
+                    vec![
+                        ("bit is boolean", bit.clone() * (one.clone() - bit.clone())),
+                        ("sum = 2 * sum + bit", sum_next - two * sum - bit.clone()),
+                        ("double t", dbl_t_check),
+                        ("double x", dbl_x_check),
+                        ("double y", dbl_y_check),
+                        ("addend x = bit * base x", add_x - bit.clone() * base_x.clone()),
+                        (
+                            "addend y = 1 + bit * (base y - 1)",
+                            add_y - one.clone() - bit * (base_y.clone() - one),
+                        ),
+                        ("add t", add_t_check),
+                        ("add x", add_x_check),
+                        ("add y", add_y_check),
+                        ("base x is constant", base_x_next - base_x),
+                        ("base y is constant", base_y_next - base_y),
+                    ],
+                )
+            },
+        );
+
+        // the bits of a scalar are 254 bits wide, which leaves room for a second decomposition of
+        // a field element x (that of x + p, if it is below 2^254). Comparing the bits with those of
+        // the modulus, most significant first, pins them to the canonical decomposition (x < p).
+        // Comparing them with the subgroup order instead range checks the scalar (x < l).
+        meta.create_gate(
+            "EdDSA scalar mul: the bits are those of an integer below the bound",
+            |meta| {
+                let q_mul = meta.query_selector(q_mul);
+                let [bit, eq, lt] =
+                    [BIT, EQ, LT].map(|i| meta.query_advice(cols[i], Rotation::cur()));
+                let [eq_next, lt_next] =
+                    [EQ, LT].map(|i| meta.query_advice(cols[i], Rotation::next()));
+                let bound_bit = meta.query_fixed(bound_bits, Rotation::cur());
+                let one = Expression::Constant(Fp::ONE);
+                let two = Expression::Constant(Fp::from(2));
+
+                // the bits differ iff bit xor bound bit is set
+                let differ =
+                    bit.clone() + bound_bit.clone() - two * bit.clone() * bound_bit.clone();
+
+                Constraints::with_selector(
+                    q_mul,
+                    [
+                        (
+                            "equal so far if equal before and the bits match",
+                            eq_next - eq.clone() * (one.clone() - differ),
+                        ),
+                        (
+                            "less so far if less before, or equal before and this bit is below",
+                            lt_next - lt - eq * (one - bit) * bound_bit,
+                        ),
+                    ],
+                )
+            },
+        );
+
+        let q_add = meta.selector();
+        meta.create_gate("EdDSA add: acc_next = acc + addend", |meta| {
+            let q_add = meta.query_selector(q_add);
+            let [acc_x, acc_y, add_x, add_y, add_t] = [ACC_X, ACC_Y, ADD_X, ADD_Y, ADD_T]
+                .map(|i| meta.query_advice(cols[i], Rotation::cur()));
+            let [acc_x_next, acc_y_next] =
+                [ACC_X, ACC_Y].map(|i| meta.query_advice(cols[i], Rotation::next()));
+
+            Constraints::with_selector(
+                q_add,
+                edwards_add(
+                    (acc_x, acc_y),
+                    (add_x, add_y),
+                    add_t,
+                    (acc_x_next, acc_y_next),
+                ),
+            )
+        });
+
+        let q_on_curve = meta.selector();
+        meta.create_gate("EdDSA point is on the curve", |meta| {
+            let q_on_curve = meta.query_selector(q_on_curve);
+            let x = meta.query_advice(cols[ACC_X], Rotation::cur());
+            let y = meta.query_advice(cols[ACC_Y], Rotation::cur());
+            let (x2, y2) = (x.clone() * x, y.clone() * y);
+            let one = Expression::Constant(Fp::ONE);
+            let a = Expression::Constant(Fp::from(EDWARDS_A));
+            let d = Expression::Constant(Fp::from(EDWARDS_D));
+
+            Constraints::with_selector(
+                q_on_curve,
+                [a * x2.clone() + y2.clone() - one - d * x2 * y2],
+            )
+        });
+
+        // in the prime order subgroup only the identity has x = 0
+        let q_not_identity = meta.selector();
+        meta.create_gate("EdDSA point is not the identity", |meta| {
+            let q_not_identity = meta.query_selector(q_not_identity);
+            let x = meta.query_advice(cols[ACC_X], Rotation::cur());
+            let x_inv = meta.query_advice(cols[ADD_X], Rotation::cur());
+
+            Constraints::with_selector(q_not_identity, [x * x_inv - Expression::Constant(Fp::ONE)])
+        });
+
+        EddsaConfig {
+            poseidon,
+            cols,
+            bound_bits,
+            q_mul,
+            q_add,
+            q_on_curve,
+            q_not_identity,
+            instance,
+        }
+    }
+
+    /// Load the signature to verify.
+    pub fn load_signature(&mut self, signature: EddsaSignature) {
+        self.signature = signature;
+    }
+
+    fn hash_cells<const L: usize>(
+        hasher: &PoseidonChip<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, L>,
+        layouter: &mut impl Layouter<Fp>,
+        cells: &[AssignedCell<Fp, Fp>],
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        let message = Tensor::from(cells.iter().map(|c| ValType::from(c.clone())));
+        let hash = hasher.layout(layouter, &[message.into()], 0)?;
+
+        match &hash.get_inner_tensor().map_err(|_| Error::Synthesis)?[0] {
+            ValType::PrevAssigned(v) => Ok(v.clone()),
+            _ => {
+                log::error!("poseidon hash should be an assigned value");
+                Err(Error::Synthesis)
+            }
+        }
+    }
+
+    fn point_value((x, y): &AssignedPoint) -> Value<EdwardsPoint> {
+        x.value()
+            .zip(y.value())
+            .map(|(x, y)| EdwardsPoint { x: *x, y: *y })
+    }
+
+    /// Multiplies the base (the generator if `None`) by the scalar with the given bits (most
+    /// significant first), returning the product and the running sum of the bits (which is the
+    /// scalar itself, the bits are constrained to be those of an integer below `bound`, at most
+    /// the modulus).
+    fn layout_scalar_mul(
+        &self,
+        layouter: &mut impl Layouter<Fp>,
+        base: Option<&AssignedPoint>,
+        bits: Value<Vec<bool>>,
+        bound: &[bool],
+    ) -> Result<(AssignedPoint, AssignedCell<Fp, Fp>), Error> {
+        let cols = &self.config.cols;
+        layouter.assign_region(
+            || "eddsa scalar mul",
+            |mut region| {
+                let base_value = match base {
+                    Some((x, y)) => {
+                        x.copy_advice(|| "base x", &mut region, cols[BASE_X], 0)?;
+                        y.copy_advice(|| "base y", &mut region, cols[BASE_Y], 0)?;
+                        Self::point_value(&(x.clone(), y.clone()))
+                    }
+                    None => {
+                        let generator = EdwardsPoint::generator();
+                        region.assign_advice_from_constant(
+                            || "base x",
+                            cols[BASE_X],
+                            0,
+                            generator.x,
+                        )?;
+                        region.assign_advice_from_constant(
+                            || "base y",
+                            cols[BASE_Y],
+                            0,
+                            generator.y,
+                        )?;
+                        Value::known(generator)
+                    }
+                };
+
+                // start from the identity and a sum of 0
+                let mut acc_x =
+                    region.assign_advice_from_constant(|| "acc x", cols[ACC_X], 0, Fp::ZERO)?;
+                let mut acc_y =
+                    region.assign_advice_from_constant(|| "acc y", cols[ACC_Y], 0, Fp::ONE)?;
+                let mut sum =
+                    region.assign_advice_from_constant(|| "sum", cols[SUM], 0, Fp::ZERO)?;
+                // the bits start out equal to those of the bound, and not less
+                region.assign_advice_from_constant(|| "eq", cols[EQ], 0, Fp::ONE)?;
+                region.assign_advice_from_constant(|| "lt", cols[LT], 0, Fp::ZERO)?;
+
+                let mut acc = Value::known(EdwardsPoint::identity());
+                let mut sum_value = Value::known(Fp::ZERO);
+                let mut eq = Value::known(true);
+                let mut lt = Value::known(false);
+
+                for (i, bound_bit) in bound.iter().enumerate() {
+                    self.config.q_mul.enable(&mut region, i)?;
+                    region.assign_fixed(
+                        || "bound bit",
+                        self.config.bound_bits,
+                        i,
+                        || Value::known(Fp::from(*bound_bit as u64)),
+                    )?;
+
+                    let bit = bits.as_ref().map(|b| b[i]);
+                    let dbl = acc.map(|p| p + p);
+                    let addend =
+                        bit.zip(base_value).map(
+                            |(bit, base)| {
+                                if bit {
+                                    base
+                                } else {
+                                    EdwardsPoint::identity()
+                                }
+                            },
+                        );
+
+                    let witnesses = [
+                        (BIT, bit.map(|b| Fp::from(b as u64))),
+                        (DBL_X, dbl.map(|p| p.x)),
+                        (DBL_Y, dbl.map(|p| p.y)),
+                        (DBL_T, acc.map(|p| (p.x * p.y).square())),
+                        (ADD_X, addend.map(|p| p.x)),
+                        (ADD_Y, addend.map(|p| p.y)),
+                        (ADD_T, dbl.zip(addend).map(|(p, q)| p.x * q.x * p.y * q.y)),
+                    ];
+                    for (col, value) in witnesses {
+                        region.assign_advice(|| "scalar mul", cols[col], i, || value)?;
+                    }
+
+                    acc = dbl.zip(addend).map(|(p, q)| p + q);
+                    sum_value = sum_value
+                        .zip(bit)
+                        .map(|(s, b)| s.double() + Fp::from(b as u64));
+
+                    acc_x = region.assign_advice(
+                        || "acc x",
+                        cols[ACC_X],
+                        i + 1,
+                        || acc.map(|p| p.x),
+                    )?;
+                    acc_y = region.assign_advice(
+                        || "acc y",
+                        cols[ACC_Y],
+                        i + 1,
+                        || acc.map(|p| p.y),
+                    )?;
+                    sum = region.assign_advice(|| "sum", cols[SUM], i + 1, || sum_value)?;
+
+                    lt = lt
+                        .zip(eq)
+                        .zip(bit)
+                        .map(|((lt, eq), bit)| lt || (eq && !bit && *bound_bit));
+                    eq = eq.zip(bit).map(|(eq, bit)| eq && bit == *bound_bit);
+                    region.assign_advice(
+                        || "eq",
+                        cols[EQ],
+                        i + 1,
+                        || eq.map(|eq| Fp::from(eq as u64)),
+                    )?;
+                    let lt_cell = region.assign_advice(
+                        || "lt",
+                        cols[LT],
+                        i + 1,
+                        || lt.map(|lt| Fp::from(lt as u64)),
+                    )?;
+                    if i + 1 == SCALAR_BITS {
+                        // all the bits are in, so they must be below the bound
+                        region.constrain_constant(lt_cell.cell(), Fp::ONE)?;
+                    }
+                    region.assign_advice(
+                        || "base x",
+                        cols[BASE_X],
+                        i + 1,
+                        || base_value.map(|p| p.x),
+                    )?;
+                    region.assign_advice(
+                        || "base y",
+                        cols[BASE_Y],
+                        i + 1,
+                        || base_value.map(|p| p.y),
+                    )?;
+                }
+
+                Ok(((acc_x, acc_y), sum))
+            },
+        )
+    }
+
+    /// Constrains `r + h_pk = s_g`.
+    fn layout_check(
+        &self,
+        layouter: &mut impl Layouter<Fp>,
+        r: &AssignedPoint,
+        h_pk: &AssignedPoint,
+        s_g: &AssignedPoint,
+    ) -> Result<(), Error> {
+        let cols = &self.config.cols;
+        layouter.assign_region(
+            || "eddsa check",
+            |mut region| {
+                self.config.q_add.enable(&mut region, 0)?;
+
+                r.0.copy_advice(|| "r x", &mut region, cols[ACC_X], 0)?;
+                r.1.copy_advice(|| "r y", &mut region, cols[ACC_Y], 0)?;
+                h_pk.0
+                    .copy_advice(|| "h pk x", &mut region, cols[ADD_X], 0)?;
+                h_pk.1
+                    .copy_advice(|| "h pk y", &mut region, cols[ADD_Y], 0)?;
+
+                let points = Self::point_value(r).zip(Self::point_value(h_pk));
+                region.assign_advice(
+                    || "t",
+                    cols[ADD_T],
+                    0,
+                    || points.map(|(p, q)| p.x * q.x * p.y * q.y),
+                )?;
+
+                let sum = points.map(|(p, q)| p + q);
+                let x = region.assign_advice(|| "sum x", cols[ACC_X], 1, || sum.map(|p| p.x))?;
+                let y = region.assign_advice(|| "sum y", cols[ACC_Y], 1, || sum.map(|p| p.y))?;
+
+                region.constrain_equal(x.cell(), s_g.0.cell())?;
+                region.constrain_equal(y.cell(), s_g.1.cell())
+            },
+        )
+    }
+}
+
+impl Module<Fp> for EddsaChip {
+    type Config = EddsaConfig;
+    type InputAssignments = Vec<AssignedCell<Fp, Fp>>;
+    type RunInputs = (Vec<Fp>, EddsaSignature);
+    type Params = ();
+
+    fn name(&self) -> &'static str {
+        "EdDSA"
+    }
+
+    fn instance_increment_input(&self) -> Vec<usize> {
+        // the x and y coordinates of the public key
+        vec![2]
+    }
+
+    fn new(config: Self::Config) -> Self {
+        Self {
+            config,
+            signature: EddsaSignature::default(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>, _: Self::Params) -> Self::Config {
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        Self::configure_with_optional_instance(meta, Some(instance))
+    }
+
+    fn run(input: Self::RunInputs) -> Result<Vec<Vec<Fp>>, Box<dyn std::error::Error>> {
+        let (message, signature) = input;
+        if !signature.verify(message)? {
+            return Err("signature does not verify against its public key".into());
+        }
+        Ok(vec![vec![signature.public_key.x, signature.public_key.y]])
+    }
+
+    fn layout_inputs(
+        &self,
+        layouter: &mut impl Layouter<Fp>,
+        input: &[ValTensor<Fp>],
+    ) -> Result<Self::InputAssignments, Error> {
+        let hasher = MessageHasher::new(self.config.poseidon.clone());
+        let (message, _) = hasher.layout_inputs(layouter, input)?;
+        Ok(message)
+    }
+
+    fn layout(
+        &self,
+        layouter: &mut impl Layouter<Fp>,
+        input: &[ValTensor<Fp>],
+        row_offset: usize,
+    ) -> Result<ValTensor<Fp>, Error> {
+        let cols = &self.config.cols;
+        let signature = &self.signature;
+
+        let message = self.layout_inputs(layouter, input)?;
+        let message_hasher = MessageHasher::new(self.config.poseidon.clone());
+        let hashed_message = Self::hash_cells(&message_hasher, layouter, &message)?;
+
+        let (public_key, r) = layouter.assign_region(
+            || "eddsa points",
+            |mut region| {
+                // both points are checked to be on the curve, which makes the addition law complete
+                self.config.q_on_curve.enable(&mut region, 0)?;
+                self.config.q_on_curve.enable(&mut region, 1)?;
+                self.config.q_not_identity.enable(&mut region, 0)?;
+
+                let public_key = match self.config.instance {
+                    Some(instance) => (
+                        region.assign_advice_from_instance(
+                            || "public key x",
+                            instance,
+                            row_offset,
+                            cols[ACC_X],
+                            0,
+                        )?,
+                        region.assign_advice_from_instance(
+                            || "public key y",
+                            instance,
+                            row_offset + 1,
+                            cols[ACC_Y],
+                            0,
+                        )?,
+                    ),
+                    None => (
+                        region.assign_advice(
+                            || "public key x",
+                            cols[ACC_X],
+                            0,
+                            || Value::known(signature.public_key.x),
+                        )?,
+                        region.assign_advice(
+                            || "public key y",
+                            cols[ACC_Y],
+                            0,
+                            || Value::known(signature.public_key.y),
+                        )?,
+                    ),
+                };
+                region.assign_advice(
+                    || "public key x inverse",
+                    cols[ADD_X],
+                    0,
+                    || public_key.0.value().map(|x| x.invert().unwrap_or(Fp::ZERO)),
+                )?;
+                let r = (
+                    region.assign_advice(
+                        || "r x",
+                        cols[ACC_X],
+                        1,
+                        || Value::known(signature.r.x),
+                    )?,
+                    region.assign_advice(
+                        || "r y",
+                        cols[ACC_Y],
+                        1,
+                        || Value::known(signature.r.y),
+                    )?,
+                );
+
+                Ok((public_key, r))
+            },
+        )?;
+
+        let challenge_hasher = ChallengeHasher::new(self.config.poseidon.clone());
+        let h = Self::hash_cells(
+            &challenge_hasher,
+            layouter,
+            &[
+                r.0.clone(),
+                r.1.clone(),
+                public_key.0.clone(),
+                public_key.1.clone(),
+                hashed_message,
+            ],
+        )?;
+
+        // the public key is in the prime order subgroup, ie. l * pk is the identity (the bits of l
+        // are pinned by their sum, as they are canonical)
+        let order = subgroup_order();
+        let (l_pk, l_bits) = self.layout_scalar_mul(
+            layouter,
+            Some(&public_key),
+            Value::known(biguint_bits_be(&order)),
+            &modulus_bits_be(),
+        )?;
+        layouter.assign_region(
+            || "eddsa public key subgroup",
+            |mut region| {
+                region.constrain_constant(l_bits.cell(), from_biguint(&order))?;
+                region.constrain_constant(l_pk.0.cell(), Fp::ZERO)?;
+                region.constrain_constant(l_pk.1.cell(), Fp::ONE)
+            },
+        )?;
+
+        // s is below the subgroup order, so that signatures can't be malleated
+        let (s_g, _) = self.layout_scalar_mul(
+            layouter,
+            None,
+            Value::known(to_bits_be(&signature.s)),
+            &biguint_bits_be(&order),
+        )?;
+        let (h_pk, h_bits) = self.layout_scalar_mul(
+            layouter,
+            Some(&public_key),
+            h.value().map(to_bits_be),
+            &modulus_bits_be(),
+        )?;
+        // the bits h_pk is computed with are those of the challenge, as they are canonical their
+        // sum only matches h if they are its bits
+        layouter.assign_region(
+            || "eddsa challenge bits",
+            |mut region| region.constrain_equal(h_bits.cell(), h.cell()),
+        )?;
+
+        self.layout_check(layouter, &r, &h_pk, &s_g)?;
+
+        let mut assigned_input: Tensor<ValType<Fp>> =
+            message.iter().map(|e| ValType::from(e.clone())).into();
+        assigned_input.reshape(input[0].dims()).map_err(|e| {
+            log::error!("reshape failed: {:?}", e);
+            Error::Synthesis
+        })?;
+
+        Ok(assigned_input.into())
+    }
+
+    fn num_rows(input_len: usize) -> usize {
+        // the message and challenge hashes, three scalar muls (the subgroup check, s * G and h * pk)
+        // of a row per bit (plus the result), the two points and the final addition
+        MessageHasher::num_rows(input_len)
+            + ChallengeHasher::num_rows(1)
+            + 3 * (SCALAR_BITS + 1)
+            + 2
+            + 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{
+        circuit::SimpleFloorPlanner,
+        plonk::{Circuit, ConstraintSystem},
+    };
+    use rand::rngs::OsRng;
+
+    struct EddsaCircuit {
+        message: Vec<Fp>,
+        signature: EddsaSignature,
+    }
+
+    impl Circuit<Fp> for EddsaCircuit {
+        type Config = EddsaConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                message: self.message.clone(),
+                signature: EddsaSignature::default(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> EddsaConfig {
+            EddsaChip::configure(meta, ())
+        }
+
+        fn synthesize(
+            &self,
+            config: EddsaConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let mut chip = EddsaChip::new(config);
+            chip.load_signature(self.signature.clone());
+            let message: Tensor<ValType<Fp>> =
+                self.message.iter().map(|m| Value::known(*m).into()).into();
+            chip.layout(&mut layouter, &[message.into()], 0)?;
+            Ok(())
+        }
+    }
+
+    /// Multiplies the generator by the scalar with the given bits.
+    struct ScalarMulCircuit {
+        bits: Vec<bool>,
+    }
+
+    impl Circuit<Fp> for ScalarMulCircuit {
+        type Config = EddsaConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                bits: vec![false; SCALAR_BITS],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> EddsaConfig {
+            EddsaChip::configure(meta, ())
+        }
+
+        fn synthesize(
+            &self,
+            config: EddsaConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = EddsaChip::new(config);
+            chip.layout_scalar_mul(
+                &mut layouter,
+                None,
+                Value::known(self.bits.clone()),
+                &modulus_bits_be(),
+            )?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn scalar_bits_are_canonical() {
+        // the largest field element
+        let circuit = ScalarMulCircuit {
+            bits: to_bits_be(&-Fp::ONE),
+        };
+        let prover = halo2_proofs::dev::MockProver::run(12, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // the bits of the modulus sum to 0, but aren't the bits of 0
+        let circuit = ScalarMulCircuit {
+            bits: modulus_bits_be(),
+        };
+        let prover = halo2_proofs::dev::MockProver::run(12, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn generator_has_subgroup_order() {
+        let generator = EdwardsPoint::generator();
+        assert!(generator.is_on_curve());
+        // the order doesn't fit in a field element of the subgroup, so multiply by order - 1
+        let order_minus_one = from_biguint(&(subgroup_order() - 1u32));
+        assert_eq!(
+            generator.scalar_mul(&order_minus_one) + generator,
+            EdwardsPoint::identity()
+        );
+    }
+
+    #[test]
+    fn signatures_verify() {
+        let mut rng = OsRng;
+        let secret_key = EddsaSignature::gen_secret_key(&mut rng);
+        let message = (0..10u64).map(Fp::from).collect::<Vec<_>>();
+
+        let signature = EddsaSignature::sign(&secret_key, message.clone(), &mut rng).unwrap();
+        assert_eq!(
+            signature.public_key,
+            EddsaSignature::public_key(&secret_key)
+        );
+        assert!(signature.verify(message.clone()).unwrap());
+
+        // a different message doesn't verify
+        let mut tampered = message;
+        tampered[0] = Fp::from(42);
+        assert!(!signature.verify(tampered).unwrap());
+
+        // nor does a different key
+        let other = EddsaSignature {
+            public_key: EddsaSignature::public_key(&(secret_key + Fp::ONE)),
+            ..signature
+        };
+        assert!(!other.verify((0..10u64).map(Fp::from).collect()).unwrap());
+    }
+
+    #[test]
+    fn signature_vector() {
+        // computed independently (BabyJubJub arithmetic and a Poseidon reference over the same
+        // parameters), with a secret key of 42 and a nonce of 7
+        let signature = EddsaSignature::sign_with_nonce(
+            &Fp::from(42),
+            vec![1u64, 2, 3].into_iter().map(Fp::from).collect(),
+            &Fp::from(7),
+        )
+        .unwrap();
+        let expected = |s: &str| Fp::from_str_vartime(s).unwrap();
+        assert_eq!(
+            signature.public_key,
+            EdwardsPoint {
+                x: expected(
+                    "2756817265436308373152970980469407708639447434621224209076647801443201833641"
+                ),
+                y: expected(
+                    "16414789158706146034337677946720139175629582444207655085744951462751993091228"
+                ),
+            }
+        );
+        assert_eq!(
+            signature.r,
+            EdwardsPoint {
+                x: expected(
+                    "20092560661213339045022877747484245238324772779820628739268223482659246842641"
+                ),
+                y: expected(
+                    "12112450042127193446189577552007703839818242727902437791835414514847797088033"
+                ),
+            }
+        );
+        assert_eq!(
+            signature.s,
+            expected(
+                "2339444366288415320925091763434998991970217045895150439219327536596048530001"
+            )
+        );
+    }
+
+    #[test]
+    fn weak_keys_and_malleated_signatures_are_rejected() {
+        let mut rng = OsRng;
+        let secret_key = EddsaSignature::gen_secret_key(&mut rng);
+        let message = (0..10u64).map(Fp::from).collect::<Vec<_>>();
+        let signature = EddsaSignature::sign(&secret_key, message.clone(), &mut rng).unwrap();
+
+        // the identity key verifies any message with s = 0 and r = identity
+        assert!(!EddsaSignature::default().verify(message.clone()).unwrap());
+        // a key with a low order component isn't in the subgroup
+        let low_order = EdwardsPoint {
+            x: Fp::ZERO,
+            y: -Fp::ONE,
+        };
+        assert!(!(signature.public_key + low_order).is_in_subgroup());
+        // s + l is another solution of s * G = r + h * pk
+        let malleated = EddsaSignature {
+            s: signature.s + from_biguint(&subgroup_order()),
+            ..signature.clone()
+        };
+        assert!(!malleated.verify(message.clone()).unwrap());
+
+        let identity = EdwardsPoint::identity();
+        let circuit = EddsaCircuit {
+            message: message.clone(),
+            signature: EddsaSignature::default(),
+        };
+        let prover =
+            halo2_proofs::dev::MockProver::run(12, &circuit, vec![vec![identity.x, identity.y]])
+                .unwrap();
+        assert!(prover.verify().is_err());
+
+        let public_key = EddsaChip::run((message.clone(), signature)).unwrap();
+        let circuit = EddsaCircuit {
+            message,
+            signature: malleated,
+        };
+        let prover = halo2_proofs::dev::MockProver::run(12, &circuit, public_key).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn signature_circuit() {
+        let mut rng = OsRng;
+        let secret_key = EddsaSignature::gen_secret_key(&mut rng);
+        let message = (0..10u64).map(Fp::from).collect::<Vec<_>>();
+        let signature = EddsaSignature::sign(&secret_key, message.clone(), &mut rng).unwrap();
+
+        let public_key = EddsaChip::run((message.clone(), signature.clone())).unwrap();
+        let circuit = EddsaCircuit {
+            message: message.clone(),
+            signature: signature.clone(),
+        };
+        let prover = halo2_proofs::dev::MockProver::run(12, &circuit, public_key.clone()).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // the signature doesn't verify against another key
+        let other = EddsaSignature::public_key(&(secret_key + Fp::ONE));
+        let prover =
+            halo2_proofs::dev::MockProver::run(12, &circuit, vec![vec![other.x, other.y]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // nor over another message
+        let circuit = EddsaCircuit {
+            message: vec![Fp::from(42)],
+            signature,
+        };
+        let prover = halo2_proofs::dev::MockProver::run(12, &circuit, public_key).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
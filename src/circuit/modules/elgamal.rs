@@ -34,7 +34,7 @@ use super::Module;
 // Absolute offsets for public inputs.
 const C1_X: usize = 0;
 const C1_Y: usize = 1;
-const SK_H: usize = 2;
+const PK_H: usize = 2;
 const C2_H: usize = 3;
 
 ///
@@ -170,8 +170,9 @@ pub struct ElGamalVariables {
     pub r: Fr,
     /// The public key.
     pub pk: G1Affine,
-    /// The secret key.
-    pub sk: Fr,
+    /// The secret key, only known when the variables were generated for our own key pair.
+    #[serde(default)]
+    pub sk: Option<Fr>,
     /// The window size used in the ECC chip.
     pub window_size: usize,
     /// The auxiliary generator used in the ECC chip.
@@ -183,7 +184,7 @@ impl Default for ElGamalVariables {
         Self {
             r: Fr::zero(),
             pk: G1Affine::identity(),
-            sk: Fr::zero(),
+            sk: None,
             window_size: 4,
             aux_generator: G1Affine::identity(),
         }
@@ -192,7 +193,13 @@ impl Default for ElGamalVariables {
 
 impl ElGamalVariables {
     /// Create new variables.
-    pub fn new(r: Fr, pk: G1Affine, sk: Fr, window_size: usize, aux_generator: G1Affine) -> Self {
+    pub fn new(
+        r: Fr,
+        pk: G1Affine,
+        sk: Option<Fr>,
+        window_size: usize,
+        aux_generator: G1Affine,
+    ) -> Self {
         Self {
             r,
             pk,
@@ -202,6 +209,18 @@ impl ElGamalVariables {
         }
     }
 
+    /// Generate variables to encrypt to a recipient's public key. The secret key isn't known
+    /// (and isn't needed) by whoever encrypts.
+    pub fn for_recipient<R: CryptoRng + RngCore>(pk: G1Affine, mut rng: &mut R) -> Self {
+        Self {
+            r: Fr::random(&mut rng),
+            pk,
+            sk: None,
+            window_size: 4,
+            aux_generator: <G1Affine as CurveAffine>::CurveExt::random(rng).to_affine(),
+        }
+    }
+
     /// Generate random variables.
     pub fn gen_random<R: CryptoRng + RngCore>(mut rng: &mut R) -> Self {
         // get a random element from the scalar field
@@ -216,13 +235,33 @@ impl ElGamalVariables {
         Self {
             r: Fr::random(&mut rng),
             pk: pk.to_affine(),
-            sk,
+            sk: Some(sk),
             window_size: 4,
             aux_generator: <G1Affine as CurveAffine>::CurveExt::random(rng).to_affine(),
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// The party encrypted values are delivered to.
+pub struct ElGamalRecipient {
+    /// The recipient's public key.
+    pub pk: G1Affine,
+}
+
+impl ElGamalRecipient {
+    /// Create a new recipient.
+    pub fn new(pk: G1Affine) -> Self {
+        Self { pk }
+    }
+
+    /// Draw fresh variables to encrypt to this recipient. The randomness must never be reused
+    /// across messages, so `rng` should be a cryptographically secure generator.
+    pub fn variables<R: CryptoRng + RngCore>(&self, rng: &mut R) -> ElGamalVariables {
+        ElGamalVariables::for_recipient(self.pk, rng)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 /// The cipher returned from the ElGamal encryption.
 pub struct ElGamalCipher {
@@ -288,6 +327,19 @@ impl ElGamalGadget {
             .unwrap()[0][0]
     }
 
+    /// Hash the recipient's public key to be used as a public input.
+    pub fn hash_pk(pk: G1Affine) -> Fr {
+        let coords = pk.coordinates().unwrap();
+
+        let x = Integer::from_fe(*coords.x(), Self::rns());
+        let y = Integer::from_fe(*coords.y(), Self::rns());
+
+        PoseidonChip::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, POSEIDON_LEN>::run(
+            [x.native(), y.native()].to_vec(),
+        )
+        .unwrap()[0][0]
+    }
+
     /// Decrypt a ciphertext using the secret key.
     pub fn decrypt(cipher: &ElGamalCipher, sk: Fr) -> Vec<Fr> {
         let c1 = cipher.c1;
@@ -312,8 +364,8 @@ impl ElGamalGadget {
     }
 
     /// Get the public inputs for the circuit.
-    pub fn get_instances(cipher: &ElGamalCipher, sk_hash: Fr) -> Vec<Vec<Fr>> {
-        let mut c1_and_sk = cipher
+    pub fn get_instances(cipher: &ElGamalCipher, pk_hash: Fr) -> Vec<Vec<Fr>> {
+        let mut c1_and_pk = cipher
             .c1
             .to_affine()
             .coordinates()
@@ -325,11 +377,11 @@ impl ElGamalGadget {
             })
             .unwrap();
 
-        c1_and_sk.push(sk_hash);
+        c1_and_pk.push(pk_hash);
 
-        c1_and_sk.push(Self::hash_encrypted_msg(cipher.c2.clone()));
+        c1_and_pk.push(Self::hash_encrypted_msg(cipher.c2.clone()));
 
-        vec![c1_and_sk]
+        vec![c1_and_pk]
     }
 
     pub(crate) fn verify_encrypted_msg_hash(
@@ -364,28 +416,32 @@ impl ElGamalGadget {
         }
     }
 
-    /// Hash the secret key to be used as a public input.
-    pub(crate) fn verify_sk_hash(
+    /// Hash the recipient's public key to be used as a public input.
+    pub(crate) fn verify_pk_hash(
         &self,
         mut layouter: impl Layouter<Fr>,
         config: &ElGamalConfig,
-        sk: &AssignedCell<Fr, Fr>,
+        pk: &AssignedPoint<Fq, Fr, NUMBER_OF_LIMBS, BIT_LEN_LIMB>,
     ) -> Result<AssignedCell<Fr, Fr>, plonk::Error> {
         let chip = ElGamalChip::new(config.clone());
 
-        // compute dh = poseidon_hash(randomness*pk)
-        let sk_hash = {
-            let poseidon_message =
-                Tensor::from([ValType::from(sk.clone()), ValType::from(sk.clone())].into_iter());
+        let pk_hash = {
+            let poseidon_message = Tensor::from(
+                [
+                    ValType::from(pk.x().native().clone()),
+                    ValType::from(pk.y().native().clone()),
+                ]
+                .into_iter(),
+            );
 
             chip.poseidon.layout(
-                &mut layouter.namespace(|| "Poseidon hash (sk)"),
+                &mut layouter.namespace(|| "Poseidon hash (pk)"),
                 &[poseidon_message.into()],
                 0,
             )?
         };
 
-        let sk_hash = match &sk_hash
+        let pk_hash = match &pk_hash
             .get_inner_tensor()
             .map_err(|_| plonk::Error::Synthesis)?[0]
         {
@@ -396,19 +452,19 @@ impl ElGamalGadget {
             }
         };
 
-        Ok(sk_hash)
+        Ok(pk_hash)
     }
 
-    pub(crate) fn verify_secret(
+    /// Derive the shared secret s = r*pk and c1 = r*G from the randomness, returning both
+    /// alongside the assigned public key.
+    pub(crate) fn verify_randomness(
         &self,
         mut layouter: impl Layouter<Fr>,
         config: &ElGamalConfig,
-        sk: &AssignedCell<Fr, Fr>,
-    ) -> Result<[AssignedPoint<Fq, Fr, NUMBER_OF_LIMBS, BIT_LEN_LIMB>; 2], plonk::Error> {
+        r: &AssignedCell<Fr, Fr>,
+    ) -> Result<[AssignedPoint<Fq, Fr, NUMBER_OF_LIMBS, BIT_LEN_LIMB>; 3], plonk::Error> {
         let mut chip = ElGamalChip::new(config.clone());
 
-        let g = G1Affine::generator();
-
         let variables = match self.variables {
             Some(ref variables) => variables,
             None => {
@@ -417,10 +473,6 @@ impl ElGamalGadget {
             }
         };
 
-        // compute s = randomness*pk
-        let s = variables.pk.mul(variables.r).to_affine();
-        let c1 = g.mul(variables.r).to_affine();
-
         layouter.assign_region(
             || "obtain_s",
             |region| {
@@ -431,15 +483,15 @@ impl ElGamalGadget {
                     .assign_aux_generator(ctx, Value::known(variables.aux_generator))?;
                 chip.ecc.assign_aux(ctx, variables.window_size, 1)?;
 
-                let s = chip.ecc.assign_point(ctx, Value::known(s)).unwrap();
-                // compute c1 = randomness*generator
-                let c1 = chip.ecc.assign_point(ctx, Value::known(c1)).unwrap();
-
-                let s_from_sk = chip.ecc.mul(ctx, &c1, sk, variables.window_size).unwrap();
+                let g = chip.ecc.assign_constant(ctx, G1Affine::generator())?;
+                let pk = chip.ecc.assign_point(ctx, Value::known(variables.pk))?;
 
-                chip.ecc.assert_equal(ctx, &s, &s_from_sk)?;
+                // compute c1 = randomness*generator
+                let c1 = chip.ecc.mul(ctx, &g, r, variables.window_size)?;
+                // compute s = randomness*pk
+                let s = chip.ecc.mul(ctx, &pk, r, variables.window_size)?;
 
-                Ok([s, c1])
+                Ok([s, c1, pk])
             },
         )
     }
@@ -512,7 +564,7 @@ impl Module<Fr> for ElGamalGadget {
 
     fn instance_increment_input(&self) -> Vec<usize> {
         // in order
-        // 1. c1, sk_hash, c2_hash
+        // 1. c1, pk_hash, c2_hash
         vec![4]
     }
 
@@ -525,7 +577,7 @@ impl Module<Fr> for ElGamalGadget {
         let cipher = Self::encrypt(var.pk, input, var.r);
         // keep 1 empty (maingate instance variable).
         let mut public_inputs: Vec<Vec<Fr>> = vec![];
-        public_inputs.extend(Self::get_instances(&cipher, Self::hash_sk(var.sk)));
+        public_inputs.extend(Self::get_instances(&cipher, Self::hash_pk(var.pk)));
 
         log::trace!("run (N={:?}) took: {:?}", len, start_time.elapsed());
 
@@ -539,10 +591,10 @@ impl Module<Fr> for ElGamalGadget {
     ) -> Result<Self::InputAssignments, Error> {
         assert_eq!(inputs.len(), 2);
         let message = inputs[0].clone();
-        let sk = inputs[1].clone();
+        let r = inputs[1].clone();
 
         let start_time = instant::Instant::now();
-        let (msg_var, sk_var) = layouter.assign_region(
+        let (msg_var, r_var) = layouter.assign_region(
             || "plaintext",
             |mut region| {
                 let msg_var: Result<Vec<AssignedCell<Fr, Fr>>, Error> = match &message {
@@ -594,7 +646,7 @@ impl Module<Fr> for ElGamalGadget {
                     }
                 };
 
-                let sk = match sk.get_inner_tensor().unwrap()[0] {
+                let r = match r.get_inner_tensor().unwrap()[0] {
                     ValType::Value(v) => v,
                     _ => {
                         log::error!("wrong input type");
@@ -604,20 +656,16 @@ impl Module<Fr> for ElGamalGadget {
 
                 let msg_var = msg_var?;
 
-                let sk_var = region.assign_advice(
-                    || "sk",
-                    self.config.plaintext_col,
-                    msg_var.len(),
-                    || sk,
-                )?;
+                let r_var =
+                    region.assign_advice(|| "r", self.config.plaintext_col, msg_var.len(), || r)?;
 
-                Ok((msg_var, sk_var))
+                Ok((msg_var, r_var))
             },
         )?;
         let duration = start_time.elapsed();
         log::trace!("layout inputs took: {:?}", duration);
 
-        Ok((msg_var, sk_var))
+        Ok((msg_var, r_var))
     }
 
     fn layout(
@@ -633,20 +681,17 @@ impl Module<Fr> for ElGamalGadget {
             self.config.config_range(layouter).unwrap();
         }
 
-        let (msg_var, sk_var) = self.layout_inputs(layouter, inputs)?;
+        let (msg_var, r_var) = self.layout_inputs(layouter, inputs)?;
 
-        let [s, c1] = self.verify_secret(
-            layouter.namespace(|| "verify_secret"),
+        let [s, c1, pk] = self.verify_randomness(
+            layouter.namespace(|| "verify_randomness"),
             &self.config,
-            &sk_var,
+            &r_var,
         )?;
 
-        // Force the public input to be the hash of the secret key so that we can ascertain decryption can happen
-        let sk_hash = self.verify_sk_hash(
-            layouter.namespace(|| "verify_sk_hash"),
-            &self.config,
-            &sk_var,
-        )?;
+        // Force the public input to be the hash of the public key so that we can ascertain who can decrypt
+        let pk_hash =
+            self.verify_pk_hash(layouter.namespace(|| "verify_pk_hash"), &self.config, &pk)?;
 
         layouter
             .constrain_instance(
@@ -660,9 +705,9 @@ impl Module<Fr> for ElGamalGadget {
                 C1_Y + row_offset,
             ))
             .and(layouter.constrain_instance(
-                sk_hash.cell(),
+                pk_hash.cell(),
                 self.config.instance,
-                SK_H + row_offset,
+                PK_H + row_offset,
             ))?;
 
         let c2: Result<Vec<AssignedCell<Fr, Fr>>, _> = msg_var
@@ -705,34 +750,13 @@ impl Module<Fr> for ElGamalGadget {
     }
 
     fn num_rows(input_len: usize) -> usize {
-        // this was determined by running the circuit and looking at the number of constraints
-        // in the test called hash_for_a_range_of_input_sizes, then regressing in python to find the slope
-        // ```python
-        // import numpy as np
-        // x = [1, 2, 3, 512, 513, 514]
-        // y = [75424, 75592, 75840, 161017, 161913, 162000]
-        // def fit_above(x, y) :
-        //     x0, y0 = x[0] - 1, y[0]
-        //     x -= x0
-        //     y -= y0
-        //     def error_function_2(b, x, y) :
-        //         a = np.min((y - b) / x)
-        //         return np.sum((y - a * x - b)**2)
-        //     b = scipy.optimize.minimize(error_function_2, [0], args=(x, y)).x[0]
-        //     a = np.max((y - b) / x)
-        //     return a, b - a * x0 + y0
-        // a, b = fit_above(x, y)
-        // plt.plot(x, y, 'o')
-        // plt.plot(x, a*x + b, '-')
-        // plt.show()
-        // for (x_i, y_i) in zip(x,y):
-        // assert y_i <= a*x_i + b
-        // print(a, b)
-        // ```
+        // the slope and intercept were fit above the row counts test_circuit_range_of_input_sizes
+        // measured for a single scalar multiplication (75424 rows for 1 element, 162000 for 514).
+        // c1 = r*G and s = r*pk take two, so the intercept is doubled, test_num_rows_fits_layout
+        // checks that the estimate lays out.
         const NUM_CONSTRAINTS_SLOPE: usize = 196;
-        const NUM_CONSTRAINTS_INTERCEPT: usize = 75257;
+        const NUM_CONSTRAINTS_INTERCEPT: usize = 2 * 75257;
 
-        // check if even or odd
         input_len * NUM_CONSTRAINTS_SLOPE + NUM_CONSTRAINTS_INTERCEPT
     }
 }
@@ -778,9 +802,9 @@ mod tests {
         ) -> Result<(), Error> {
             let mut chip = ElGamalGadget::new(config);
             chip.load_variables(self.variables.clone());
-            let sk: Tensor<ValType<Fr>> =
-                Tensor::new(Some(&[Value::known(self.variables.sk).into()]), &[1]).unwrap();
-            chip.layout(&mut layouter, &[self.message.clone(), sk.into()], 0)?;
+            let r: Tensor<ValType<Fr>> =
+                Tensor::new(Some(&[Value::known(self.variables.r).into()]), &[1]).unwrap();
+            chip.layout(&mut layouter, &[self.message.clone(), r.into()], 0)?;
             Ok(())
         }
     }
@@ -814,7 +838,7 @@ mod tests {
 
         let cipher = ElGamalGadget::encrypt(var.pk, msg.clone(), var.r);
 
-        let decrypted_msg = ElGamalGadget::decrypt(&cipher, var.sk);
+        let decrypted_msg = ElGamalGadget::decrypt(&cipher, var.sk.unwrap());
 
         assert_eq!(decrypted_msg, msg);
     }
@@ -841,10 +865,70 @@ mod tests {
             variables: var,
         };
 
-        let res = MockProver::run(17, &circuit, public_inputs).unwrap();
+        let res = MockProver::run(18, &circuit, public_inputs).unwrap();
         res.assert_satisfied_par();
     }
 
+    #[test]
+    pub fn test_circuit_for_recipient() {
+        let mut rng = test_rng();
+
+        // only the recipient holds the secret key
+        let recipient = ElGamalVariables::gen_random(&mut rng);
+        let var = ElGamalRecipient::new(recipient.pk).variables(&mut rng);
+        assert_eq!(var.sk, None);
+        // every draw uses fresh randomness
+        assert_ne!(
+            var.r,
+            ElGamalRecipient::new(recipient.pk).variables(&mut rng).r
+        );
+
+        let msg = vec![Fr::random(&mut rng), Fr::random(&mut rng)];
+
+        let run_inputs = (msg.clone(), var.clone());
+        let public_inputs: Vec<Vec<Fr>> = ElGamalGadget::run(run_inputs).unwrap();
+        assert_eq!(public_inputs[0][PK_H], ElGamalGadget::hash_pk(recipient.pk));
+
+        let cipher = ElGamalGadget::encrypt(var.pk, msg.clone(), var.r);
+        assert_eq!(ElGamalGadget::decrypt(&cipher, recipient.sk.unwrap()), msg);
+
+        let message: Tensor<ValType<Fr>> = msg.into_iter().map(|m| Value::known(m).into()).into();
+
+        let circuit = EncryptionCircuit {
+            message: message.into(),
+            variables: var,
+        };
+
+        let res = MockProver::run(18, &circuit, public_inputs).unwrap();
+        res.assert_satisfied_par();
+    }
+
+    #[test]
+    pub fn test_num_rows_fits_layout() {
+        let mut rng = test_rng();
+
+        // the logrows picked from the estimate must leave enough rows to lay the circuit out
+        for i in [1, 2] {
+            let var = ElGamalVariables::gen_random(&mut rng);
+            let msg = (0..i).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+
+            let run_inputs = (msg.clone(), var.clone());
+            let public_inputs: Vec<Vec<Fr>> = ElGamalGadget::run(run_inputs).unwrap();
+
+            let message: Tensor<ValType<Fr>> =
+                msg.into_iter().map(|m| Value::known(m).into()).into();
+
+            let circuit = EncryptionCircuit {
+                message: message.into(),
+                variables: var,
+            };
+
+            let k = (ElGamalGadget::num_rows(i) as f64).log2().ceil() as u32;
+            let res = MockProver::run(k, &circuit, public_inputs).unwrap();
+            res.assert_satisfied_par();
+        }
+    }
+
     #[test]
     #[ignore]
     pub fn test_circuit_range_of_input_sizes() {
@@ -0,0 +1,553 @@
+/*
+A binary Merkle tree with Poseidon nodes, in the form of a Halo2 Chip.
+The chip either commits to a whole message (the root of the tree over its chunks is the public
+instance), or opens a message as a leaf of a larger published tree (a dataset, say) with a
+private authentication path, so the root is public but the position of the leaf isn't.
+*/
+
+use super::poseidon::spec::{PoseidonSpec, POSEIDON_RATE, POSEIDON_WIDTH};
+use super::poseidon::{PoseidonChip, PoseidonConfig};
+use super::Module;
+use crate::tensor::{Tensor, ValTensor, ValType};
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::{AssignedCell, Layouter, Value};
+use halo2_proofs::plonk::{
+    Advice, Column, ConstraintSystem, Constraints, Error, Expression, Instance, Selector,
+};
+use halo2_proofs::poly::Rotation;
+use halo2curves::bn256::Fr as Fp;
+use serde::{Deserialize, Serialize};
+
+/// The number of instance columns used by the Merkle module.
+pub const NUM_INSTANCE_COLUMNS: usize = 1;
+
+/// The number of elements hashed into each leaf when committing to a whole message.
+pub const MERKLE_LEAF_LEN: usize = 32;
+
+/// Hashes a leaf, this is the same (tree) hash as the `hashed` visibility, so a record's leaf is
+/// the hash ezkl would publish for it
+type LeafHasher = PoseidonChip<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, MERKLE_LEAF_LEN>;
+/// Hashes a pair of nodes
+type NodeHasher = PoseidonChip<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 2>;
+
+/// Hashes a record into a leaf.
+pub fn hash_leaf(record: Vec<Fp>) -> Result<Fp, Box<dyn std::error::Error>> {
+    Ok(LeafHasher::run(record)?[0][0])
+}
+
+/// Hashes two nodes into their parent.
+pub fn hash_nodes(left: Fp, right: Fp) -> Result<Fp, Box<dyn std::error::Error>> {
+    Ok(NodeHasher::run(vec![left, right])?[0][0])
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+/// An authentication path from a leaf to the root of a [MerkleTree].
+pub struct MerkleProof {
+    /// The position of the leaf in the tree.
+    pub index: usize,
+    /// The siblings on the path from the leaf to the root, starting at the leaf.
+    pub siblings: Vec<Fp>,
+}
+
+impl MerkleProof {
+    /// A placeholder proof of the given depth, used to lay out the circuit without a witness.
+    pub fn dummy(depth: usize) -> Self {
+        Self {
+            index: 0,
+            siblings: vec![Fp::ZERO; depth],
+        }
+    }
+
+    /// The depth of the tree the proof is for.
+    pub fn depth(&self) -> usize {
+        self.siblings.len()
+    }
+
+    /// Whether the node at `level` of the path is a right child.
+    fn is_right(&self, level: usize) -> bool {
+        (self.index >> level) & 1 == 1
+    }
+
+    /// The root `leaf` opens to along this path.
+    pub fn root(&self, leaf: Fp) -> Result<Fp, Box<dyn std::error::Error>> {
+        self.siblings
+            .iter()
+            .enumerate()
+            .try_fold(leaf, |node, (level, sibling)| {
+                if self.is_right(level) {
+                    hash_nodes(*sibling, node)
+                } else {
+                    hash_nodes(node, *sibling)
+                }
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A binary Merkle tree with Poseidon nodes. The leaves are padded with zeros to a power of two.
+pub struct MerkleTree {
+    /// The layers of the tree, from the leaves up to the root.
+    layers: Vec<Vec<Fp>>,
+}
+
+impl MerkleTree {
+    /// Builds the tree over the given leaves.
+    pub fn new(mut leaves: Vec<Fp>) -> Result<Self, Box<dyn std::error::Error>> {
+        let width = leaves.len().next_power_of_two();
+        leaves.resize(width, Fp::ZERO);
+
+        let mut layers = vec![leaves];
+        while layers[layers.len() - 1].len() > 1 {
+            let parents = layers[layers.len() - 1]
+                .chunks(2)
+                .map(|pair| hash_nodes(pair[0], pair[1]))
+                .collect::<Result<Vec<_>, _>>()?;
+            layers.push(parents);
+        }
+
+        Ok(Self { layers })
+    }
+
+    /// Builds the tree over a set of records (a dataset), one leaf per record.
+    pub fn from_records(records: &[Vec<Fp>]) -> Result<Self, Box<dyn std::error::Error>> {
+        let leaves = records
+            .iter()
+            .map(|record| hash_leaf(record.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(leaves)
+    }
+
+    /// Builds the tree over a message, one leaf per chunk of [MERKLE_LEAF_LEN] elements.
+    pub fn from_elements(elements: &[Fp]) -> Result<Self, Box<dyn std::error::Error>> {
+        let records = elements
+            .chunks(MERKLE_LEAF_LEN)
+            .map(|chunk| chunk.to_vec())
+            .collect::<Vec<_>>();
+        Self::from_records(&records)
+    }
+
+    /// The root of the tree.
+    pub fn root(&self) -> Fp {
+        self.layers[self.layers.len() - 1][0]
+    }
+
+    /// The depth of the tree.
+    pub fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    /// The authentication path of the leaf at `index`.
+    pub fn proof(&self, index: usize) -> Result<MerkleProof, Box<dyn std::error::Error>> {
+        if index >= self.layers[0].len() {
+            return Err(format!(
+                "leaf {} is out of range for a tree of {} leaves",
+                index,
+                self.layers[0].len()
+            )
+            .into());
+        }
+
+        let siblings = self.layers[..self.depth()]
+            .iter()
+            .enumerate()
+            .map(|(level, layer)| layer[(index >> level) ^ 1])
+            .collect();
+
+        Ok(MerkleProof { index, siblings })
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Configuration for the Merkle chip.
+pub struct MerkleConfig {
+    poseidon: PoseidonConfig<POSEIDON_WIDTH, POSEIDON_RATE>,
+    /// The node, sibling, path bit, left and right child columns of the path gate.
+    path: [Column<Advice>; 5],
+    q_path: Selector,
+    /// The column used for the instance.
+    pub instance: Option<Column<Instance>>,
+}
+
+#[derive(Debug, Clone)]
+/// A chip committing to a message with the root of a Merkle tree.
+pub struct MerkleChip {
+    config: MerkleConfig,
+    /// The path the message is opened along, if it is a leaf of a larger tree.
+    proof: Option<MerkleProof>,
+}
+
+impl MerkleChip {
+    /// Configure the chip with an optional (shared) instance column.
+    pub fn configure_with_optional_instance(
+        meta: &mut ConstraintSystem<Fp>,
+        instance: Option<Column<Instance>>,
+    ) -> MerkleConfig {
+        let poseidon = LeafHasher::configure_with_optional_instance(meta, None);
+
+        let path = [(); 5].map(|_| meta.advice_column());
+        for col in path.iter() {
+            meta.enable_equality(*col);
+        }
+
+        let q_path = meta.selector();
+        meta.create_gate(
+            "Merkle path: order node and sibling by the path bit",
+            |meta| {
+                let q_path = meta.query_selector(q_path);
+                let [node, sibling, bit, left, right] =
+                    path.map(|col| meta.query_advice(col, Rotation::cur()));
+                let one = Expression::Constant(Fp::ONE);
+
+                Constraints::with_selector(
+                    q_path,
+                    [
+                        ("bit is boolean", bit.clone() * (one - bit.clone())),
+                        (
+                            "left = node + bit * (sibling - node)",
+                            left - node.clone() - bit.clone() * (sibling.clone() - node.clone()),
+                        ),
+                        (
+                            "right = sibling + bit * (node - sibling)",
+                            right - sibling.clone() - bit * (node - sibling),
+                        ),
+                    ],
+                )
+            },
+        );
+
+        MerkleConfig {
+            poseidon,
+            path,
+            q_path,
+            instance,
+        }
+    }
+
+    /// Open the message as a leaf of a larger tree along `proof` (rather than commit to it as a whole).
+    pub fn load_proof(&mut self, proof: MerkleProof) {
+        self.proof = Some(proof);
+    }
+
+    /// The number of rows used to open a message of `input_len` elements along a path of `depth`.
+    pub fn num_rows_with_depth(input_len: usize, depth: usize) -> usize {
+        // a node is a single block of the node hasher, and a row of the path gate
+        LeafHasher::num_rows(input_len) + depth * (NodeHasher::num_rows(1) + 1)
+    }
+
+    fn hash_cells<const L: usize>(
+        hasher: &PoseidonChip<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, L>,
+        layouter: &mut impl Layouter<Fp>,
+        cells: &[AssignedCell<Fp, Fp>],
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        let message = Tensor::from(cells.iter().map(|c| ValType::from(c.clone())));
+        let hash = hasher.layout(layouter, &[message.into()], 0)?;
+
+        match &hash.get_inner_tensor().map_err(|_| Error::Synthesis)?[0] {
+            ValType::PrevAssigned(v) => Ok(v.clone()),
+            _ => {
+                log::error!("poseidon hash should be an assigned value");
+                Err(Error::Synthesis)
+            }
+        }
+    }
+
+    /// Hashes the leaves up to the root.
+    fn layout_tree(
+        &self,
+        layouter: &mut impl Layouter<Fp>,
+        mut layer: Vec<AssignedCell<Fp, Fp>>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        let hasher = NodeHasher::new(self.config.poseidon.clone());
+
+        let width = layer.len().next_power_of_two();
+        if width > layer.len() {
+            let zero = layouter.assign_region(
+                || "zero leaf",
+                |mut region| {
+                    region.assign_advice_from_constant(|| "zero", self.config.path[0], 0, Fp::ZERO)
+                },
+            )?;
+            layer.resize(width, zero);
+        }
+
+        while layer.len() > 1 {
+            layer = layer
+                .chunks(2)
+                .map(|pair| Self::hash_cells(&hasher, layouter, pair))
+                .collect::<Result<Vec<_>, _>>()?;
+        }
+
+        Ok(layer[0].clone())
+    }
+
+    /// Hashes the leaf up to the root along `proof`.
+    fn layout_path(
+        &self,
+        layouter: &mut impl Layouter<Fp>,
+        leaf: AssignedCell<Fp, Fp>,
+        proof: &MerkleProof,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        let hasher = NodeHasher::new(self.config.poseidon.clone());
+
+        let mut node = leaf;
+        for (level, sibling) in proof.siblings.iter().enumerate() {
+            let is_right = proof.is_right(level);
+            let (left, right) = layouter.assign_region(
+                || format!("merkle path (level {})", level),
+                |mut region| {
+                    self.config.q_path.enable(&mut region, 0)?;
+
+                    let node = node.copy_advice(|| "node", &mut region, self.config.path[0], 0)?;
+                    region.assign_advice(
+                        || "sibling",
+                        self.config.path[1],
+                        0,
+                        || Value::known(*sibling),
+                    )?;
+                    region.assign_advice(
+                        || "bit",
+                        self.config.path[2],
+                        0,
+                        || Value::known(Fp::from(is_right as u64)),
+                    )?;
+
+                    let (left, right) = if is_right {
+                        (Value::known(*sibling), node.value().copied())
+                    } else {
+                        (node.value().copied(), Value::known(*sibling))
+                    };
+                    let left = region.assign_advice(|| "left", self.config.path[3], 0, || left)?;
+                    let right =
+                        region.assign_advice(|| "right", self.config.path[4], 0, || right)?;
+
+                    Ok((left, right))
+                },
+            )?;
+
+            node = Self::hash_cells(&hasher, layouter, &[left, right])?;
+        }
+
+        Ok(node)
+    }
+}
+
+impl Module<Fp> for MerkleChip {
+    type Config = MerkleConfig;
+    type InputAssignments = Vec<AssignedCell<Fp, Fp>>;
+    type RunInputs = (Vec<Fp>, Option<MerkleProof>);
+    type Params = ();
+
+    fn name(&self) -> &'static str {
+        "Merkle"
+    }
+
+    fn instance_increment_input(&self) -> Vec<usize> {
+        vec![1]
+    }
+
+    fn new(config: Self::Config) -> Self {
+        Self {
+            config,
+            proof: None,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>, _: Self::Params) -> Self::Config {
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        Self::configure_with_optional_instance(meta, Some(instance))
+    }
+
+    fn run(input: Self::RunInputs) -> Result<Vec<Vec<Fp>>, Box<dyn std::error::Error>> {
+        let (message, proof) = input;
+        let root = match proof {
+            Some(proof) => proof.root(hash_leaf(message)?)?,
+            None => MerkleTree::from_elements(&message)?.root(),
+        };
+        Ok(vec![vec![root]])
+    }
+
+    fn layout_inputs(
+        &self,
+        layouter: &mut impl Layouter<Fp>,
+        input: &[ValTensor<Fp>],
+    ) -> Result<Self::InputAssignments, Error> {
+        let hasher = LeafHasher::new(self.config.poseidon.clone());
+        let (record, _) = hasher.layout_inputs(layouter, input)?;
+        Ok(record)
+    }
+
+    fn layout(
+        &self,
+        layouter: &mut impl Layouter<Fp>,
+        input: &[ValTensor<Fp>],
+        row_offset: usize,
+    ) -> Result<ValTensor<Fp>, Error> {
+        let hasher = LeafHasher::new(self.config.poseidon.clone());
+        let record = self.layout_inputs(layouter, input)?;
+
+        let root = match &self.proof {
+            Some(proof) => {
+                let leaf = Self::hash_cells(&hasher, layouter, &record)?;
+                self.layout_path(layouter, leaf, proof)?
+            }
+            None => {
+                let leaves = record
+                    .chunks(MERKLE_LEAF_LEN)
+                    .map(|chunk| Self::hash_cells(&hasher, layouter, chunk))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.layout_tree(layouter, leaves)?
+            }
+        };
+
+        if let Some(instance) = self.config.instance {
+            layouter.assign_region(
+                || "constrain root",
+                |mut region| {
+                    let expected_var = region.assign_advice_from_instance(
+                        || "pub input anchor",
+                        instance,
+                        row_offset,
+                        self.config.path[0],
+                        0,
+                    )?;
+
+                    region.constrain_equal(root.cell(), expected_var.cell())
+                },
+            )?;
+        }
+
+        let mut assigned_input: Tensor<ValType<Fp>> =
+            record.iter().map(|e| ValType::from(e.clone())).into();
+        assigned_input.reshape(input[0].dims()).map_err(|e| {
+            log::error!("reshape failed: {:?}", e);
+            Error::Synthesis
+        })?;
+
+        Ok(assigned_input.into())
+    }
+
+    fn num_rows(input_len: usize) -> usize {
+        // each chunk is hashed into a leaf, then the (padded) leaves are hashed pairwise
+        let num_leaves = (input_len + MERKLE_LEAF_LEN - 1) / MERKLE_LEAF_LEN;
+        let num_nodes = num_leaves.next_power_of_two() - 1;
+        num_leaves * LeafHasher::num_rows(MERKLE_LEAF_LEN) + num_nodes * NodeHasher::num_rows(1) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{
+        circuit::SimpleFloorPlanner,
+        plonk::{Circuit, ConstraintSystem},
+    };
+
+    struct MerkleCircuit {
+        message: Vec<Fp>,
+        proof: Option<MerkleProof>,
+    }
+
+    impl Circuit<Fp> for MerkleCircuit {
+        type Config = MerkleConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                message: self.message.clone(),
+                proof: self.proof.clone(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MerkleConfig {
+            MerkleChip::configure(meta, ())
+        }
+
+        fn synthesize(
+            &self,
+            config: MerkleConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let mut chip = MerkleChip::new(config);
+            if let Some(proof) = &self.proof {
+                chip.load_proof(proof.clone());
+            }
+            let message: Tensor<ValType<Fp>> =
+                self.message.iter().map(|m| Value::known(*m).into()).into();
+            chip.layout(&mut layouter, &[message.into()], 0)?;
+            Ok(())
+        }
+    }
+
+    fn records() -> Vec<Vec<Fp>> {
+        (0..5u64)
+            .map(|i| vec![Fp::from(i), Fp::from(i + 1), Fp::from(i + 2)])
+            .collect()
+    }
+
+    #[test]
+    fn proofs_open_to_the_root() {
+        let records = records();
+        let tree = MerkleTree::from_records(&records).unwrap();
+        assert_eq!(tree.depth(), 3);
+
+        for (index, record) in records.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            let leaf = hash_leaf(record.clone()).unwrap();
+            assert_eq!(proof.root(leaf).unwrap(), tree.root());
+            // the wrong position doesn't open to the root
+            let wrong = MerkleProof {
+                index: index ^ 1,
+                ..proof
+            };
+            assert_ne!(wrong.root(leaf).unwrap(), tree.root());
+        }
+
+        assert!(tree.proof(8).is_err());
+    }
+
+    #[test]
+    fn membership_circuit() {
+        let records = records();
+        let tree = MerkleTree::from_records(&records).unwrap();
+        let proof = tree.proof(3).unwrap();
+
+        let circuit = MerkleCircuit {
+            message: records[3].clone(),
+            proof: Some(proof.clone()),
+        };
+        let root = MerkleChip::run((records[3].clone(), Some(proof))).unwrap();
+        assert_eq!(root, vec![vec![tree.root()]]);
+
+        let prover = halo2_proofs::dev::MockProver::run(12, &circuit, root).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // a record that isn't in the dataset doesn't open to the root
+        let circuit = MerkleCircuit {
+            message: vec![Fp::from(42)],
+            proof: circuit.proof,
+        };
+        let prover =
+            halo2_proofs::dev::MockProver::run(12, &circuit, vec![vec![tree.root()]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn commitment_circuit() {
+        let message = (0..70u64).map(Fp::from).collect::<Vec<_>>();
+        let root = MerkleChip::run((message.clone(), None)).unwrap();
+        assert_eq!(
+            root,
+            vec![vec![MerkleTree::from_elements(&message).unwrap().root()]]
+        );
+
+        let circuit = MerkleCircuit {
+            message,
+            proof: None,
+        };
+        let prover = halo2_proofs::dev::MockProver::run(13, &circuit, root).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+}
@@ -7,6 +7,12 @@ pub mod elgamal;
 ///
 pub mod kzg;
 
+///
+pub mod merkle;
+
+///
+pub mod eddsa;
+
 ///
 pub mod planner;
 use halo2_proofs::{
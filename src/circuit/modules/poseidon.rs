@@ -5,6 +5,7 @@ Thanks to https://github.com/summa-dev/summa-solvency/blob/master/src/chips/pose
 */
 
 pub mod poseidon_params;
+pub mod poseidon_params_t5;
 pub mod spec;
 
 // This chip adds a set of advice columns to the gadget Chip to store the inputs of the hash
@@ -16,6 +17,7 @@ use halo2_proofs::{circuit::*, plonk::*};
 use rayon::prelude::ParallelIterator;
 use rayon::slice::ParallelSlice;
 
+use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 
 use crate::tensor::{Tensor, ValTensor, ValType};
@@ -25,6 +27,143 @@ use super::Module;
 /// The number of instance columns used by the Poseidon hash function
 pub const NUM_INSTANCE_COLUMNS: usize = 1;
 
+/// A named set of Poseidon parameters (width, rate and round constants). All sets use the
+/// reference (grain LFSR generated) constants, which circomlib, Noir and most on-chain Poseidon
+/// libraries share for the same width, so their permutations match. The hash on top of the
+/// permutation is halo2's constant length sponge (the length tag is the last element of the
+/// state) over blocks of L elements, hashed as a tree, so digests differ from circomlib's
+/// `poseidon(inputs)` (which permutes `[0, inputs...]` once).
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum PoseidonParams {
+    /// width 2 (rate 1), the default
+    #[default]
+    T2,
+    /// width 5 (rate 4)
+    T5,
+}
+
+/// Runs `$body` with `$spec`, `$width` and `$rate` bound to the spec, width and rate of `$params`
+macro_rules! with_poseidon_params {
+    ($params:expr, $spec:ident, $width:ident, $rate:ident, $body:expr) => {
+        match $params {
+            $crate::circuit::modules::poseidon::PoseidonParams::T2 => {
+                type $spec = $crate::circuit::modules::poseidon::spec::PoseidonSpec;
+                const $width: usize = $crate::circuit::modules::poseidon::spec::POSEIDON_WIDTH;
+                const $rate: usize = $crate::circuit::modules::poseidon::spec::POSEIDON_RATE;
+                $body
+            }
+            $crate::circuit::modules::poseidon::PoseidonParams::T5 => {
+                type $spec = $crate::circuit::modules::poseidon::spec::PoseidonSpecT5;
+                const $width: usize = $crate::circuit::modules::poseidon::spec::POSEIDON_T5_WIDTH;
+                const $rate: usize = $crate::circuit::modules::poseidon::spec::POSEIDON_T5_RATE;
+                $body
+            }
+        }
+    };
+}
+pub(crate) use with_poseidon_params;
+
+impl std::fmt::Display for PoseidonParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PoseidonParams::T2 => write!(f, "t2"),
+            PoseidonParams::T5 => write!(f, "t5"),
+        }
+    }
+}
+
+impl std::str::FromStr for PoseidonParams {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "t2" => Ok(PoseidonParams::T2),
+            "t5" => Ok(PoseidonParams::T5),
+            _ => Err(format!(
+                "unknown poseidon parameter set {}, expected one of t2, t5",
+                s
+            )),
+        }
+    }
+}
+
+impl PoseidonParams {
+    /// Whether these are the default parameters
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The width of the permutation
+    pub fn width(&self) -> usize {
+        with_poseidon_params!(self, _S, WIDTH, _RATE, WIDTH)
+    }
+
+    /// The rate of the sponge
+    pub fn rate(&self) -> usize {
+        with_poseidon_params!(self, _S, _WIDTH, RATE, RATE)
+    }
+
+    /// The number of full and partial rounds
+    pub fn rounds(&self) -> (usize, usize) {
+        with_poseidon_params!(self, S, WIDTH, RATE, {
+            (
+                <S as Spec<Fp, WIDTH, RATE>>::full_rounds(),
+                <S as Spec<Fp, WIDTH, RATE>>::partial_rounds(),
+            )
+        })
+    }
+
+    /// The round constants (one row per round) and the MDS matrix
+    pub fn constants(&self) -> (Vec<Vec<Fp>>, Vec<Vec<Fp>>) {
+        with_poseidon_params!(self, S, WIDTH, RATE, {
+            let (round_constants, mds, _) = <S as Spec<Fp, WIDTH, RATE>>::constants();
+            (
+                round_constants.iter().map(|r| r.to_vec()).collect(),
+                mds.iter().map(|r| r.to_vec()).collect(),
+            )
+        })
+    }
+
+    /// Applies the Poseidon permutation to `state`, which must hold `width` elements
+    pub fn permute(&self, state: &mut [Fp]) {
+        let (round_constants, mds) = self.constants();
+        let (full_rounds, partial_rounds) = self.rounds();
+        let half_full_rounds = full_rounds / 2;
+        for (round, rcs) in round_constants.iter().enumerate() {
+            let is_full = round < half_full_rounds || round >= half_full_rounds + partial_rounds;
+            for (i, (s, rc)) in state.iter_mut().zip(rcs).enumerate() {
+                *s += rc;
+                if is_full || i == 0 {
+                    *s = s.pow_vartime([5]);
+                }
+            }
+            let mixed = mds
+                .iter()
+                .map(|row| row.iter().zip(state.iter()).map(|(m, s)| *m * s).sum())
+                .collect::<Vec<Fp>>();
+            state.copy_from_slice(&mixed);
+        }
+    }
+
+    /// Hashes `message` as a tree of constant length (L) sponges, like the hashed visibility
+    pub fn hash<const L: usize>(
+        &self,
+        message: Vec<Fp>,
+    ) -> Result<Vec<Vec<Fp>>, Box<dyn std::error::Error>> {
+        with_poseidon_params!(self, S, WIDTH, RATE, {
+            PoseidonChip::<S, WIDTH, RATE, L>::run(message)
+        })
+    }
+
+    /// The number of rows used to hash a message of `input_len` elements
+    pub fn num_rows<const L: usize>(&self, input_len: usize) -> usize {
+        with_poseidon_params!(self, S, WIDTH, RATE, {
+            PoseidonChip::<S, WIDTH, RATE, L>::num_rows(input_len)
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 /// WIDTH, RATE and L are const generics for the struct, which represent the width, rate, and number of inputs for the Poseidon hash function, respectively.
 /// This means they are values that are known at compile time and can be used to specialize the implementation of the struct.
@@ -173,7 +312,9 @@ impl<S: Spec<Fp, WIDTH, RATE> + Sync, const WIDTH: usize, const RATE: usize, con
         layouter: &mut impl Layouter<Fp>,
         message: &[ValTensor<Fp>],
     ) -> Result<Self::InputAssignments, Error> {
-        assert_eq!(message.len(), 1);
+        // the message can be followed by a single element salt, which is absorbed before it
+        assert!(message.len() == 1 || message.len() == 2);
+        let salt = message.get(1).cloned();
         let message = message[0].clone();
 
         let start_time = instant::Instant::now();
@@ -251,7 +392,27 @@ impl<S: Spec<Fp, WIDTH, RATE> + Sync, const WIDTH: usize, const RATE: usize, con
                     )
                     .unwrap();
 
-                Ok((assigned_message?, zero_val))
+                let mut assigned_message = assigned_message?;
+
+                if let Some(salt) = &salt {
+                    let salt = salt.get_inner_tensor().map_err(|_| Error::Synthesis)?;
+                    let assigned_salt = match &salt[0] {
+                        ValType::Value(v) => region.assign_advice(
+                            || "load salt",
+                            self.config.hash_inputs[0],
+                            offset + 1,
+                            || *v,
+                        )?,
+                        ValType::PrevAssigned(v) => v.clone(),
+                        e => {
+                            log::error!("wrong salt type {:?}, must be a value", e);
+                            return Err(Error::Synthesis);
+                        }
+                    };
+                    assigned_message.insert(0, assigned_salt);
+                }
+
+                Ok((assigned_message, zero_val))
             },
         );
         log::trace!(
@@ -272,9 +433,13 @@ impl<S: Spec<Fp, WIDTH, RATE> + Sync, const WIDTH: usize, const RATE: usize, con
         row_offset: usize,
     ) -> Result<ValTensor<Fp>, Error> {
         let (mut input_cells, zero_val) = self.layout_inputs(layouter, input)?;
-        // extract the values from the input cells
-        let mut assigned_input: Tensor<ValType<Fp>> =
-            input_cells.iter().map(|e| ValType::from(e.clone())).into();
+        // extract the values from the input cells (leaving out the salt, if any)
+        let num_salt = input.len() - 1;
+        let mut assigned_input: Tensor<ValType<Fp>> = input_cells
+            .iter()
+            .skip(num_salt)
+            .map(|e| ValType::from(e.clone()))
+            .into();
         let len = assigned_input.len();
 
         let start_time = instant::Instant::now();
@@ -401,9 +566,11 @@ impl<S: Spec<Fp, WIDTH, RATE> + Sync, const WIDTH: usize, const RATE: usize, con
     }
 
     fn num_rows(mut input_len: usize) -> usize {
-        // this was determined by running the circuit and looking at the number of constraints
-        // in the test called hash_for_a_range_of_input_sizes, then regressing in python to find the slope
-        let fixed_cost: usize = 41 * L;
+        // each permutation takes a row per full round and per pair of partial rounds, plus 5 rows
+        // of overhead (as laid out by halo2_gadgets' Pow5Chip), and a block of L elements takes
+        // L / RATE permutations
+        let permutation_rows = S::full_rounds() + (S::partial_rounds() + 1) / 2 + 5;
+        let fixed_cost: usize = permutation_rows * ((L + RATE - 1) / RATE);
 
         let mut num_rows = 0;
 
@@ -444,13 +611,15 @@ mod tests {
     const RATE: usize = POSEIDON_RATE;
     const R: usize = 240;
 
-    struct HashCircuit<S: Spec<Fp, WIDTH, RATE>, const L: usize> {
+    struct HashCircuit<S, const W: usize, const RT: usize, const L: usize> {
         message: ValTensor<Fp>,
         _spec: PhantomData<S>,
     }
 
-    impl<S: Spec<Fp, WIDTH, RATE>, const L: usize> Circuit<Fp> for HashCircuit<S, L> {
-        type Config = PoseidonConfig<WIDTH, RATE>;
+    impl<S: Spec<Fp, W, RT> + Sync, const W: usize, const RT: usize, const L: usize> Circuit<Fp>
+        for HashCircuit<S, W, RT, L>
+    {
+        type Config = PoseidonConfig<W, RT>;
         type FloorPlanner = ModulePlanner;
         type Params = ();
 
@@ -464,16 +633,16 @@ mod tests {
             }
         }
 
-        fn configure(meta: &mut ConstraintSystem<Fp>) -> PoseidonConfig<WIDTH, RATE> {
-            PoseidonChip::<PoseidonSpec, WIDTH, RATE, L>::configure(meta, ())
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PoseidonConfig<W, RT> {
+            PoseidonChip::<S, W, RT, L>::configure(meta, ())
         }
 
         fn synthesize(
             &self,
-            config: PoseidonConfig<WIDTH, RATE>,
+            config: PoseidonConfig<W, RT>,
             mut layouter: impl Layouter<Fp>,
         ) -> Result<(), Error> {
-            let chip: PoseidonChip<PoseidonSpec, WIDTH, RATE, L> = PoseidonChip::new(config);
+            let chip: PoseidonChip<S, W, RT, L> = PoseidonChip::new(config);
             chip.layout(&mut layouter, &[self.message.clone()], 0)?;
 
             Ok(())
@@ -491,7 +660,7 @@ mod tests {
             message.into_iter().map(|m| Value::known(m).into()).into();
 
         let k = 9;
-        let circuit = HashCircuit::<PoseidonSpec, 2> {
+        let circuit = HashCircuit::<PoseidonSpec, WIDTH, RATE, 2> {
             message: message.into(),
             _spec: PhantomData,
         };
@@ -510,7 +679,28 @@ mod tests {
             message.into_iter().map(|m| Value::known(m).into()).into();
 
         let k = 9;
-        let circuit = HashCircuit::<PoseidonSpec, 3> {
+        let circuit = HashCircuit::<PoseidonSpec, WIDTH, RATE, 3> {
+            message: message.into(),
+            _spec: PhantomData,
+        };
+        let prover = halo2_proofs::dev::MockProver::run(k, &circuit, output).unwrap();
+        assert_eq!(prover.verify_par(), Ok(()))
+    }
+
+    #[test]
+    fn poseidon_hash_t5() {
+        use super::spec::{PoseidonSpecT5, POSEIDON_T5_RATE, POSEIDON_T5_WIDTH};
+
+        let rng = rand::rngs::OsRng;
+
+        let message: Vec<Fp> = (0..5).map(|_| Fp::random(rng)).collect::<Vec<_>>();
+        let output = PoseidonParams::T5.hash::<4>(message.clone()).unwrap();
+
+        let message: Tensor<ValType<Fp>> =
+            message.into_iter().map(|m| Value::known(m).into()).into();
+
+        let k = 9;
+        let circuit = HashCircuit::<PoseidonSpecT5, POSEIDON_T5_WIDTH, POSEIDON_T5_RATE, 4> {
             message: message.into(),
             _spec: PhantomData,
         };
@@ -542,7 +732,7 @@ mod tests {
                 message.into_iter().map(|m| Value::known(m).into()).into();
 
             let k = 17;
-            let circuit = HashCircuit::<PoseidonSpec, 32> {
+            let circuit = HashCircuit::<PoseidonSpec, WIDTH, RATE, 32> {
                 message: message.into(),
                 _spec: PhantomData,
             };
@@ -565,11 +755,97 @@ mod tests {
             message.into_iter().map(|m| Value::known(m).into()).into();
 
         let k = 17;
-        let circuit = HashCircuit::<PoseidonSpec, 25> {
+        let circuit = HashCircuit::<PoseidonSpec, WIDTH, RATE, 25> {
             message: message.into(),
             _spec: PhantomData,
         };
         let prover = halo2_proofs::dev::MockProver::run(k, &circuit, output).unwrap();
         assert_eq!(prover.verify_par(), Ok(()))
     }
+
+    #[test]
+    fn default_params_match_the_chip() {
+        let rng = rand::rngs::OsRng;
+
+        let message: Vec<Fp> = (0..5).map(|_| Fp::random(rng)).collect::<Vec<_>>();
+        let output = PoseidonChip::<PoseidonSpec, WIDTH, RATE, 2>::run(message.clone()).unwrap();
+
+        assert_eq!(
+            PoseidonParams::default().hash::<2>(message).unwrap(),
+            output
+        );
+    }
+
+    #[test]
+    fn named_params_match_circomlib() {
+        use halo2curves::ff::PrimeField;
+
+        // circomlib's poseidon(inputs) is the first element of the permutation of [0, inputs...],
+        // here poseidon([1]) and poseidon([1, 2, 3, 4])
+        let cases = [
+            (
+                PoseidonParams::T2,
+                "18586133768512220936620570745912940619677854269274689475585506675881198879027",
+            ),
+            (
+                PoseidonParams::T5,
+                "18821383157269793795438455681495246036402687001665670618754263018637548127333",
+            ),
+        ];
+
+        for (params, expected) in cases {
+            let mut state = (0..params.width() as u64).map(Fp::from).collect::<Vec<_>>();
+            params.permute(&mut state);
+            assert_eq!(state[0], Fp::from_str_vartime(expected).unwrap());
+        }
+    }
+
+    #[test]
+    fn named_params_hash_vectors() {
+        use crate::graph::modules::POSEIDON_LEN_GRAPH;
+        use halo2curves::ff::PrimeField;
+
+        // the sponge and tree layout of the hashed visibility, for a single block and for a
+        // message spanning two blocks
+        let cases = [
+            (
+                PoseidonParams::T2,
+                "14006404267878923236758898149148614215220771377139897741143745593689709476330",
+                "13681763697888677916471347155668629638590017264237076734471082490870134975325",
+            ),
+            (
+                PoseidonParams::T5,
+                "7089618057529506138516900907731089812817370813464209211592339241199591402930",
+                "10086622076674943339171071519170512229440059030282577590232106770033799043241",
+            ),
+        ];
+
+        for (params, short, long) in cases {
+            let hash = |message: Vec<Fp>| params.hash::<POSEIDON_LEN_GRAPH>(message).unwrap()[0][0];
+            assert_eq!(
+                hash((1..=4u64).map(Fp::from).collect()),
+                Fp::from_str_vartime(short).unwrap()
+            );
+            assert_eq!(
+                hash((0..40u64).map(Fp::from).collect()),
+                Fp::from_str_vartime(long).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn params_round_trip() {
+        for params in [PoseidonParams::T2, PoseidonParams::T5] {
+            assert_eq!(params.to_string().parse::<PoseidonParams>(), Ok(params));
+            let json = serde_json::to_string(&params).unwrap();
+            assert_eq!(
+                serde_json::from_str::<PoseidonParams>(&json).unwrap(),
+                params
+            );
+        }
+        assert!("t4".parse::<PoseidonParams>().is_err());
+        // circomlib's width 3 parameters have an odd number of partial rounds, which the chip
+        // can't lay out
+        assert!("t3".parse::<PoseidonParams>().is_err());
+    }
 }
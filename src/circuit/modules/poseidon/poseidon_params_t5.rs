@@ -0,0 +1,2520 @@
+//! This file was generated by running generate_params.py
+//! Number of round constants: 340
+//! Round constants for GF(p):
+//! Parameters for using rate 4 Poseidon with the BN256 field.
+//! The parameters can be reproduced by running the following Sage script from
+//! [this repository](https://github.com/daira/pasta-hadeshash):
+//!
+//! ```text
+//! $ sage generate_parameters_grain.sage 1 0 254 5 8 60 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001 --rust
+//! ```
+//!
+//! where 1 means "prime field", 0 means "non-negative sbox", 254 is the bitsize
+//! of the field, 5 is the Poseidon width (rate + 1), 8 is the number of full
+//! rounds, 60 is the number of partial rounds.
+//! These are the same parameters as circomlib's Poseidon for 4 inputs.
+use halo2_proofs::halo2curves::bn256::Fr as Fp;
+pub(crate) const ROUND_CONSTANTS: [[Fp; 5]; 68] = [
+    [
+        Fp::from_raw([
+            0x4f3c_2bd8_1a6d_a891,
+            0xd889_bb4e_bd47_c386,
+            0x7f53_e29c_cac9_8ed7,
+            0x0eb5_44fe_e281_5dda,
+        ]),
+        Fp::from_raw([
+            0xba73_3f28_4751_28cb,
+            0xa197_aeb1_2ea6_4713,
+            0xf02f_dba7_dd73_7fbc,
+            0x0554_d736_315b_8662,
+        ]),
+        Fp::from_raw([
+            0xf508_7c58_d5e8_c2d4,
+            0x5490_7df0_c0fb_0035,
+            0xbcd7_4805_6307_c377,
+            0x2f83_b9df_259b_2b68,
+        ]),
+        Fp::from_raw([
+            0x1915_208f_5aba_9683,
+            0x61f1_5f8b_41a7_5ef3,
+            0x2447_ac83_0524_51b4,
+            0x2ca7_0e2e_8d7f_39a1,
+        ]),
+        Fp::from_raw([
+            0x4ec7_19cb_83ec_fea9,
+            0x9941_96f1_2ed2_2c5d,
+            0x91b0_4d72_2227_1c94,
+            0x1cb5_f931_9be6_a45e,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xb9ea_08d1_8446_c122,
+            0x21c7_bb47_b937_50c2,
+            0xf8a4_2192_de7f_f616,
+            0x2eb4_f99c_69f9_66eb,
+        ]),
+        Fp::from_raw([
+            0xd5f7_d099_e299_520e,
+            0x0fc7_da8b_93ee_13b6,
+            0xc519_8169_e405_d9ea,
+            0x224a_28e5_a353_85a7,
+        ]),
+        Fp::from_raw([
+            0x2379_6b99_3bbd_82f7,
+            0x36f3_3ecb_d9a0_f978,
+            0xd8af_dd6a_fca4_9c30,
+            0x0f74_11b4_65e6_00ee,
+        ]),
+        Fp::from_raw([
+            0x0626_f9ff_5d90_e4e3,
+            0x19b2_08ae_3370_f99a,
+            0xa2be_7150_392d_8d98,
+            0x0f9d_0d5a_ad2c_9555,
+        ]),
+        Fp::from_raw([
+            0x6a12_d307_02d6_fba0,
+            0x9732_b252_59cf_744b,
+            0x6f52_a595_38d3_2922,
+            0x1e9a_96dc_8292_bb59,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xc2cd_4d52_8fb3_fe3c,
+            0x3cfe_52ea_b4b9_45c6,
+            0x887d_578c_4555_5e59,
+            0x0878_0514_ccd9_0380,
+        ]),
+        Fp::from_raw([
+            0x59f1_19d6_29cc_b5fc,
+            0xced6_4717_e355_6d5a,
+            0xc814_9fa3_f73e_f8c2,
+            0x2724_98fc_ed68_6c7a,
+        ]),
+        Fp::from_raw([
+            0xe3d0_ef8a_782e_f7df,
+            0x45bd_350a_ff58_5f10,
+            0x4b7c_b809_30bd_06eb,
+            0x01ef_8f9d_d7c9_3aac,
+        ]),
+        Fp::from_raw([
+            0x97ea_0ae7_5493_4d30,
+            0x8e88_6e64_bf3c_40aa,
+            0x4dc0_8f22_2b46_9b13,
+            0x045b_9f59_b659_5e61,
+        ]),
+        Fp::from_raw([
+            0xadf2_5842_95d6_1c66,
+            0xa3e4_1e24_e247_a387,
+            0x9fd6_f59d_2a40_ff8e,
+            0x0ac1_e91c_57d9_da91,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xd12e_68f0_4e62_d134,
+            0x0fd6_7061_aee9_9979,
+            0xc7f9_a421_353c_d89d,
+            0x028a_1621_a940_54b0,
+        ]),
+        Fp::from_raw([
+            0xd02a_ae2a_0dcd_9dbc,
+            0x50c1_9c3f_b3c9_6d09,
+            0x9632_647e_d059_236e,
+            0x26b4_1802_c071_ea4c,
+        ]),
+        Fp::from_raw([
+            0x4af3_4384_aedb_462b,
+            0x05c9_de06_758d_b6a9,
+            0xbaac_2f63_e468_215e,
+            0x2fb5_dda8_072b_b72c,
+        ]),
+        Fp::from_raw([
+            0xdd05_c56e_e894_d850,
+            0xad8a_b8ba_2a18_d383,
+            0x44ff_3547_fd82_3249,
+            0x2212_d3a0_f5fc_caf2,
+        ]),
+        Fp::from_raw([
+            0x5cd0_c7ee_d465_e2e3,
+            0xa327_6fdb_19f4_4c01,
+            0x58e4_dfae_ea09_be56,
+            0x1b04_1ad5_b2f0_6842,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xa91e_64af_b150_0eff,
+            0x144f_b7e3_ac14_e846,
+            0x8ecc_ff33_e76f_ded3,
+            0x0a01_776b_b22f_4b6b,
+        ]),
+        Fp::from_raw([
+            0xf793_6440_810a_ce43,
+            0xa4f3_3ae8_c15c_f827,
+            0xf34d_3f27_5066_d549,
+            0x2b7b_5674_aaec_c3cb,
+        ]),
+        Fp::from_raw([
+            0x4c1b_8033_81a3_bdfd,
+            0x60b0_4225_7b78_fc00,
+            0x4cf7_5779_ed54_b48c,
+            0x29d2_99b8_0cd4_489e,
+        ]),
+        Fp::from_raw([
+            0x19dd_e304_24be_401e,
+            0xa427_1100_32b5_e1dd,
+            0x5764_1c21_9d72_1a74,
+            0x1c46_831d_9a74_5293,
+        ]),
+        Fp::from_raw([
+            0x739a_e1d8_83e9_1269,
+            0x3629_6c06_5767_4f80,
+            0xf371_41dc_34d5_78e0,
+            0x06d7_626c_953c_cb72,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x0f10_8c71_cda2_930c,
+            0xdc5c_440a_3022_cd96,
+            0xc540_0274_8e0c_410e,
+            0x28ff_ddc8_6f18_c136,
+        ]),
+        Fp::from_raw([
+            0xb8ec_0619_f6fb_c5e9,
+            0xbe67_f1b7_ed2a_b6ad,
+            0xf85d_eed0_9e40_0b17,
+            0x2e67_f7ee_5e4a_a295,
+        ]),
+        Fp::from_raw([
+            0xe7ab_f22c_24e8_0f27,
+            0xca56_859e_f759_e53c,
+            0x0e97_f251_14a7_9a2d,
+            0x26ce_38fa_636c_9063,
+        ]),
+        Fp::from_raw([
+            0xafac_b4c4_cfd3_5db1,
+            0x42cb_3d16_a1f7_2721,
+            0x4dd7_a01d_00a7_ffec,
+            0x2e6e_07c3_c95b_f7c3,
+        ]),
+        Fp::from_raw([
+            0x384b_1470_da24_d8cc,
+            0x8890_d276_612e_1246,
+            0x5f91_d796_1c3a_54fb,
+            0x2aa7_4f75_97f0_c9f4,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x2ec0_ca63_9b7f_73fe,
+            0x8a71_313c_1509_183e,
+            0x2c7c_090f_668a_b45b,
+            0x287d_681a_46a2_faae,
+        ]),
+        Fp::from_raw([
+            0x11e2_9f33_05e7_3c04,
+            0xa5d3_106f_f565_aa3b,
+            0xf4a4_0600_528f_3d7d,
+            0x212b_d19d_f812_eaae,
+        ]),
+        Fp::from_raw([
+            0x3809_c284_0471_3504,
+            0xf97f_d974_0926_dab9,
+            0x1aaf_b14b_350e_b860,
+            0x1154_f7cf_5191_86bf,
+        ]),
+        Fp::from_raw([
+            0xda74_7e79_661f_c207,
+            0xbf51_5290_5be3_6583,
+            0x4637_810a_4bd1_b16f,
+            0x1dff_6385_cb31_f1c2,
+        ]),
+        Fp::from_raw([
+            0x63ea_3c60_6b55_1e5c,
+            0x4011_a34d_5476_2528,
+            0xc081_d34c_44c1_8e42,
+            0x0e44_4582_d22b_4e76,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xff72_d3aa_b7e4_eff8,
+            0x8157_73e9_c284_6323,
+            0xabab_6638_328f_02f1,
+            0x0323_c9e4_33ba_66c4,
+        ]),
+        Fp::from_raw([
+            0xb70f_2c68_76a9_c29d,
+            0x25b8_cf00_2740_112d,
+            0x193b_ba79_cdec_448f,
+            0x1274_6bbd_7179_1059,
+        ]),
+        Fp::from_raw([
+            0xfd04_9eb4_438a_2240,
+            0xd466_c837_cf50_d73e,
+            0xfd9b_9d37_5184_2c75,
+            0x1173_b7d1_12c2_a798,
+        ]),
+        Fp::from_raw([
+            0x5fdb_4808_7032_43da,
+            0xa8e5_713b_2502_6ebe,
+            0x76d1_e555_d7fe_d13d,
+            0x13d5_1c10_90a1_ad48,
+        ]),
+        Fp::from_raw([
+            0xf4a5_8ebe_b956_baa1,
+            0xcb72_743f_0394_efe7,
+            0xff8d_cb7c_bd2d_9743,
+            0x0087_4c13_44a4_ad51,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xa32a_5620_74fe_f08f,
+            0x0eea_48d3_546e_97d6,
+            0x65ce_236b_07f2_44fa,
+            0x22df_2213_1aaa_b858,
+        ]),
+        Fp::from_raw([
+            0x8bf5_eb05_a919_f155,
+            0x9845_24a5_9101_e6c1,
+            0x8708_b437_a445_fc3e,
+            0x0bf9_64d2_dbd2_5b90,
+        ]),
+        Fp::from_raw([
+            0x69b4_35b5_fc50_2f32,
+            0x640b_9d73_a9ab_298c,
+            0xa302_be1f_7f18_1e0e,
+            0x09b1_8d9b_917a_55bc,
+        ]),
+        Fp::from_raw([
+            0xd674_5a50_6728_9e43,
+            0xfbbb_c70a_6365_366d,
+            0xa4bf_c1d5_bf3d_c05b,
+            0x094f_5534_444f_ae36,
+        ]),
+        Fp::from_raw([
+            0x784f_ddc0_dc23_f01f,
+            0xa3e2_40c0_da57_01cb,
+            0x519f_a662_2af5_3a15,
+            0x2999_bab1_a5f2_5210,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x6957_5a11_b03a_3d23,
+            0x8430_1bce_8a93_d136,
+            0x1ca9_4db7_3710_e880,
+            0x2f68_98c0_7581_f637,
+        ]),
+        Fp::from_raw([
+            0x6217_4b1a_6866_fccb,
+            0x565d_eb1e_8e57_42f8,
+            0xec16_d7e1_318a_4740,
+            0x0726_8eaa_ba08_bc19,
+        ]),
+        Fp::from_raw([
+            0xa6c9_582c_41a0_529f,
+            0x6260_3e07_8e1c_6689,
+            0x1339_ff77_113b_c9eb,
+            0x1862_79b0_0345_4db0,
+        ]),
+        Fp::from_raw([
+            0x0768_e552_4737_172c,
+            0xb67e_2cc5_de9a_2275,
+            0xe491_5bdd_04d3_e5dd,
+            0x18a3_f736_5091_97d6,
+        ]),
+        Fp::from_raw([
+            0x7b59_87b8_7085_671d,
+            0x725e_2d4b_cb2d_3a00,
+            0x77cc_1e2e_d24c_808c,
+            0x0a21_fa19_88cf_38d8,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x0b36_a135_e785_fba2,
+            0x8328_c184_a2c4_3bc0,
+            0x1faf_5ef6_a646_2522,
+            0x15b2_85cb_e26c_467f,
+        ]),
+        Fp::from_raw([
+            0x8cd2_8de3_e779_f161,
+            0x0b77_75b7_c902_f578,
+            0x8c08_b8c3_f980_6d56,
+            0x164b_7062_c467_1cf0,
+        ]),
+        Fp::from_raw([
+            0x7a24_f651_249b_aa70,
+            0x61c6_1d3d_43b6_e65d,
+            0x86d9_865f_e7e5_0ef3,
+            0x0890_ba08_19ac_0a6f,
+        ]),
+        Fp::from_raw([
+            0xcc2e_e0ae_d543_e922,
+            0xa627_ac5c_b0eb_878c,
+            0xa427_12e5_a721_e4ea,
+            0x2fbe_a4d6_5d7e_d425,
+        ]),
+        Fp::from_raw([
+            0x103d_7f5f_379a_baaa,
+            0x7b70_a58e_854a_b9b9,
+            0x5403_03a3_b536_f85e,
+            0x0492_bf38_3c36_fa55,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x6a96_9d56_292d_c24e,
+            0x1d61_85a9_ce85_675f,
+            0x4e20_251c_5651_42d6,
+            0x05e9_1fe9_44e9_4410,
+        ]),
+        Fp::from_raw([
+            0x7e38_0a76_e36e_6c1c,
+            0x995b_9621_e6e4_9c3b,
+            0x93d4_63cb_041a_cad0,
+            0x12fe_5c20_29e4_b338,
+        ]),
+        Fp::from_raw([
+            0x06cd_01dc_6fa0_784e,
+            0x1f26_29fa_dc89_4969,
+            0x958f_7723_9214_7413,
+            0x0241_54ad_f025_5d47,
+        ]),
+        Fp::from_raw([
+            0x8813_c134_57a4_5550,
+            0xd798_8156_44f2_bbde,
+            0x36ed_2462_a86b_d0ba,
+            0x1882_4a09_e6af_af4a,
+        ]),
+        Fp::from_raw([
+            0xbb06_983e_3d5d_58a5,
+            0xdf84_a630_af68_d50b,
+            0xe9f2_55de_0c3d_bddd,
+            0x0c8b_482d_ba0a_d51b,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x4413_9287_8fdb_05e6,
+            0x5a4f_a67f_cd6a_af86,
+            0x363e_0a16_67d3_b67c,
+            0x1732_5fd0_ab63_5871,
+        ]),
+        Fp::from_raw([
+            0x1cc6_d0e9_11fa_402e,
+            0xe550_773f_a8d1_8bf7,
+            0x122f_5af6_7b69_0f31,
+            0x050a_e95f_6d2f_1519,
+        ]),
+        Fp::from_raw([
+            0x5771_ec84_edc5_0c40,
+            0x4bbb_6295_f075_6988,
+            0x038c_b288_d626_3676,
+            0x0f0d_139a_0e81_e943,
+        ]),
+        Fp::from_raw([
+            0x9dbb_ca7b_8e74_7cd6,
+            0x79b3_9ebc_7a1b_1c54,
+            0xf70f_d2f2_c0f9_3d1a,
+            0x1c0f_8697_7956_89cd,
+        ]),
+        Fp::from_raw([
+            0xfe53_6a16_dc1d_81e6,
+            0x49be_23a4_b135_98f9,
+            0x6d2b_c2e0_48bc_979e,
+            0x2bd0_f940_ad93_6b79,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x7ce5_4d1e_96ee_62cb,
+            0xa06e_bb27_5e09_6d16,
+            0x4778_c09a_0053_337f,
+            0x27eb_1be2_7c9c_4e93,
+        ]),
+        Fp::from_raw([
+            0xf151_be62_548e_2aea,
+            0x284f_bd30_7d1f_71b0,
+            0x8f96_bdd3_155a_7ca3,
+            0x2e48_89d8_30a6_7e5a,
+        ]),
+        Fp::from_raw([
+            0x6064_bbe6_fcc1_e305,
+            0xc989_1f2c_adc1_65db,
+            0x5d2e_c5e9_c5bd_9983,
+            0x193f_e3db_0ab4_7d3c,
+        ]),
+        Fp::from_raw([
+            0x37cb_1302_7c83_e525,
+            0x6e96_61c0_0967_9e4e,
+            0xce41_5907_ad0c_40ed,
+            0x2bf3_086e_96c3_6c7b,
+        ]),
+        Fp::from_raw([
+            0x9ff2_ea48_6e59_bb28,
+            0xd5e7_e413_f741_ccf2,
+            0xa98c_db69_7c6c_ad5d,
+            0x12f1_6e2d_e6d4_ad46,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xad91_9b03_43b9_2d2f,
+            0x33f3_d5d6_ec6c_4bf0,
+            0xa026_2e36_53dd_d19f,
+            0x2a72_147d_2301_19f3,
+        ]),
+        Fp::from_raw([
+            0x77df_79ac_c10b_a974,
+            0x0a2d_9bcc_2641_2e29,
+            0x6dc4_7f95_7806_dc5f,
+            0x21be_0e2c_4bfd_64e5,
+        ]),
+        Fp::from_raw([
+            0x137f_d4b6_c21b_444a,
+            0x71b8_4fb9_11aa_57ae,
+            0x2749_a3b5_4367_b25a,
+            0x0e2d_7e1d_c946_d70b,
+        ]),
+        Fp::from_raw([
+            0x9f3f_07d4_b92b_3e2e,
+            0xc31a_db0e_ae33_25dc,
+            0x6170_a745_d8a4_188c,
+            0x2667_f7fb_5a4f_a124,
+        ]),
+        Fp::from_raw([
+            0x830e_107d_a78e_3405,
+            0x1550_c12b_08df_eb72,
+            0x30a7_83b6_6064_697a,
+            0x2ccc_6f43_1fb7_4007,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x2ec5_0621_e38e_6e5d,
+            0xae6d_bee9_e8ca_0c24,
+            0x4f02_0146_2420_001f,
+            0x0888_8a94_fc5a_2ca3,
+        ]),
+        Fp::from_raw([
+            0x6acf_cd3c_cbc5_3f2a,
+            0xa0d2_fbe7_53af_88b3,
+            0xad40_dd42_c9b6_fdd7,
+            0x0297_7b34_eeaa_3cb6,
+        ]),
+        Fp::from_raw([
+            0x10b5_e9f9_71e1_6b9a,
+            0xbfcf_e0d7_e6ff_8e96,
+            0xfd6f_b6c9_ea13_a648,
+            0x120c_cce1_3d28_b75c,
+        ]),
+        Fp::from_raw([
+            0xa0bf_2af3_2f86_ff3c,
+            0x9278_7a45_75b2_bd73,
+            0xc81e_1b97_70ea_098c,
+            0x09fa_d226_9c4a_8e93,
+        ]),
+        Fp::from_raw([
+            0xf630_cffb_6009_2d6f,
+            0x0deb_db70_775e_eb8a,
+            0x0a4b_310e_4ac6_f0fa,
+            0x0260_91fd_3d4c_44d5,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xb904_fd2b_ca89_3994,
+            0x2543_cc56_afad_6afc,
+            0xbb7f_ba9d_fb6f_c321,
+            0x2940_4aa2_ba56_5b77,
+        ]),
+        Fp::from_raw([
+            0xe720_1351_b7c8_83f9,
+            0xf1ff_d865_90e0_827d,
+            0xd4e8_7c25_4869_5b4e,
+            0x2749_475c_399a_af39,
+        ]),
+        Fp::from_raw([
+            0xc755_7dab_65ff_a222,
+            0xa2eb_e2dc_2e4d_a70a,
+            0x3991_2b50_4246_85cb,
+            0x098c_8423_2247_9f72,
+        ]),
+        Fp::from_raw([
+            0x0191_d0c0_53b5_1936,
+            0x758a_ce14_c93c_4da4,
+            0x3123_8e57_fead_7d5c,
+            0x18ce_f581_222b_647e,
+        ]),
+        Fp::from_raw([
+            0x63b2_aa68_1698_de60,
+            0x3cbc_0ca4_a108_f98d,
+            0xd4e7_4674_5e43_711d,
+            0x1317_7839_c68a_5080,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xa907_d88e_5f0d_effd,
+            0x26cc_4df7_12c0_e5f0,
+            0xc088_f56f_4b74_3256,
+            0x020c_a696_f531_e43e,
+        ]),
+        Fp::from_raw([
+            0x16e0_2838_7c7a_c022,
+            0x93d1_3708_c646_841d,
+            0xfa80_5a30_fc54_8db6,
+            0x2723_0eed_e9cc_cfc9,
+        ]),
+        Fp::from_raw([
+            0x9057_d2fe_75bb_281c,
+            0x6497_c059_69a0_1543,
+            0xd64f_de34_a342_a178,
+            0x0164_5911_c119_8b01,
+        ]),
+        Fp::from_raw([
+            0xfdca_4a45_1a5d_8643,
+            0x1989_71e1_4487_056c,
+            0x6e43_9c88_341c_e25f,
+            0x2c32_3fe1_6481_bf49,
+        ]),
+        Fp::from_raw([
+            0x5af8_8ae6_db50_85c8,
+            0xb022_c124_d3bf_fe8b,
+            0x450b_d207_4c3e_22e1,
+            0x0fc0_82df_e707_28e8,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xfe8a_488b_4235_21ee,
+            0xbe96_4211_6ac4_c77e,
+            0xd8cd_ca56_8dcc_25b3,
+            0x2052_c174_800d_b209,
+        ]),
+        Fp::from_raw([
+            0x8dd9_fd05_b3ec_e9c0,
+            0x0be3_51ce_8129_065a,
+            0xaf96_d621_d554_2319,
+            0x28e4_20e1_0df2_fbb5,
+        ]),
+        Fp::from_raw([
+            0x49d6_e0bc_3ae5_069a,
+            0x55d6_ae1b_dacd_1cb5,
+            0x9f78_3c44_62a2_4db6,
+            0x2569_8ca5_e24a_1b79,
+        ]),
+        Fp::from_raw([
+            0x34d9_b857_d698_4d35,
+            0xa297_b610_7442_2ac1,
+            0xcf8f_fbfa_57d5_1049,
+            0x160a_9981_a5c8_9a57,
+        ]),
+        Fp::from_raw([
+            0x6464_2b06_9997_f3d4,
+            0x8b7c_ebf5_9ddb_b0a0,
+            0x34d9_b694_b843_f3bf,
+            0x21c9_1a39_e145_c3bc,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xd109_7546_1e41_734c,
+            0xd6ea_a029_d93f_03b6,
+            0xd2b0_9345_ef11_2345,
+            0x1ac8_d80d_cd5e_e876,
+        ]),
+        Fp::from_raw([
+            0xb8db_cea5_657c_e02c,
+            0x5d82_2895_e275_5544,
+            0x7c16_62a4_174c_5222,
+            0x0ab3_e6ad_0ecf_8b8e,
+        ]),
+        Fp::from_raw([
+            0xc5b2_237c_bdab_3377,
+            0xa52e_f3ef_5909_b4e1,
+            0x27e3_b0b9_17b3_a21c,
+            0x1c67_5182_5126_20ae,
+        ]),
+        Fp::from_raw([
+            0x0c8a_9092_ac2b_ed45,
+            0xe37a_4a3e_07a7_d75d,
+            0x3d94_8d0c_85ba_d2e2,
+            0x2cdb_c998_dfd7_affd,
+        ]),
+        Fp::from_raw([
+            0x3e49_1a13_3bb6_3b2e,
+            0x2433_7350_309d_ff83,
+            0x774b_f67c_c0de_e333,
+            0x23b5_84a5_6e21_17b0,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x2f3d_20de_1465_e9a5,
+            0x0d13_3bc6_ba4e_c115,
+            0x8cb7_3030_a3c9_d2a1,
+            0x1e9e_2b31_0f60_ba9f,
+        ]),
+        Fp::from_raw([
+            0x60bc_d4f1_fa5b_22db,
+            0xc9ab_5dab_9875_20c4,
+            0xabc3_e720_140a_e746,
+            0x0e01_e365_ba5b_3031,
+        ]),
+        Fp::from_raw([
+            0x1387_d85b_c126_4e68,
+            0x4338_2011_b61c_9a4b,
+            0x7b71_2734_0498_d5c4,
+            0x0408_84cd_cfc6_4bfc,
+        ]),
+        Fp::from_raw([
+            0xa0a1_7de3_11ef_9668,
+            0x353f_1724_d606_7ed0,
+            0x0c74_a399_8f2b_ea36,
+            0x190b_1ee1_205e_b950,
+        ]),
+        Fp::from_raw([
+            0xfc28_d1f8_32bd_3b2c,
+            0xc1df_cf65_ce61_e165,
+            0xd04f_52fc_23cd_9c08,
+            0x1647_c72a_ec6c_4388,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x842e_595b_71e4_541d,
+            0x9f54_94d8_9fb4_8b02,
+            0x7998_80cc_4c87_3626,
+            0x2430_0063_46a0_145f,
+        ]),
+        Fp::from_raw([
+            0x3221_8502_52b7_57af,
+            0x9d85_3902_bb16_bacb,
+            0x3651_07a3_da3a_e7f6,
+            0x177b_9a08_3439_17e1,
+        ]),
+        Fp::from_raw([
+            0x39b1_1d04_666d_f4f8,
+            0x09cd_53d0_ae29_4234,
+            0x4e58_862a_68f5_e326,
+            0x04a4_20e6_42b1_1ae9,
+        ]),
+        Fp::from_raw([
+            0xcdfa_bedd_b6a2_5c8f,
+            0xde24_6185_8e95_6ccc,
+            0x105a_88fa_b0af_d810,
+            0x25d0_e0f7_39fb_39fc,
+        ]),
+        Fp::from_raw([
+            0x2c4d_4ffa_0c74_0a27,
+            0x320c_b156_10ea_ed45,
+            0x8590_5cbf_5865_1edc,
+            0x0447_6d91_b7ef_f2fd,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xe978_0fd9_ca28_6fae,
+            0x1c28_f6d5_e125_0cb5,
+            0x8bc9_ca24_19eb_8dea,
+            0x1090_c0b6_8b3d_7d7b,
+        ]),
+        Fp::from_raw([
+            0xbf28_99cb_5c66_17be,
+            0x376f_2d43_5855_c10e,
+            0x448a_725c_5c7c_d5ad,
+            0x2539_3ce3_b925_6d50,
+        ]),
+        Fp::from_raw([
+            0x97d1_44f0_fab4_6630,
+            0xed82_4388_d6b9_3426,
+            0xfc86_2f30_6e6e_5830,
+            0x2593_1c0c_7371_f4f1,
+        ]),
+        Fp::from_raw([
+            0x03f7_3f22_afbf_62f6,
+            0x8a4d_3531_85d5_8082,
+            0xc82a_ad51_b0fb_79cf,
+            0x2396_cb50_1700_bbe6,
+        ]),
+        Fp::from_raw([
+            0xb3f0_ce03_b872_4884,
+            0xa3dc_9068_c3cc_a7b5,
+            0x54ea_748a_7129_a7b0,
+            0x26a3_6348_3348_b589,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xa7a0_e5b4_5fcd_6897,
+            0xc99b_8933_3421_5f6b,
+            0x8d6f_1535_b92c_5478,
+            0x27ca_107c_a204_f2a1,
+        ]),
+        Fp::from_raw([
+            0x8d2d_02b3_b2d9_1056,
+            0xac15_f730_1178_581d,
+            0xe466_2bde_326b_2cce,
+            0x26da_28fc_097e_d77c,
+        ]),
+        Fp::from_raw([
+            0x7297_1ba5_6ee0_cb89,
+            0x6557_74c1_bb35_d575,
+            0x703e_3055_070a_c9cc,
+            0x056a_b351_691d_8bb3,
+        ]),
+        Fp::from_raw([
+            0x2d72_9af6_3237_6a90,
+            0xc225_47a1_1ffc_5015,
+            0xc76d_109a_2f48_1aa3,
+            0x2638_b57f_23b7_54ae,
+        ]),
+        Fp::from_raw([
+            0xea7b_c551_9ede_7cef,
+            0xc33e_46a5_32bd_ec80,
+            0x32f4_92c2_6051_84fd,
+            0x3047_54bb_8c57_d607,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x752a_c93a_9b76_19ac,
+            0x48ec_6857_fc55_4010,
+            0x514f_155b_5806_cbf7,
+            0x00d1_727f_8457_ee03,
+        ]),
+        Fp::from_raw([
+            0xc588_e509_4776_1fa3,
+            0xb5bc_a868_05ec_9419,
+            0x43ba_295a_303c_72fa,
+            0x00ee_1f3c_66fb_c05c,
+        ]),
+        Fp::from_raw([
+            0x61f9_297e_b675_d972,
+            0x10a1_9fbc_fc59_078c,
+            0x4a76_b5a1_d824_15fd,
+            0x0afa_fadc_f5b4_dd4a,
+        ]),
+        Fp::from_raw([
+            0xa899_6d12_4dd0_4d0a,
+            0x65a2_3483_5a0a_6a5e,
+            0x86ce_45e8_eed1_08ee,
+            0x0b24_49f3_9746_085e,
+        ]),
+        Fp::from_raw([
+            0x6a7d_df48_23dd_5dd6,
+            0xf6c6_f071_ec3b_dda7,
+            0xc9f3_7b00_4522_7095,
+            0x206b_0ce2_f1b2_c5b7,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x52c7_e102_9459_409c,
+            0xcaff_c3a4_ef20_fea8,
+            0xb696_e674_3362_8cd6,
+            0x0feb_a4fb_8783_4c7c,
+        ]),
+        Fp::from_raw([
+            0x8173_e972_336e_55d3,
+            0x3b06_f131_5e6d_70e1,
+            0x8926_752e_084e_0251,
+            0x254d_bfac_74c4_9b0b,
+        ]),
+        Fp::from_raw([
+            0x3771_9edf_a0ca_8762,
+            0x9606_c5bd_1791_0aeb,
+            0x4655_168c_3675_59e1,
+            0x0add_b137_2cee_4e16,
+        ]),
+        Fp::from_raw([
+            0x21a5_89d6_8831_d759,
+            0x6ca4_d8d8_1b1a_e162,
+            0xc799_024f_b019_f65c,
+            0x26b2_5b7e_257f_3e97,
+        ]),
+        Fp::from_raw([
+            0x1960_b105_9e11_3ba3,
+            0xa465_7b9a_b00b_db5b,
+            0x413b_8d4c_6587_87e5,
+            0x0909_95b7_9ace_c240,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x2974_178d_44f7_3b7b,
+            0xeb0d_8e40_e991_31f4,
+            0xc572_9968_7843_cea3,
+            0x08db_dc2e_21ef_11f2,
+        ]),
+        Fp::from_raw([
+            0x58f1_7093_9785_179f,
+            0x42fe_9c49_1596_ab67,
+            0x679f_af75_2a0f_78e3,
+            0x09e8_aba6_7148_1197,
+        ]),
+        Fp::from_raw([
+            0x7bc7_c683_f136_3d5c,
+            0x7efd_12a7_f9ee_c94b,
+            0x6590_52a7_ebaf_816c,
+            0x1deb_0518_0e83_3e45,
+        ]),
+        Fp::from_raw([
+            0x35fd_6523_e525_0879,
+            0x2489_97e8_b2c2_4af3,
+            0xa926_efbc_c04a_a9ee,
+            0x19a7_0ec6_bdfc_9098,
+        ]),
+        Fp::from_raw([
+            0xeb93_abe1_0bbf_1f64,
+            0x6635_3a37_77d8_a3f1,
+            0x8799_86f9_aab4_8905,
+            0x21d7_7366_0ada_fb8a,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xf6f4_6617_841c_3901,
+            0x97a6_b01f_cd66_7347,
+            0x3e20_ba63_7b89_d5d3,
+            0x09f1_890f_72e9_dc71,
+        ]),
+        Fp::from_raw([
+            0x08c2_145c_33b1_11c3,
+            0x48fa_1f89_7bf2_19d6,
+            0x2a30_0c61_e446_998d,
+            0x05af_4593_61eb_454d,
+        ]),
+        Fp::from_raw([
+            0xfa12_fc85_0cc8_b513,
+            0x5f33_6f15_f340_756c,
+            0x664a_66dc_75a6_5733,
+            0x0fa1_a1d6_829f_0345,
+        ]),
+        Fp::from_raw([
+            0x5365_5cf9_7a62_8bb0,
+            0xf428_0fcf_87f6_36f8,
+            0xbda0_b1c0_307a_d543,
+            0x02e4_7a35_bcc0_c3a0,
+        ]),
+        Fp::from_raw([
+            0x3662_1895_204d_0f12,
+            0x2034_1146_0112_4910,
+            0xb8f9_0e78_bf4c_24b7,
+            0x14f7_73e9_834c_6bde,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xffb0_9196_2fc8_f7cc,
+            0x642a_be7c_fd63_9992,
+            0x255c_f19d_29bc_7d8e,
+            0x102d_98cf_502e_d843,
+        ]),
+        Fp::from_raw([
+            0xcba6_86a7_e913_73c2,
+            0x20d4_c73a_d329_4738,
+            0x4c47_f6c6_5da7_ca23,
+            0x043d_d5f4_aa5a_76dd,
+        ]),
+        Fp::from_raw([
+            0x4cfd_b2b2_6bd1_1efa,
+            0x6f0e_7c79_743a_306f,
+            0xa6c0_d29a_48d4_f267,
+            0x2183_3819_c333_7194,
+        ]),
+        Fp::from_raw([
+            0xdc53_262a_3cff_2b53,
+            0xeb4e_ca24_6c31_1eca,
+            0xb474_a681_9d11_6ca3,
+            0x0f28_1925_cf5e_e649,
+        ]),
+        Fp::from_raw([
+            0x93ce_662f_15c2_38d6,
+            0xdf62_5dd6_0504_d5dc,
+            0x4470_9c77_46d6_824e,
+            0x0d3e_2477_a7b1_0beb,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xacbf_c3ef_f5ae_fc44,
+            0xd80f_80ab_51e7_3b49,
+            0x56ff_8a01_be9c_de35,
+            0x2cd7_f641_bedb_f669,
+        ]),
+        Fp::from_raw([
+            0x86dd_8613_1046_3cf8,
+            0x9149_d240_4581_1d7a,
+            0x4d09_380f_98b7_4e38,
+            0x29e9_5b49_2bf2_f95f,
+        ]),
+        Fp::from_raw([
+            0xf8b3_c50d_f83c_c13e,
+            0x9ae4_c51a_f6ff_eb57,
+            0x266e_fca8_6a6c_810f,
+            0x22da_66bc_62e8_f011,
+        ]),
+        Fp::from_raw([
+            0x4307_2d84_1925_554a,
+            0x220d_b79e_8129_df36,
+            0x3023_4917_94f4_aca3,
+            0x0fe6_d30d_e7a8_2d16,
+        ]),
+        Fp::from_raw([
+            0xc7e0_7ecb_298f_d67f,
+            0xd312_d03f_ef1a_dfec,
+            0x123c_46ef_f185_c23a,
+            0x0050_e842_a129_9909,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x885c_bbdb_6310_8c21,
+            0x666f_9ddf_714e_d7c5,
+            0xbe34_cc53_a42d_7733,
+            0x2130_a3a7_b322_1222,
+        ]),
+        Fp::from_raw([
+            0xe1d3_28e6_7b33_c9fa,
+            0xaa66_731f_34a9_3280,
+            0xd8d5_883f_e056_6c24,
+            0x2df9_ee29_4edf_99e3,
+        ]),
+        Fp::from_raw([
+            0xa4b6_fc5f_c337_2762,
+            0x5813_2396_dc25_0aeb,
+            0xf26e_b68c_c21f_f541,
+            0x1bf7_d6e4_89ad_8c0c,
+        ]),
+        Fp::from_raw([
+            0x5d47_78d3_1178_0e54,
+            0xcf7b_8077_28bf_7fe3,
+            0x61ea_f739_617a_b136,
+            0x0c60_2fa1_55be_9587,
+        ]),
+        Fp::from_raw([
+            0xb63f_af01_21ed_7f21,
+            0x5154_080a_2497_2fae,
+            0x3240_7d86_b8d2_2d7d,
+            0x2e50_e2c5_b36a_a205,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xb459_123b_1803_32e1,
+            0xf674_995e_e840_9b42,
+            0x5710_d629_0ec4_f782,
+            0x17c2_5109_82a7_b582,
+        ]),
+        Fp::from_raw([
+            0xb2ab_255f_376b_42a8,
+            0x2133_7b53_8eab_d2f6,
+            0x6803_ecf2_465b_885b,
+            0x0b0d_52f0_3c8a_f727,
+        ]),
+        Fp::from_raw([
+            0x6197_2dd8_fab8_bd14,
+            0xa9ac_77c6_c0f8_5d45,
+            0x5953_d88a_63f8_0647,
+            0x0f56_33df_1972_b945,
+        ]),
+        Fp::from_raw([
+            0x0354_9813_0a7f_1572,
+            0x2478_0ff4_3e76_e929,
+            0xe142_2e93_9681_1551,
+            0x0ebf_7ad2_9ca1_3804,
+        ]),
+        Fp::from_raw([
+            0x661b_1103_a720_ffe2,
+            0xe18f_94be_e27c_8a57,
+            0x0b02_9621_73bb_a343,
+            0x1aff_13c8_1bda_47e8,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x5713_be57_efac_6c07,
+            0xd3f3_1de1_a3b5_8ff3,
+            0xda24_65be_8550_5862,
+            0x2104_49db_f5cf_3061,
+        ]),
+        Fp::from_raw([
+            0xb93a_d1c3_ee62_9817,
+            0x19d1_e2f1_d300_1044,
+            0x7d75_cd6d_3c7b_9dbe,
+            0x0882_30c2_794e_50c5,
+        ]),
+        Fp::from_raw([
+            0x17a4_d6db_b20e_7e3a,
+            0x8cce_9a9e_16c7_7056,
+            0xda08_dc46_4138_dfc7,
+            0x1c40_8c25_6490_b0a1,
+        ]),
+        Fp::from_raw([
+            0x90dd_6f55_7e9e_3903,
+            0x8f7c_7765_4d58_4404,
+            0x22d1_7712_00fb_0765,
+            0x0745_17e0_81eb_4c1f,
+        ]),
+        Fp::from_raw([
+            0xa05a_df61_e12f_dcbf,
+            0xcee5_5305_59d6_cf0f,
+            0x8852_4bdb_2036_91b4,
+            0x02d0_4e9c_21df_1dbd,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xa81d_b32c_f1b6_7b13,
+            0xeb9b_4650_dae9_f11a,
+            0x82e1_3ebd_75de_3b58,
+            0x2eb7_a011_b8bc_e910,
+        ]),
+        Fp::from_raw([
+            0xe603_0c18_f0cf_17b5,
+            0x4d2a_c6bf_9536_8304,
+            0x299f_75d6_e8a8_49b5,
+            0x2efd_a77e_d35f_4af0,
+        ]),
+        Fp::from_raw([
+            0xc51b_2440_192a_e064,
+            0x61a7_3d10_852b_8114,
+            0x2edd_beda_6520_6d4f,
+            0x0919_9dca_fd50_ce64,
+        ]),
+        Fp::from_raw([
+            0x7618_1cb4_216e_1562,
+            0xcb65_5d8c_1797_e9fa,
+            0x4dd3_19db_666a_75b5,
+            0x268c_5cfc_446d_399c,
+        ]),
+        Fp::from_raw([
+            0x0128_54ed_a11a_18dc,
+            0x97b4_4e91_2cce_6687,
+            0x26b0_e9a3_6c80_5786,
+            0x2303_a652_c949_0718,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x3707_3f4e_fb35_fbdf,
+            0xbc53_53eb_1108_68d2,
+            0xc3f0_41f3_1dc4_5922,
+            0x27c5_3563_b12a_6ee2,
+        ]),
+        Fp::from_raw([
+            0x2c27_8f22_615d_2b0e,
+            0x9969_b524_8cfe_90f4,
+            0xf02b_d82d_0a51_0904,
+            0x1201_a87e_af4a_e618,
+        ]),
+        Fp::from_raw([
+            0x6cb4_b43d_ab2a_443d,
+            0xcafc_b1ba_2c51_e570,
+            0xad82_1499_7bb0_69be,
+            0x2c43_1694_39fc_d69e,
+        ]),
+        Fp::from_raw([
+            0x4fb9_847e_6304_d944,
+            0x4f46_cbb3_6d70_2e3c,
+            0xea03_c45d_6984_c689,
+            0x0683_5973_1535_9040,
+        ]),
+        Fp::from_raw([
+            0x7779_5ad3_a798_d183,
+            0xb042_5e15_8314_197b,
+            0xafb9_3b12_8feb_d16f,
+            0x0354_5706_706e_ab36,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xedb3_93b7_1a0c_0e6b,
+            0x740b_ed23_a6a3_7870,
+            0xd35f_1fc0_51b3_1728,
+            0x1a33_c254_ec11_7619,
+        ]),
+        Fp::from_raw([
+            0xf338_e517_f169_0c78,
+            0x6e88_f71e_759b_87e6,
+            0x67b0_c002_281c_af99,
+            0x1ffe_6968_a447_0cd5,
+        ]),
+        Fp::from_raw([
+            0x107f_4e02_e355_b393,
+            0x140d_dd5d_2a5c_4483,
+            0xecb0_59c8_99fd_80f4,
+            0x0fd6_6e03_ba88_08ff,
+        ]),
+        Fp::from_raw([
+            0x7be3_396b_7fe0_13ab,
+            0x6c86_17a7_bdd5_d74a,
+            0x1973_9455_2906_b17e,
+            0x263a_b69f_13b9_66f8,
+        ]),
+        Fp::from_raw([
+            0x6eb7_e03e_39ba_4046,
+            0x3bd8_7d5a_a395_8fdd,
+            0x5054_d5a1_65de_413e,
+            0x16a4_25e4_7d11_1062,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xfcc5_f73a_f913_8d9a,
+            0x53cc_31d1_3e39_e909,
+            0xad75_2f03_c673_f0e2,
+            0x2dc5_10a4_719e_c10c,
+        ]),
+        Fp::from_raw([
+            0xcb4a_a709_a945_76e5,
+            0x423c_5179_329b_7a82,
+            0x1bd1_cad2_3d07_dda3,
+            0x24df_8e8d_856c_5b5e,
+        ]),
+        Fp::from_raw([
+            0xbdf2_4a6c_dce5_620f,
+            0x7628_249a_01b0_9561,
+            0x3cd5_c689_15a0_42e8,
+            0x2bcc_94ff_4fc3_c76f,
+        ]),
+        Fp::from_raw([
+            0xb48b_e868_52da_97df,
+            0xd329_5f52_c38c_ffe6,
+            0x8de5_4e34_3df7_c429,
+            0x076c_1e88_dc54_0c8d,
+        ]),
+        Fp::from_raw([
+            0xa990_fb8e_12cb_46e1,
+            0xe40e_e160_1120_947d,
+            0x1c05_1fb1_2d9a_5e4f,
+            0x09b5_f209_a451_ac43,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x0683_af75_eb67_7c07,
+            0x64e9_424f_55b0_f1ea,
+            0xaa88_d6a4_4135_a6ab,
+            0x205f_17b0_d872_9e2e,
+        ]),
+        Fp::from_raw([
+            0x0cdd_1edf_9f23_7029,
+            0x0916_81f0_a417_6172,
+            0x9126_38c3_8be0_46cd,
+            0x281c_5c68_8836_f6cf,
+        ]),
+        Fp::from_raw([
+            0x4afa_44f2_5926_21f5,
+            0x009a_44e7_a02e_a50e,
+            0x5f4d_6744_8c47_1cf3,
+            0x1a05_3e68_78e9_00f4,
+        ]),
+        Fp::from_raw([
+            0x8174_2839_d59e_064c,
+            0x68ef_cb89_7e7b_bee9,
+            0x07fb_7cea_c84e_4f54,
+            0x100d_c7d4_26de_be30,
+        ]),
+        Fp::from_raw([
+            0x0b1c_b31b_411e_49c8,
+            0xb289_05bd_b62c_82c8,
+            0xbb87_e2cf_adc8_b75f,
+            0x1702_2672_a016_a957,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xe0c2_7203_f954_f4d2,
+            0x9fb8_b547_182b_170d,
+            0x7105_3a87_ebe1_5123,
+            0x1086_db7e_2760_fc8b,
+        ]),
+        Fp::from_raw([
+            0xb85d_d24f_d758_4064,
+            0x2b41_fb65_a185_536f,
+            0x0246_0ae4_c294_2fac,
+            0x1538_4fe3_9d73_b633,
+        ]),
+        Fp::from_raw([
+            0x519e_5513_5770_9008,
+            0x47b1_a853_205f_cfb5,
+            0x4bf4_abc5_342c_6c74,
+            0x2ebb_599f_e913_6d42,
+        ]),
+        Fp::from_raw([
+            0xd996_fac6_f4d3_7288,
+            0x4cf2_7829_2b4c_e3ee,
+            0xfec3_c0f0_542e_4c5a,
+            0x1b4b_5e87_cfb9_262c,
+        ]),
+        Fp::from_raw([
+            0xb637_febe_659e_5057,
+            0xbb4a_7581_bb4f_ba60,
+            0x801f_3f82_e302_cafb,
+            0x2465_053a_e50b_6885,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x6e0a_b9d3_d548_59ef,
+            0xda9f_05e1_8b37_08bf,
+            0x095c_5bb5_d38f_1b97,
+            0x114f_32ed_cdea_09cd,
+        ]),
+        Fp::from_raw([
+            0xee28_d8c2_543c_7148,
+            0x2e5e_5519_f3d1_8123,
+            0x6b38_7cd7_7be7_79ac,
+            0x2bc7_0dfe_b2ba_ab2f,
+        ]),
+        Fp::from_raw([
+            0x12e4_9bfe_32c0_5415,
+            0xa783_48b9_f6ec_68a4,
+            0x775e_3a61_ad7e_77b6,
+            0x01c9_bf7a_203c_e22b,
+        ]),
+        Fp::from_raw([
+            0x9f4a_d00c_cb57_ee9b,
+            0x55cf_b575_ff6a_97cd,
+            0x7bed_b029_5fbb_cec3,
+            0x0514_b0fe_5909_ea88,
+        ]),
+        Fp::from_raw([
+            0x03e3_202d_7b6c_1b7e,
+            0x2092_520b_12a2_01af,
+            0x1a13_2a8b_0589_10a1,
+            0x267c_76ec_8193_4cc8,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xfb15_1cf7_57bd_e5d6,
+            0xf162_2493_ce83_e95c,
+            0xc78c_84ba_bbb4_70ad,
+            0x2917_0e33_22b3_d8d5,
+        ]),
+        Fp::from_raw([
+            0x36dd_596c_a41d_9519,
+            0x5c6f_09a4_5486_cab5,
+            0xaf33_e5d3_873f_9c33,
+            0x019f_6a81_24b1_9e33,
+        ]),
+        Fp::from_raw([
+            0x5405_d036_242b_60e9,
+            0x009e_d8ea_1715_18ae,
+            0x8b34_8e9d_b198_1c27,
+            0x1904_aa4d_6908_544a,
+        ]),
+        Fp::from_raw([
+            0x3b93_2a47_6455_ff1a,
+            0xb3ce_e1de_9dd6_f647,
+            0xf7f0_4395_6694_e422,
+            0x26f1_7873_949b_c679,
+        ]),
+        Fp::from_raw([
+            0xbdca_c9b1_8bc4_8f75,
+            0x40c4_7603_1197_131e,
+            0x193b_3372_0b8a_a540,
+            0x1ac6_68f6_12b8_243c,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xa52f_8156_8ef0_663d,
+            0xccfb_f855_5be9_796d,
+            0x196d_ae45_bf62_4766,
+            0x0996_d961_a75c_0d07,
+        ]),
+        Fp::from_raw([
+            0x5425_b395_c24f_c819,
+            0x74d1_71f9_9c63_febb,
+            0xfd50_d1b4_383f_be66,
+            0x030c_97e1_b8ca_d1d4,
+        ]),
+        Fp::from_raw([
+            0xee22_6f2d_8bd0_848f,
+            0xb3e5_23f1_fe50_2642,
+            0x3953_3702_55b6_8f89,
+            0x06e3_ad6a_4690_0e2d,
+        ]),
+        Fp::from_raw([
+            0xa329_8905_88cc_916e,
+            0x65cb_94b0_e045_5153,
+            0x6b68_80e4_2f98_80f5,
+            0x1d6b_3755_331c_d021,
+        ]),
+        Fp::from_raw([
+            0x3dd3_8c08_bae5_31f2,
+            0xc351_89dc_0b85_ac03,
+            0x59b0_4153_5e73_0ac8,
+            0x28e4_dcba_4b96_f12a,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x7a3b_3e4b_c4a4_7a14,
+            0xb071_2a47_6260_376c,
+            0x8ccf_484f_2974_b6a6,
+            0x08b6_0860_46a8_3550,
+        ]),
+        Fp::from_raw([
+            0x8964_3e15_b9bb_3b52,
+            0xb6fd_85fb_a6a0_536a,
+            0x444b_cec9_7812_019b,
+            0x162c_d2ca_7fe3_b5f1,
+        ]),
+        Fp::from_raw([
+            0x998c_01c6_4d48_3a76,
+            0xb5c9_a9c1_1920_63d1,
+            0x05af_5b11_937e_4f5c,
+            0x28f1_e03b_aaea_9bbc,
+        ]),
+        Fp::from_raw([
+            0x6031_a0bb_6791_ce10,
+            0x0127_d2aa_b4aa_7136,
+            0xa395_af27_34c2_5faa,
+            0x1bdb_0627_78d7_c15d,
+        ]),
+        Fp::from_raw([
+            0x5032_9ebd_d247_49cb,
+            0x0fc9_8870_b232_4a8b,
+            0xcb29_14e8_2962_7e0e,
+            0x2375_8395_02e0_9890,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xe92d_3361_1ed7_bb50,
+            0x332d_c87c_fb2d_f456,
+            0xad7c_5566_8dc9_423a,
+            0x1fa8_662f_bcb6_1fb3,
+        ]),
+        Fp::from_raw([
+            0x3f7f_9736_079d_7694,
+            0x46e2_fb2c_47a5_138f,
+            0xf870_7f72_1716_c8a4,
+            0x1e4f_ad2d_d6b0_a6f1,
+        ]),
+        Fp::from_raw([
+            0x61b0_f5f1_3731_ffe7,
+            0xba3b_d050_059f_53d2,
+            0x6df6_f5fc_dd1f_a788,
+            0x2112_56d1_6c72_69fd,
+        ]),
+        Fp::from_raw([
+            0xf4a1_079b_12bc_c5a5,
+            0xf420_60e5_74dd_a341,
+            0x4f8e_2a2e_6af0_8318,
+            0x2e49_084b_336e_ceaa,
+        ]),
+        Fp::from_raw([
+            0x758d_2589_1fb0_0bb9,
+            0x1aec_ea08_dfe1_4cab,
+            0x3bf3_5192_ac68_0821,
+            0x0ce1_9f54_cdc3_9f7f,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x84a8_468b_ab2c_14cb,
+            0x8dc6_0451_e4ae_4e1c,
+            0x3cc3_9422_1261_d874,
+            0x0011_c5d5_6c39_0e89,
+        ]),
+        Fp::from_raw([
+            0x14a8_cd80_5157_9b4c,
+            0xca60_e17b_fa39_b475,
+            0x8a9e_05ee_6af3_dbb7,
+            0x17d7_9ff0_6b63_ac2a,
+        ]),
+        Fp::from_raw([
+            0xcab0_0173_6390_15fa,
+            0xb1a8_b35c_d641_6a2e,
+            0xdc74_5600_9359_2b06,
+            0x19a7_d3a4_46cb_5393,
+        ]),
+        Fp::from_raw([
+            0xaa6c_3c97_2370_37a6,
+            0x31f0_4596_d892_8da7,
+            0x2a80_8b2e_1b92_82f3,
+            0x030c_00a0_933d_cdba,
+        ]),
+        Fp::from_raw([
+            0x9370_be8a_ab64_139c,
+            0x935d_2d00_184c_4acc,
+            0xae25_ad08_0695_382e,
+            0x16bc_b447_ce2d_50f3,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x3925_5b7c_d66f_eb1d,
+            0xe621_24f3_7cab_7b6d,
+            0x5ea4_ec87_1531_2997,
+            0x1234_1b46_b015_0aa2,
+        ]),
+        Fp::from_raw([
+            0xdc89_212d_b6a4_9ff4,
+            0x002f_c28e_296d_1044,
+            0xb72a_97b2_bf61_0c84,
+            0x0e86_d139_17f4_4050,
+        ]),
+        Fp::from_raw([
+            0xd059_025b_110c_7262,
+            0x1052_a181_f8f2_eb14,
+            0xd357_e00b_53d7_f30d,
+            0x08e6_eb40_89d3_7d66,
+        ]),
+        Fp::from_raw([
+            0xe10c_48ce_97ca_7b18,
+            0x0415_ccb3_51a1_e0ce,
+            0x4738_d15d_d148_1a0c,
+            0x2ea1_2385_6245_f6c8,
+        ]),
+        Fp::from_raw([
+            0x664d_b0f9_c84d_fa6f,
+            0x4195_7890_2541_3abf,
+            0x3446_e003_30b1_6310,
+            0x2dca_72b2_ebca_b8c2,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xc64a_26cb_d42b_6a6b,
+            0xf2f6_b423_5f03_6fa4,
+            0x6332_9f58_5ec9_24b3,
+            0x06ff_9ed5_0d32_7e84,
+        ]),
+        Fp::from_raw([
+            0xc630_a453_5afb_f730,
+            0xe2a6_0e0c_ca84_ea2a,
+            0x47f7_c9bd_a3d5_4df8,
+            0x246a_10b7_e3e0_0899,
+        ]),
+        Fp::from_raw([
+            0x5c96_b39d_688b_6691,
+            0x2f84_6a71_5ae6_7ad7,
+            0x1871_9ed9_9d70_0ee5,
+            0x22a6_3501_c5f0_4b90,
+        ]),
+        Fp::from_raw([
+            0x40ec_07e5_14fa_e937,
+            0xb916_4f58_351d_8aa1,
+            0x7179_9ac5_d2e2_24cd,
+            0x2f4c_5047_7f7f_d9c6,
+        ]),
+        Fp::from_raw([
+            0x0cda_32ca_d851_567e,
+            0x38f0_ba8a_4a23_d4b5,
+            0x13b1_7f4d_876d_9a1e,
+            0x10ff_b7aa_d1f5_1c7d,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x1a05_6935_c358_03ae,
+            0x7840_2735_2187_e7af,
+            0xa4d3_9722_532d_5420,
+            0x0e9c_efdd_c3c2_d3be,
+        ]),
+        Fp::from_raw([
+            0xa3e8_3bdd_4ba6_2b41,
+            0xa165_6f96_a33c_8978,
+            0xc233_52e6_dc6e_a4af,
+            0x07af_84a4_d314_1e7a,
+        ]),
+        Fp::from_raw([
+            0xe940_f3ec_8a22_c3c5,
+            0x1a39_323d_6e89_b638,
+            0xf8de_00d1_4b1e_566d,
+            0x2d9e_31a1_0aeb_c761,
+        ]),
+        Fp::from_raw([
+            0x7656_747b_e27e_64c7,
+            0xd360_34b3_14da_d844,
+            0x33db_1afd_592f_66f1,
+            0x27f1_9a65_32e6_6b53,
+        ]),
+        Fp::from_raw([
+            0x7e4d_617d_47d0_7ffd,
+            0x180e_d99f_8f31_55cd,
+            0x54b2_024c_3b4a_577a,
+            0x0058_fa3c_8454_d633,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x7089_6495_6816_a5d5,
+            0xb041_4a20_5d3a_175d,
+            0x6795_7c08_0699_343e,
+            0x0416_27b6_715b_7809,
+        ]),
+        Fp::from_raw([
+            0x8988_8f12_b727_c52d,
+            0xd982_0147_1cf1_f665,
+            0x7f63_2e57_b958_ccec,
+            0x006a_c49d_d925_3edc,
+        ]),
+        Fp::from_raw([
+            0xec46_a6bf_1830_1398,
+            0xec0c_9c0d_6d25_a9a4,
+            0xb1d8_c361_6bbe_3386,
+            0x0131_adff_d8bd_7254,
+        ]),
+        Fp::from_raw([
+            0xcf03_0e1c_d8f9_f5b6,
+            0x7797_7ad7_e25e_49a3,
+            0x4138_e413_ef62_a283,
+            0x1c4a_6f52_c9fc_cf7a,
+        ]),
+        Fp::from_raw([
+            0xd1f7_958d_2c26_45f6,
+            0xfee0_48ae_2078_aeb7,
+            0x9465_51b3_860e_a479,
+            0x03f2_a6be_51ec_677f,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x4d73_7639_6b8d_dc62,
+            0x8a13_72d8_5431_1956,
+            0x391a_0cb7_8ef3_a964,
+            0x2da7_70aa_d2c2_eb09,
+        ]),
+        Fp::from_raw([
+            0x3806_2afb_75d6_4a03,
+            0xc9d4_5fe8_66c3_59c7,
+            0xdc18_02fe_bfab_02ce,
+            0x1527_8463_665f_74cd,
+        ]),
+        Fp::from_raw([
+            0x8d58_0638_ac54_c773,
+            0x34e3_8ea9_66a0_8a6f,
+            0xc973_1027_0905_18d4,
+            0x12fe_278a_a365_44ea,
+        ]),
+        Fp::from_raw([
+            0x21b0_6ff3_0b6a_23b6,
+            0xd858_7604_ca4f_0d6e,
+            0x4c45_d119_d3f4_cc7f,
+            0x149b_9c80_2182_558a,
+        ]),
+        Fp::from_raw([
+            0x0adc_4959_b691_dfe4,
+            0x55e0_44fd_60db_ac9a,
+            0x17d1_9319_772f_3c98,
+            0x0812_e7b4_d847_bc85,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x2a66_9737_03a0_c61b,
+            0x5558_13c7_e750_3aea,
+            0xd8df_7f28_a0bf_aa7f,
+            0x02ed_8d8d_deaf_e3d9,
+        ]),
+        Fp::from_raw([
+            0xa130_4038_662d_4db8,
+            0x55e5_e4d9_a03d_6b6b,
+            0x4deb_6029_f921_029e,
+            0x0ebd_073b_a053_7b51,
+        ]),
+        Fp::from_raw([
+            0x44ee_75b6_2eff_9f59,
+            0x55b3_e792_c6af_a08b,
+            0x05c6_ba8d_2ccd_0282,
+            0x15c7_54d5_b14b_2c42,
+        ]),
+        Fp::from_raw([
+            0xc29e_7cbc_30e8_732a,
+            0x1cc1_2352_70f4_cbc5,
+            0xb0ed_8fa6_fa31_1b39,
+            0x1695_15c8_9ac5_479d,
+        ]),
+        Fp::from_raw([
+            0x6d91_61f5_cd9a_4fef,
+            0x08bd_c29f_6ff0_3769,
+            0x2388_f262_1001_1016,
+            0x2547_9fbf_b3a6_8f98,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xce09_486e_94be_6071,
+            0xa7f8_e6e9_7218_2196,
+            0x3c85_2cb0_311a_578c,
+            0x1447_5c4b_d520_451f,
+        ]),
+        Fp::from_raw([
+            0x5c4e_7336_3d09_7799,
+            0xd3a8_4750_2aec_8d5f,
+            0xc9ba_f279_8833_a1df,
+            0x045a_6910_66cc_66be,
+        ]),
+        Fp::from_raw([
+            0x013b_8bcb_37eb_a683,
+            0x147a_8ca0_3722_1b90,
+            0xb833_ac8a_11e3_a3f0,
+            0x2602_9c0c_267c_799f,
+        ]),
+        Fp::from_raw([
+            0xb810_1d5b_948d_1641,
+            0x73ce_12a6_a94a_3e45,
+            0xf7c9_4696_9c1c_2608,
+            0x163f_acb3_4ff5_72fb,
+        ]),
+        Fp::from_raw([
+            0xaf8e_e00c_4240_ee28,
+            0x13e0_6a62_75e5_8688,
+            0x1d96_9320_cc69_d5ec,
+            0x2c71_4e96_e191_3b35,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xd52b_804e_ff1d_5fa6,
+            0xddd6_268f_06de_bfe2,
+            0x5aba_8466_5ecd_2bf9,
+            0x1c16_61e2_a7ce_74b7,
+        ]),
+        Fp::from_raw([
+            0xa8c3_c068_b7dc_2c71,
+            0x6635_b34c_2a08_89fe,
+            0x5e5a_f3e6_619a_47d2,
+            0x06a6_9ae7_95ee_9bfe,
+        ]),
+        Fp::from_raw([
+            0x3141_6c85_d731_d46a,
+            0x74db_dbad_f541_95c7,
+            0xc5d2_8b4c_19a3_6093,
+            0x113d_5853_5d89_2115,
+        ]),
+        Fp::from_raw([
+            0x2ca1_a740_63b4_6101,
+            0xe534_f1ff_47f7_917a,
+            0x38ff_97d7_61da_6042,
+            0x2ab8_9102_e2b8_d5e6,
+        ]),
+        Fp::from_raw([
+            0x3980_4fd6_a15a_d1b3,
+            0x3490_3189_3da2_b4fd,
+            0x9627_30c4_5e69_9546,
+            0x03c1_1ca7_9e41_fdfe,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x6adf_040f_aaf2_669c,
+            0xf7f6_7b4d_4cfe_846c,
+            0x8801_4ddb_bbfc_9da1,
+            0x2709_6c67_2621_4038,
+        ]),
+        Fp::from_raw([
+            0xaa9d_c2b5_7ef5_be0d,
+            0x66db_790c_e486_130c,
+            0xd504_d4de_eb53_b13c,
+            0x2de3_2ad1_5497_aef4,
+        ]),
+        Fp::from_raw([
+            0x2557_5444_8eef_d001,
+            0x28c7_38dd_daec_9f3d,
+            0xfd5d_3413_1072_2a2d,
+            0x0dc1_08f2_b0a2_80d2,
+        ]),
+        Fp::from_raw([
+            0x7d34_ca50_365d_832f,
+            0xbcdc_3eeb_c409_be7c,
+            0xc968_58a1_bb9e_fad5,
+            0x1869_f3b7_63fe_8164,
+        ]),
+        Fp::from_raw([
+            0x8c6d_3376_7129_682d,
+            0xb616_945e_16a5_68d4,
+            0xf825_59fe_6a91_1843,
+            0x022e_d3a2_d9ff_31cb,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x10c6_aec0_77d0_26bc,
+            0x92fc_a1f2_7c19_c266,
+            0x3944_ed13_65bd_0e72,
+            0x2155_d600_5210_169e,
+        ]),
+        Fp::from_raw([
+            0x3ea5_928c_8cae_ae85,
+            0xec0c_0556_c91a_f3db,
+            0xcae9_3263_f5f1_b4bb,
+            0x0de1_ba7a_562a_8f7a,
+        ]),
+        Fp::from_raw([
+            0x6351_dfa7_da90_2563,
+            0x126f_740b_ce8d_637b,
+            0xcfce_5bf4_6ec7_da38,
+            0x05db_b440_6024_beab,
+        ]),
+        Fp::from_raw([
+            0xc97e_5427_a368_fd5e,
+            0x00e7_89e3_fcd7_2dcc,
+            0xd4d8_dc8a_d778_d32c,
+            0x05d4_149b_aac4_13be,
+        ]),
+        Fp::from_raw([
+            0x9212_e221_72c2_7b2e,
+            0xf0b6_802f_a941_c787,
+            0x9be5_046e_7397_e76f,
+            0x01cd_f8b4_52d9_7c2b,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x1b0a_2227_9d46_c07c,
+            0xc4d7_c501_5ece_d8c7,
+            0xaf80_85ff_81ad_ce33,
+            0x1fc6_a718_6702_7f56,
+        ]),
+        Fp::from_raw([
+            0x568e_0fda_96aa_afc2,
+            0xa9e1_85b7_5306_d9c3,
+            0x5d4d_59a5_a7a3_a42b,
+            0x1040_bef4_c642_d034,
+        ]),
+        Fp::from_raw([
+            0xa808_1475_ab8f_ad0d,
+            0x2b21_e956_7643_1918,
+            0xff2c_91b2_8933_4a4d,
+            0x16b7_9c3a_6bf3_16e0,
+        ]),
+        Fp::from_raw([
+            0x3cbe_1cdc_d59f_474e,
+            0xa340_70e5_2b60_1fc1,
+            0x434b_3a13_87e3_c8c6,
+            0x20df_f1bc_30f6_db6b,
+        ]),
+        Fp::from_raw([
+            0xdfb7_fd85_12ae_060d,
+            0x062d_d417_1a72_6a8b,
+            0xc254_9550_30a9_70f8,
+            0x0212_ac2a_b7a6_eaae,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x13c8_119f_6996_ae09,
+            0xb02d_c031_34f0_0442,
+            0x869a_109c_9215_637c,
+            0x2f29_3774_9147_4442,
+        ]),
+        Fp::from_raw([
+            0x5837_d9f3_2d81_4bfa,
+            0x73be_9f38_66aa_284c,
+            0x25ec_93c3_3fea_6032,
+            0x0984_ca6a_5f91_85d5,
+        ]),
+        Fp::from_raw([
+            0xeb0e_e929_4b24_f028,
+            0xe491_361c_8a6b_d19c,
+            0x0d29_9bd6_fa81_220d,
+            0x0d08_0a6b_6b3b_6070,
+        ]),
+        Fp::from_raw([
+            0xce92_9ed7_c85a_4544,
+            0x21ac_c85b_6400_264d,
+            0x6789_5306_38cb_0ad8,
+            0x0e65_cd99_e84b_052f,
+        ]),
+        Fp::from_raw([
+            0x5597_5da1_2736_920b,
+            0xe30e_3d20_380f_f6a6,
+            0x4808_f72c_716c_d05e,
+            0x2e20_8875_bc7a_c122,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x4a60_d1aa_8592_bad5,
+            0xae2e_3b89_4afd_29f6,
+            0x76a0_b0ff_3d7d_fac1,
+            0x2989_f3ae_477c_2fd3,
+        ]),
+        Fp::from_raw([
+            0x3a74_c3ce_a718_9459,
+            0x9181_06a4_6329_0a3e,
+            0x9222_d101_e6fa_c0ce,
+            0x1136_1ce5_44e9_4137,
+        ]),
+        Fp::from_raw([
+            0x34c8_bd05_a206_1438,
+            0xd122_a822_b8fb_366c,
+            0xa539_e10c_173f_6a75,
+            0x1e8d_014b_86cb_5a7d,
+        ]),
+        Fp::from_raw([
+            0x212e_e2c2_8ee9_8733,
+            0xa0c2_3241_67ef_6c91,
+            0x7ba8_12ad_2955_8e23,
+            0x173f_65ad_ec8d_eee2,
+        ]),
+        Fp::from_raw([
+            0x991b_d695_310e_ddd9,
+            0x5da5_df7a_d454_99d0,
+            0xafee_8bd0_c779_ac3e,
+            0x01c3_6daa_f9f0_1f1b,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x4a6f_5741_f381_e562,
+            0xf277_d1a3_f2fc_8994,
+            0xaa9a_b1c4_85bb_85ff,
+            0x1353_acb0_8c05_adb4,
+        ]),
+        Fp::from_raw([
+            0x20f1_c87a_3b06_4d34,
+            0x009d_33de_b4f9_3aeb,
+            0x1860_e71e_a118_8ee4,
+            0x2e5a_bd25_3720_7cad,
+        ]),
+        Fp::from_raw([
+            0x4217_26ba_8f69_455c,
+            0x13de_b4eb_3491_3a13,
+            0xd02e_edbb_7ab8_5625,
+            0x191d_5c5e_daef_42d3,
+        ]),
+        Fp::from_raw([
+            0xee79_39dd_2dcd_089e,
+            0x82a0_4c74_c127_de9d,
+            0x82a2_63fe_a6d7_599d,
+            0x11d7_f8d1_f269_2642,
+        ]),
+        Fp::from_raw([
+            0x1bad_0852_86ca_c971,
+            0x3445_cb4c_d6bc_6f95,
+            0x90f7_9ad5_e679_9797,
+            0x0421_8fde_3668_29ed,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x27b7_1c73_0d76_d6dd,
+            0x027b_73b4_8930_1c32,
+            0x0483_97ca_5f47_a202,
+            0x0070_772f_7cf5_2453,
+        ]),
+        Fp::from_raw([
+            0xc1dc_04db_e3d2_b861,
+            0x6768_1a98_cd05_1634,
+            0xc865_b065_687a_1d9b,
+            0x038a_389b_aef5_d9a7,
+        ]),
+        Fp::from_raw([
+            0xf198_6078_9015_a6e5,
+            0x0f39_d009_66a5_0bea,
+            0xcda4_46b2_b4b5_9ccd,
+            0x09a5_eefa_b8b3_6a80,
+        ]),
+        Fp::from_raw([
+            0x9d16_212c_7584_cd8c,
+            0x3d9e_c99e_dfac_b748,
+            0xb969_c145_109b_4b58,
+            0x01b5_8884_8b8b_47c8,
+        ]),
+        Fp::from_raw([
+            0xfed9_1e30_d429_54a6,
+            0x545e_5abf_a323_d817,
+            0x6e1a_f6df_c334_1419,
+            0x0b84_6e4a_390e_560f,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x85ca_bfe8_5ce7_2fe3,
+            0xeb15_13bc_394f_c4f9,
+            0x0d43_a02d_db90_0040,
+            0x23a6_679c_7d9a_db66,
+        ]),
+        Fp::from_raw([
+            0xf72f_31d6_fe08_9254,
+            0x4c34_02fb_7c85_eccc,
+            0x3e5c_aa35_f135_1e9f,
+            0x2e03_74a6_9919_7e34,
+        ]),
+        Fp::from_raw([
+            0x37d4_1913_a7a2_7b48,
+            0x4b8c_c0b1_176b_b9ec,
+            0x7f7a_08af_4cde_3ff6,
+            0x0752_cd89_9e52_dc4d,
+        ]),
+        Fp::from_raw([
+            0x99e2_873d_d7ae_55a7,
+            0x0275_142b_664b_802c,
+            0xc349_a2b6_d573_97a5,
+            0x068f_8813_1272_99da,
+        ]),
+        Fp::from_raw([
+            0xb374_330f_2da2_02b5,
+            0x8687_2d04_a295_b5b8,
+            0x6775_7416_7434_b3f9,
+            0x2ba7_0a10_2355_d549,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xb860_9e70_a0b5_0828,
+            0x09f9_099b_825d_d289,
+            0xa334_d1df_03b5_5213,
+            0x2c46_7af8_8748_abf6,
+        ]),
+        Fp::from_raw([
+            0x96d2_9e57_63e8_f497,
+            0xe3a5_2c2d_1a31_9572,
+            0x0100_9a2b_448a_e881,
+            0x05c5_f20b_ef1b_d827,
+        ]),
+        Fp::from_raw([
+            0x3ac8_c9fe_61b7_3871,
+            0x0cd0_83a2_c649_d9f2,
+            0x842a_381f_6006_e2c6,
+            0x0dc6_385f_dc56_7be5,
+        ]),
+        Fp::from_raw([
+            0xd255_baa8_114b_369c,
+            0x0378_d5b8_4150_d25e,
+            0x19d4_9911_b867_0fa7,
+            0x142d_3983_f3dc_7f7e,
+        ]),
+        Fp::from_raw([
+            0x00fa_18c1_b2df_67bf,
+            0xf36f_86a7_a99a_a35c,
+            0xfd7e_6d98_c96a_0fa0,
+            0x29a0_1efb_2f6a_a894,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xd2a1_af04_eb61_3a76,
+            0x0ab9_e8af_c645_55b7,
+            0x38c4_a506_6644_ec63,
+            0x0525_ffee_737d_6051,
+        ]),
+        Fp::from_raw([
+            0xf177_cf42_3830_1dc8,
+            0x7e16_4f61_4910_264e,
+            0xf076_677c_a0e8_2276,
+            0x1e80_7dca_81d7_9581,
+        ]),
+        Fp::from_raw([
+            0xdba5_6082_dbd8_757c,
+            0x4c02_23e0_f733_a52f,
+            0x9351_0816_4724_74d3,
+            0x0385_fb3f_89c7_4dc9,
+        ]),
+        Fp::from_raw([
+            0xf558_f337_bab0_ea01,
+            0xcfab_d701_6fd6_ef1a,
+            0xe1a6_298e_53ca_e59f,
+            0x0376_40dc_1afc_0143,
+        ]),
+        Fp::from_raw([
+            0xb7e4_c0de_8960_74b4,
+            0x5fd5_6cf0_31da_8050,
+            0xf12a_6c52_6082_9eee,
+            0x1341_999a_1ed8_6919,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x7b24_4f65_bed8_ece7,
+            0x73af_d642_efdc_c565,
+            0x5690_6d4b_afb1_0ad7,
+            0x069e_b075_866b_0af3,
+        ]),
+        Fp::from_raw([
+            0xea19_09a6_1903_3696,
+            0xd272_6101_d3af_aa02,
+            0x95b3_8e8e_08b3_e646,
+            0x171c_0b81_e621_36e3,
+        ]),
+        Fp::from_raw([
+            0x5575_1075_02ac_ced8,
+            0xcbbd_cb39_bfe6_96f9,
+            0xb6eb_55c3_1175_3e84,
+            0x2c81_814c_9453_f51c,
+        ]),
+        Fp::from_raw([
+            0xe39b_8a86_a7c3_a604,
+            0xb04b_3903_2adc_a92c,
+            0xe3b3_3fad_cf27_4b2a,
+            0x29d8_43c0_415d_35d9,
+        ]),
+        Fp::from_raw([
+            0xa766_9cf3_fae7_728b,
+            0xd8e1_5ea5_947f_2cda,
+            0x8436_bccd_abb7_8750,
+            0x085d_6a10_70f3_513d,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x9e2a_5cda_412f_c394,
+            0xdbf1_dd4e_6706_b02c,
+            0x10a4_4ba6_65bf_302c,
+            0x1182_0363_ed54_1daa,
+        ]),
+        Fp::from_raw([
+            0x1058_40b7_51a1_6317,
+            0xdfd3_150e_05f1_df5d,
+            0x02b6_0d61_a837_85bd,
+            0x2019_35a5_8f5c_57fc,
+        ]),
+        Fp::from_raw([
+            0xcfed_610e_87c0_2e9a,
+            0x4679_4eed_d686_cd8e,
+            0xe27a_952a_bd33_a03d,
+            0x0a8c_2820_c569_71aa,
+        ]),
+        Fp::from_raw([
+            0xd50d_bc16_3a28_1877,
+            0x650b_65ff_33e6_be1f,
+            0x04ab_d6d0_bd75_00b6,
+            0x1806_38ff_301a_64ca,
+        ]),
+        Fp::from_raw([
+            0x3e9b_321a_812d_d36b,
+            0xf85c_a8a9_37cf_bec6,
+            0x044f_9711_4a41_58a3,
+            0x095c_7162_66f1_de59,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x8d40_87a4_97d7_3490,
+            0x83b6_18c5_c1a6_8781,
+            0x20d8_6ffe_d6c7_ca15,
+            0x17c3_1ea0_2fbc_3783,
+        ]),
+        Fp::from_raw([
+            0xe4d5_0a77_f192_a91b,
+            0x3c17_a976_4ccd_660d,
+            0x6a72_27e4_192d_149d,
+            0x05b8_6c4b_b8ef_318b,
+        ]),
+        Fp::from_raw([
+            0xd6ce_74ba_986c_7a7b,
+            0xab15_f4a6_ae0d_237c,
+            0x6ff7_0d7e_a2fd_e2c7,
+            0x265b_c95d_f4a4_c487,
+        ]),
+        Fp::from_raw([
+            0xd6c8_86d4_715c_7929,
+            0x0870_1739_c5f5_b4b3,
+            0xd9bb_e48f_5fef_2f69,
+            0x2475_2b47_bc6c_6bc8,
+        ]),
+        Fp::from_raw([
+            0xe583_b932_4d97_4efe,
+            0x78d6_24b9_8da9_6ee5,
+            0xa0d8_6e52_7a96_4821,
+            0x1481_4a1e_0f49_2a4e,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xc752_3bca_906f_00bd,
+            0x997c_8e04_1d3c_fb3d,
+            0xbd60_5773_78f2_9381,
+            0x10de_f931_073b_6479,
+        ]),
+        Fp::from_raw([
+            0x1b45_92c9_8610_175f,
+            0xfa0b_880d_28c6_9d03,
+            0x7f70_6c0d_8ab4_ed03,
+            0x14f7_ae77_0bf7_e95f,
+        ]),
+        Fp::from_raw([
+            0x75c5_cc9d_ce1c_e589,
+            0x9dec_c9d4_28eb_e4e7,
+            0x9f92_6af4_0e80_35d1,
+            0x1aef_50a0_cee7_51b5,
+        ]),
+        Fp::from_raw([
+            0x9512_14b5_7e73_cf5a,
+            0x086c_2a2d_57d0_9602,
+            0xba65_ca60_068d_fe3b,
+            0x0419_3560_7172_f68e,
+        ]),
+        Fp::from_raw([
+            0x37a1_51d3_bf45_2cb8,
+            0x0493_fbef_e83c_8198,
+            0x573b_d083_959b_856c,
+            0x2686_3e9d_d242_55d1,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x6527_38e6_3ff8_b3af,
+            0xf5ad_f251_ba62_052c,
+            0xeb3d_7a06_8bd0_87c9,
+            0x2036_efb6_f983_0965,
+        ]),
+        Fp::from_raw([
+            0xb00f_a4f1_b4f4_ee9b,
+            0x30be_4f75_a753_f854,
+            0x766b_639a_0299_69ca,
+            0x0c71_2a97_5b74_dc9d,
+        ]),
+        Fp::from_raw([
+            0x3757_31d3_8753_9699,
+            0x7afd_ff64_5649_2ca3,
+            0x27af_c99b_fac1_e680,
+            0x0801_4dab_3cd1_667e,
+        ]),
+        Fp::from_raw([
+            0xb530_09f7_9b34_e6a4,
+            0x97c4_dd4d_37b4_e8f3,
+            0xa82a_4a79_839d_6a2b,
+            0x198d_0719_2db4_fac2,
+        ]),
+        Fp::from_raw([
+            0xfd15_784d_1f63_e572,
+            0x2709_b29d_53bb_946d,
+            0xb23b_4131_4268_97a3,
+            0x29eb_1de4_2a3a_d381,
+        ]),
+    ],
+];
+// MDS matrix:
+pub(crate) const MDS: [[Fp; 5]; 5] = [
+    [
+        Fp::from_raw([
+            0x4fc6_fdbc_f38d_7da1,
+            0x9f22_e57a_ce3c_d7f6,
+            0x080b_0af1_33b9_e436,
+            0x251e_7fdf_9959_1080,
+        ]),
+        Fp::from_raw([
+            0x4855_0486_e91c_7765,
+            0xfe26_ea9c_a238_d6e3,
+            0x47cb_d3b1_c17d_97c7,
+            0x25fb_50b6_5acf_4fb0,
+        ]),
+        Fp::from_raw([
+            0x6ea1_e31e_d576_7833,
+            0x6deb_5325_f367_a455,
+            0x355f_39eb_f62f_91b0,
+            0x293d_617d_7da7_2102,
+        ]),
+        Fp::from_raw([
+            0x6145_f14b_a6d3_c1c4,
+            0x6659_9e57_5a9b_7edf,
+            0x9601_11ac_25da_4743,
+            0x104d_0295_ab00_c85e,
+        ]),
+        Fp::from_raw([
+            0x3ed5_b90f_2f69_c977,
+            0x792b_3813_954f_e9bf,
+            0x7dea_3e33_6cd9_6a39,
+            0x0aaa_35e2_c84b_af11,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x69f6_61b2_eb74_c839,
+            0x0529_03dc_6609_ea69,
+            0xc03e_17c1_d1dc_db02,
+            0x2a70_b9f1_d4bb_ccdb,
+        ]),
+        Fp::from_raw([
+            0x115b_8e2e_991c_cd7a,
+            0xba9f_92ad_8ef4_b979,
+            0x6315_a993_4f1b_8a1b,
+            0x2811_5465_1c92_1e74,
+        ]),
+        Fp::from_raw([
+            0xb86a_894f_7db3_6c37,
+            0x8ccd_8fdb_9ee2_b45f,
+            0x0b53_c732_134e_fa33,
+            0x28c2_be2f_8264_f95f,
+        ]),
+        Fp::from_raw([
+            0xc4ec_8fa7_5e53_0a13,
+            0x9b62_6d8c_b4dc_18dc,
+            0x6d42_7c89_0b18_83bb,
+            0x2188_8041_e6fe_bd54,
+        ]),
+        Fp::from_raw([
+            0x350d_65e2_cbff_4941,
+            0xe810_930e_3ea4_574a,
+            0x8019_5b95_92d8_cf2b,
+            0x14dd_b5fa_da01_71db,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xa4fa_8478_970d_401d,
+            0x55b9_1bff_652a_d69a,
+            0xdea4_3265_306a_37ed,
+            0x2f69_a719_8e1f_bcc7,
+        ]),
+        Fp::from_raw([
+            0x6d6a_3747_594d_3052,
+            0x67ba_312b_3414_0e71,
+            0xad93_1ab8_0e37_bbb2,
+            0x001c_1edd_6264_5b73,
+        ]),
+        Fp::from_raw([
+            0xa7f9_a4b2_28bf_c32b,
+            0x39c4_0c60_3049_466f,
+            0xce2f_2c96_c696_63c4,
+            0x15b9_8ce9_3e47_bc64,
+        ]),
+        Fp::from_raw([
+            0x6505_1de3_3163_cf9c,
+            0xcba8_458b_28e4_4d92,
+            0x58f6_5be2_fbac_809f,
+            0x12c7_e2ad_fa52_4e59,
+        ]),
+        Fp::from_raw([
+            0x531e_c2de_53bb_d167,
+            0xaf67_ce79_816e_f468,
+            0x4901_8222_e7b8_922e,
+            0x2efc_2b90_d688_1348,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xf1af_d6c5_f721_f830,
+            0x3c3f_fa45_50bd_2514,
+            0x1981_e55e_3e1a_29a1,
+            0x0c3f_050a_6bf5_af15,
+        ]),
+        Fp::from_raw([
+            0xeda7_7843_20a1_a36e,
+            0x8b2e_fe2e_cd42_4a73,
+            0xfa75_ba79_92bd_34f0,
+            0x0dec_54e6_dbf7_5205,
+        ]),
+        Fp::from_raw([
+            0x7cc7_5cf3_2d81_36fa,
+            0x9836_4a11_f4d9_88fb,
+            0x2022_5815_034b_1960,
+            0x1c48_2a25_a729_f5df,
+        ]),
+        Fp::from_raw([
+            0xb8b6_07ae_9fd8_514a,
+            0x0812_ac2f_c9a1_4a5f,
+            0x5273_2624_e4ab_9436,
+            0x2625_ce48_a7b3_9a42,
+        ]),
+        Fp::from_raw([
+            0x9fb9_f28a_f710_251f,
+            0xed7e_f8e3_00b9_a8bb,
+            0x86f7_cd4f_d710_c509,
+            0x07f0_17a7_ebd5_6dd0,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xe2f7_8c2c_cc2e_3595,
+            0x7c5e_55c2_0146_259b,
+            0xf97c_9d61_86c6_c3ea,
+            0x2a20_e3a4_a0e5_7d92,
+        ]),
+        Fp::from_raw([
+            0x403b_01fe_b727_a549,
+            0x7016_73ae_d820_d9c4,
+            0xaafb_1e9a_5d63_c0ee,
+            0x1049_f821_0566_b51f,
+        ]),
+        Fp::from_raw([
+            0x61b9_299b_82d6_9c8e,
+            0xef35_7a69_e3e8_6b55,
+            0x6800_2bd9_d1b9_6b4b,
+            0x02ec_ac68_7ef5_b4b5,
+        ]),
+        Fp::from_raw([
+            0xd4eb_cf11_bbe1_e37b,
+            0xbdcb_6b58_ba40_441e,
+            0x6808_f88c_9ba9_03d3,
+            0x2d3a_1aea_2e6d_4446,
+        ]),
+        Fp::from_raw([
+            0xc98d_803b_fed6_5e64,
+            0xb39c_4a7a_72db_b6d9,
+            0xc9ad_171e_4f35_fe49,
+            0x1407_4bb1_4c98_2c81,
+        ]),
+    ],
+];
+// Inverse MDS matrix:
+pub(crate) const MDS_INV: [[Fp; 5]; 5] = [
+    [
+        Fp::from_raw([
+            0x0a07_b795_463d_35d5,
+            0xfa40_1f28_8668_6830,
+            0x5530_d7a8_f9f8_9493,
+            0x164c_e768_9cd3_4e71,
+        ]),
+        Fp::from_raw([
+            0xd023_820e_b560_9d5c,
+            0x12b2_b6d1_8e5b_b2fc,
+            0xf20e_a20e_014a_394a,
+            0x1db3_93d0_c226_3141,
+        ]),
+        Fp::from_raw([
+            0x0036_4d7e_376c_668a,
+            0x6384_229a_60b5_b10e,
+            0xc5c1_feea_7573_b0bd,
+            0x2ea4_b39c_798c_b58f,
+        ]),
+        Fp::from_raw([
+            0x9d41_b342_029b_a735,
+            0x81bd_3a5a_0ed0_4aed,
+            0x32f7_e059_1681_966b,
+            0x2d2b_4966_cbb1_a878,
+        ]),
+        Fp::from_raw([
+            0x6442_41f9_86b0_fed0,
+            0xe608_a349_b9dd_3773,
+            0x05c8_7598_5566_7af4,
+            0x245f_6aa5_4869_51fc,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x44ed_2060_93d6_aabd,
+            0x1b4b_d6e6_9583_8469,
+            0xd986_605b_83ae_1016,
+            0x2ee7_5426_48a7_61d8,
+        ]),
+        Fp::from_raw([
+            0x1519_0ef0_969c_8858,
+            0x833b_15f6_f4e4_c37d,
+            0x7ed0_b2b9_3fc0_a2a6,
+            0x03f8_d436_2488_e27a,
+        ]),
+        Fp::from_raw([
+            0x7510_417e_0bb5_e415,
+            0x9ddd_05b3_aebe_0373,
+            0x5313_8d6d_7efd_5b13,
+            0x0d71_55cc_3c81_7a44,
+        ]),
+        Fp::from_raw([
+            0x8e6b_f161_2749_b599,
+            0xa98d_ded9_0fa5_0d82,
+            0x2dd5_fa16_bd6a_ff83,
+            0x2a3f_9c29_2dfd_d278,
+        ]),
+        Fp::from_raw([
+            0xb9a9_386f_96f8_8ce4,
+            0x4584_a3ce_4044_b0db,
+            0x6e4a_5dbc_7bbc_2f86,
+            0x2ef0_87b9_5a30_db20,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x9b0a_dc8b_e22c_2097,
+            0x8b3b_3579_9934_113f,
+            0x1e46_b9c6_5795_09bf,
+            0x268b_8879_63db_67de,
+        ]),
+        Fp::from_raw([
+            0x2b8e_5163_2fdd_069d,
+            0x2a8a_52a7_6b3b_1707,
+            0xd25c_3c95_da1d_1f6e,
+            0x07c4_cf63_17d3_1876,
+        ]),
+        Fp::from_raw([
+            0xfd70_2b37_cca4_7eae,
+            0xcd50_3388_d240_f0a3,
+            0x9621_c484_9c42_8d51,
+            0x1bb9_9031_ff61_99b8,
+        ]),
+        Fp::from_raw([
+            0xe85d_b447_c038_bd9b,
+            0x2ea1_4994_16b4_849e,
+            0x30bc_6db6_5916_28d5,
+            0x2bb7_f772_403e_18b5,
+        ]),
+        Fp::from_raw([
+            0xa70f_45bf_661b_a481,
+            0x0242_4059_a4aa_f101,
+            0x5d41_bdd3_ccf8_e33f,
+            0x07d1_50a7_2e22_cdd1,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x9305_cd67_3be8_ad9e,
+            0xbcd5_922e_8e81_b550,
+            0xa06a_0858_2093_30f5,
+            0x1aea_063d_15e7_adc4,
+        ]),
+        Fp::from_raw([
+            0xe378_276b_fdd3_2f08,
+            0x5c33_6747_63c7_7b6b,
+            0x438c_2b06_0ec9_9174,
+            0x1ab1_8e5f_72e6_7dcc,
+        ]),
+        Fp::from_raw([
+            0x3cd9_40ae_057a_9f08,
+            0x14e1_9fd3_481b_ad42,
+            0xe321_6274_cde2_e859,
+            0x1473_c50d_66be_6cbe,
+        ]),
+        Fp::from_raw([
+            0xbb73_27dc_2956_b7a0,
+            0xc8c6_e6e7_7849_266c,
+            0xef9e_e1d4_c8aa_a35a,
+            0x262e_f341_6da6_8dcb,
+        ]),
+        Fp::from_raw([
+            0x58b0_d3bf_0f3e_2fdc,
+            0xe85b_23a5_7fd1_48e7,
+            0xd9e0_31c1_0ea9_700d,
+            0x229c_fdf3_f130_b726,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x9062_3a1f_a068_26c5,
+            0xdeff_a4d4_9dfd_b739,
+            0xf986_809c_e528_a884,
+            0x125e_07c3_24bf_c084,
+        ]),
+        Fp::from_raw([
+            0x60db_1afc_a351_100f,
+            0x11f1_b42a_4390_9d72,
+            0xc1d2_4a0d_c7fb_7b61,
+            0x10ef_80f7_94f0_c1ce,
+        ]),
+        Fp::from_raw([
+            0x89de_85d9_ecd3_c179,
+            0x15b0_d5de_8c2d_49b7,
+            0xe31b_fe9d_3806_054c,
+            0x1e2f_2d99_8606_58a8,
+        ]),
+        Fp::from_raw([
+            0x9a25_4567_4b42_7d94,
+            0x107e_cbf4_a7f5_4066,
+            0x9afd_5ae4_7f61_3afa,
+            0x199f_0a09_d4d0_c2bf,
+        ]),
+        Fp::from_raw([
+            0x7497_9f2e_71b0_1a16,
+            0x1429_9813_8018_4c81,
+            0x0164_dd73_b1a7_c35e,
+            0x1cef_d8df_4cfb_e341,
+        ]),
+    ],
+];
@@ -5,7 +5,7 @@ use halo2_gadgets::poseidon::primitives::*;
 use halo2_proofs::arithmetic::Field;
 use halo2_proofs::halo2curves::bn256::Fr as Fp;
 
-use super::poseidon_params;
+use super::{poseidon_params, poseidon_params_t5};
 
 /// The specification for the Poseidon hash function.
 #[derive(Debug, Clone, Copy)]
@@ -16,34 +16,58 @@ pub const POSEIDON_WIDTH: usize = 2;
 /// The number of full SBox rounds
 pub const POSEIDON_RATE: usize = 1;
 
+/// The specification for the width 5 Poseidon hash function (circomlib's parameters for 4 inputs).
+#[derive(Debug, Clone, Copy)]
+pub struct PoseidonSpecT5;
+
+/// The width of [PoseidonSpecT5]
+pub const POSEIDON_T5_WIDTH: usize = 5;
+/// The rate of [PoseidonSpecT5]
+pub const POSEIDON_T5_RATE: usize = 4;
+
 pub(crate) type Mds<Fp, const T: usize> = [[Fp; T]; T];
 
-impl Spec<Fp, POSEIDON_WIDTH, POSEIDON_RATE> for PoseidonSpec {
-    fn full_rounds() -> usize {
-        8
-    }
-
-    fn partial_rounds() -> usize {
-        56
-    }
-
-    fn sbox(val: Fp) -> Fp {
-        val.pow_vartime([5])
-    }
-
-    fn secure_mds() -> usize {
-        unimplemented!()
-    }
-
-    fn constants() -> (
-        Vec<[Fp; POSEIDON_WIDTH]>,
-        Mds<Fp, POSEIDON_WIDTH>,
-        Mds<Fp, POSEIDON_WIDTH>,
-    ) {
-        (
-            poseidon_params::ROUND_CONSTANTS[..].to_vec(),
-            poseidon_params::MDS,
-            poseidon_params::MDS_INV,
-        )
-    }
+macro_rules! impl_spec {
+    ($spec:ty, $width:expr, $rate:expr, $partial_rounds:expr, $params:ident) => {
+        impl Spec<Fp, $width, $rate> for $spec {
+            fn full_rounds() -> usize {
+                8
+            }
+
+            fn partial_rounds() -> usize {
+                $partial_rounds
+            }
+
+            fn sbox(val: Fp) -> Fp {
+                val.pow_vartime([5])
+            }
+
+            fn secure_mds() -> usize {
+                unimplemented!()
+            }
+
+            fn constants() -> (Vec<[Fp; $width]>, Mds<Fp, $width>, Mds<Fp, $width>) {
+                (
+                    $params::ROUND_CONSTANTS[..].to_vec(),
+                    $params::MDS,
+                    $params::MDS_INV,
+                )
+            }
+        }
+    };
 }
+
+impl_spec!(
+    PoseidonSpec,
+    POSEIDON_WIDTH,
+    POSEIDON_RATE,
+    56,
+    poseidon_params
+);
+impl_spec!(
+    PoseidonSpecT5,
+    POSEIDON_T5_WIDTH,
+    POSEIDON_T5_RATE,
+    60,
+    poseidon_params_t5
+);
@@ -340,6 +340,7 @@ mod matmul_col_ultra_overflow_double_col {
             // use safe mode to verify that the proof is correct
             CheckMode::SAFE,
             None,
+            crate::pfsys::MultiOpenScheme::SHPLONK,
         );
 
         assert!(prover.is_ok());
@@ -454,6 +455,7 @@ mod matmul_col_ultra_overflow {
             // use safe mode to verify that the proof is correct
             CheckMode::SAFE,
             None,
+            crate::pfsys::MultiOpenScheme::SHPLONK,
         );
 
         assert!(prover.is_ok());
@@ -1249,6 +1251,7 @@ mod conv_col_ultra_overflow {
             // use safe mode to verify that the proof is correct
             CheckMode::SAFE,
             None,
+            crate::pfsys::MultiOpenScheme::SHPLONK,
         );
 
         assert!(prover.is_ok());
@@ -1401,6 +1404,7 @@ mod conv_relu_col_ultra_overflow {
             // use safe mode to verify that the proof is correct
             CheckMode::SAFE,
             None,
+            crate::pfsys::MultiOpenScheme::SHPLONK,
         );
 
         assert!(prover.is_ok());
@@ -2514,6 +2518,7 @@ mod lookup_ultra_overflow {
             // use safe mode to verify that the proof is correct
             CheckMode::SAFE,
             None,
+            crate::pfsys::MultiOpenScheme::SHPLONK,
         );
 
         assert!(prover.is_ok());
@@ -12,10 +12,15 @@ use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::path::PathBuf;
 
-use crate::{pfsys::ProofType, RunArgs};
+use crate::{
+    pfsys::{Commitments, ProofType},
+    RunArgs,
+};
 
 use crate::circuit::CheckMode;
 #[cfg(not(target_arch = "wasm32"))]
+use crate::eth::DEFAULT_CREATE2_FACTORY;
+#[cfg(not(target_arch = "wasm32"))]
 use crate::graph::TestDataSource;
 use crate::pfsys::TranscriptType;
 
@@ -51,6 +56,52 @@ impl<'source> FromPyObject<'source> for TranscriptType {
     }
 }
 
+#[derive(
+    Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, ValueEnum, Default,
+)]
+/// The contract function evm calldata is encoded for
+pub enum EvmCalldataType {
+    /// `verifyProof` on a single or aggregate proof verifier
+    #[default]
+    Verifier,
+    /// `verifyWithDataAttestation` on a data attestation contract
+    DataAttestation,
+}
+
+impl std::fmt::Display for EvmCalldataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
+
+#[cfg(feature = "python-bindings")]
+/// Converts EvmCalldataType into a PyObject (Required for EvmCalldataType to be compatible with Python)
+impl IntoPy<PyObject> for EvmCalldataType {
+    fn into_py(self, py: Python) -> PyObject {
+        match self {
+            EvmCalldataType::Verifier => "verifier".to_object(py),
+            EvmCalldataType::DataAttestation => "data-attestation".to_object(py),
+        }
+    }
+}
+
+#[cfg(feature = "python-bindings")]
+/// Obtains EvmCalldataType from PyObject (Required for EvmCalldataType to be compatible with Python)
+impl<'source> FromPyObject<'source> for EvmCalldataType {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        let trystr = <PyString as PyTryFrom>::try_from(ob)?;
+        let strval = trystr.to_string();
+        match strval.to_lowercase().as_str() {
+            "verifier" => Ok(EvmCalldataType::Verifier),
+            "data-attestation" => Ok(EvmCalldataType::DataAttestation),
+            _ => Err(PyValueError::new_err("Invalid value for EvmCalldataType")),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
 /// Determines what the calibration pass should optimize for
 pub enum CalibrationTarget {
@@ -205,6 +256,7 @@ pub enum Commands {
     },
 
     /// Generates the witness from an input file.
+    /// On-chain inputs are read at their pinned block (or the latest block), which is recorded in the witness.
     #[command(arg_required_else_help = true)]
     GenWitness {
         /// The path to the .json data file
@@ -222,6 +274,9 @@ pub enum Commands {
         /// Path to the srs file (optional - solely used to generate kzg commits)
         #[arg(short = 'P', long)]
         srs_path: Option<PathBuf>,
+        /// Path to write a json report of the wall time and peak memory of each phase to (optional - enables profiling)
+        #[arg(long)]
+        profile_path: Option<PathBuf>,
     },
 
     /// Produces the proving hyperparameters, from run-args
@@ -233,6 +288,9 @@ pub enum Commands {
         /// Path to circuit_settings file to output
         #[arg(short = 'O', long, default_value = "settings.json")]
         settings_path: PathBuf,
+        /// Path to a json file declaring the preprocessing applied to each input before quantization (optional)
+        #[arg(long)]
+        preprocessing: Option<PathBuf>,
         /// proving arguments
         #[clap(flatten)]
         args: RunArgs,
@@ -260,6 +318,12 @@ pub enum Commands {
         /// max logrows to use for calibration, 26 is the max public SRS size
         #[arg(long)]
         max_logrows: Option<u32>,
+        /// Calibrate on a random subset of this many batches (samples) of the calibration data, rather than on all of them
+        #[arg(long)]
+        num_samples: Option<usize>,
+        /// The memory (in MB) the candidate settings calibrated at a time may use (each holds a circuit and its forward pass in memory), defaults to a candidate per thread
+        #[arg(long)]
+        max_memory: Option<usize>,
     },
 
     /// Generates a dummy SRS
@@ -271,6 +335,9 @@ pub enum Commands {
         /// number of logrows to use for srs
         #[arg(long)]
         logrows: usize,
+        /// the commitment scheme to generate the srs for
+        #[arg(long, default_value = "kzg")]
+        commitment: Commitments,
     },
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -301,12 +368,15 @@ pub enum Commands {
         model: PathBuf,
     },
 
-    /// Mock aggregate proofs
+    /// Mock aggregate proofs (only shplonk proofs can be aggregated, see `--multi-open`)
     #[command(arg_required_else_help = true)]
     MockAggregate {
         /// The path to the snarks to aggregate over
         #[arg(long)]
         aggregation_snarks: Vec<PathBuf>,
+        /// The paths to the settings the snarks were generated for (one per snark, or one shared by all), binary snarks must carry their hash
+        #[arg(long)]
+        settings_paths: Vec<PathBuf>,
         /// logrows used for aggregation circuit
         #[arg(long)]
         logrows: u32,
@@ -315,12 +385,15 @@ pub enum Commands {
         split_proofs: bool,
     },
 
-    /// setup aggregation circuit :)
+    /// setup aggregation circuit, for shplonk proofs only (see `--multi-open`) :)
     #[command(arg_required_else_help = true)]
     SetupAggregate {
         /// The path to samples of snarks that will be aggregated over
         #[arg(long)]
         sample_snarks: Vec<PathBuf>,
+        /// The paths to the settings the snarks were generated for (one per snark, or one shared by all), binary snarks must carry their hash
+        #[arg(long)]
+        settings_paths: Vec<PathBuf>,
         /// The path to save the desired verification key file
         #[arg(long, default_value = "vk_aggr.key")]
         vk_path: PathBuf,
@@ -337,12 +410,15 @@ pub enum Commands {
         #[arg(long, default_value = "false")]
         split_proofs: bool,
     },
-    /// Aggregates proofs :)
+    /// Aggregates proofs, shplonk proofs only (see `--multi-open`) :)
     #[command(arg_required_else_help = true)]
     Aggregate {
         /// The path to the snarks to aggregate over
         #[arg(long)]
         aggregation_snarks: Vec<PathBuf>,
+        /// The paths to the settings the snarks were generated for (one per snark, or one shared by all), binary snarks must carry their hash
+        #[arg(long)]
+        settings_paths: Vec<PathBuf>,
         /// The path to load the desired proving key file
         #[arg(long)]
         pk_path: PathBuf,
@@ -401,6 +477,9 @@ pub enum Commands {
         /// The graph witness (optional - used to override fixed values in the circuit)
         #[arg(short = 'W', long)]
         witness: Option<PathBuf>,
+        /// Path to write a json report of the wall time and peak memory of each phase to (optional - enables profiling)
+        #[arg(long)]
+        profile_path: Option<PathBuf>,
     },
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -472,6 +551,21 @@ pub enum Commands {
         witness_path: PathBuf,
     },
 
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Converts a proof between the json and compact binary formats
+    #[command(arg_required_else_help = true)]
+    ConvertProof {
+        /// The path to the proof file to convert (json or binary)
+        #[arg(short = 'P', long)]
+        proof_path: PathBuf,
+        /// The path to output the converted proof to
+        #[arg(short = 'O', long)]
+        output_path: PathBuf,
+        /// The path to the settings the proof was generated for, recorded (hashed) in the binary header
+        #[arg(short = 'S', long)]
+        settings_path: Option<PathBuf>,
+    },
+
     #[cfg(not(target_arch = "wasm32"))]
     /// Loads model, data, and creates proof
     #[command(arg_required_else_help = true)]
@@ -502,6 +596,9 @@ pub enum Commands {
         /// run sanity checks during calculations (safe or unsafe)
         #[arg(long, default_value = "safe")]
         check_mode: CheckMode,
+        /// Path to write a json report of the wall time and peak memory of each phase to (optional - enables profiling)
+        #[arg(long)]
+        profile_path: Option<PathBuf>,
     },
     #[cfg(not(target_arch = "wasm32"))]
     /// Creates an EVM verifier for a single proof
@@ -522,9 +619,61 @@ pub enum Commands {
         /// The path to output the Solidity verifier ABI
         #[arg(long, default_value = "verifier_abi.json")]
         abi_path: PathBuf,
+        /// Whether to render the verifier without its verification key, the vk is then rendered by `create-evm-vk` and passed to the verifier by address. Circuits with the same constraint system can share the verifier
+        #[arg(long, default_value = "false")]
+        render_vk_separately: bool,
+        /// Whether the verifier can also check that the proof's KZG committed values (`kzgcommit` visibility) are equal to externally supplied commitments, e.g. commitments stored on-chain
+        #[arg(long, default_value = "false")]
+        bind_commitments: bool,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Creates an EVM verifying key contract for a verifier rendered with `--render-vk-separately`
+    #[command(name = "create-evm-vk", arg_required_else_help = true)]
+    CreateEVMVK {
+        /// The path to load the desired params file
+        #[arg(long)]
+        srs_path: PathBuf,
+        /// The path to load circuit settings from
+        #[arg(short = 'S', long)]
+        settings_path: PathBuf,
+        /// The path to load the desired verification key file
+        #[arg(long)]
+        vk_path: PathBuf,
+        /// The path to output the Solidity code
+        #[arg(long, default_value = "evm_deploy_vk.sol")]
+        sol_code_path: PathBuf,
+        /// The path to output the Solidity verifying key ABI
+        #[arg(long, default_value = "vk_abi.json")]
+        abi_path: PathBuf,
     },
     #[cfg(not(target_arch = "wasm32"))]
-    /// Creates an EVM verifier that attests to on-chain inputs for a single proof
+    /// Quantizes the input (and output) data of a .json data file and signs it, for data attestation of signed off-chain data
+    #[command(name = "sign-data", arg_required_else_help = true)]
+    SignData {
+        /// The path to the .json data file, the input data and (optional) output data must come from a file
+        #[arg(short = 'D', long)]
+        data: PathBuf,
+        /// The path to the compiled model file (used to quantize the data)
+        #[arg(short = 'M', long)]
+        compiled_circuit: PathBuf,
+        /// Private secp256K1 key in hex format, 64 chars, no 0x prefix, of the account signing the data
+        #[arg(short = 'P', long)]
+        private_key: String,
+        /// The address of the verifier contract the data is attested for
+        #[arg(long)]
+        addr_verifier: H160,
+        /// The id of the chain the data is attested on
+        #[arg(long)]
+        chain_id: u64,
+        /// The nonce of the data attestation contract, signatures over older nonces are rejected
+        #[arg(long, default_value = "0")]
+        nonce: u64,
+        /// The path to output the signed .json data file
+        #[arg(short = 'O', long, default_value = "signed_input.json")]
+        output: PathBuf,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Creates an EVM verifier that attests to on-chain (or signed off-chain) inputs for a single proof
     #[command(name = "create-evm-da", arg_required_else_help = true)]
     CreateEVMDataAttestation {
         /// The path to load the desired srs file from
@@ -546,12 +695,24 @@ pub enum Commands {
         /// contain the necessary calldata and accoount addresses
         /// needed need to read from all the on-chain
         /// view functions that return the data that the network
-        /// ingests as inputs.
+        /// ingests as inputs (or the signed data, see `sign-data`).
         #[arg(short = 'D', long)]
         data: PathBuf,
         // todo, optionally allow supplying proving key
     },
 
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Creates a Solidity library that decodes the public outputs of a verified proof into signed fixed point integers, using the output scales and shapes of the circuit
+    #[command(name = "create-evm-output-decoder", arg_required_else_help = true)]
+    CreateEVMOutputDecoder {
+        /// The path to load circuit settings from
+        #[arg(short = 'S', long)]
+        settings_path: PathBuf,
+        /// The path to output the Solidity code
+        #[arg(long, default_value = "evm_output_decoder.sol")]
+        sol_code_path: PathBuf,
+    },
+
     #[cfg(not(target_arch = "wasm32"))]
     /// Creates an EVM verifier for an aggregate proof
     #[command(name = "create-evm-verifier-aggr", arg_required_else_help = true)]
@@ -571,6 +732,12 @@ pub enum Commands {
         // aggregated circuit settings paths, used to calculate the number of instances in the aggregate proof
         #[arg(long)]
         aggregation_settings: Vec<PathBuf>,
+        /// Whether the aggregate proof is over sequential split proofs (`aggregate --split-proofs`), the verifier then exposes the inputs of the first and the outputs of the last segment
+        #[arg(long, default_value = "false")]
+        split_proofs: bool,
+        /// The path to output a json description of the aggregate proof's instances to
+        #[arg(long)]
+        layout_path: Option<PathBuf>,
     },
     /// Verifies a proof, returning accept or reject
     #[command(arg_required_else_help = true)]
@@ -621,6 +788,47 @@ pub enum Commands {
         /// Private secp256K1 key in hex format, 64 chars, no 0x prefix, of the account signing transactions. If None the private key will be generated by Anvil
         #[arg(short = 'P', long)]
         private_key: Option<String>,
+        /// Address of an already deployed verifier to reuse if its bytecode matches the Solidity code (a new verifier is deployed otherwise)
+        #[arg(long)]
+        reuse_addr: Option<H160>,
+        /// Deploy through a CREATE2 factory, so the contract gets the same address on every chain.
+        /// Uses the deterministic deployment proxy if no factory address is given
+        #[arg(
+            long,
+            require_equals = true,
+            num_args = 0..=1,
+            default_missing_value = DEFAULT_CREATE2_FACTORY
+        )]
+        create2: Option<H160>,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Deploys an EVM verifying key contract, for use with a verifier rendered with `--render-vk-separately`
+    #[command(name = "deploy-evm-vk", arg_required_else_help = true)]
+    DeployEvmVK {
+        /// The path to the Solidity code
+        #[arg(long)]
+        sol_code_path: PathBuf,
+        /// RPC URL for an Ethereum node, if None will use Anvil but WON'T persist state
+        #[arg(short = 'U', long)]
+        rpc_url: Option<String>,
+        #[arg(long, default_value = "vk.address")]
+        /// The path to output the contract address
+        addr_path: PathBuf,
+        /// The optimizer runs to set on the verifier. (Lower values optimize for deployment, while higher values optimize for execution)
+        #[arg(long, default_value = "1")]
+        optimizer_runs: usize,
+        /// Private secp256K1 key in hex format, 64 chars, no 0x prefix, of the account signing transactions. If None the private key will be generated by Anvil
+        #[arg(short = 'P', long)]
+        private_key: Option<String>,
+        /// Deploy through a CREATE2 factory, so the contract gets the same address on every chain.
+        /// Uses the deterministic deployment proxy if no factory address is given
+        #[arg(
+            long,
+            require_equals = true,
+            num_args = 0..=1,
+            default_missing_value = DEFAULT_CREATE2_FACTORY
+        )]
+        create2: Option<H160>,
     },
     #[cfg(not(target_arch = "wasm32"))]
     #[command(name = "deploy-evm-da", arg_required_else_help = true)]
@@ -646,6 +854,45 @@ pub enum Commands {
         /// Private secp256K1 key in hex format, 64 chars, no 0x prefix, of the account signing transactions. If None the private key will be generated by Anvil
         #[arg(short = 'P', long)]
         private_key: Option<String>,
+        /// Deploy through a CREATE2 factory, so the contract gets the same address on every chain (the admin is the deployer, so it must deploy from the same account).
+        /// Uses the deterministic deployment proxy if no factory address is given
+        #[arg(
+            long,
+            require_equals = true,
+            num_args = 0..=1,
+            default_missing_value = DEFAULT_CREATE2_FACTORY
+        )]
+        create2: Option<H160>,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Computes the address a contract is deployed at through a CREATE2 factory (with `--create2`), without connecting to a node
+    #[command(name = "create2-address", arg_required_else_help = true)]
+    Create2Address {
+        /// The path to the Solidity code
+        #[arg(long)]
+        sol_code_path: PathBuf,
+        /// The name of the contract to deploy, `Halo2VerifyingKey` for a vk rendered with `--render-vk-separately`
+        #[arg(long, default_value = "Halo2Verifier")]
+        contract_name: String,
+        /// The optimizer runs the contract is deployed with
+        #[arg(long, default_value = "1")]
+        optimizer_runs: usize,
+        /// The address of the CREATE2 factory
+        #[arg(long, default_value = DEFAULT_CREATE2_FACTORY)]
+        create2_factory: H160,
+        /// The path to output the address to
+        #[arg(long)]
+        addr_path: Option<PathBuf>,
+        /// For a data attestation contract, the path to the .json data file it is deployed with (the contract name is then
+        /// derived from the data). Its constructor arguments are part of the init code, so they change its address
+        #[arg(short = 'D', long)]
+        data: Option<PathBuf>,
+        /// For a data attestation contract, the path to load circuit params from
+        #[arg(long)]
+        settings_path: Option<PathBuf>,
+        /// For a data attestation contract, the account deploying it, which becomes its admin
+        #[arg(long)]
+        deployer: Option<H160>,
     },
     #[cfg(not(target_arch = "wasm32"))]
     /// Verifies a proof using a local EVM executor, returning accept or reject
@@ -663,6 +910,91 @@ pub enum Commands {
         /// does the verifier use data attestation ?
         #[arg(long)]
         addr_da: Option<H160>,
+        /// The verifying key contract's address, for verifiers rendered with `--render-vk-separately`
+        #[arg(long)]
+        addr_vk: Option<H160>,
+        /// The path to the signed .json data file, required if the data attestation contract attests signed data
+        #[arg(short = 'D', long)]
+        data: Option<PathBuf>,
+        /// The path to a witness with KZG commitments, for verifiers created with `--bind-commitments` the proof is checked against its commitments
+        #[arg(short = 'W', long)]
+        witness: Option<PathBuf>,
+        /// The path to the settings the proof was generated for, a binary proof must carry their hash
+        #[arg(short = 'S', long)]
+        settings_path: Option<PathBuf>,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Decodes the public outputs of a proof with an output decoder library (see `create-evm-output-decoder`) using a local EVM executor
+    #[command(name = "decode-evm-outputs", arg_required_else_help = true)]
+    DecodeEVMOutputs {
+        /// The path to the proof file
+        #[arg(long)]
+        proof_path: PathBuf,
+        /// The path to the output decoder's Solidity code
+        #[arg(long, default_value = "evm_output_decoder.sol")]
+        sol_code_path: PathBuf,
+        /// RPC URL for an Ethereum node, if None will use Anvil but WON'T persist state
+        #[arg(short = 'U', long)]
+        rpc_url: Option<String>,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Deploys verifier contracts to a local EVM and reports their deployment gas, runtime code size, calldata size and verification gas
+    #[command(name = "estimate-evm", arg_required_else_help = true)]
+    EstimateEVM {
+        /// The path to the proof file (a single or aggregated proof)
+        #[arg(long)]
+        proof_path: PathBuf,
+        /// The path to the verifier's Solidity code
+        #[arg(long)]
+        sol_code_path: PathBuf,
+        /// The path to the verifying key contract's Solidity code, for verifiers rendered with `--render-vk-separately`
+        #[arg(long)]
+        vk_sol_code_path: Option<PathBuf>,
+        /// The path to the data attestation contract's Solidity code (optional - also estimates data attestation)
+        #[arg(long)]
+        sol_code_path_da: Option<PathBuf>,
+        /// The path to the .json data file (required for data attestation)
+        #[arg(short = 'D', long)]
+        data: Option<PathBuf>,
+        /// The path to load circuit settings from (required for data attestation)
+        #[arg(short = 'S', long)]
+        settings_path: Option<PathBuf>,
+        /// RPC URL for an Ethereum node, if None a local Anvil instance is used. Contracts are deployed with Anvil's default account so this should point to an Anvil node
+        #[arg(short = 'U', long)]
+        rpc_url: Option<String>,
+        /// The optimizer runs to set on the verifier. (Lower values optimize for deployment, while higher values optimize for execution)
+        #[arg(long, default_value = "1")]
+        optimizer_runs: usize,
+        /// The path to output a json report of the estimates to
+        #[arg(short = 'O', long)]
+        output_path: Option<PathBuf>,
+    },
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Encodes the calldata to verify a proof on chain, for submitting the transaction yourself
+    #[command(name = "encode-evm-calldata", arg_required_else_help = true)]
+    EncodeEvmCalldata {
+        /// The path to the proof file
+        #[arg(long)]
+        proof_path: PathBuf,
+        /// The path to output the hex encoded calldata to
+        #[arg(long, default_value = "calldata.hex")]
+        calldata_path: PathBuf,
+        /// The contract function to encode the calldata for
+        #[arg(long, default_value = "verifier", value_enum)]
+        calldata_type: EvmCalldataType,
+        /// The verifier's address (required for data attestation, which calls the verifier)
+        #[arg(long)]
+        addr_verifier: Option<H160>,
+        /// The verifying key contract's address, for verifiers rendered with `--render-vk-separately`
+        #[arg(long)]
+        addr_vk: Option<H160>,
+        /// Overrides the 4 byte function selector (hex), e.g. for wrapper contracts with the same arguments
+        #[arg(long)]
+        selector: Option<String>,
+        /// The path to the settings the proof was generated for, a binary proof must carry their hash
+        #[arg(short = 'S', long)]
+        settings_path: Option<PathBuf>,
     },
 
     /// Print the proof in hexadecimal
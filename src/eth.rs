@@ -1,11 +1,18 @@
-use crate::graph::input::{CallsToAccount, FileSourceInner, GraphData};
-use crate::graph::modules::{ELGAMAL_INSTANCES, POSEIDON_INSTANCES};
+use crate::circuit::modules::poseidon::PoseidonParams;
+use crate::graph::input::{
+    CallsToAccount, FileSourceInner, GraphData, OnChainBlock, OnChainSource, SignedDataDomain,
+    SignedSource,
+};
+use crate::graph::modules::{ELGAMAL_INSTANCES, POSEIDON_INSTANCES, POSEIDON_LEN_GRAPH};
 use crate::graph::DataSource;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::graph::GraphSettings;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::graph::{VarVisibility, Visibility};
+use crate::pfsys::evm::aggregation::{AggregateInstanceLayout, InstanceRange, SegmentLayout};
 use crate::pfsys::evm::EvmVerificationError;
 use crate::pfsys::Snark;
-use ethers::abi::Contract;
+use ethers::abi::{Contract, Token, Tokenize};
 use ethers::contract::abigen;
 use ethers::contract::ContractFactory;
 use ethers::core::k256::ecdsa::SigningKey;
@@ -19,9 +26,10 @@ use ethers::signers::Signer;
 use ethers::solc::{CompilerInput, Solc};
 use ethers::types::transaction::eip2718::TypedTransaction;
 use ethers::types::TransactionRequest;
-use ethers::types::H160;
 use ethers::types::U256;
+use ethers::types::{BlockId, BlockNumber};
 use ethers::types::{Bytes, I256};
+use ethers::types::{H160, H256};
 #[cfg(not(target_arch = "wasm32"))]
 use ethers::{
     prelude::{LocalWallet, Wallet},
@@ -30,9 +38,12 @@ use ethers::{
 use halo2_solidity_verifier::encode_calldata;
 use halo2curves::bn256::{Fr, G1Affine};
 use halo2curves::group::ff::PrimeField;
+use itertools::Itertools;
 use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::path::PathBuf;
+use std::str::FromStr;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::Duration;
 use std::{convert::TryFrom, sync::Arc};
@@ -49,6 +60,15 @@ const TESTREADS_SOL: &str = include_str!("../contracts/TestReads.sol");
 const QUANTIZE_DATA_SOL: &str = include_str!("../contracts/QuantizeData.sol");
 const ATTESTDATA_SOL: &str = include_str!("../contracts/AttestData.sol");
 const LOADINSTANCES_SOL: &str = include_str!("../contracts/LoadInstances.sol");
+const ATTESTSIGNEDDATA_SOL: &str = include_str!("../contracts/AttestSignedData.sol");
+const BINDCOMMITMENTS_SOL: &str = include_str!("../contracts/BindCommitments.sol");
+
+/// The (EIP-170) limit on the size of deployed bytecode, Anvil is run with this limit disabled
+pub const MAX_RUNTIME_BYTECODE_SIZE: usize = 24577;
+
+/// The deterministic deployment proxy, a CREATE2 factory deployed at the same address on most
+/// chains (and on Anvil). It deploys the init code following a 32 byte salt in its calldata.
+pub const DEFAULT_CREATE2_FACTORY: &str = "0x4e59b44847b379578588920ca78fbf26c0b4956c";
 
 /// Return an instance of Anvil and a client for the given RPC URL. If none is provided, a local client is used.
 #[cfg(not(target_arch = "wasm32"))]
@@ -78,15 +98,7 @@ pub async fn setup_eth_backend(
     let wallet: LocalWallet;
     if let Some(private_key) = private_key {
         debug!("using private key {}", private_key);
-        // Sanity checks for private_key
-        let private_key_format_error =
-            "Private key must be in hex format, 64 chars, without 0x prefix";
-        if private_key.len() != 64 {
-            return Err(private_key_format_error.into());
-        }
-        let private_key_buffer = hex::decode(private_key)?;
-        let signing_key = SigningKey::from_slice(&private_key_buffer)?;
-        wallet = LocalWallet::from(signing_key);
+        wallet = wallet_from_private_key(private_key)?;
     } else {
         wallet = anvil.keys()[0].clone().into();
     }
@@ -100,28 +112,203 @@ pub async fn setup_eth_backend(
     Ok((anvil, client))
 }
 
+/// Instantiate a wallet from a hex encoded private key
+#[cfg(not(target_arch = "wasm32"))]
+pub fn wallet_from_private_key(private_key: &str) -> Result<LocalWallet, Box<dyn Error>> {
+    // Sanity checks for private_key
+    let private_key_format_error = "Private key must be in hex format, 64 chars, without 0x prefix";
+    if private_key.len() != 64 {
+        return Err(private_key_format_error.into());
+    }
+    let private_key_buffer = hex::decode(private_key)?;
+    let signing_key = SigningKey::from_slice(&private_key_buffer)?;
+    Ok(LocalWallet::from(signing_key))
+}
+
 ///
 pub async fn deploy_verifier_via_solidity(
     sol_code_path: PathBuf,
     rpc_url: Option<&str>,
     runs: usize,
     private_key: Option<&str>,
+    create2_factory: Option<H160>,
+) -> Result<ethers::types::Address, Box<dyn Error>> {
+    deploy_contract_via_solidity(
+        sol_code_path,
+        rpc_url,
+        runs,
+        private_key,
+        "Halo2Verifier",
+        create2_factory,
+    )
+    .await
+}
+
+/// Deploys the contract `contract_name` defined in the solidity file at `sol_code_path`,
+/// through the CREATE2 factory at `create2_factory` if set
+pub async fn deploy_contract_via_solidity(
+    sol_code_path: PathBuf,
+    rpc_url: Option<&str>,
+    runs: usize,
+    private_key: Option<&str>,
+    contract_name: &str,
+    create2_factory: Option<H160>,
 ) -> Result<ethers::types::Address, Box<dyn Error>> {
     // anvil instance must be alive at least until the factory completes the deploy
     let (anvil, client) = setup_eth_backend(rpc_url, private_key).await?;
 
     let (abi, bytecode, runtime_bytecode) =
-        get_contract_artifacts(sol_code_path, "Halo2Verifier", runs)?;
-
-    let factory = get_sol_contract_factory(abi, bytecode, runtime_bytecode, client.clone())?;
-    let contract = factory.deploy(())?.send().await?;
-    let addr = contract.address();
+        get_contract_artifacts(sol_code_path, contract_name, runs)?;
+
+    let addr = deploy_contract(
+        abi,
+        bytecode,
+        runtime_bytecode,
+        client,
+        vec![],
+        create2_factory,
+    )
+    .await?;
 
     drop(anvil);
     Ok(addr)
 }
 
-///
+/// Deploys a contract with the constructor arguments `args`, either with a regular contract creation
+/// transaction (so the address depends on the deployer's nonce) or through the CREATE2 factory at
+/// `create2_factory` (so the address only depends on the factory and the init code)
+async fn deploy_contract(
+    abi: Contract,
+    bytecode: Bytes,
+    runtime_bytecode: Bytes,
+    client: EthersClient,
+    args: Vec<ethers::abi::Token>,
+    create2_factory: Option<H160>,
+) -> Result<ethers::types::Address, Box<dyn Error>> {
+    match create2_factory {
+        Some(create2_factory) => {
+            // checks the size of the runtime bytecode
+            get_sol_contract_factory(
+                abi.clone(),
+                bytecode.clone(),
+                runtime_bytecode,
+                client.clone(),
+            )?;
+            let init_code = contract_init_code(&abi, &bytecode, args)?;
+            deploy_via_create2(client, create2_factory, init_code).await
+        }
+        None => {
+            let factory = get_sol_contract_factory(abi, bytecode, runtime_bytecode, client)?;
+            let contract = factory.deploy_tokens(args)?.send().await?;
+            Ok(contract.address())
+        }
+    }
+}
+
+/// The init code of a contract, ie. its bytecode followed by its abi encoded constructor arguments
+fn contract_init_code(
+    abi: &Contract,
+    bytecode: &Bytes,
+    args: Vec<ethers::abi::Token>,
+) -> Result<Bytes, Box<dyn Error>> {
+    let init_code = match abi.constructor() {
+        Some(constructor) if constructor.inputs.len() != args.len() => {
+            return Err(format!(
+                "the contract's constructor takes {} arguments but {} were given",
+                constructor.inputs.len(),
+                args.len()
+            )
+            .into())
+        }
+        Some(constructor) => constructor.encode_input(bytecode.to_vec(), &args)?,
+        None if args.is_empty() => bytecode.to_vec(),
+        None => return Err("the contract has no constructor to pass arguments to".into()),
+    };
+    Ok(init_code.into())
+}
+
+/// The CREATE2 salt of a contract, derived from its init code so that the same contract
+/// (verifier bytecode, vk and constructor arguments) always gets the same salt
+pub fn create2_salt(init_code: &[u8]) -> [u8; 32] {
+    ethers::utils::keccak256(init_code)
+}
+
+/// The address the CREATE2 factory at `create2_factory` deploys `init_code` at
+pub fn create2_address(create2_factory: H160, init_code: &[u8]) -> H160 {
+    ethers::utils::get_create2_address(create2_factory, create2_salt(init_code), init_code.to_vec())
+}
+
+/// Deploys `init_code` through the CREATE2 factory at `create2_factory`, returning the address of
+/// the contract (which is reused if it is already deployed)
+async fn deploy_via_create2(
+    client: EthersClient,
+    create2_factory: H160,
+    init_code: Bytes,
+) -> Result<ethers::types::Address, Box<dyn Error>> {
+    let addr = create2_address(create2_factory, &init_code);
+    if !client.get_code(addr, None).await?.is_empty() {
+        info!("contract already deployed at {:#?}", addr);
+        return Ok(addr);
+    }
+    if client.get_code(create2_factory, None).await?.is_empty() {
+        return Err(format!("no CREATE2 factory is deployed at {:#?}", create2_factory).into());
+    }
+
+    let tx: TypedTransaction = TransactionRequest::default()
+        .to(create2_factory)
+        .from(client.address())
+        .data([&create2_salt(&init_code)[..], &init_code[..]].concat())
+        .into();
+    client
+        .send_transaction(tx, None)
+        .await?
+        .await?
+        .ok_or("the CREATE2 deployment transaction was dropped")?;
+
+    if client.get_code(addr, None).await?.is_empty() {
+        return Err(format!(
+            "the CREATE2 factory didn't deploy the contract at {:#?}",
+            addr
+        )
+        .into());
+    }
+    Ok(addr)
+}
+
+/// Computes the address the contract `contract_name` defined in the solidity file at `sol_code_path`
+/// is deployed at with the constructor arguments `args` through the CREATE2 factory at `create2_factory`,
+/// without connecting to a node
+pub fn compute_create2_address(
+    sol_code_path: PathBuf,
+    runs: usize,
+    contract_name: &str,
+    args: Vec<Token>,
+    create2_factory: H160,
+) -> Result<ethers::types::Address, Box<dyn Error>> {
+    let (abi, bytecode, _) = get_contract_artifacts(sol_code_path, contract_name, runs)?;
+    let init_code = contract_init_code(&abi, &bytecode, args)?;
+    Ok(create2_address(create2_factory, &init_code))
+}
+
+/// Checks that the contract deployed at `addr` has the runtime bytecode of `contract_name`
+/// (compiled from `sol_code_path`), so that an already deployed verifier can be reused
+pub async fn is_contract_deployed_at(
+    sol_code_path: PathBuf,
+    addr: ethers::types::Address,
+    rpc_url: Option<&str>,
+    runs: usize,
+    contract_name: &str,
+) -> Result<bool, Box<dyn Error>> {
+    let (anvil, client) = setup_eth_backend(rpc_url, None).await?;
+
+    let (_, _, runtime_bytecode) = get_contract_artifacts(sol_code_path, contract_name, runs)?;
+    let deployed = client.get_code(addr, None).await?;
+
+    drop(anvil);
+    Ok(!deployed.is_empty() && deployed == runtime_bytecode)
+}
+
+/// Deploys the data attestation contract for the data sources of `input`, with the deployer as its admin
 pub async fn deploy_da_verifier_via_solidity(
     settings_path: PathBuf,
     input: PathBuf,
@@ -129,99 +316,113 @@ pub async fn deploy_da_verifier_via_solidity(
     rpc_url: Option<&str>,
     runs: usize,
     private_key: Option<&str>,
+    create2_factory: Option<H160>,
 ) -> Result<ethers::types::Address, Box<dyn Error>> {
-    let (anvil, client) = setup_eth_backend(rpc_url, private_key).await?;
-
     let input = GraphData::from_path(input)?;
 
     let settings = GraphSettings::load(&settings_path)?;
 
-    let mut scales: Vec<u32> = vec![];
-    // The data that will be stored in the test contracts that will eventually be read from.
-    let mut calls_to_accounts = vec![];
+    let (anvil, client) = setup_eth_backend(rpc_url, private_key).await?;
 
-    let mut instance_shapes = vec![];
-    let mut model_instance_offset = 0;
+    let (contract_name, args) = da_constructor_args(&settings, &input, client.address())?;
 
-    if settings.run_args.input_visibility.is_hashed() {
-        instance_shapes.push(POSEIDON_INSTANCES)
-    } else if settings.run_args.input_visibility.is_encrypted() {
-        instance_shapes.push(ELGAMAL_INSTANCES)
-    } else if settings.run_args.input_visibility.is_public() {
-        for idx in 0..settings.model_input_scales.len() {
-            let shape = &settings.model_instance_shapes[idx];
-            instance_shapes.push(shape.iter().product::<usize>());
-            model_instance_offset += 1;
-        }
-    }
+    let (abi, bytecode, runtime_bytecode) =
+        get_contract_artifacts(sol_code_path, contract_name, runs)?;
+
+    let addr = deploy_contract(
+        abi,
+        bytecode,
+        runtime_bytecode,
+        client.clone(),
+        args,
+        create2_factory,
+    )
+    .await?;
 
-    if settings.run_args.param_visibility.is_hashed()
-        || settings.run_args.param_visibility.is_encrypted()
-    {
-        return Err(Box::new(EvmVerificationError::InvalidVisibility));
-    }
+    drop(anvil);
+    Ok(addr)
+}
 
-    if settings.run_args.output_visibility.is_hashed() {
-        instance_shapes.push(POSEIDON_INSTANCES)
-    } else if settings.run_args.output_visibility.is_encrypted() {
-        instance_shapes.push(ELGAMAL_INSTANCES)
-    } else if settings.run_args.output_visibility.is_public() {
-        for idx in model_instance_offset..model_instance_offset + settings.model_output_scales.len()
-        {
-            let shape = &settings.model_instance_shapes[idx];
-            instance_shapes.push(shape.iter().product::<usize>());
-        }
+/// The data attestation contract (`DataAttestation` or `DataAttestationSigned`) attesting to the data
+/// sources of `input` and its constructor arguments, with `admin` as the account allowed to update it.
+/// These don't depend on a node, so they also give the contract's CREATE2 address offline
+pub fn da_constructor_args(
+    settings: &GraphSettings,
+    input: &GraphData,
+    admin: H160,
+) -> Result<(&'static str, Vec<Token>), Box<dyn Error>> {
+    if !input.signed_sources().is_empty() {
+        return Ok((
+            "DataAttestationSigned",
+            signed_da_constructor_args(settings, input, admin)?,
+        ));
     }
 
-    println!("instance_shapes: {:#?}", instance_shapes);
+    let mut scales: Vec<u32> = vec![];
+    // The data that will be stored in the test contracts that will eventually be read from.
+    let mut calls_to_accounts = vec![];
+
+    let (input_shapes, output_shapes) = da_instance_shapes(settings)?;
+
+    debug!(
+        "instance_shapes: {:#?}",
+        [&input_shapes[..], &output_shapes[..]].concat()
+    );
 
-    let mut instance_idx = 0;
     let mut contract_instance_offset = 0;
 
-    if let DataSource::OnChain(source) = input.input_data {
-        if settings.run_args.input_visibility.is_hashed_public()
+    if let DataSource::OnChain(source) = &input.input_data {
+        if source.hash_on_chain {
+            // the calls return the data itself, which is quantized then hashed on-chain
+            scales.extend(on_chain_hash_scales(
+                source,
+                &settings.run_args.input_visibility,
+                &settings.model_input_scales,
+            )?);
+        } else if settings.run_args.input_visibility.is_hashed_public()
             | settings.run_args.input_visibility.is_encrypted()
         {
             // set scales 1.0
-            scales.extend(vec![0; instance_shapes[instance_idx]]);
-            instance_idx += 1;
+            scales.extend(vec![0; input_shapes.iter().sum::<usize>()]);
         } else {
-            let input_scales = settings.model_input_scales;
+            let input_scales = &settings.model_input_scales;
             // give each input a scale
-            for scale in input_scales {
-                scales.extend(vec![scale as u32; instance_shapes[instance_idx]]);
-                instance_idx += 1;
+            for (scale, shape) in input_scales.iter().zip(&input_shapes) {
+                scales.extend(vec![*scale as u32; *shape]);
             }
         }
-        for call in source.calls {
-            calls_to_accounts.push(call);
-        }
-    } else if let DataSource::File(source) = input.input_data {
+        calls_to_accounts.extend(source.calls.iter().cloned());
+    } else if let DataSource::File(source) = &input.input_data {
         if settings.run_args.input_visibility.is_public() {
-            instance_idx += source.len();
             for s in source {
                 contract_instance_offset += s.len();
             }
+        } else if settings.run_args.input_visibility.is_hashed_public() {
+            // skip the hashes of the inputs
+            contract_instance_offset += input_shapes.iter().sum::<usize>();
         }
     }
 
-    if let Some(DataSource::OnChain(source)) = input.output_data {
-        if settings.run_args.output_visibility.is_hashed_public()
+    if let Some(DataSource::OnChain(source)) = &input.output_data {
+        if source.hash_on_chain {
+            scales.extend(on_chain_hash_scales(
+                source,
+                &settings.run_args.output_visibility,
+                &settings.model_output_scales,
+            )?);
+        } else if settings.run_args.output_visibility.is_hashed_public()
             | settings.run_args.output_visibility.is_encrypted()
         {
             // set scales 1.0
-            scales.extend(vec![0; instance_shapes[instance_idx]]);
+            scales.extend(vec![0; output_shapes.iter().sum::<usize>()]);
         } else {
-            let input_scales = settings.model_output_scales;
+            let output_scales = &settings.model_output_scales;
             // give each output a scale
-            for scale in input_scales {
-                scales.extend(vec![scale as u32; instance_shapes[instance_idx]]);
-                instance_idx += 1;
+            for (scale, shape) in output_scales.iter().zip(&output_shapes) {
+                scales.extend(vec![*scale as u32; *shape]);
             }
         }
-        for call in source.calls {
-            calls_to_accounts.push(call);
-        }
+        calls_to_accounts.extend(source.calls.iter().cloned());
     }
 
     let (contract_addresses, call_data, decimals) = if !calls_to_accounts.is_empty() {
@@ -230,29 +431,283 @@ pub async fn deploy_da_verifier_via_solidity(
         return Err("Data source for either input_data or output_data must be OnChain".into());
     };
 
-    let (abi, bytecode, runtime_bytecode) =
-        get_contract_artifacts(sol_code_path, "DataAttestation", runs)?;
-    let factory =
-        get_sol_contract_factory(abi, bytecode, runtime_bytecode, client.clone()).unwrap();
-
     info!("call_data: {:#?}", call_data);
     info!("contract_addresses: {:#?}", contract_addresses);
     info!("decimals: {:#?}", decimals);
 
-    let contract = factory
-        .deploy((
+    Ok((
+        "DataAttestation",
+        (
             contract_addresses,
             call_data,
             decimals,
             scales,
             contract_instance_offset as u32,
-            client.address(),
-        ))?
-        .send()
-        .await?;
+            admin,
+        )
+            .into_tokens(),
+    ))
+}
 
-    drop(anvil);
-    Ok(contract.address())
+/// The scales to quantize the data returned by the calls of `source` with, when the data is hashed on-chain
+fn on_chain_hash_scales(
+    source: &OnChainSource,
+    visibility: &Visibility,
+    model_scales: &[crate::Scale],
+) -> Result<Vec<u32>, Box<dyn Error>> {
+    if !visibility.is_hashed_public() {
+        return Err("only data with a public hash can be hashed on-chain".into());
+    }
+    // the contract hashes all the data of a source as a single message
+    if model_scales.len() != 1 {
+        return Err(
+            "hashing data on-chain is only supported for a single model input (or output)".into(),
+        );
+    }
+    let num_calls: usize = source.calls.iter().map(|c| c.call_data.len()).sum();
+    Ok(vec![model_scales[0] as u32; num_calls])
+}
+
+/// The number of public instances of each of the model's inputs and outputs, in the order
+/// the data attestation contracts read them
+fn da_instance_shapes(
+    settings: &GraphSettings,
+) -> Result<(Vec<usize>, Vec<usize>), Box<dyn Error>> {
+    // the contracts read the instances of all the inputs (and outputs) as a single range
+    if VarVisibility::from_args(&settings.run_args)?.is_per_tensor() {
+        return Err(Box::new(EvmVerificationError::InvalidVisibility));
+    }
+
+    let mut input_shapes = vec![];
+    let mut output_shapes = vec![];
+    let mut model_instance_offset = 0;
+
+    if settings.run_args.input_visibility.is_hashed() {
+        // one hash per input
+        for _ in 0..settings.model_input_scales.len() {
+            input_shapes.push(POSEIDON_INSTANCES)
+        }
+    } else if settings.run_args.input_visibility.is_encrypted() {
+        input_shapes.push(ELGAMAL_INSTANCES)
+    } else if settings.run_args.input_visibility.is_public() {
+        for idx in 0..settings.model_input_scales.len() {
+            let shape = &settings.model_instance_shapes[idx];
+            input_shapes.push(shape.iter().product::<usize>());
+            model_instance_offset += 1;
+        }
+    }
+
+    if settings.run_args.param_visibility.is_hashed()
+        || settings.run_args.param_visibility.is_encrypted()
+    {
+        return Err(Box::new(EvmVerificationError::InvalidVisibility));
+    }
+
+    // merkle roots and signers' keys don't commit to the data itself, so can't be checked against it on chain
+    if settings.run_args.input_visibility.is_merkle()
+        || settings.run_args.input_visibility.is_signed()
+        || settings.run_args.output_visibility.is_merkle()
+    {
+        return Err(Box::new(EvmVerificationError::InvalidVisibility));
+    }
+
+    if settings.run_args.output_visibility.is_hashed() {
+        // one hash per output
+        for _ in 0..settings.model_output_scales.len() {
+            output_shapes.push(POSEIDON_INSTANCES)
+        }
+    } else if settings.run_args.output_visibility.is_encrypted() {
+        output_shapes.push(ELGAMAL_INSTANCES)
+    } else if settings.run_args.output_visibility.is_public() {
+        for idx in model_instance_offset..model_instance_offset + settings.model_output_scales.len()
+        {
+            let shape = &settings.model_instance_shapes[idx];
+            output_shapes.push(shape.iter().product::<usize>());
+        }
+    }
+
+    Ok((input_shapes, output_shapes))
+}
+
+/// The constructor arguments of a `DataAttestationSigned` contract for the signed sources of `input`
+fn signed_da_constructor_args(
+    settings: &GraphSettings,
+    input: &GraphData,
+    admin: H160,
+) -> Result<Vec<Token>, Box<dyn Error>> {
+    if matches!(input.input_data, DataSource::OnChain(_))
+        || matches!(input.output_data, Some(DataSource::OnChain(_)))
+    {
+        return Err(
+            "on-chain and signed data sources can't be attested by the same contract".into(),
+        );
+    }
+
+    let (input_shapes, output_shapes) = da_instance_shapes(settings)?;
+    let input_len: usize = input_shapes.iter().sum();
+    let output_len: usize = output_shapes.iter().sum();
+
+    let sources = [
+        (
+            match &input.input_data {
+                DataSource::Signed(source) => Some(source),
+                _ => None,
+            },
+            settings.run_args.input_visibility.is_public(),
+            0,
+            input_len,
+        ),
+        (
+            match &input.output_data {
+                Some(DataSource::Signed(source)) => Some(source),
+                _ => None,
+            },
+            settings.run_args.output_visibility.is_public(),
+            input_len,
+            output_len,
+        ),
+    ];
+
+    let mut signer: Option<H160> = None;
+    let mut instance_offsets: Vec<U256> = vec![];
+    let mut instance_lengths: Vec<U256> = vec![];
+    for (source, is_public, offset, len) in sources {
+        let source = match source {
+            Some(source) => source,
+            None => continue,
+        };
+        if !is_public {
+            return Err("signed data must have public visibility".into());
+        }
+        let data_len: usize = source.data.iter().map(|d| d.len()).sum();
+        if data_len != len {
+            return Err(format!(
+                "signed data has {} elements but the model has {} public instances for it",
+                data_len, len
+            )
+            .into());
+        }
+        let source_signer = H160::from_str(&source.signer)?;
+        if signer.is_some_and(|s| s != source_signer) {
+            return Err("all the signed data must be signed by the same signer".into());
+        }
+        signer = Some(source_signer);
+        instance_offsets.push(U256::from(offset));
+        instance_lengths.push(U256::from(len));
+    }
+    let signer = signer.ok_or("no signed data source")?;
+
+    info!("signer: {:#?}", signer);
+    info!("instance offsets: {:#?}", instance_offsets);
+    info!("instance lengths: {:#?}", instance_lengths);
+
+    Ok((signer, instance_offsets, instance_lengths, admin).into_tokens())
+}
+
+/// Name of the EIP-712 domain data is signed in, must match `DataAttestationSigned`
+const SIGNED_DATA_DOMAIN_NAME: &str = "EZKL Data Attestation";
+/// Version of the EIP-712 domain data is signed in, must match `DataAttestationSigned`
+const SIGNED_DATA_DOMAIN_VERSION: &str = "1";
+
+/// The EIP-712 digest an off-chain signer signs to attest to quantized data. The signed struct is
+/// `SignedData(uint256 nonce,bytes32 dataHash)` with `dataHash = keccak256(abi.encodePacked(data))`
+/// (the field elements encoded as uint256s), and the domain binds it to the chain and the verifier
+/// contract of `domain`. This is what the `DataAttestationSigned` contract recovers the signer of.
+pub fn signed_data_digest(
+    data: &[Fr],
+    domain: &SignedDataDomain,
+) -> Result<[u8; 32], Box<dyn Error>> {
+    use ethers::utils::keccak256;
+
+    let mut packed = Vec::with_capacity(32 * data.len());
+    for val in data {
+        // field elements are little endian, uint256s are big endian
+        let mut bytes = val.to_repr();
+        bytes.reverse();
+        packed.extend_from_slice(&bytes);
+    }
+
+    let domain_separator = keccak256(ethers::abi::encode(&[
+        Token::FixedBytes(
+            keccak256(
+                "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+            )
+            .to_vec(),
+        ),
+        Token::FixedBytes(keccak256(SIGNED_DATA_DOMAIN_NAME).to_vec()),
+        Token::FixedBytes(keccak256(SIGNED_DATA_DOMAIN_VERSION).to_vec()),
+        Token::Uint(U256::from(domain.chain_id)),
+        Token::Address(H160::from_str(&domain.verifier)?),
+    ]));
+    let struct_hash = keccak256(ethers::abi::encode(&[
+        Token::FixedBytes(keccak256("SignedData(uint256 nonce,bytes32 dataHash)").to_vec()),
+        Token::Uint(U256::from(domain.nonce)),
+        Token::FixedBytes(keccak256(packed).to_vec()),
+    ]));
+    Ok(keccak256(
+        [&[0x19, 0x01], &domain_separator[..], &struct_hash[..]].concat(),
+    ))
+}
+
+/// Signs quantized data with `wallet`, producing a signature the `DataAttestationSigned` contract accepts
+#[cfg(not(target_arch = "wasm32"))]
+pub fn sign_quantized_data(
+    data: &[Fr],
+    domain: &SignedDataDomain,
+    wallet: &LocalWallet,
+) -> Result<ethers::types::Signature, Box<dyn Error>> {
+    let digest = signed_data_digest(data, domain)?;
+    Ok(wallet.sign_hash(H256::from(digest))?)
+}
+
+/// Signs the quantized version (`quantized`) of `data` in `domain` with `wallet`
+#[cfg(not(target_arch = "wasm32"))]
+pub fn sign_data_source(
+    data: crate::graph::input::FileSource,
+    quantized: &[Fr],
+    domain: SignedDataDomain,
+    wallet: &LocalWallet,
+) -> Result<SignedSource, Box<dyn Error>> {
+    let signature = sign_quantized_data(quantized, &domain, wallet)?;
+    Ok(SignedSource::new(
+        data,
+        hex::encode(signature.to_vec()),
+        hex::encode(wallet.address()),
+        domain,
+    ))
+}
+
+/// Recovers the signer of quantized data signed in `domain`
+pub fn recover_data_signer(
+    data: &[Fr],
+    domain: &SignedDataDomain,
+    signature: &str,
+) -> Result<H160, Box<dyn Error>> {
+    let signature: ethers::types::Signature = signature.trim_start_matches("0x").parse()?;
+    let digest = signed_data_digest(data, domain)?;
+    Ok(signature.recover(H256::from(digest))?)
+}
+
+/// Checks that `quantized` (the quantized data of `source`) was signed by the signer of `source`
+pub fn check_data_signature(quantized: &[Fr], source: &SignedSource) -> Result<(), Box<dyn Error>> {
+    let signer = H160::from_str(&source.signer)?;
+    let recovered = recover_data_signer(quantized, &source.domain, &source.signature)?;
+    if recovered != signer {
+        return Err(format!(
+            "data was signed by {:#?} rather than the signer {:#?}",
+            recovered, signer
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// The signatures of the signed sources of `data`, in the order the `DataAttestationSigned` contract checks them
+pub fn data_signatures(data: &GraphData) -> Result<Vec<Vec<u8>>, Box<dyn Error>> {
+    data.signed_sources()
+        .iter()
+        .map(|source| Ok(hex::decode(source.signature.trim_start_matches("0x"))?))
+        .collect()
 }
 
 type ParsedCallsToAccount = (Vec<H160>, Vec<Vec<Bytes>>, Vec<Vec<U256>>);
@@ -348,11 +803,21 @@ pub async fn update_account_calls(
 pub async fn verify_proof_via_solidity(
     proof: Snark<Fr, G1Affine>,
     addr: ethers::types::Address,
+    addr_vk: Option<ethers::types::Address>,
+    commitments: Option<&[G1Affine]>,
     rpc_url: Option<&str>,
 ) -> Result<bool, Box<dyn Error>> {
-    let flattened_instances = proof.instances.into_iter().flatten();
-
-    let encoded = encode_calldata(None, &proof.proof, &flattened_instances.collect::<Vec<_>>());
+    let encoded = match commitments {
+        Some(commitments) => {
+            if addr_vk.is_some() {
+                return Err(Box::new(EvmVerificationError::SeparateVk(
+                    "binding commitments",
+                )));
+            }
+            encode_verifier_calldata_with_commitments(&proof, commitments)?
+        }
+        None => encode_verifier_calldata(&proof, addr_vk),
+    };
 
     info!("encoded: {:#?}", hex::encode(&encoded));
     let (anvil, client) = setup_eth_backend(rpc_url, None).await?;
@@ -447,65 +912,19 @@ pub async fn setup_test_contract<M: 'static + Middleware>(
     Ok((contract, decimals))
 }
 
-/// Verify a proof using a Solidity DataAttestation contract.
+/// Verify a proof using a Solidity DataAttestation contract (or a DataAttestationSigned contract
+/// if the `signatures` of the data are provided). If the on-chain data was pinned to a `block`,
+/// the contract also checks the hash of that block.
 /// Used for testing purposes.
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn verify_proof_with_data_attestation(
     proof: Snark<Fr, G1Affine>,
     addr_verifier: ethers::types::Address,
     addr_da: ethers::types::Address,
+    signatures: Option<&[Vec<u8>]>,
     rpc_url: Option<&str>,
 ) -> Result<bool, Box<dyn Error>> {
-    use ethers::abi::{Function, Param, ParamType, StateMutability, Token};
-
-    let mut public_inputs: Vec<U256> = vec![];
-    let flattened_instances = proof.instances.into_iter().flatten();
-
-    for val in flattened_instances.clone() {
-        let bytes = val.to_repr();
-        let u = U256::from_little_endian(bytes.as_slice());
-        public_inputs.push(u);
-    }
-
-    let encoded_verifier =
-        encode_calldata(None, &proof.proof, &flattened_instances.collect::<Vec<_>>());
-
-    info!("encoded: {:#?}", hex::encode(&encoded_verifier));
-
-    info!("public_inputs: {:#?}", public_inputs);
-    info!(
-        "proof: {:#?}",
-        ethers::types::Bytes::from(proof.proof.to_vec())
-    );
-
-    #[allow(deprecated)]
-    let func = Function {
-        name: "verifyWithDataAttestation".to_owned(),
-        inputs: vec![
-            Param {
-                name: "verifier".to_owned(),
-                kind: ParamType::Address,
-                internal_type: None,
-            },
-            Param {
-                name: "encoded".to_owned(),
-                kind: ParamType::Bytes,
-                internal_type: None,
-            },
-        ],
-        outputs: vec![Param {
-            name: "success".to_owned(),
-            kind: ParamType::Bool,
-            internal_type: None,
-        }],
-        constant: None,
-        state_mutability: StateMutability::View,
-    };
-
-    let encoded = func.encode_input(&[
-        Token::Address(addr_verifier),
-        Token::Bytes(encoded_verifier),
-    ])?;
+    let encoded = encode_da_calldata(&proof, addr_verifier, signatures)?;
 
     info!("encoded: {:#?}", hex::encode(&encoded));
     let (anvil, client) = setup_eth_backend(rpc_url, None).await?;
@@ -535,6 +954,236 @@ pub async fn verify_proof_with_data_attestation(
     Ok(true)
 }
 
+/// Encodes the calldata for a call to `verifyProof` on a verifier contract.
+/// Verifiers rendered without an embedded vk take the address of the vk contract as their first argument.
+pub fn encode_verifier_calldata(
+    proof: &Snark<Fr, G1Affine>,
+    addr_vk: Option<ethers::types::Address>,
+) -> Vec<u8> {
+    let flattened_instances = proof.instances.iter().flatten().cloned();
+    encode_calldata(
+        addr_vk.map(|addr| addr.0),
+        &proof.proof,
+        &flattened_instances.collect::<Vec<_>>(),
+    )
+}
+
+/// Encodes the calldata for a call to `verifyProofWithCommitments` on a verifier rendered with
+/// `--bind-commitments`, which checks that the proof starts with the KZG `commitments`
+pub fn encode_verifier_calldata_with_commitments(
+    proof: &Snark<Fr, G1Affine>,
+    commitments: &[G1Affine],
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    use ethers::abi::{Function, Param, ParamType, StateMutability, Token};
+
+    let to_uint = |repr: &[u8]| Token::Uint(U256::from_little_endian(repr));
+    let instances = proof
+        .instances
+        .iter()
+        .flatten()
+        .map(|val| to_uint(val.to_repr().as_slice()))
+        .collect();
+    // the evm transcript writes each commitment as its (x, y) coordinates
+    let commitments = commitments
+        .iter()
+        .flat_map(|c| {
+            [
+                to_uint(c.x.to_repr().as_slice()),
+                to_uint(c.y.to_repr().as_slice()),
+            ]
+        })
+        .collect();
+
+    let param = |name: &str, kind: ParamType| Param {
+        name: name.to_owned(),
+        kind,
+        internal_type: None,
+    };
+    #[allow(deprecated)]
+    let func = Function {
+        name: "verifyProofWithCommitments".to_owned(),
+        inputs: vec![
+            param("proof", ParamType::Bytes),
+            param(
+                "instances",
+                ParamType::Array(Box::new(ParamType::Uint(256))),
+            ),
+            param(
+                "commitments",
+                ParamType::Array(Box::new(ParamType::Uint(256))),
+            ),
+        ],
+        outputs: vec![param("", ParamType::Bool)],
+        constant: None,
+        state_mutability: StateMutability::NonPayable,
+    };
+
+    Ok(func.encode_input(&[
+        Token::Bytes(proof.proof.clone()),
+        Token::Array(instances),
+        Token::Array(commitments),
+    ])?)
+}
+
+/// Encodes the calldata for a call to `verifyWithDataAttestation` on a data attestation contract,
+/// or to `verifyWithSignedDataAttestation` on a signed data attestation contract if `signatures` are provided
+pub fn encode_da_calldata(
+    proof: &Snark<Fr, G1Affine>,
+    addr_verifier: ethers::types::Address,
+    signatures: Option<&[Vec<u8>]>,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    use ethers::abi::{Function, Param, ParamType, StateMutability, Token};
+
+    let public_inputs: Vec<U256> = proof
+        .instances
+        .iter()
+        .flatten()
+        .map(|val| U256::from_little_endian(val.to_repr().as_slice()))
+        .collect();
+
+    let encoded_verifier = encode_verifier_calldata(proof, None);
+
+    debug!("encoded: {:#?}", hex::encode(&encoded_verifier));
+    debug!("public_inputs: {:#?}", public_inputs);
+    debug!(
+        "proof: {:#?}",
+        ethers::types::Bytes::from(proof.proof.to_vec())
+    );
+
+    let mut inputs = vec![
+        Param {
+            name: "verifier".to_owned(),
+            kind: ParamType::Address,
+            internal_type: None,
+        },
+        Param {
+            name: "encoded".to_owned(),
+            kind: ParamType::Bytes,
+            internal_type: None,
+        },
+    ];
+    let mut tokens = vec![
+        Token::Address(addr_verifier),
+        Token::Bytes(encoded_verifier),
+    ];
+    let name = match signatures {
+        Some(signatures) => {
+            inputs.push(Param {
+                name: "signatures".to_owned(),
+                kind: ParamType::Array(Box::new(ParamType::Bytes)),
+                internal_type: None,
+            });
+            tokens.push(Token::Array(
+                signatures.iter().cloned().map(Token::Bytes).collect(),
+            ));
+            "verifyWithSignedDataAttestation"
+        }
+        None => "verifyWithDataAttestation",
+    };
+
+    #[allow(deprecated)]
+    let func = Function {
+        name: name.to_owned(),
+        inputs,
+        outputs: vec![Param {
+            name: "success".to_owned(),
+            kind: ParamType::Bool,
+            internal_type: None,
+        }],
+        constant: None,
+        state_mutability: StateMutability::View,
+    };
+
+    Ok(func.encode_input(&tokens)?)
+}
+
+/// Deployment and verification costs of a verifier contract
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EvmCostReport {
+    /// name of the contract
+    pub contract: String,
+    /// address the contract was deployed at
+    pub address: ethers::types::Address,
+    /// gas used by the deployment transaction (if it could be found)
+    pub deploy_gas: Option<u64>,
+    /// size of the deployed (runtime) bytecode in bytes
+    pub runtime_code_size: usize,
+    /// whether the runtime bytecode exceeds the EIP-170 contract size limit
+    pub exceeds_code_size_limit: bool,
+    /// size of the calldata of a single verification in bytes (for contracts that verify proofs)
+    pub calldata_bytes: Option<usize>,
+    /// gas used to verify a single proof (for contracts that verify proofs)
+    pub verify_gas: Option<u64>,
+}
+
+/// Reports the runtime code size of the contract at `addr`, its deployment gas if the block it was
+/// deployed in (`deploy_block`) is known, and if `calldata` is provided the gas used by calling the
+/// contract with it (which must return true, i.e. verify).
+pub async fn estimate_contract_costs(
+    contract: &str,
+    addr: ethers::types::Address,
+    deploy_block: Option<u64>,
+    calldata: Option<Vec<u8>>,
+    rpc_url: Option<&str>,
+) -> Result<EvmCostReport, Box<dyn Error>> {
+    let (anvil, client) = setup_eth_backend(rpc_url, None).await?;
+
+    let runtime_code_size = client.get_code(addr, None).await?.len();
+
+    // find the deployment transaction amongst those of the block the contract was deployed in
+    let mut deploy_gas = None;
+    let block = match deploy_block {
+        Some(number) => client.get_block(number).await?,
+        None => None,
+    };
+    for tx in block.map(|b| b.transactions).unwrap_or_default() {
+        if let Some(receipt) = client.get_transaction_receipt(tx).await? {
+            if receipt.contract_address == Some(addr) {
+                deploy_gas = receipt.gas_used.map(|g| g.as_u64());
+                break;
+            }
+        }
+    }
+    if deploy_block.is_some() && deploy_gas.is_none() {
+        warn!(
+            "could not find the deployment of {} in block {:?}",
+            contract, deploy_block
+        );
+    }
+
+    let (calldata_bytes, verify_gas) = match calldata {
+        Some(calldata) => {
+            let calldata_bytes = calldata.len();
+            let tx: TypedTransaction = TransactionRequest::default()
+                .to(addr)
+                .from(client.address())
+                .data(calldata)
+                .into();
+            let result = client
+                .call(&tx, None)
+                .await
+                .map_err(|_| EvmVerificationError::SolidityExecution)?;
+            if result.to_vec().last() != Some(&1u8) {
+                return Err(Box::new(EvmVerificationError::InvalidProof));
+            }
+            let gas = client.estimate_gas(&tx, None).await?;
+            (Some(calldata_bytes), Some(gas.as_u64()))
+        }
+        None => (None, None),
+    };
+
+    drop(anvil);
+    Ok(EvmCostReport {
+        contract: contract.to_string(),
+        address: addr,
+        deploy_gas,
+        runtime_code_size,
+        exceeds_code_size_limit: runtime_code_size > MAX_RUNTIME_BYTECODE_SIZE,
+        calldata_bytes,
+        verify_gas,
+    })
+}
+
 /// get_provider returns a JSON RPC HTTP Provider
 pub fn get_provider(rpc_url: &str) -> Result<Provider<Http>, Box<dyn Error>> {
     let provider = Provider::<Http>::try_from(rpc_url)?;
@@ -576,6 +1225,7 @@ pub async fn read_on_chain_inputs<M: 'static + Middleware>(
     client: Arc<M>,
     address: H160,
     data: &Vec<CallsToAccount>,
+    block: Option<BlockId>,
 ) -> Result<(Vec<Bytes>, Vec<u8>), Box<dyn Error>> {
     // Iterate over all on-chain inputs
     let mut fetched_inputs = vec![];
@@ -593,7 +1243,7 @@ pub async fn read_on_chain_inputs<M: 'static + Middleware>(
                 .into();
             debug!("transaction {:#?}", tx);
 
-            let result = client.call(&tx, None).await?;
+            let result = client.call(&tx, block).await?;
             debug!("return data {:#?}", result);
             fetched_inputs.push(result);
             decimals.push(*decimal);
@@ -602,6 +1252,46 @@ pub async fn read_on_chain_inputs<M: 'static + Middleware>(
     Ok((fetched_inputs, decimals))
 }
 
+/// The block the calls of an [OnChainSource] are made at, the latest block if it isn't pinned
+pub fn on_chain_block_id(source: &OnChainSource) -> Result<Option<BlockId>, Box<dyn Error>> {
+    if let Some(hash) = &source.block_hash {
+        let hash = H256::from_slice(&hex::decode(hash.trim_start_matches("0x"))?);
+        return Ok(Some(BlockId::Hash(hash)));
+    }
+    Ok(source
+        .block_number
+        .map(|number| BlockId::Number(BlockNumber::Number(number.into()))))
+}
+
+/// Resolves the block the calls of `source` are made at (the latest block if it isn't pinned)
+/// and pins the source to it, so that the data can be read again later
+pub async fn pin_on_chain_block(
+    source: &mut OnChainSource,
+) -> Result<OnChainBlock, Box<dyn Error>> {
+    let provider = Provider::<Http>::try_from(source.rpc.as_str())?;
+    let block_id = on_chain_block_id(source)?.unwrap_or(BlockId::Number(BlockNumber::Latest));
+    let block = provider
+        .get_block(block_id)
+        .await?
+        .ok_or(format!("block {:?} not found", block_id))?;
+    let (number, hash) = match (block.number, block.hash) {
+        (Some(number), Some(hash)) => (number.as_u64(), format!("{:#x}", hash)),
+        _ => return Err("can't pin on-chain data to a pending block".into()),
+    };
+    if let Some(pinned) = source.block_number {
+        if pinned != number {
+            return Err(format!(
+                "block hash {} is block {}, not the pinned block {}",
+                hash, number, pinned
+            )
+            .into());
+        }
+    }
+    source.block_number = Some(number);
+    source.block_hash = Some(hash.clone());
+    Ok(OnChainBlock { number, hash })
+}
+
 ///
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn evm_quantize<M: 'static + Middleware>(
@@ -672,7 +1362,6 @@ fn get_sol_contract_factory<M: 'static + Middleware>(
     runtime_bytecode: Bytes,
     client: Arc<M>,
 ) -> Result<ContractFactory<M>, Box<dyn Error>> {
-    const MAX_RUNTIME_BYTECODE_SIZE: usize = 24577;
     let size = runtime_bytecode.len();
     debug!("runtime bytecode size: {:#?}", size);
     if size > MAX_RUNTIME_BYTECODE_SIZE {
@@ -717,19 +1406,310 @@ pub fn get_contract_artifacts(
     Ok((abi, bytecode, runtime_bytecode))
 }
 
+/// Inlines the LoadInstances contract that the da verifiers import
+fn inline_load_instances(contract: &str) -> String {
+    let load_instances = LOADINSTANCES_SOL.to_string();
+    // replace the import statement with the load_instances contract, not including the
+    // `SPDX-License-Identifier: MIT pragma solidity ^0.8.20;` at the top of the file
+    contract.replace(
+        "import './LoadInstances.sol';",
+        &load_instances[load_instances.find("contract").unwrap()..],
+    )
+}
+
+/// The signed data attestation verifier
+pub fn signed_da_sol() -> String {
+    inline_load_instances(ATTESTSIGNEDDATA_SOL)
+}
+
+/// Extends a rendered verifier to check that its proofs commit to externally supplied KZG commitments.
+/// The rendered verifier is renamed to `Halo2VerifierBase` and the extended contract is the new `Halo2Verifier`.
+pub fn bind_commitments_sol(verifier_solidity: &str) -> Result<String, Box<dyn Error>> {
+    let verifier = rename_verifier_to_base(verifier_solidity)?;
+    // drop the `SPDX-License-Identifier: MIT pragma solidity ^0.8.20;` at the top of the file
+    let binding = &BINDCOMMITMENTS_SOL[BINDCOMMITMENTS_SOL.find("// This contract").unwrap()..];
+    Ok(format!("{}\n{}", verifier, binding))
+}
+
+/// Renames the rendered `Halo2Verifier` contract to `Halo2VerifierBase`, so that a contract extending it
+/// can take its name (and be deployed, verified and have its abi generated as the verifier)
+fn rename_verifier_to_base(verifier_solidity: &str) -> Result<String, Box<dyn Error>> {
+    if !verifier_solidity.contains("contract Halo2Verifier {") {
+        return Err("could not find the Halo2Verifier contract in the rendered verifier".into());
+    }
+    Ok(verifier_solidity.replace("contract Halo2Verifier {", "contract Halo2VerifierBase {"))
+}
+
+/// Extends a rendered aggregate verifier of sequential split proofs with typed accessors for the
+/// inputs of the first segment and the outputs of the last segment (as laid out in `layout`)
+pub fn split_proof_verifier_sol(
+    verifier_solidity: &str,
+    layout: &AggregateInstanceLayout,
+) -> Result<String, Box<dyn Error>> {
+    if layout.inputs.is_empty() && layout.outputs.is_empty() {
+        return Err("the split proofs have no public inputs or outputs to expose".into());
+    }
+    let verifier = rename_verifier_to_base(verifier_solidity)?;
+
+    let returns = |ranges: &[InstanceRange]| {
+        ranges
+            .iter()
+            .map(|r| format!("uint256[] memory {}", r.name))
+            .join(", ")
+    };
+    let names = |ranges: &[InstanceRange]| ranges.iter().map(|r| r.name.clone()).join(", ");
+    let getter = |fn_name: &str, ranges: &[InstanceRange]| {
+        if ranges.is_empty() {
+            return String::new();
+        }
+        let body = ranges
+            .iter()
+            .map(|r| {
+                format!(
+                    "        {} = slice(instances, {}, {});\n",
+                    r.name, r.offset, r.len
+                )
+            })
+            .collect::<String>();
+        format!(
+            "    function {}(\n        uint256[] calldata instances\n    ) public pure returns ({}) {{\n        require(instances.length == NUM_INSTANCES, \"Invalid number of instances\");\n{}    }}\n\n",
+            fn_name,
+            returns(ranges),
+            body
+        )
+    };
+    let mut assignments = String::new();
+    for (fn_name, ranges) in [
+        ("getInputs", &layout.inputs),
+        ("getOutputs", &layout.outputs),
+    ] {
+        if !ranges.is_empty() {
+            assignments += &format!("        ({}) = {}(instances);\n", names(ranges), fn_name);
+        }
+    }
+
+    let wrapper = format!(
+        r#"// This contract extends the aggregate verifier of sequential split proofs (rendered with `--split-proofs`, which renames the
+// rendered verifier contract to Halo2VerifierBase). The instances of the aggregate proof are the limbs of the KZG accumulator
+// followed by the instances of each segment, this contract exposes the inputs of the first segment and the outputs of the last segment.
+// See the instance layout json for the offsets of the instances of every segment.
+contract Halo2Verifier is Halo2VerifierBase {{
+    uint256 constant NUM_INSTANCES = {};
+
+    function slice(
+        uint256[] calldata instances,
+        uint256 offset,
+        uint256 length
+    ) internal pure returns (uint256[] memory values) {{
+        values = new uint256[](length);
+        for (uint i = 0; i < length; i++) {{
+            values[i] = instances[offset + i];
+        }}
+    }}
+
+{}{}    function verifySplitProof(
+        bytes calldata proof,
+        uint256[] calldata instances
+    ) public returns ({}) {{
+        // the verifier reads the proof from the calldata so it has to be called externally
+        require(this.verifyProof(proof, instances), "Invalid proof");
+{}    }}
+}}
+"#,
+        layout.num_instances,
+        getter("getInputs", &layout.inputs),
+        getter("getOutputs", &layout.outputs),
+        returns(&[&layout.inputs[..], &layout.outputs[..]].concat()),
+        assignments,
+    );
+
+    Ok(format!("{}\n{}", verifier, wrapper))
+}
+
+/// Renders the `OutputDecoder` Solidity library for a circuit with the `settings`, which decodes the public
+/// outputs in the instances of a verified proof into signed fixed point integers (as `felt_to_i128` does),
+/// with the output's scale (the value is `int / 2^scale`) and shape stored as constants
+#[cfg(not(target_arch = "wasm32"))]
+pub fn output_decoder_sol(settings: &GraphSettings) -> Result<String, Box<dyn Error>> {
+    let layout = SegmentLayout::new(settings, 0)?;
+    // only the public outputs (which have a shape, unlike hashes) can be decoded
+    let outputs = layout
+        .ranges
+        .iter()
+        .filter(|r| r.name.starts_with("output") && r.shape.is_some())
+        .collect::<Vec<_>>();
+    if outputs.is_empty() {
+        return Err("only public outputs can be decoded from the instances".into());
+    }
+
+    let mut constants = String::new();
+    let mut decoders = String::new();
+    let mut decode_all = String::new();
+    let mut scales = String::new();
+    for (i, output) in outputs.iter().enumerate() {
+        let shape = output.shape.clone().unwrap_or_default();
+        let scale = output.scale.unwrap_or_default();
+        constants += &format!(
+            "    // {} has shape {shape:?}\n    uint256 constant OUTPUT_{i}_OFFSET = {};\n    uint256 constant OUTPUT_{i}_LEN = {};\n    int256 constant OUTPUT_{i}_SCALE = {scale};\n\n",
+            output.name, output.offset, output.len,
+        );
+        let shape_values = shape
+            .iter()
+            .enumerate()
+            .map(|(j, dim)| format!("        shape[{j}] = {dim};\n"))
+            .collect::<String>();
+        decoders += &format!(
+            r#"    function decodeOutput{i}(
+        uint256[] memory instances
+    ) internal pure returns (int256[] memory values) {{
+        require(instances.length == NUM_INSTANCES, "Invalid number of instances");
+        values = new int256[](OUTPUT_{i}_LEN);
+        for (uint i = 0; i < OUTPUT_{i}_LEN; i++) {{
+            values[i] = feltToInt(instances[OUTPUT_{i}_OFFSET + i]);
+        }}
+    }}
+
+    function outputShape{i}() internal pure returns (uint256[] memory shape) {{
+        shape = new uint256[]({});
+{shape_values}    }}
+
+"#,
+            shape.len(),
+        );
+        decode_all += &format!("        outputs[{i}] = decodeOutput{i}(instances);\n");
+        scales += &format!("        scales[{i}] = OUTPUT_{i}_SCALE;\n");
+    }
+
+    Ok(format!(
+        r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+// This library decodes the public outputs in the instances of a proof (once verified) into signed fixed point integers.
+// Field elements above the largest int128 are negative, the output value is then `int / 2^scale`.
+library OutputDecoder {{
+    uint256 constant ORDER =
+        0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
+
+    uint256 constant MAX_INT = uint256(uint128(type(int128).max));
+
+    uint256 constant NUM_INSTANCES = {num_instances};
+
+    uint256 constant NUM_OUTPUTS = {num_outputs};
+
+{constants}    function feltToInt(uint256 felt) internal pure returns (int256) {{
+        require(felt < ORDER, "Instance is not a field element");
+        if (felt > MAX_INT) {{
+            return -int256(ORDER - felt);
+        }}
+        return int256(felt);
+    }}
+
+    /**
+     * @dev Rescales a fixed point integer of scale `scale` to one with `decimals` decimals.
+     */
+    function toDecimals(
+        int256 value,
+        int256 scale,
+        uint256 decimals
+    ) internal pure returns (int256) {{
+        int256 multiplier = int256(10 ** decimals);
+        if (scale >= 0) {{
+            return (value * multiplier) / int256(1 << uint256(scale));
+        }}
+        return value * multiplier * int256(1 << uint256(-scale));
+    }}
+
+{decoders}    function decodeOutputs(
+        uint256[] memory instances
+    ) internal pure returns (int256[][] memory outputs) {{
+        outputs = new int256[][](NUM_OUTPUTS);
+{decode_all}    }}
+
+    function outputScales() internal pure returns (int256[] memory scales) {{
+        scales = new int256[](NUM_OUTPUTS);
+{scales}    }}
+}}
+"#,
+        num_instances = layout.len,
+        num_outputs = outputs.len(),
+    ))
+}
+
+/// Decodes the public outputs of a proof's `instances` with the `OutputDecoder` library at `sol_code_path`,
+/// by deploying a contract that calls the library.
+/// Used for testing purposes.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn decode_outputs_via_solidity(
+    sol_code_path: PathBuf,
+    instances: &[Fr],
+    rpc_url: Option<&str>,
+) -> Result<Vec<Vec<i128>>, Box<dyn Error>> {
+    let decoder = std::fs::read_to_string(sol_code_path)?;
+    // save the library with a contract calling it to a tmp file
+    let mut sol_path = std::env::temp_dir();
+    sol_path.push("testoutputdecoder.sol");
+    std::fs::write(
+        &sol_path,
+        format!(
+            r#"{}
+contract TestOutputDecoder {{
+    function decodeOutputs(
+        uint256[] calldata instances
+    ) external pure returns (int256[][] memory) {{
+        return OutputDecoder.decodeOutputs(instances);
+    }}
+}}
+"#,
+            decoder
+        ),
+    )?;
+
+    let (anvil, client) = setup_eth_backend(rpc_url, None).await?;
+    let (abi, bytecode, runtime_bytecode) =
+        get_contract_artifacts(sol_path, "TestOutputDecoder", 0)?;
+    let factory = get_sol_contract_factory(abi, bytecode, runtime_bytecode, client.clone())?;
+    let contract = factory.deploy(())?.send().await?;
+
+    let instances = instances
+        .iter()
+        .map(|x| U256::from_little_endian(x.to_repr().as_ref()))
+        .collect::<Vec<_>>();
+    let outputs: Vec<Vec<I256>> = contract.method("decodeOutputs", instances)?.call().await?;
+    drop(anvil);
+
+    Ok(outputs
+        .into_iter()
+        .map(|output| output.into_iter().map(|x| x.as_i128()).collect())
+        .collect())
+}
+
 /// Sets the constants stored in the da verifier
+/// (`hash_inputs` and `hash_outputs` set whether the fetched data is hashed on-chain,
+/// with the Poseidon parameter set `poseidon_params`, and `commits_block` whether the
+/// last instances hold the block the data was read at).
 pub fn fix_da_sol(
     input_data: Option<Vec<CallsToAccount>>,
     output_data: Option<Vec<CallsToAccount>>,
+    hash_inputs: bool,
+    hash_outputs: bool,
+    commits_block: bool,
+    poseidon_params: PoseidonParams,
 ) -> Result<String, Box<dyn Error>> {
     let mut accounts_len = 0;
-    let mut contract = ATTESTDATA_SOL.to_string();
-    let load_instances = LOADINSTANCES_SOL.to_string();
-    // replace the import statement with the load_instances contract, not including the
-    // `SPDX-License-Identifier: MIT pragma solidity ^0.8.20;` at the top of the file
+    let mut contract = inline_load_instances(ATTESTDATA_SOL);
+
     contract = contract.replace(
-        "import './LoadInstances.sol';",
-        &load_instances[load_instances.find("contract").unwrap()..],
+        "bool constant COMMITS_BLOCK = false;",
+        &format!("bool constant COMMITS_BLOCK = {};", commits_block),
+    );
+
+    contract = contract.replace(
+        "bool constant HASH_INPUTS = false;",
+        &format!("bool constant HASH_INPUTS = {};", hash_inputs),
+    );
+    contract = contract.replace(
+        "bool constant HASH_OUTPUTS = false;",
+        &format!("bool constant HASH_OUTPUTS = {};", hash_outputs),
     );
 
     // fill in the quantization params and total calls
@@ -751,6 +1731,110 @@ pub fn fix_da_sol(
         );
     }
     contract = contract.replace("AccountCall[]", &format!("AccountCall[{}]", accounts_len));
+    contract.push_str(&poseidon_sol(poseidon_params));
 
     Ok(contract)
 }
+
+/// Renders the `PoseidonHash` Solidity library, which computes the same (tree) Poseidon hash as
+/// the `hashed` visibility, with the round constants and MDS matrix of `params` inlined
+pub fn poseidon_sol(params: PoseidonParams) -> String {
+    let (round_constants, mds) = params.constants();
+    let (full_rounds, partial_rounds) = params.rounds();
+    let half_full_rounds = full_rounds / 2;
+
+    let mut rounds = String::new();
+    for (round, rcs) in round_constants.iter().enumerate() {
+        let is_full = round < half_full_rounds || round >= half_full_rounds + partial_rounds;
+        for (i, rc) in rcs.iter().enumerate() {
+            if is_full || i == 0 {
+                rounds += &format!("        s[{i}] = sbox(addmod(s[{i}], {rc:?}, P));\n");
+            } else {
+                rounds += &format!("        s[{i}] = addmod(s[{i}], {rc:?}, P);\n");
+            }
+        }
+        rounds += "        mix(s);\n";
+    }
+
+    let mut mix = String::new();
+    for (i, row) in mds.iter().enumerate() {
+        let terms = row
+            .iter()
+            .enumerate()
+            .map(|(j, m)| format!("mulmod({m:?}, s[{j}], P)"))
+            .reduce(|acc, term| format!("addmod({acc}, {term}, P)"))
+            .unwrap_or_default();
+        mix += &format!("        uint256 n{i} = {terms};\n");
+    }
+    for i in 0..params.width() {
+        mix += &format!("        s[{i}] = n{i};\n");
+    }
+
+    format!(
+        r#"
+library PoseidonHash {{
+    uint256 constant P =
+        0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
+
+    uint256 constant WIDTH = {width};
+
+    uint256 constant RATE = {rate};
+
+    // the number of elements hashed at a time, larger messages are hashed as a tree
+    uint256 constant L = {len};
+
+    /**
+     * @dev Hash `message` the same way EZKL hashes a hashed input or output.
+     * @param message - The message, as field elements.
+     */
+    function hash(uint256[] memory message) internal pure returns (uint256) {{
+        do {{
+            uint256 numBlocks = (message.length + L - 1) / L;
+            uint256[] memory hashes = new uint256[](numBlocks);
+            for (uint256 i = 0; i < numBlocks; i++) {{
+                hashes[i] = hashBlock(message, i * L);
+            }}
+            message = hashes;
+        }} while (message.length > 1);
+        return message[0];
+    }}
+
+    /**
+     * @dev Hash the (zero padded) L elements of `message` starting at `start`, with a constant length sponge.
+     */
+    function hashBlock(
+        uint256[] memory message,
+        uint256 start
+    ) private pure returns (uint256) {{
+        uint256[WIDTH] memory s;
+        s[RATE] = L << 64;
+        for (uint256 i = 0; i < L; i += RATE) {{
+            for (uint256 j = 0; j < RATE; j++) {{
+                if (i + j < L && start + i + j < message.length) {{
+                    s[j] = addmod(s[j], message[start + i + j], P);
+                }}
+            }}
+            permute(s);
+        }}
+        return s[0];
+    }}
+
+    function sbox(uint256 x) private pure returns (uint256) {{
+        uint256 x2 = mulmod(x, x, P);
+        return mulmod(mulmod(x2, x2, P), x, P);
+    }}
+
+    function mix(uint256[WIDTH] memory s) private pure {{
+{mix}    }}
+
+    function permute(uint256[WIDTH] memory s) private pure {{
+{rounds}    }}
+}}
+"#,
+        width = params.width(),
+        rate = params.rate(),
+        len = POSEIDON_LEN_GRAPH,
+        mix = mix,
+        rounds = rounds,
+    )
+}
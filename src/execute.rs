@@ -1,24 +1,36 @@
 use crate::circuit::CheckMode;
-#[cfg(not(target_arch = "wasm32"))]
-use crate::commands::CalibrationTarget;
 use crate::commands::Commands;
 #[cfg(not(target_arch = "wasm32"))]
-use crate::eth::{deploy_da_verifier_via_solidity, deploy_verifier_via_solidity};
+use crate::commands::{CalibrationTarget, EvmCalldataType};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::eth::{
+    bind_commitments_sol, fix_da_sol, get_contract_artifacts, signed_da_sol,
+    split_proof_verifier_sol, verify_proof_via_solidity,
+};
 #[cfg(not(target_arch = "wasm32"))]
-use crate::eth::{fix_da_sol, get_contract_artifacts, verify_proof_via_solidity};
+use crate::eth::{
+    compute_create2_address, deploy_contract_via_solidity, deploy_da_verifier_via_solidity,
+    deploy_verifier_via_solidity, is_contract_deployed_at, setup_eth_backend, EvmCostReport,
+    MAX_RUNTIME_BYTECODE_SIZE,
+};
 use crate::graph::input::GraphData;
-use crate::graph::{GraphCircuit, GraphSettings, GraphWitness, Model};
+use crate::graph::{GraphCircuit, GraphSettings, GraphWitness, Model, VarVisibility};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::graph::{TestDataSource, TestSources};
-use crate::pfsys::evm::aggregation::AggregationCircuit;
+use crate::pfsys::container::{SettingsDigest, SettingsHash, MAGIC};
+use crate::pfsys::evm::aggregation::{AggregateInstanceLayout, AggregationCircuit};
 #[cfg(not(target_arch = "wasm32"))]
-use crate::pfsys::evm::{single::gen_evm_verifier, YulCode};
+use crate::pfsys::evm::{single::gen_evm_verifier, EvmVerificationError, YulCode};
 use crate::pfsys::{
-    create_keys, load_pk, load_vk, save_params, save_pk, swap_proof_commitments_kzg, Snark,
-    StrategyType, TranscriptType,
+    create_keys, load_pk, load_vk, save_params, save_pk, swap_proof_commitments_kzg, Commitments,
+    MultiOpenScheme, PfSysError, Snark, StrategyType, TranscriptType,
 };
+use crate::pfsys::{create_proof_circuit_ipa, verify_proof_circuit_ipa};
 use crate::pfsys::{create_proof_circuit_kzg, verify_proof_circuit_kzg};
 use crate::pfsys::{save_vk, srs::*};
+use crate::profiler;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::tensor::Tensor;
 use crate::RunArgs;
 #[cfg(not(target_arch = "wasm32"))]
 use ethers::types::H160;
@@ -26,6 +38,8 @@ use gag::Gag;
 use halo2_proofs::dev::VerifyFailure;
 use halo2_proofs::poly::commitment::Params;
 use halo2_proofs::poly::commitment::ParamsProver;
+use halo2_proofs::poly::ipa::commitment::{IPACommitmentScheme, ParamsIPA};
+use halo2_proofs::poly::ipa::strategy::SingleStrategy as IPASingleStrategy;
 use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
 use halo2_proofs::poly::kzg::strategy::AccumulatorStrategy;
 use halo2_proofs::poly::kzg::{
@@ -49,7 +63,9 @@ use plotters::prelude::*;
 #[cfg(not(target_arch = "wasm32"))]
 use rand::Rng;
 #[cfg(not(target_arch = "wasm32"))]
-use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use rayon::prelude::{IntoParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashMap;
 use std::error::Error;
 use std::fs::File;
 #[cfg(not(target_arch = "wasm32"))]
@@ -114,7 +130,11 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
             num_runs,
         } => fuzz(compiled_circuit, witness, transcript, num_runs),
 
-        Commands::GenSrs { srs_path, logrows } => gen_srs_cmd(srs_path, logrows as u32),
+        Commands::GenSrs {
+            srs_path,
+            logrows,
+            commitment,
+        } => gen_srs_cmd(srs_path, logrows as u32, commitment),
         #[cfg(not(target_arch = "wasm32"))]
         Commands::GetSrs {
             srs_path,
@@ -132,8 +152,9 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
         Commands::GenSettings {
             model,
             settings_path,
+            preprocessing,
             args,
-        } => gen_circuit_settings(model, settings_path, args),
+        } => gen_circuit_settings(model, settings_path, args, preprocessing),
         #[cfg(not(target_arch = "wasm32"))]
         Commands::CalibrateSettings {
             model,
@@ -142,16 +163,30 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
             target,
             scales,
             max_logrows,
-        } => calibrate(model, data, settings_path, target, scales, max_logrows),
+            num_samples,
+            max_memory,
+        } => calibrate(
+            model,
+            data,
+            settings_path,
+            target,
+            scales,
+            max_logrows,
+            num_samples,
+            max_memory,
+        ),
         Commands::GenWitness {
             data,
             compiled_circuit,
             output,
             vk_path,
             srs_path,
-        } => gen_witness(compiled_circuit, data, Some(output), vk_path, srs_path)
-            .await
-            .map(|_| ()),
+            profile_path,
+        } => {
+            start_profile(&profile_path, "gen-witness");
+            gen_witness(compiled_circuit, data, Some(output), vk_path, srs_path).await?;
+            save_profile(profile_path)
+        }
         Commands::Mock { model, witness } => mock(model, witness),
         #[cfg(not(target_arch = "wasm32"))]
         Commands::CreateEVMVerifier {
@@ -160,7 +195,44 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
             settings_path,
             sol_code_path,
             abi_path,
-        } => create_evm_verifier(vk_path, srs_path, settings_path, sol_code_path, abi_path),
+            render_vk_separately,
+            bind_commitments,
+        } => create_evm_verifier(
+            vk_path,
+            srs_path,
+            settings_path,
+            sol_code_path,
+            abi_path,
+            render_vk_separately,
+            bind_commitments,
+        ),
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::CreateEVMVK {
+            vk_path,
+            srs_path,
+            settings_path,
+            sol_code_path,
+            abi_path,
+        } => create_evm_vk(vk_path, srs_path, settings_path, sol_code_path, abi_path),
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::SignData {
+            data,
+            compiled_circuit,
+            private_key,
+            addr_verifier,
+            chain_id,
+            nonce,
+            output,
+        } => sign_data(
+            data,
+            compiled_circuit,
+            private_key,
+            addr_verifier,
+            chain_id,
+            nonce,
+            output,
+        )
+        .map(|_| ()),
         #[cfg(not(target_arch = "wasm32"))]
         Commands::CreateEVMDataAttestation {
             vk_path,
@@ -178,18 +250,27 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
             data,
         ),
         #[cfg(not(target_arch = "wasm32"))]
+        Commands::CreateEVMOutputDecoder {
+            settings_path,
+            sol_code_path,
+        } => create_evm_output_decoder(settings_path, sol_code_path),
+        #[cfg(not(target_arch = "wasm32"))]
         Commands::CreateEVMVerifierAggr {
             vk_path,
             srs_path,
             sol_code_path,
             abi_path,
             aggregation_settings,
+            split_proofs,
+            layout_path,
         } => create_evm_aggregate_verifier(
             vk_path,
             srs_path,
             sol_code_path,
             abi_path,
             aggregation_settings,
+            split_proofs,
+            layout_path,
         ),
         Commands::CompileCircuit {
             model,
@@ -202,7 +283,12 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
             vk_path,
             pk_path,
             witness,
-        } => setup(compiled_circuit, srs_path, vk_path, pk_path, witness),
+            profile_path,
+        } => {
+            start_profile(&profile_path, "setup");
+            setup(compiled_circuit, srs_path, vk_path, pk_path, witness)?;
+            save_profile(profile_path)
+        }
         #[cfg(not(target_arch = "wasm32"))]
         Commands::SetupTestEVMData {
             data,
@@ -234,6 +320,12 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
             witness_path,
         } => swap_proof_commitments(proof_path, witness_path),
         #[cfg(not(target_arch = "wasm32"))]
+        Commands::ConvertProof {
+            proof_path,
+            output_path,
+            settings_path,
+        } => convert_proof(proof_path, output_path, settings_path),
+        #[cfg(not(target_arch = "wasm32"))]
         Commands::Prove {
             witness,
             compiled_circuit,
@@ -242,23 +334,29 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
             srs_path,
             proof_type,
             check_mode,
-        } => prove(
-            witness,
-            compiled_circuit,
-            pk_path,
-            Some(proof_path),
-            srs_path,
-            proof_type,
-            check_mode,
-        )
-        .map(|_| ()),
+            profile_path,
+        } => {
+            start_profile(&profile_path, "prove");
+            prove(
+                witness,
+                compiled_circuit,
+                pk_path,
+                Some(proof_path),
+                srs_path,
+                proof_type,
+                check_mode,
+            )?;
+            save_profile(profile_path)
+        }
         Commands::MockAggregate {
             aggregation_snarks,
+            settings_paths,
             logrows,
             split_proofs,
-        } => mock_aggregate(aggregation_snarks, logrows, split_proofs),
+        } => mock_aggregate(aggregation_snarks, settings_paths, logrows, split_proofs),
         Commands::SetupAggregate {
             sample_snarks,
+            settings_paths,
             vk_path,
             pk_path,
             srs_path,
@@ -266,6 +364,7 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
             split_proofs,
         } => setup_aggregate(
             sample_snarks,
+            settings_paths,
             vk_path,
             pk_path,
             srs_path,
@@ -275,6 +374,7 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
         Commands::Aggregate {
             proof_path,
             aggregation_snarks,
+            settings_paths,
             pk_path,
             srs_path,
             transcript,
@@ -284,6 +384,7 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
         } => aggregate(
             proof_path,
             aggregation_snarks,
+            settings_paths,
             pk_path,
             srs_path,
             transcript,
@@ -310,6 +411,8 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
             addr_path,
             optimizer_runs,
             private_key,
+            reuse_addr,
+            create2,
         } => {
             deploy_evm(
                 sol_code_path,
@@ -317,6 +420,27 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
                 addr_path,
                 optimizer_runs,
                 private_key,
+                reuse_addr,
+                create2,
+            )
+            .await
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::DeployEvmVK {
+            sol_code_path,
+            rpc_url,
+            addr_path,
+            optimizer_runs,
+            private_key,
+            create2,
+        } => {
+            deploy_evm_vk(
+                sol_code_path,
+                rpc_url,
+                addr_path,
+                optimizer_runs,
+                private_key,
+                create2,
             )
             .await
         }
@@ -329,6 +453,7 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
             addr_path,
             optimizer_runs,
             private_key,
+            create2,
         } => {
             deploy_da_evm(
                 data,
@@ -338,16 +463,105 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
                 addr_path,
                 optimizer_runs,
                 private_key,
+                create2,
             )
             .await
         }
         #[cfg(not(target_arch = "wasm32"))]
+        Commands::Create2Address {
+            sol_code_path,
+            contract_name,
+            optimizer_runs,
+            create2_factory,
+            addr_path,
+            data,
+            settings_path,
+            deployer,
+        } => create2_address(
+            sol_code_path,
+            contract_name,
+            optimizer_runs,
+            create2_factory,
+            addr_path,
+            data,
+            settings_path,
+            deployer,
+        )
+        .map(|_| ()),
+        #[cfg(not(target_arch = "wasm32"))]
         Commands::VerifyEVM {
             proof_path,
             addr_verifier,
             rpc_url,
             addr_da,
-        } => verify_evm(proof_path, addr_verifier, rpc_url, addr_da).await,
+            addr_vk,
+            data,
+            witness,
+            settings_path,
+        } => {
+            verify_evm(
+                proof_path,
+                addr_verifier,
+                rpc_url,
+                addr_da,
+                addr_vk,
+                data,
+                witness,
+                settings_path,
+            )
+            .await
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::DecodeEVMOutputs {
+            proof_path,
+            sol_code_path,
+            rpc_url,
+        } => decode_evm_outputs(proof_path, sol_code_path, rpc_url)
+            .await
+            .map(|_| ()),
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::EstimateEVM {
+            proof_path,
+            sol_code_path,
+            vk_sol_code_path,
+            sol_code_path_da,
+            data,
+            settings_path,
+            rpc_url,
+            optimizer_runs,
+            output_path,
+        } => estimate_evm(
+            proof_path,
+            sol_code_path,
+            vk_sol_code_path,
+            sol_code_path_da,
+            data,
+            settings_path,
+            rpc_url,
+            optimizer_runs,
+            output_path,
+        )
+        .await
+        .map(|_| ()),
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::EncodeEvmCalldata {
+            proof_path,
+            calldata_path,
+            calldata_type,
+            addr_verifier,
+            addr_vk,
+            selector,
+            settings_path,
+        } => encode_evm_calldata(
+            proof_path,
+            Some(calldata_path),
+            calldata_type,
+            addr_verifier,
+            addr_vk,
+            selector,
+            settings_path,
+        )
+        .map(|_| ()),
         Commands::PrintProofHex { proof_path } => print_proof_hex(proof_path),
         #[cfg(not(target_arch = "wasm32"))]
         Commands::GetHubCredentials {
@@ -407,12 +621,107 @@ pub async fn run(command: Commands) -> Result<(), Box<dyn Error>> {
     }
 }
 
-pub(crate) fn gen_srs_cmd(srs_path: PathBuf, logrows: u32) -> Result<(), Box<dyn Error>> {
-    let params = gen_srs::<KZGCommitmentScheme<Bn256>>(logrows);
-    save_params::<KZGCommitmentScheme<Bn256>>(&srs_path, &params)?;
+/// Starts profiling `command` if a report was requested
+fn start_profile(profile_path: &Option<PathBuf>, command: &str) {
+    if profile_path.is_some() {
+        profiler::start(command);
+    }
+}
+
+/// Writes the profiling report (if one was requested) to `profile_path`
+fn save_profile(profile_path: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    if let (Some(path), Some(report)) = (profile_path, profiler::finish()) {
+        info!(
+            "{} took {:.3}s, peak rss {:?} bytes",
+            report.command, report.total_wall_time_secs, report.peak_rss_bytes
+        );
+        report.save(&path)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn gen_srs_cmd(
+    srs_path: PathBuf,
+    logrows: u32,
+    commitment: Commitments,
+) -> Result<(), Box<dyn Error>> {
+    match commitment {
+        Commitments::KZG => {
+            let params = gen_srs::<KZGCommitmentScheme<Bn256>>(logrows);
+            save_params::<KZGCommitmentScheme<Bn256>>(&srs_path, &params)?;
+        }
+        Commitments::IPA => {
+            let params = gen_srs::<IPACommitmentScheme<G1Affine>>(logrows);
+            save_params::<IPACommitmentScheme<G1Affine>>(&srs_path, &params)?;
+        }
+    }
+    Ok(())
+}
+
+/// Errors out if a KZG-only feature is requested for a non-KZG commitment scheme
+fn ensure_kzg(commitment: Commitments, feature: &str) -> Result<(), Box<dyn Error>> {
+    if commitment != Commitments::KZG {
+        return Err(Box::new(PfSysError::UnsupportedCommitment(
+            feature.to_string(),
+            Commitments::KZG,
+        )));
+    }
+    Ok(())
+}
+
+/// Errors out for proofs that aren't shplonk proofs, the aggregation circuit (and its accumulator)
+/// only verifies shplonk openings
+fn ensure_shplonk(multi_open: MultiOpenScheme, feature: &str) -> Result<(), Box<dyn Error>> {
+    if multi_open != MultiOpenScheme::SHPLONK {
+        return Err(Box::new(PfSysError::UnsupportedMultiOpen(
+            feature.to_string(),
+            MultiOpenScheme::SHPLONK,
+        )));
+    }
     Ok(())
 }
 
+/// The settings hash a binary proof must carry, if the settings it was generated for are given
+fn expected_settings_hash(
+    settings_path: Option<&PathBuf>,
+) -> Result<Option<SettingsHash>, Box<dyn Error>> {
+    settings_path
+        .map(|path| -> Result<SettingsHash, Box<dyn Error>> {
+            Ok(GraphSettings::load(path)?.settings_hash()?)
+        })
+        .transpose()
+}
+
+/// Loads the snarks to aggregate, checking them against the settings they were generated for (one
+/// per snark, or one shared by all) if given
+fn load_aggregation_snarks(
+    snark_paths: &[PathBuf],
+    settings_paths: &[PathBuf],
+) -> Result<Vec<Snark<Fr, G1Affine>>, Box<dyn Error>> {
+    if settings_paths.len() > 1 && settings_paths.len() != snark_paths.len() {
+        return Err(format!(
+            "got settings for {} of {} snarks, pass one per snark or one shared by all",
+            settings_paths.len(),
+            snark_paths.len()
+        )
+        .into());
+    }
+    snark_paths
+        .iter()
+        .enumerate()
+        .map(|(i, proof_path)| {
+            let settings_path = settings_paths.get(i).or(settings_paths.first());
+            let snark = Snark::load::<KZGCommitmentScheme<Bn256>>(
+                proof_path,
+                expected_settings_hash(settings_path)?,
+            )?;
+            ensure_kzg(snark.commitment(), "proof aggregation")?;
+            ensure_shplonk(snark.multi_open(), "proof aggregation")?;
+            Ok(snark)
+        })
+        .collect()
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 async fn fetch_srs(uri: &str) -> Result<Vec<u8>, Box<dyn Error>> {
     let pb = {
@@ -443,6 +752,11 @@ pub(crate) async fn get_srs_cmd(
     let k = if let Some(settings_p) = settings_path {
         if settings_p.exists() {
             let settings = GraphSettings::load(&settings_p)?;
+            if settings.run_args.commitment == Commitments::IPA {
+                // ipa params are transparent, so there is nothing to download
+                info!("ipa commitments require no trusted setup, generating params locally");
+                return gen_srs_cmd(srs_path, settings.run_args.logrows, Commitments::IPA);
+            }
             settings.run_args.logrows
         } else {
             let err_string = format!(
@@ -494,10 +808,26 @@ pub(crate) async fn gen_witness(
 ) -> Result<GraphWitness, Box<dyn Error>> {
     // these aren't real values so the sanity checks are mostly meaningless
 
-    let mut circuit = GraphCircuit::load(compiled_circuit_path)?;
-    let data = GraphData::from_path(data)?;
+    let mut circuit = profiler::phase(profiler::MODEL_LOAD, || {
+        GraphCircuit::load(compiled_circuit_path)
+    })?;
+    let data_path = data;
+    let mut data = GraphData::from_path(data_path.clone())?;
     let settings = circuit.settings().clone();
 
+    // pin on-chain inputs to a block (the latest one if unset) so the witness can be regenerated later
+    let on_chain_block = match &mut data.input_data {
+        crate::graph::DataSource::OnChain(source) => {
+            Some(crate::eth::pin_on_chain_block(source).await?)
+        }
+        _ => None,
+    };
+    if settings.run_args.commit_on_chain_block && on_chain_block.is_none() {
+        return Err(
+            "the circuit commits the on-chain block, which requires on-chain inputs".into(),
+        );
+    }
+
     let vk = if let Some(vk) = vk_path {
         Some(load_vk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(
             vk,
@@ -520,7 +850,18 @@ pub(crate) async fn gen_witness(
 
     let start_time = Instant::now();
 
-    let witness = circuit.forward(&mut input, vk.as_ref(), srs.as_ref())?;
+    let mut witness = profiler::phase(profiler::WITNESS_LAYOUT, || {
+        circuit.forward(&mut input, vk.as_ref(), srs.as_ref())
+    })?;
+    witness.on_chain_block = on_chain_block;
+
+    // keep a freshly drawn salt with the data, so the witness (and its salted hashes) can be regenerated
+    if data.hash_salt.is_none() {
+        if let Some(salt) = witness.hash_salt() {
+            data.hash_salt = Some(salt);
+            data.save(data_path)?;
+        }
+    }
 
     // print each variable tuple (symbol, value) as symbol=value
     trace!(
@@ -546,8 +887,13 @@ pub(crate) fn gen_circuit_settings(
     model_path: PathBuf,
     params_output: PathBuf,
     run_args: RunArgs,
+    preprocessing: Option<PathBuf>,
 ) -> Result<(), Box<dyn Error>> {
-    let circuit = GraphCircuit::from_run_args(&run_args, &model_path)?;
+    let mut circuit = GraphCircuit::from_run_args(&run_args, &model_path)?;
+    if let Some(preprocessing) = preprocessing {
+        let spec = std::fs::read_to_string(preprocessing)?;
+        circuit.settings_mut().preprocessing = serde_json::from_str(&spec)?;
+    }
     let params = circuit.settings();
     params.save(&params_output).map_err(Box::<dyn Error>::from)
 }
@@ -592,9 +938,95 @@ pub(crate) fn init_bar(len: u64) -> ProgressBar {
 #[cfg(not(target_arch = "wasm32"))]
 use colored_json::ToColoredJson;
 
-/// Calibrate the circuit parameters to a given a dataset
+/// A candidate set of scales, and the range of its lookup inputs over the calibration batches
+#[cfg(not(target_arch = "wasm32"))]
+struct CalibrationCandidate {
+    circuit: GraphCircuit,
+    /// candidates with the same input scale and model go through the same forward passes
+    forward_key: (crate::Scale, u64),
+    /// whether the candidate runs the forward passes of its key, rather than reusing another candidate's
+    runs_forward: bool,
+    /// the range of the lookup inputs over the batches, None if no batch went through
+    lookup_range: Option<(i128, i128)>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CalibrationCandidate {
+    fn new(circuit: GraphCircuit) -> Result<Self, Box<dyn Error>> {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        bincode::serialize(circuit.model())?.hash(&mut hasher);
+        Ok(CalibrationCandidate {
+            forward_key: (circuit.settings().run_args.input_scale, hasher.finish()),
+            circuit,
+            runs_forward: true,
+            lookup_range: None,
+        })
+    }
+
+    /// An estimate of the memory (in bytes) the candidate holds while it is calibrated: its
+    /// circuit and the values of a forward pass (a field element per assigned cell)
+    fn memory(&self) -> u64 {
+        bincode::serialized_size(&self.circuit).unwrap_or_default()
+            + (self.circuit.settings().total_assignments * std::mem::size_of::<Fr>()) as u64
+    }
+
+    /// Runs the forward pass of a batch of quantized inputs, returning the range of its lookup
+    /// inputs, or None if the batch fails
+    fn forward(&self, inputs: &[Tensor<Fr>]) -> Option<(i128, i128)> {
+        let res = self
+            .circuit
+            .forward(&mut inputs.to_vec(), None, None)
+            .ok()?;
+        Some((res.min_lookup_inputs, res.max_lookup_inputs))
+    }
+
+    /// Widens the range of the lookup inputs to that of a batch
+    fn merge(&mut self, (min, max): (i128, i128)) {
+        self.lookup_range = Some(match self.lookup_range {
+            Some((current_min, current_max)) => (current_min.min(min), current_max.max(max)),
+            None => (min, max),
+        });
+    }
+
+    /// The settings calibrated to the range of the lookup inputs over all the batches
+    fn settings(
+        mut self,
+        max_logrows: Option<u32>,
+        original_settings: &GraphSettings,
+    ) -> Option<GraphSettings> {
+        let (min, max) = self.lookup_range?;
+        self.circuit
+            .calibrate_from_min_max(min, max, max_logrows)
+            .ok()?;
+        let settings = self.circuit.settings().clone();
+
+        let found_run_args = RunArgs {
+            input_scale: settings.run_args.input_scale,
+            param_scale: settings.run_args.param_scale,
+            lookup_range: settings.run_args.lookup_range,
+            logrows: settings.run_args.logrows,
+            scale_rebase_multiplier: settings.run_args.scale_rebase_multiplier,
+            ..original_settings.run_args.clone()
+        };
+
+        Some(GraphSettings {
+            run_args: found_run_args,
+            required_lookups: settings.required_lookups,
+            model_output_scales: settings.model_output_scales,
+            model_input_scales: settings.model_input_scales,
+            num_rows: settings.num_rows,
+            total_assignments: settings.total_assignments,
+            total_const_size: settings.total_const_size,
+            ..original_settings.clone()
+        })
+    }
+}
+
+/// Calibrate the circuit parameters to a given a dataset, streaming its batches through groups of candidate settings
 #[cfg(not(target_arch = "wasm32"))]
 #[allow(trivial_casts)]
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn calibrate(
     model_path: PathBuf,
     data: PathBuf,
@@ -602,10 +1034,25 @@ pub(crate) fn calibrate(
     target: CalibrationTarget,
     scales: Option<Vec<crate::Scale>>,
     max_logrows: Option<u32>,
+    num_samples: Option<usize>,
+    max_memory: Option<usize>,
 ) -> Result<(), Box<dyn Error>> {
     let data = GraphData::from_path(data)?;
     // load the pre-generated settings
     let settings = GraphSettings::load(&settings_path)?;
+    // the signatures are over the quantized inputs, so only the scales they were signed at calibrate
+    if settings.run_args.input_visibility.is_signed() && data.signatures.is_none() {
+        return Err("signed inputs are calibrated with the data provider's signatures (over the inputs quantized at the calibrated scales), add them to the data's `signatures`".into());
+    }
+    let visibility = VarVisibility::from_args(&settings.run_args)?;
+    if [&visibility.input, &visibility.params, &visibility.output]
+        .iter()
+        .any(|v| v.is_encrypted())
+        && data.elgamal_recipient.is_none()
+        && settings.elgamal_recipient.is_none()
+    {
+        return Err("encrypted values need a recipient, add their public key to the data's `elgamal_recipient`".into());
+    }
     // now retrieve the run args
     // we load the model to get the input and output shapes
     // check if gag already exists
@@ -628,9 +1075,39 @@ pub(crate) fn calibrate(
         }
     };
 
-    let chunks = data.split_into_batches(model.graph.input_shapes()).unwrap();
+    // batches are split from the raw data, i.e. before any preprocessing changes its shape
+    let input_shapes = crate::graph::preprocessing::raw_input_shapes(
+        &settings.preprocessing,
+        model.graph.input_shapes(),
+    );
 
-    info!("num of calibration batches: {}", chunks.len());
+    // calibrate on a random subset of the batches, sampled with a fixed seed so calibration is reproducible
+    let sampled = match num_samples {
+        Some(num_samples) => {
+            // count the batches, streaming them from the data source
+            let mut num_batches = 0;
+            for batch in data.batches(input_shapes.clone())? {
+                batch?;
+                num_batches += 1;
+            }
+            info!(
+                "num of calibration batches: {} (sampled from {})",
+                num_samples.min(num_batches),
+                num_batches
+            );
+            if num_samples < num_batches {
+                let mut rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(0);
+                Some(
+                    rand::seq::index::sample(&mut rng, num_batches, num_samples)
+                        .into_iter()
+                        .collect::<std::collections::HashSet<usize>>(),
+                )
+            } else {
+                None
+            }
+        }
+        None => None,
+    };
 
     let mut found_params: Vec<GraphSettings> = vec![];
 
@@ -671,17 +1148,24 @@ pub(crate) fn calibrate(
         .map(|(a, b)| (*a, *b))
         .collect::<Vec<((crate::Scale, crate::Scale), u32)>>();
 
+    // the candidates load their own copy of the model
+    std::mem::drop(model);
+
+    // each candidate holds a circuit (and its forward pass) in memory, so we calibrate groups of
+    // candidates fitting in the memory limit (or a candidate per thread if there is none) at a
+    // time, streaming every batch through a group
+    let max_memory = max_memory.map(|mb| (mb as u64).saturating_mul(1024 * 1024));
+
     let pb = init_bar(range_grid.len() as u64);
     pb.set_message("calibrating...");
 
-    for ((input_scale, param_scale), scale_rebase_multiplier) in range_grid {
-        pb.set_message(format!(
-            "input scale: {}, param scale: {}, scale rebase multiplier: {}",
-            input_scale, param_scale, scale_rebase_multiplier
-        ));
-        // vec of settings copied chunks.len() times
-        let run_args_iterable = vec![settings.run_args.clone(); chunks.len()];
+    // the range of the lookup inputs of the forward passes run by previous groups, by forward key
+    let mut forward_ranges: HashMap<(crate::Scale, u64), Option<(i128, i128)>> = HashMap::new();
+    let mut pending = range_grid.iter();
+    // a candidate that didn't fit in the previous group
+    let mut carried: Option<CalibrationCandidate> = None;
 
+    loop {
         let _r = match Gag::stdout() {
             Ok(r) => Some(r),
             Err(_) => None,
@@ -691,103 +1175,147 @@ pub(crate) fn calibrate(
             Err(_) => None,
         };
 
-        let tasks = chunks
-            .iter()
-            .zip(run_args_iterable)
-            .map(|(chunk, run_args)| {
-                // we need to create a new run args for each chunk
-                // time it
-                let chunk = chunk.clone();
-                let local_run_args = RunArgs {
-                    input_scale,
-                    param_scale,
-                    scale_rebase_multiplier,
-                    ..run_args.clone()
-                };
-
-                let original_settings = settings.clone();
-
-                let mut circuit = match GraphCircuit::from_run_args(&local_run_args, &model_path) {
-                    Ok(c) => c,
-                    Err(_) => {
-                        return Err(format!("failed to create circuit from run args"))
-                            as Result<GraphSettings, String>
+        // the circuit of each candidate is built once and reused across the batches
+        let mut group: Vec<CalibrationCandidate> = vec![];
+        let mut group_memory = 0;
+        loop {
+            let mut candidate = match carried.take() {
+                Some(candidate) => candidate,
+                None => match pending.next() {
+                    Some(((input_scale, param_scale), scale_rebase_multiplier)) => {
+                        let local_run_args = RunArgs {
+                            input_scale: *input_scale,
+                            param_scale: *param_scale,
+                            scale_rebase_multiplier: *scale_rebase_multiplier,
+                            ..settings.run_args.clone()
+                        };
+                        let candidate = GraphCircuit::from_run_args(&local_run_args, &model_path)
+                            .and_then(|mut circuit| {
+                                // inputs are preprocessed the same way they will be at witness generation
+                                circuit.settings_mut().preprocessing =
+                                    settings.preprocessing.clone();
+                                // the batches are only inputs, the modules (eg. merkle paths) need the rest
+                                circuit.load_module_data(&data);
+                                CalibrationCandidate::new(circuit)
+                            });
+                        match candidate {
+                            Ok(candidate) => candidate,
+                            Err(_) => {
+                                pb.inc(1);
+                                continue;
+                            }
+                        }
                     }
-                };
-
-                let data = circuit
-                    .load_graph_from_file_exclusively(&chunk)
-                    .map_err(|e| format!("failed to load circuit inputs: {}", e))?;
-
-                circuit
-                    .calibrate(&data, max_logrows)
-                    .map_err(|e| format!("failed to calibrate: {}", e))?;
-
-                let settings = circuit.settings().clone();
-
-                let found_run_args = RunArgs {
-                    input_scale: settings.run_args.input_scale,
-                    param_scale: settings.run_args.param_scale,
-                    lookup_range: settings.run_args.lookup_range,
-                    logrows: settings.run_args.logrows,
-                    scale_rebase_multiplier: settings.run_args.scale_rebase_multiplier,
-                    ..run_args.clone()
-                };
-
-                let found_settings = GraphSettings {
-                    run_args: found_run_args,
-                    required_lookups: settings.required_lookups,
-                    model_output_scales: settings.model_output_scales,
-                    model_input_scales: settings.model_input_scales,
-                    num_rows: settings.num_rows,
-                    total_assignments: settings.total_assignments,
-                    total_const_size: settings.total_const_size,
-                    ..original_settings.clone()
-                };
-
-                Ok(found_settings) as Result<GraphSettings, String>
-            })
-            .collect::<Vec<Result<GraphSettings, String>>>();
-
-        let mut res: Vec<GraphSettings> = vec![];
-        for task in tasks {
-            if let Ok(task) = task {
-                res.push(task);
+                    None => break,
+                },
+            };
+
+            let memory = candidate.memory();
+            let full = match max_memory {
+                Some(max_memory) => group_memory + memory > max_memory,
+                None => group.len() >= rayon::current_num_threads(),
+            };
+            // a group holds at least one candidate
+            if full && !group.is_empty() {
+                carried = Some(candidate);
+                break;
+            }
+            group_memory += memory;
+
+            // the forward passes of a model are only run once, by the first candidate of its key
+            if let Some(range) = forward_ranges.get(&candidate.forward_key) {
+                candidate.runs_forward = false;
+                candidate.lookup_range = *range;
+            } else if group
+                .iter()
+                .any(|c| c.runs_forward && c.forward_key == candidate.forward_key)
+            {
+                candidate.runs_forward = false;
             }
+            group.push(candidate);
+        }
+
+        if group.is_empty() {
+            break;
         }
 
+        pb.set_message(format!(
+            "input scale, param scale, scale rebase multiplier: {}",
+            group
+                .iter()
+                .map(|c| {
+                    let run_args = &c.circuit.settings().run_args;
+                    format!(
+                        "({}, {}, {})",
+                        run_args.input_scale,
+                        run_args.param_scale,
+                        run_args.scale_rebase_multiplier
+                    )
+                })
+                .join(", ")
+        ));
+
+        if group.iter().any(|c| c.runs_forward) {
+            for (i, batch) in data.batches(input_shapes.clone())?.enumerate() {
+                let batch = batch?;
+                if matches!(&sampled, Some(sampled) if !sampled.contains(&i)) {
+                    continue;
+                }
+
+                // the quantized inputs only depend on the input scale, so candidates share them
+                let mut quantized: HashMap<crate::Scale, Vec<Tensor<Fr>>> = HashMap::new();
+                for candidate in group.iter_mut().filter(|c| c.runs_forward) {
+                    if let std::collections::hash_map::Entry::Vacant(e) =
+                        quantized.entry(candidate.forward_key.0)
+                    {
+                        if let Ok(inputs) =
+                            candidate.circuit.load_graph_from_file_exclusively(&batch)
+                        {
+                            e.insert(inputs);
+                        }
+                    }
+                }
+
+                let batch_ranges = group
+                    .par_iter_mut()
+                    .filter(|c| c.runs_forward)
+                    .filter_map(|c| {
+                        let inputs = quantized.get(&c.forward_key.0)?;
+                        Some((c.forward_key, c.forward(inputs)?))
+                    })
+                    .collect::<HashMap<_, _>>();
+
+                for candidate in group.iter_mut() {
+                    if let Some(range) = batch_ranges.get(&candidate.forward_key) {
+                        candidate.merge(*range);
+                    }
+                }
+            }
+        }
+
+        for candidate in group.iter().filter(|c| c.runs_forward) {
+            forward_ranges.insert(candidate.forward_key, candidate.lookup_range);
+        }
+
+        let group_len = group.len();
+        let group_params = group
+            .into_par_iter()
+            .filter_map(|candidate| candidate.settings(max_logrows, &settings))
+            .collect::<Vec<_>>();
+
         // drop the gag
         std::mem::drop(_r);
         std::mem::drop(_q);
 
-        let max_lookup_range = res
-            .iter()
-            .map(|x| x.run_args.lookup_range.1)
-            .max()
-            .unwrap_or(0);
-        let min_lookup_range = res
-            .iter()
-            .map(|x| x.run_args.lookup_range.0)
-            .min()
-            .unwrap_or(0);
-
-        if let Some(mut best) = res.into_iter().max_by_key(|p| {
-            (
-                p.run_args.logrows,
-                p.run_args.input_scale,
-                p.run_args.param_scale,
-            )
-        }) {
-            best.run_args.lookup_range = (min_lookup_range, max_lookup_range);
-            // pick the one with the largest logrows
-            found_params.push(best.clone());
+        for best in group_params {
             debug!(
                 "found settings: \n {}",
                 best.as_json()?.to_colored_json_auto()?
             );
+            found_params.push(best);
         }
 
-        pb.inc(1);
+        pb.inc(group_len as u64);
     }
 
     pb.finish_with_message("Calibration Done.");
@@ -916,8 +1444,57 @@ pub(crate) fn mock(
     Ok(())
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn encode_evm_calldata(
+    proof_path: PathBuf,
+    calldata_path: Option<PathBuf>,
+    calldata_type: EvmCalldataType,
+    addr_verifier: Option<H160>,
+    addr_vk: Option<H160>,
+    selector: Option<String>,
+    settings_path: Option<PathBuf>,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    use crate::eth::{encode_da_calldata, encode_verifier_calldata};
+
+    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(
+        &proof_path,
+        expected_settings_hash(settings_path.as_ref())?,
+    )?;
+    ensure_kzg(proof.commitment(), "evm verification")?;
+
+    let mut calldata = match calldata_type {
+        EvmCalldataType::Verifier => encode_verifier_calldata(&proof, addr_vk),
+        EvmCalldataType::DataAttestation => {
+            if addr_vk.is_some() {
+                return Err(Box::new(EvmVerificationError::SeparateVk(
+                    "data attestation",
+                )));
+            }
+            let addr_verifier =
+                addr_verifier.ok_or("data attestation calldata requires the verifier address")?;
+            encode_da_calldata(&proof, addr_verifier, None)?
+        }
+    };
+
+    if let Some(selector) = selector {
+        let selector = hex::decode(selector.trim_start_matches("0x"))?;
+        if selector.len() != 4 {
+            return Err("function selectors are 4 bytes".into());
+        }
+        calldata[..4].copy_from_slice(&selector);
+    }
+
+    let encoded = format!("0x{}", hex::encode(&calldata));
+    info!("calldata ({} bytes): {}", calldata.len(), encoded);
+    if let Some(calldata_path) = calldata_path {
+        std::fs::write(calldata_path, &encoded)?;
+    }
+
+    Ok(calldata)
+}
+
 pub(crate) fn print_proof_hex(proof_path: PathBuf) -> Result<(), Box<dyn Error>> {
-    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path)?;
+    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path, None)?;
     for instance in proof.instances {
         println!("{:?}", instance);
     }
@@ -925,27 +1502,85 @@ pub(crate) fn print_proof_hex(proof_path: PathBuf) -> Result<(), Box<dyn Error>>
     Ok(())
 }
 
-#[cfg(feature = "render")]
-pub(crate) fn render(model: PathBuf, output: PathBuf, args: RunArgs) -> Result<(), Box<dyn Error>> {
-    let circuit = GraphCircuit::from_run_args(&args, &model)?;
-    info!("Rendering circuit");
+#[cfg(feature = "render")]
+pub(crate) fn render(model: PathBuf, output: PathBuf, args: RunArgs) -> Result<(), Box<dyn Error>> {
+    let circuit = GraphCircuit::from_run_args(&args, &model)?;
+    info!("Rendering circuit");
+
+    // Create the area we want to draw on.
+    // We could use SVGBackend if we want to render to .svg instead.
+    // for an overview of how to interpret these plots, see https://zcash.github.io/halo2/user/dev-tools.html
+    let root = BitMapBackend::new(&output, (512, 512)).into_drawing_area();
+    root.fill(&TRANSPARENT).unwrap();
+    let root = root.titled("Layout", ("sans-serif", 20))?;
+
+    halo2_proofs::dev::CircuitLayout::default()
+        // We hide labels, else most circuits become impossible to decipher because of overlaid text
+        .show_labels(false)
+        .render(circuit.settings().run_args.logrows, &circuit, &root)?;
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn create_evm_verifier(
+    vk_path: PathBuf,
+    srs_path: PathBuf,
+    settings_path: PathBuf,
+    sol_code_path: PathBuf,
+    abi_path: PathBuf,
+    render_vk_separately: bool,
+    bind_commitments: bool,
+) -> Result<(), Box<dyn Error>> {
+    check_solc_requirement();
+    if bind_commitments && render_vk_separately {
+        return Err(Box::new(EvmVerificationError::SeparateVk(
+            "binding commitments",
+        )));
+    }
+    let circuit_settings = GraphSettings::load(&settings_path)?;
+    ensure_kzg(circuit_settings.run_args.commitment, "evm verification")?;
+    let params = load_params_cmd(srs_path, circuit_settings.run_args.logrows)?;
+    let multi_open = circuit_settings.run_args.multi_open;
+
+    let num_instance = circuit_settings.total_instances();
+    let num_instance: usize = num_instance.iter().sum::<usize>();
+
+    let vk = load_vk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(vk_path, circuit_settings)?;
+    trace!("params computed");
+
+    let generator = halo2_solidity_verifier::SolidityGenerator::new(
+        &params,
+        &vk,
+        multi_open.into(),
+        num_instance,
+    );
+    // a verifier rendered without its vk can be shared by circuits with the same constraint system
+    let verifier_solidity = if render_vk_separately {
+        generator.render_separately()?.0
+    } else {
+        generator.render()?
+    };
+    let verifier_solidity = if bind_commitments {
+        bind_commitments_sol(&verifier_solidity)?
+    } else {
+        verifier_solidity
+    };
+
+    File::create(sol_code_path.clone())
+        .unwrap()
+        .write_all(verifier_solidity.as_bytes())
+        .unwrap();
 
-    // Create the area we want to draw on.
-    // We could use SVGBackend if we want to render to .svg instead.
-    // for an overview of how to interpret these plots, see https://zcash.github.io/halo2/user/dev-tools.html
-    let root = BitMapBackend::new(&output, (512, 512)).into_drawing_area();
-    root.fill(&TRANSPARENT).unwrap();
-    let root = root.titled("Layout", ("sans-serif", 20))?;
+    // fetch abi of the contract
+    let (abi, _, _) = get_contract_artifacts(sol_code_path, "Halo2Verifier", 0)?;
+    // save abi to file
+    serde_json::to_writer(std::fs::File::create(abi_path)?, &abi)?;
 
-    halo2_proofs::dev::CircuitLayout::default()
-        // We hide labels, else most circuits become impossible to decipher because of overlaid text
-        .show_labels(false)
-        .render(circuit.settings().run_args.logrows, &circuit, &root)?;
     Ok(())
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-pub(crate) fn create_evm_verifier(
+pub(crate) fn create_evm_vk(
     vk_path: PathBuf,
     srs_path: PathBuf,
     settings_path: PathBuf,
@@ -954,7 +1589,9 @@ pub(crate) fn create_evm_verifier(
 ) -> Result<(), Box<dyn Error>> {
     check_solc_requirement();
     let circuit_settings = GraphSettings::load(&settings_path)?;
+    ensure_kzg(circuit_settings.run_args.commitment, "evm verification")?;
     let params = load_params_cmd(srs_path, circuit_settings.run_args.logrows)?;
+    let multi_open = circuit_settings.run_args.multi_open;
 
     let num_instance = circuit_settings.total_instances();
     let num_instance: usize = num_instance.iter().sum::<usize>();
@@ -965,24 +1602,70 @@ pub(crate) fn create_evm_verifier(
     let generator = halo2_solidity_verifier::SolidityGenerator::new(
         &params,
         &vk,
-        halo2_solidity_verifier::BatchOpenScheme::Bdfg21,
+        multi_open.into(),
         num_instance,
     );
-    let verifier_solidity = generator.render().unwrap();
+    let (_, vk_solidity) = generator.render_separately()?;
 
-    File::create(sol_code_path.clone())
-        .unwrap()
-        .write_all(verifier_solidity.as_bytes())
-        .unwrap();
+    File::create(sol_code_path.clone())?.write_all(vk_solidity.as_bytes())?;
 
     // fetch abi of the contract
-    let (abi, _, _) = get_contract_artifacts(sol_code_path, "Halo2Verifier", 0)?;
+    let (abi, _, _) = get_contract_artifacts(sol_code_path, "Halo2VerifyingKey", 0)?;
     // save abi to file
     serde_json::to_writer(std::fs::File::create(abi_path)?, &abi)?;
 
     Ok(())
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn sign_data(
+    data: PathBuf,
+    compiled_circuit_path: PathBuf,
+    private_key: String,
+    addr_verifier: H160,
+    chain_id: u64,
+    nonce: u64,
+    output: PathBuf,
+) -> Result<GraphData, Box<dyn Error>> {
+    use crate::eth::{sign_data_source, wallet_from_private_key};
+    use crate::graph::input::SignedDataDomain;
+    use crate::graph::DataSource;
+    use ethers::signers::Signer;
+
+    let mut circuit = GraphCircuit::load(compiled_circuit_path)?;
+    let mut data = GraphData::from_path(data)?;
+    let wallet = wallet_from_private_key(&private_key)?;
+    let domain = SignedDataDomain {
+        chain_id,
+        verifier: hex::encode(addr_verifier),
+        nonce,
+    };
+
+    // quantize the data exactly as it will be when generating the witness
+    let input_data = match &data.input_data {
+        DataSource::File(input_data) => input_data.clone(),
+        _ => return Err("only input data from a file can be signed".into()),
+    };
+    let inputs = circuit.load_graph_from_file_exclusively(&data)?;
+    let quantized: Vec<Fr> = inputs.iter().flat_map(|t| t.iter().cloned()).collect();
+    data.input_data = sign_data_source(input_data, &quantized, domain.clone(), &wallet)?.into();
+
+    if let Some(DataSource::File(output_data)) = &data.output_data {
+        let scales = circuit.model().graph.get_output_scales();
+        let quantized: Vec<Fr> = output_data
+            .iter()
+            .zip(scales)
+            .flat_map(|(d, scale)| d.iter().map(move |x| x.to_field(scale)))
+            .collect();
+        data.output_data =
+            Some(sign_data_source(output_data.clone(), &quantized, domain, &wallet)?.into());
+    }
+
+    info!("data signed by {:#?}", wallet.address());
+    data.save(output)?;
+    Ok(data)
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) fn create_evm_data_attestation(
     vk_path: PathBuf,
@@ -992,13 +1675,18 @@ pub(crate) fn create_evm_data_attestation(
     abi_path: PathBuf,
     input: PathBuf,
 ) -> Result<(), Box<dyn Error>> {
-    use crate::graph::{DataSource, VarVisibility};
+    use crate::graph::DataSource;
     check_solc_requirement();
 
     let settings = GraphSettings::load(&settings_path)?;
+    ensure_kzg(settings.run_args.commitment, "evm data attestation")?;
     let params = load_params_cmd(srs_path, settings.run_args.logrows)?;
 
     let visibility = VarVisibility::from_args(&settings.run_args)?;
+    // the contracts read the instances of all the inputs (and outputs) as a single range
+    if visibility.is_per_tensor() {
+        return Err("data attestation isn't supported for visibilities set per tensor".into());
+    }
 
     let num_instance = settings.total_instances();
     let num_instance: usize = num_instance.iter().sum::<usize>();
@@ -1006,17 +1694,52 @@ pub(crate) fn create_evm_data_attestation(
     let vk = load_vk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(vk_path, settings.clone())?;
     trace!("params computed");
 
-    let yul_code: YulCode = gen_evm_verifier(&params, &vk, num_instance)?;
+    let yul_code: YulCode =
+        gen_evm_verifier(&params, &vk, num_instance, settings.run_args.multi_open)?;
 
     let mut f = File::create(sol_code_path.clone())?;
     let _ = f.write(yul_code.as_bytes());
 
     let data = GraphData::from_path(input)?;
 
+    if !data.signed_sources().is_empty() {
+        if matches!(data.input_data, DataSource::OnChain(_))
+            || matches!(data.output_data, Some(DataSource::OnChain(_)))
+        {
+            return Err(
+                "on-chain and signed data sources can't be attested by the same contract".into(),
+            );
+        }
+        if matches!(data.input_data, DataSource::Signed(_)) && !visibility.input.is_public() {
+            return Err("signed input data must be public".into());
+        }
+        if matches!(data.output_data, Some(DataSource::Signed(_))) && !visibility.output.is_public()
+        {
+            return Err("signed output data must be public".into());
+        }
+        let mut f = File::create(sol_code_path.clone())?;
+        let _ = f.write(signed_da_sol().as_bytes());
+        // fetch abi of the contract
+        let (abi, _, _) = get_contract_artifacts(sol_code_path, "DataAttestationSigned", 0)?;
+        // save abi to file
+        serde_json::to_writer(std::fs::File::create(abi_path)?, &abi)?;
+        return Ok(());
+    }
+
+    let mut hash_inputs = false;
+    let mut hash_outputs = false;
+
     let output_data = if let Some(DataSource::OnChain(source)) = data.output_data {
         if visibility.output.is_private() {
             return Err("private output data on chain is not supported on chain".into());
         }
+        if source.hash_on_chain && !visibility.output.is_hashed_public() {
+            return Err("only output data with a public hash can be hashed on chain".into());
+        }
+        if source.hash_on_chain && visibility.output.is_salted() {
+            return Err("salted output hashes cannot be recomputed on chain".into());
+        }
+        hash_outputs = source.hash_on_chain;
         let mut on_chain_output_data = vec![];
         for call in source.calls {
             on_chain_output_data.push(call);
@@ -1030,6 +1753,13 @@ pub(crate) fn create_evm_data_attestation(
         if visibility.input.is_private() {
             return Err("private input data on chain is not supported on chain".into());
         }
+        if source.hash_on_chain && !visibility.input.is_hashed_public() {
+            return Err("only input data with a public hash can be hashed on chain".into());
+        }
+        if source.hash_on_chain && visibility.input.is_salted() {
+            return Err("salted input hashes cannot be recomputed on chain".into());
+        }
+        hash_inputs = source.hash_on_chain;
         let mut on_chain_input_data = vec![];
         for call in source.calls {
             on_chain_input_data.push(call);
@@ -1040,7 +1770,14 @@ pub(crate) fn create_evm_data_attestation(
     };
 
     if input_data.is_some() || output_data.is_some() {
-        let output = fix_da_sol(input_data, output_data)?;
+        let output = fix_da_sol(
+            input_data,
+            output_data,
+            hash_inputs,
+            hash_outputs,
+            settings.run_args.commit_on_chain_block,
+            visibility.poseidon_params(),
+        )?;
         let mut f = File::create(sol_code_path.clone())?;
         let _ = f.write(output.as_bytes());
         // fetch abi of the contract
@@ -1064,6 +1801,7 @@ pub(crate) async fn deploy_da_evm(
     addr_path: PathBuf,
     runs: usize,
     private_key: Option<String>,
+    create2_factory: Option<H160>,
 ) -> Result<(), Box<dyn Error>> {
     check_solc_requirement();
     let contract_address = deploy_da_verifier_via_solidity(
@@ -1073,6 +1811,7 @@ pub(crate) async fn deploy_da_evm(
         rpc_url.as_deref(),
         runs,
         private_key.as_deref(),
+        create2_factory,
     )
     .await?;
     info!("Contract deployed at: {}", contract_address);
@@ -1090,13 +1829,71 @@ pub(crate) async fn deploy_evm(
     addr_path: PathBuf,
     runs: usize,
     private_key: Option<String>,
+    reuse_addr: Option<H160>,
+    create2_factory: Option<H160>,
+) -> Result<(), Box<dyn Error>> {
+    check_solc_requirement();
+    let reusable = match reuse_addr {
+        Some(addr) => {
+            is_contract_deployed_at(
+                sol_code_path.clone(),
+                addr,
+                rpc_url.as_deref(),
+                runs,
+                "Halo2Verifier",
+            )
+            .await?
+        }
+        None => false,
+    };
+
+    let contract_address = match reuse_addr {
+        Some(addr) if reusable => {
+            info!("Reusing verifier already deployed at: {:#?}", addr);
+            addr
+        }
+        _ => {
+            if let Some(addr) = reuse_addr {
+                log::warn!(
+                    "the contract at {:#?} doesn't match the verifier, deploying a new one",
+                    addr
+                );
+            }
+            let addr = deploy_verifier_via_solidity(
+                sol_code_path,
+                rpc_url.as_deref(),
+                runs,
+                private_key.as_deref(),
+                create2_factory,
+            )
+            .await?;
+            info!("Contract deployed at: {:#?}", addr);
+            addr
+        }
+    };
+
+    let mut f = File::create(addr_path)?;
+    write!(f, "{:#?}", contract_address)?;
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn deploy_evm_vk(
+    sol_code_path: PathBuf,
+    rpc_url: Option<String>,
+    addr_path: PathBuf,
+    runs: usize,
+    private_key: Option<String>,
+    create2_factory: Option<H160>,
 ) -> Result<(), Box<dyn Error>> {
     check_solc_requirement();
-    let contract_address = deploy_verifier_via_solidity(
+    let contract_address = deploy_contract_via_solidity(
         sol_code_path,
         rpc_url.as_deref(),
         runs,
         private_key.as_deref(),
+        "Halo2VerifyingKey",
+        create2_factory,
     )
     .await?;
 
@@ -1108,27 +1905,106 @@ pub(crate) async fn deploy_evm(
 }
 
 #[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn create2_address(
+    sol_code_path: PathBuf,
+    contract_name: String,
+    runs: usize,
+    create2_factory: H160,
+    addr_path: Option<PathBuf>,
+    data: Option<PathBuf>,
+    settings_path: Option<PathBuf>,
+    deployer: Option<H160>,
+) -> Result<H160, Box<dyn Error>> {
+    use crate::eth::da_constructor_args;
+    check_solc_requirement();
+
+    // data attestation contracts are deployed with constructor arguments derived from the data
+    let (contract_name, args) = match data {
+        Some(data) => {
+            let settings_path = settings_path
+                .ok_or("a data attestation contract's address requires the settings")?;
+            let deployer =
+                deployer.ok_or("a data attestation contract's address requires its deployer")?;
+            let (contract_name, args) = da_constructor_args(
+                &GraphSettings::load(&settings_path)?,
+                &GraphData::from_path(data)?,
+                deployer,
+            )?;
+            (contract_name.to_string(), args)
+        }
+        None => (contract_name, vec![]),
+    };
+
+    let contract_address =
+        compute_create2_address(sol_code_path, runs, &contract_name, args, create2_factory)?;
+
+    info!(
+        "{} will be deployed at: {:#?}",
+        contract_name, contract_address
+    );
+
+    if let Some(addr_path) = addr_path {
+        let mut f = File::create(addr_path)?;
+        write!(f, "{:#?}", contract_address)?;
+    }
+    Ok(contract_address)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn verify_evm(
     proof_path: PathBuf,
     addr_verifier: H160,
     rpc_url: Option<String>,
     addr_da: Option<H160>,
+    addr_vk: Option<H160>,
+    data: Option<PathBuf>,
+    witness: Option<PathBuf>,
+    settings_path: Option<PathBuf>,
 ) -> Result<(), Box<dyn Error>> {
-    use crate::eth::verify_proof_with_data_attestation;
+    use crate::eth::{data_signatures, verify_proof_with_data_attestation};
     check_solc_requirement();
 
-    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path)?;
+    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(
+        &proof_path,
+        expected_settings_hash(settings_path.as_ref())?,
+    )?;
+    ensure_kzg(proof.commitment(), "evm verification")?;
 
     let result = if let Some(addr_da) = addr_da {
+        if addr_vk.is_some() {
+            return Err(Box::new(EvmVerificationError::SeparateVk(
+                "data attestation",
+            )));
+        }
+        // signed data attestation contracts also need the signatures of the data
+        let signatures = match data {
+            Some(data) => Some(data_signatures(&GraphData::from_path(data)?)?),
+            None => None,
+        }
+        .filter(|signatures| !signatures.is_empty());
         verify_proof_with_data_attestation(
             proof.clone(),
             addr_verifier,
             addr_da,
+            signatures.as_deref(),
             rpc_url.as_deref(),
         )
         .await?
     } else {
-        verify_proof_via_solidity(proof.clone(), addr_verifier, rpc_url.as_deref()).await?
+        // verifiers rendered with `--bind-commitments` can check the proof against the witness' KZG commitments
+        let commitments = match witness {
+            Some(witness) => Some(GraphWitness::from_path(witness)?.get_kzg_commitments()),
+            None => None,
+        };
+        verify_proof_via_solidity(
+            proof.clone(),
+            addr_verifier,
+            addr_vk,
+            commitments.as_deref(),
+            rpc_url.as_deref(),
+        )
+        .await?
     };
 
     info!("Solidity verification result: {}", result);
@@ -1138,6 +2014,184 @@ pub(crate) async fn verify_evm(
     Ok(())
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn decode_evm_outputs(
+    proof_path: PathBuf,
+    sol_code_path: PathBuf,
+    rpc_url: Option<String>,
+) -> Result<Vec<Vec<i128>>, Box<dyn Error>> {
+    use crate::eth::decode_outputs_via_solidity;
+    check_solc_requirement();
+
+    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path, None)?;
+    let instances = proof.instances.concat();
+
+    let outputs =
+        decode_outputs_via_solidity(sol_code_path, &instances, rpc_url.as_deref()).await?;
+    info!("decoded outputs: {:?}", outputs);
+
+    Ok(outputs)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn estimate_evm(
+    proof_path: PathBuf,
+    sol_code_path: PathBuf,
+    vk_sol_code_path: Option<PathBuf>,
+    sol_code_path_da: Option<PathBuf>,
+    data: Option<PathBuf>,
+    settings_path: Option<PathBuf>,
+    rpc_url: Option<String>,
+    runs: usize,
+    output_path: Option<PathBuf>,
+) -> Result<Vec<EvmCostReport>, Box<dyn Error>> {
+    use crate::eth::{
+        data_signatures, encode_da_calldata, encode_verifier_calldata, estimate_contract_costs,
+    };
+    use ethers::providers::Middleware;
+    check_solc_requirement();
+
+    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(
+        &proof_path,
+        expected_settings_hash(settings_path.as_ref())?,
+    )?;
+    ensure_kzg(proof.commitment(), "evm verification")?;
+
+    // keep a single local chain alive for all the deployments (unless we're given a node)
+    let (anvil, client) = setup_eth_backend(rpc_url.as_deref(), None).await?;
+    let endpoint = rpc_url.unwrap_or_else(|| anvil.endpoint());
+    let endpoint = Some(endpoint.as_str());
+
+    let mut reports = vec![];
+
+    let addr_vk = match vk_sol_code_path {
+        Some(vk_sol_code_path) => {
+            let addr = deploy_contract_via_solidity(
+                vk_sol_code_path,
+                endpoint,
+                runs,
+                None,
+                "Halo2VerifyingKey",
+                None,
+            )
+            .await?;
+            // deployments wait for their receipt, so the latest block is the one they were mined in
+            let deploy_block = client.get_block_number().await?.as_u64();
+            reports.push(
+                estimate_contract_costs(
+                    "Halo2VerifyingKey",
+                    addr,
+                    Some(deploy_block),
+                    None,
+                    endpoint,
+                )
+                .await?,
+            );
+            Some(addr)
+        }
+        None => None,
+    };
+
+    let addr_verifier =
+        deploy_verifier_via_solidity(sol_code_path, endpoint, runs, None, None).await?;
+    let deploy_block = client.get_block_number().await?.as_u64();
+    reports.push(
+        estimate_contract_costs(
+            "Halo2Verifier",
+            addr_verifier,
+            Some(deploy_block),
+            Some(encode_verifier_calldata(&proof, addr_vk)),
+            endpoint,
+        )
+        .await?,
+    );
+
+    if let Some(sol_code_path_da) = sol_code_path_da {
+        if addr_vk.is_some() {
+            return Err(Box::new(EvmVerificationError::SeparateVk(
+                "data attestation",
+            )));
+        }
+        let (data, settings_path) =
+            match (data, settings_path) {
+                (Some(data), Some(settings_path)) => (data, settings_path),
+                _ => return Err(
+                    "estimating a data attestation verifier requires --data and --settings-path"
+                        .into(),
+                ),
+            };
+        let signatures = data_signatures(&GraphData::from_path(data.clone())?)?;
+        let addr_da = deploy_da_verifier_via_solidity(
+            settings_path,
+            data,
+            sol_code_path_da,
+            endpoint,
+            runs,
+            None,
+            None,
+        )
+        .await?;
+        let deploy_block = client.get_block_number().await?.as_u64();
+        let (contract, signatures) = if signatures.is_empty() {
+            ("DataAttestation", None)
+        } else {
+            ("DataAttestationSigned", Some(signatures.as_slice()))
+        };
+        reports.push(
+            estimate_contract_costs(
+                contract,
+                addr_da,
+                Some(deploy_block),
+                Some(encode_da_calldata(&proof, addr_verifier, signatures)?),
+                endpoint,
+            )
+            .await?,
+        );
+    }
+    drop(anvil);
+
+    for report in &reports {
+        info!(
+            "{}: deploy gas {:?}, runtime code size {} bytes, calldata {:?} bytes, verify gas {:?}",
+            report.contract,
+            report.deploy_gas,
+            report.runtime_code_size,
+            report.calldata_bytes,
+            report.verify_gas
+        );
+        if report.exceeds_code_size_limit {
+            log::warn!(
+                "{} exceeds the {} byte contract size limit and will fail to deploy on chains that enforce it (EIP-170)",
+                report.contract,
+                MAX_RUNTIME_BYTECODE_SIZE
+            );
+        }
+    }
+
+    if let Some(output_path) = output_path {
+        serde_json::to_writer_pretty(File::create(output_path)?, &reports)?;
+    }
+
+    Ok(reports)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn create_evm_output_decoder(
+    settings_path: PathBuf,
+    sol_code_path: PathBuf,
+) -> Result<(), Box<dyn Error>> {
+    use crate::eth::output_decoder_sol;
+
+    let settings = GraphSettings::load(&settings_path)?;
+    let decoder = output_decoder_sol(&settings)?;
+
+    let mut f = File::create(sol_code_path)?;
+    f.write_all(decoder.as_bytes())?;
+
+    Ok(())
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) fn create_evm_aggregate_verifier(
     vk_path: PathBuf,
@@ -1145,6 +2199,8 @@ pub(crate) fn create_evm_aggregate_verifier(
     sol_code_path: PathBuf,
     abi_path: PathBuf,
     circuit_settings: Vec<PathBuf>,
+    split_proofs: bool,
+    layout_path: Option<PathBuf>,
 ) -> Result<(), Box<dyn Error>> {
     check_solc_requirement();
     let params: ParamsKZG<Bn256> = load_srs::<KZGCommitmentScheme<Bn256>>(srs_path)?;
@@ -1154,6 +2210,10 @@ pub(crate) fn create_evm_aggregate_verifier(
         .map(|path| GraphSettings::load(path).unwrap())
         .collect::<Vec<_>>();
 
+    for s in &settings {
+        ensure_kzg(s.run_args.commitment, "evm verification")?;
+    }
+
     let num_instance: usize = settings
         .iter()
         .map(|s| s.total_instances().iter().sum::<usize>())
@@ -1163,6 +2223,15 @@ pub(crate) fn create_evm_aggregate_verifier(
     assert_eq!(num_instance.len(), 1);
     let num_instance = num_instance[0];
 
+    let layout = AggregateInstanceLayout::new(&settings)?;
+    if layout.num_instances != num_instance {
+        return Err(format!(
+            "the aggregate proof has {} instances but its layout has {}",
+            num_instance, layout.num_instances
+        )
+        .into());
+    }
+
     let agg_vk = load_vk::<KZGCommitmentScheme<Bn256>, Fr, AggregationCircuit>(vk_path, ())?;
 
     let mut generator = halo2_solidity_verifier::SolidityGenerator::new(
@@ -1181,6 +2250,15 @@ pub(crate) fn create_evm_aggregate_verifier(
     generator = generator.set_acc_encoding(Some(acc_encoding));
 
     let verifier_solidity = generator.render().unwrap();
+    let verifier_solidity = if split_proofs {
+        split_proof_verifier_sol(&verifier_solidity, &layout)?
+    } else {
+        verifier_solidity
+    };
+
+    if let Some(layout_path) = layout_path {
+        layout.save(&layout_path)?;
+    }
 
     File::create(sol_code_path.clone())
         .unwrap()
@@ -1214,19 +2292,38 @@ pub(crate) fn setup(
     witness: Option<PathBuf>,
 ) -> Result<(), Box<dyn Error>> {
     // these aren't real values so the sanity checks are mostly meaningless
-    let mut circuit = GraphCircuit::load(compiled_circuit)?;
+    let mut circuit = profiler::phase(profiler::MODEL_LOAD, || {
+        GraphCircuit::load(compiled_circuit)
+    })?;
     if let Some(witness) = witness {
         let data = GraphWitness::from_path(witness)?;
-        circuit.load_graph_witness(&data)?;
+        profiler::phase(profiler::WITNESS_LAYOUT, || {
+            circuit.load_graph_witness(&data)
+        })?;
     }
 
-    let params = load_params_cmd(srs_path, circuit.settings().run_args.logrows)?;
-
-    let pk = create_keys::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(&circuit, &params)
-        .map_err(Box::<dyn Error>::from)?;
+    let logrows = circuit.settings().run_args.logrows;
 
-    save_vk::<KZGCommitmentScheme<Bn256>>(&vk_path, pk.get_vk())?;
-    save_pk::<KZGCommitmentScheme<Bn256>>(&pk_path, &pk)?;
+    match circuit.settings().run_args.commitment {
+        Commitments::KZG => {
+            let params =
+                profiler::phase(profiler::SRS_LOAD, || load_params_cmd(srs_path, logrows))?;
+            let pk = create_keys::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(&circuit, &params)
+                .map_err(Box::<dyn Error>::from)?;
+            save_vk::<KZGCommitmentScheme<Bn256>>(&vk_path, pk.get_vk(), circuit.settings())?;
+            save_pk::<KZGCommitmentScheme<Bn256>>(&pk_path, &pk, circuit.settings())?;
+        }
+        Commitments::IPA => {
+            let params = profiler::phase(profiler::SRS_LOAD, || {
+                load_params_ipa_cmd(srs_path, logrows)
+            })?;
+            let pk =
+                create_keys::<IPACommitmentScheme<G1Affine>, Fr, GraphCircuit>(&circuit, &params)
+                    .map_err(Box::<dyn Error>::from)?;
+            save_vk::<IPACommitmentScheme<G1Affine>>(&vk_path, pk.get_vk(), circuit.settings())?;
+            save_pk::<IPACommitmentScheme<G1Affine>>(&pk_path, &pk, circuit.settings())?;
+        }
+    }
     Ok(())
 }
 
@@ -1297,25 +2394,64 @@ pub(crate) fn prove(
     use crate::pfsys::ProofSplitCommit;
 
     let data = GraphWitness::from_path(data_path)?;
-    let mut circuit = GraphCircuit::load(compiled_circuit_path)?;
-
-    circuit.load_graph_witness(&data)?;
+    let mut circuit = profiler::phase(profiler::MODEL_LOAD, || {
+        GraphCircuit::load(compiled_circuit_path)
+    })?;
 
-    let public_inputs = circuit.prepare_public_inputs(&data)?;
+    let public_inputs = profiler::phase(profiler::WITNESS_LAYOUT, || {
+        circuit.load_graph_witness(&data)?;
+        circuit.prepare_public_inputs(&data)
+    })?;
 
     let circuit_settings = circuit.settings().clone();
 
-    let params = load_params_cmd(srs_path, circuit_settings.run_args.logrows)?;
-
-    let pk = load_pk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(pk_path, circuit_settings)
-        .map_err(Box::<dyn Error>::from)?;
-
-    trace!("params computed");
-
     let strategy: StrategyType = proof_type.into();
     let transcript: TranscriptType = proof_type.into();
     let proof_split_commits: Option<ProofSplitCommit> = data.into();
 
+    if circuit_settings.run_args.commitment == Commitments::IPA {
+        // accumulation (and hence aggregation) is only supported for kzg proofs
+        if strategy == StrategyType::Accum {
+            ensure_kzg(Commitments::IPA, "proof aggregation")?;
+        }
+        let params = profiler::phase(profiler::SRS_LOAD, || {
+            load_params_ipa_cmd(srs_path, circuit_settings.run_args.logrows)
+        })?;
+        let pk = profiler::phase(profiler::PK_LOAD, || {
+            load_pk::<IPACommitmentScheme<G1Affine>, Fr, GraphCircuit>(pk_path, circuit_settings)
+        })?;
+        trace!("params computed");
+
+        let strategy = IPASingleStrategy::new(&params);
+        let snark = create_proof_circuit_ipa(
+            circuit,
+            &params,
+            Some(public_inputs),
+            &pk,
+            transcript,
+            strategy,
+            check_mode,
+            proof_split_commits,
+        )?;
+
+        if let Some(proof_path) = proof_path {
+            snark.save(&proof_path)?;
+        }
+
+        return Ok(snark);
+    }
+
+    let params = profiler::phase(profiler::SRS_LOAD, || {
+        load_params_cmd(srs_path, circuit_settings.run_args.logrows)
+    })?;
+    let multi_open = circuit_settings.run_args.multi_open;
+
+    let pk = profiler::phase(profiler::PK_LOAD, || {
+        load_pk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(pk_path, circuit_settings)
+    })?;
+
+    trace!("params computed");
+
     // creates and verifies the proof
     let snark = match strategy {
         StrategyType::Single => {
@@ -1329,6 +2465,7 @@ pub(crate) fn prove(
                 strategy,
                 check_mode,
                 proof_split_commits,
+                multi_open,
             )?
         }
         StrategyType::Accum => {
@@ -1342,6 +2479,7 @@ pub(crate) fn prove(
                 strategy,
                 check_mode,
                 proof_split_commits,
+                multi_open,
             )?
         }
     };
@@ -1366,6 +2504,7 @@ pub(crate) fn fuzz(
     // these aren't real values so the sanity checks are mostly meaningless
     let mut circuit = GraphCircuit::load(compiled_circuit_path)?;
     let logrows = circuit.settings().run_args.logrows;
+    let multi_open = circuit.settings().run_args.multi_open;
 
     info!("setting up tests");
 
@@ -1404,6 +2543,7 @@ pub(crate) fn fuzz(
             strategy.clone(),
             CheckMode::UNSAFE,
             None,
+            multi_open,
         )
         .unwrap();
 
@@ -1434,6 +2574,7 @@ pub(crate) fn fuzz(
             strategy.clone(),
             CheckMode::UNSAFE,
             None,
+            multi_open,
         )
         .map_err(|_| ())?;
 
@@ -1459,6 +2600,7 @@ pub(crate) fn fuzz(
         strategy.clone(),
         CheckMode::SAFE,
         None,
+        multi_open,
     )?;
 
     let fuzz_vk = || {
@@ -1496,6 +2638,8 @@ pub(crate) fn fuzz(
             protocol: proof.protocol.clone(),
             transcript_type: transcript,
             split: None,
+            commitment: proof.commitment,
+            multi_open: proof.multi_open,
         };
 
         verify_proof_circuit_kzg(
@@ -1528,6 +2672,8 @@ pub(crate) fn fuzz(
             protocol: proof.protocol.clone(),
             transcript_type: transcript,
             split: None,
+            commitment: proof.commitment,
+            multi_open: proof.multi_open,
         };
 
         verify_proof_circuit_kzg(
@@ -1580,7 +2726,7 @@ pub(crate) fn swap_proof_commitments(
     proof_path: PathBuf,
     witness: PathBuf,
 ) -> Result<(), Box<dyn Error>> {
-    let snark = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path).unwrap();
+    let snark = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path, None).unwrap();
     let witness = GraphWitness::from_path(witness)?;
     let commitments = witness.get_kzg_commitments();
 
@@ -1598,15 +2744,35 @@ pub(crate) fn swap_proof_commitments(
     Ok(())
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn convert_proof(
+    proof_path: PathBuf,
+    output_path: PathBuf,
+    settings_path: Option<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let data = std::fs::read(&proof_path)?;
+    let snark = Snark::<Fr, G1Affine>::from_bytes(&data, None)?;
+
+    if data.starts_with(&MAGIC) {
+        info!("converting binary proof to json");
+        snark.save(&output_path)?;
+    } else {
+        info!("converting json proof to binary");
+        snark.save_binary(
+            &output_path,
+            expected_settings_hash(settings_path.as_ref())?,
+        )?;
+    }
+    Ok(())
+}
+
 pub(crate) fn mock_aggregate(
     aggregation_snarks: Vec<PathBuf>,
+    settings_paths: Vec<PathBuf>,
     logrows: u32,
     split_proofs: bool,
 ) -> Result<(), Box<dyn Error>> {
-    let mut snarks = vec![];
-    for proof_path in aggregation_snarks.iter() {
-        snarks.push(Snark::load::<KZGCommitmentScheme<Bn256>>(proof_path)?);
-    }
+    let snarks = load_aggregation_snarks(&aggregation_snarks, &settings_paths)?;
     // proof aggregation
     #[cfg(not(target_arch = "wasm32"))]
     let pb = {
@@ -1629,6 +2795,7 @@ pub(crate) fn mock_aggregate(
 
 pub(crate) fn setup_aggregate(
     sample_snarks: Vec<PathBuf>,
+    settings_paths: Vec<PathBuf>,
     vk_path: PathBuf,
     pk_path: PathBuf,
     srs_path: PathBuf,
@@ -1638,10 +2805,7 @@ pub(crate) fn setup_aggregate(
     // the K used for the aggregation circuit
     let params = load_params_cmd(srs_path, logrows)?;
 
-    let mut snarks = vec![];
-    for proof_path in sample_snarks.iter() {
-        snarks.push(Snark::load::<KZGCommitmentScheme<Bn256>>(proof_path)?);
-    }
+    let snarks = load_aggregation_snarks(&sample_snarks, &settings_paths)?;
 
     let agg_circuit = AggregationCircuit::new(&params.get_g()[0].into(), snarks, split_proofs)?;
     let agg_pk =
@@ -1650,8 +2814,8 @@ pub(crate) fn setup_aggregate(
     let agg_vk = agg_pk.get_vk();
 
     // now save
-    save_vk::<KZGCommitmentScheme<Bn256>>(&vk_path, agg_vk)?;
-    save_pk::<KZGCommitmentScheme<Bn256>>(&pk_path, &agg_pk)?;
+    save_vk::<KZGCommitmentScheme<Bn256>>(&vk_path, agg_vk, &())?;
+    save_pk::<KZGCommitmentScheme<Bn256>>(&pk_path, &agg_pk, &())?;
     Ok(())
 }
 
@@ -1659,6 +2823,7 @@ pub(crate) fn setup_aggregate(
 pub(crate) fn aggregate(
     proof_path: PathBuf,
     aggregation_snarks: Vec<PathBuf>,
+    settings_paths: Vec<PathBuf>,
     pk_path: PathBuf,
     srs_path: PathBuf,
     transcript: TranscriptType,
@@ -1669,10 +2834,7 @@ pub(crate) fn aggregate(
     // the K used for the aggregation circuit
     let params = load_params_cmd(srs_path, logrows)?;
 
-    let mut snarks = vec![];
-    for proof_path in aggregation_snarks.iter() {
-        snarks.push(Snark::load::<KZGCommitmentScheme<Bn256>>(proof_path)?);
-    }
+    let snarks = load_aggregation_snarks(&aggregation_snarks, &settings_paths)?;
 
     let agg_pk = load_pk::<KZGCommitmentScheme<Bn256>, Fr, AggregationCircuit>(pk_path, ())?;
     // proof aggregation
@@ -1696,6 +2858,8 @@ pub(crate) fn aggregate(
             AccumulatorStrategy::new(&params),
             check_mode,
             None,
+            // the aggregation verifiers (native and evm) expect shplonk proofs
+            MultiOpenScheme::SHPLONK,
         )?;
 
         let elapsed = now.elapsed();
@@ -1719,13 +2883,42 @@ pub(crate) fn verify(
     srs_path: PathBuf,
 ) -> Result<(), Box<dyn Error>> {
     let circuit_settings = GraphSettings::load(&settings_path)?;
-    let params = load_params_cmd(srs_path, circuit_settings.run_args.logrows)?;
-    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path)?;
+    let logrows = circuit_settings.run_args.logrows;
+    let commitment = circuit_settings.run_args.commitment;
+    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(
+        &proof_path,
+        Some(circuit_settings.settings_hash()?),
+    )?;
+
+    if proof.commitment() != commitment {
+        return Err(format!(
+            "proof was generated with {} commitments but the settings specify {}",
+            proof.commitment(),
+            commitment
+        )
+        .into());
+    }
 
-    let strategy = KZGSingleStrategy::new(params.verifier_params());
-    let vk = load_vk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(vk_path, circuit_settings)?;
     let now = Instant::now();
-    let result = verify_proof_circuit_kzg(params.verifier_params(), proof, &vk, strategy);
+    let result = match commitment {
+        Commitments::KZG => {
+            proof.check_multi_open(circuit_settings.run_args.multi_open)?;
+            let params = load_params_cmd(srs_path, logrows)?;
+            let strategy = KZGSingleStrategy::new(params.verifier_params());
+            let vk =
+                load_vk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(vk_path, circuit_settings)?;
+            verify_proof_circuit_kzg(params.verifier_params(), proof, &vk, strategy)
+        }
+        Commitments::IPA => {
+            let params = load_params_ipa_cmd(srs_path, logrows)?;
+            let strategy = IPASingleStrategy::new(params.verifier_params());
+            let vk = load_vk::<IPACommitmentScheme<G1Affine>, Fr, GraphCircuit>(
+                vk_path,
+                circuit_settings,
+            )?;
+            verify_proof_circuit_ipa(params.verifier_params(), proof, &vk, strategy)
+        }
+    };
     let elapsed = now.elapsed();
     info!(
         "verify took {}.{}",
@@ -1744,7 +2937,9 @@ pub(crate) fn verify_aggr(
 ) -> Result<(), Box<dyn Error>> {
     let params = load_params_cmd(srs_path, logrows)?;
 
-    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path)?;
+    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path, None)?;
+    ensure_kzg(proof.commitment(), "proof aggregation")?;
+    ensure_shplonk(proof.multi_open(), "proof aggregation")?;
 
     let strategy = AccumulatorStrategy::new(params.verifier_params());
     let vk = load_vk::<KZGCommitmentScheme<Bn256>, Fr, AggregationCircuit>(vk_path, ())?;
@@ -2010,3 +3205,16 @@ pub(crate) fn load_params_cmd(
     }
     Ok(params)
 }
+
+/// helper function for loading ipa params
+pub(crate) fn load_params_ipa_cmd(
+    srs_path: PathBuf,
+    logrows: u32,
+) -> Result<ParamsIPA<G1Affine>, Box<dyn Error>> {
+    let mut params: ParamsIPA<G1Affine> = load_srs::<IPACommitmentScheme<G1Affine>>(srs_path)?;
+    info!("downsizing params to {} logrows", logrows);
+    if logrows < params.k() {
+        params.downsize(logrows);
+    }
+    Ok(params)
+}
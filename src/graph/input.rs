@@ -1,8 +1,12 @@
+use crate::circuit::modules::eddsa::EddsaSignature;
+use crate::circuit::modules::elgamal::ElGamalRecipient;
+use crate::circuit::modules::merkle::MerkleProof;
 use crate::circuit::InputType;
 use crate::fieldutils::i128_to_felt;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::tensor::Tensor;
 use halo2curves::bn256::Fr as Fp;
+use halo2curves::ff::PrimeField;
 #[cfg(not(target_arch = "wasm32"))]
 use postgres::{Client, NoTls};
 #[cfg(feature = "python-bindings")]
@@ -13,6 +17,7 @@ use pyo3::types::PyDict;
 use pyo3::ToPyObject;
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::VecDeque;
 use std::io::Read;
 use std::panic::UnwindSafe;
 #[cfg(not(target_arch = "wasm32"))]
@@ -157,15 +162,161 @@ pub struct OnChainSource {
     pub calls: Vec<CallsToAccount>,
     /// RPC url
     pub rpc: RPCUrl,
+    /// For hashed data, whether the calls return the data itself (which the data attestation
+    /// contract then hashes on-chain) rather than its precomputed hash
+    #[serde(default)]
+    pub hash_on_chain: bool,
+    /// Block number to make the calls at (the latest block if neither this nor the hash is set)
+    #[serde(default)]
+    pub block_number: Option<u64>,
+    /// Hex encoded hash of the block to make the calls at, takes precedence over the block number
+    #[serde(default)]
+    pub block_hash: Option<String>,
 }
 
 impl OnChainSource {
     /// Create a new OnChainSource
     pub fn new(calls: Vec<CallsToAccount>, rpc: RPCUrl) -> Self {
-        OnChainSource { calls, rpc }
+        OnChainSource {
+            calls,
+            rpc,
+            hash_on_chain: false,
+            block_number: None,
+            block_hash: None,
+        }
+    }
+}
+
+/// The number of public instances a committed [OnChainBlock] takes up: its number and the
+/// high and low 128 bits of its hash (a 256 bit hash doesn't fit in a field element)
+pub const ON_CHAIN_BLOCK_INSTANCES: usize = 3;
+
+/// The block on-chain data was read at
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialOrd, PartialEq, Eq)]
+pub struct OnChainBlock {
+    /// block number
+    pub number: u64,
+    /// hex encoded block hash
+    pub hash: String,
+}
+
+impl OnChainBlock {
+    /// The public instances the block is committed to: its number, then the high and low 128 bits of its hash
+    pub fn instances(&self) -> Result<[Fp; ON_CHAIN_BLOCK_INSTANCES], Box<dyn std::error::Error>> {
+        let hash = hex::decode(self.hash.trim_start_matches("0x"))?;
+        let hash: [u8; 32] = hash
+            .try_into()
+            .map_err(|_| format!("block hash {} isn't 32 bytes", self.hash))?;
+        let mut high = [0u8; 16];
+        let mut low = [0u8; 16];
+        high.copy_from_slice(&hash[..16]);
+        low.copy_from_slice(&hash[16..]);
+        Ok([
+            Fp::from(self.number),
+            Fp::from_u128(u128::from_be_bytes(high)),
+            Fp::from_u128(u128::from_be_bytes(low)),
+        ])
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+/// A parameter bound to a SQL query
+#[derive(Clone, Debug, Deserialize, Serialize, PartialOrd, PartialEq)]
+#[serde(untagged)]
+pub enum SqlParam {
+    /// bool parameter
+    Bool(bool),
+    /// integer parameter
+    Int(i64),
+    /// float parameter
+    Float(f64),
+    /// text parameter
+    Text(String),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// A data source backed by a SQL query. Each row of the query's result holds a sample, and the
+/// selected columns of the rows make up the model inputs, so that calibration data split into
+/// batches (see [GraphData::split_into_batches]) has a batch per row.
+pub trait SqlSource {
+    /// Runs a query with the source's bound parameters, returning the names of the result's columns and its rows
+    fn fetch_rows(
+        &self,
+        query: &str,
+    ) -> Result<(Vec<String>, Vec<Vec<FileSourceInner>>), Box<dyn std::error::Error>>;
+
+    /// The query of the source
+    fn query(&self) -> &str;
+
+    /// The columns of each model input, if empty all the columns make up a single input
+    fn columns(&self) -> &[Vec<Column>];
+
+    /// Runs the query and formats its result as a FileSource
+    fn fetch_and_format_as_file(
+        &self,
+    ) -> Result<Vec<Vec<FileSourceInner>>, Box<dyn std::error::Error>> {
+        self.format_as_file(self.fetch_rows(self.query())?)
+    }
+
+    /// Runs the query for `len` rows of its result starting at row `start`, and formats them as
+    /// a FileSource. The query must order its rows (`ORDER BY` a unique key) for consecutive
+    /// windows to neither skip nor repeat rows, queries without an `ORDER BY` are rejected.
+    fn fetch_window_as_file(
+        &self,
+        start: usize,
+        len: usize,
+    ) -> Result<Vec<Vec<FileSourceInner>>, Box<dyn std::error::Error>> {
+        if !orders_rows(self.query()) {
+            return Err(
+                "queries read in windows must order their rows with an ORDER BY on a unique key"
+                    .into(),
+            );
+        }
+        let query = format!(
+            "SELECT * FROM ({}) AS window_rows LIMIT {} OFFSET {}",
+            self.query().trim().trim_end_matches(';'),
+            len,
+            start
+        );
+        self.format_as_file(self.fetch_rows(&query)?)
+    }
+
+    /// Formats the names of the columns and the rows of a query's result as a FileSource
+    fn format_as_file(
+        &self,
+        (names, rows): (Vec<String>, Vec<Vec<FileSourceInner>>),
+    ) -> Result<Vec<Vec<FileSourceInner>>, Box<dyn std::error::Error>> {
+        let inputs = resolve_columns(self.columns(), &names)?;
+        let num_inputs = inputs.len();
+        let rows = select_cells(rows.into_iter().map(Ok), inputs, |row: &Vec<_>, column| {
+            row.get(column)
+                .cloned()
+                .ok_or_else(|| format!("row is missing column {}", column).into())
+        });
+        tabulate(rows, num_inputs)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Whether a query orders its rows, i.e. has an `ORDER BY` outside of any subquery
+fn orders_rows(query: &str) -> bool {
+    let mut depth = 0usize;
+    let top_level: String = query
+        .chars()
+        .map(|c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                _ if depth == 0 => return c.to_ascii_uppercase(),
+                _ => {}
+            }
+            ' '
+        })
+        .collect();
+    let words: Vec<&str> = top_level.split_whitespace().collect();
+    words.windows(2).any(|pair| pair == ["ORDER", "BY"])
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 /// Inner elements of inputs/outputs coming from postgres DB
 #[derive(Clone, Debug, Deserialize, Serialize, Default, PartialOrd, PartialEq)]
@@ -176,12 +327,18 @@ pub struct PostgresSource {
     pub user: String,
     /// password to connect to postgres
     pub password: String,
-    /// query to execute
+    /// query to execute, with `$1`, `$2`, ... placeholders for the params
     pub query: String,
     /// dbname
     pub dbname: String,
     /// port
     pub port: String,
+    /// parameters bound to the query
+    #[serde(default)]
+    pub params: Vec<SqlParam>,
+    /// the columns of each model input, if empty all the columns make up a single input
+    #[serde(default)]
+    pub columns: Vec<Vec<Column>>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -202,70 +359,737 @@ impl PostgresSource {
             query,
             dbname,
             port,
+            params: vec![],
+            columns: vec![],
         }
     }
 
-    /// Fetch data from postgres
-    pub fn fetch(&self) -> Result<Vec<Vec<pg_bigdecimal::PgNumeric>>, Box<dyn std::error::Error>> {
+    /// Reads the value of a column of a row (bool, int, float or numeric columns are supported)
+    fn read_value(
+        row: &postgres::Row,
+        i: usize,
+    ) -> Result<FileSourceInner, Box<dyn std::error::Error + Send + Sync>> {
+        use postgres::types::Type;
+
+        let column = &row.columns()[i];
+        let ty = column.type_();
+        let value = if *ty == Type::BOOL {
+            FileSourceInner::Bool(row.try_get(i)?)
+        } else if *ty == Type::INT2 {
+            FileSourceInner::Float(row.try_get::<_, i16>(i)? as f64)
+        } else if *ty == Type::INT4 {
+            FileSourceInner::Float(row.try_get::<_, i32>(i)? as f64)
+        } else if *ty == Type::INT8 {
+            FileSourceInner::Float(row.try_get::<_, i64>(i)? as f64)
+        } else if *ty == Type::FLOAT4 {
+            FileSourceInner::Float(row.try_get::<_, f32>(i)? as f64)
+        } else if *ty == Type::FLOAT8 {
+            FileSourceInner::Float(row.try_get(i)?)
+        } else if *ty == Type::NUMERIC {
+            let numeric: pg_bigdecimal::PgNumeric = row.try_get(i)?;
+            FileSourceInner::Float(
+                numeric
+                    .n
+                    .as_ref()
+                    .and_then(|n| n.to_f64())
+                    .ok_or("could not convert decimal to f64")?,
+            )
+        } else {
+            return Err(format!("unsupported type {} of column {}", ty, column.name()).into());
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SqlSource for PostgresSource {
+    fn fetch_rows(
+        &self,
+        query: &str,
+    ) -> Result<(Vec<String>, Vec<Vec<FileSourceInner>>), Box<dyn std::error::Error>> {
         // clone to move into thread
-        let user = self.user.clone();
-        let host = self.host.clone();
-        let query = self.query.clone();
-        let dbname = self.dbname.clone();
-        let port = self.port.clone();
-        let password = self.password.clone();
-
-        let config = if password.is_empty() {
+        let query = query.to_string();
+        let params = self.params.clone();
+
+        let config = if self.password.is_empty() {
             format!(
                 "host={} user={} dbname={} port={}",
-                host, user, dbname, port
+                self.host, self.user, self.dbname, self.port
             )
         } else {
             format!(
                 "host={} user={} dbname={} port={} password={}",
-                host, user, dbname, port, password
+                self.host, self.user, self.dbname, self.port, self.password
             )
         };
 
-        let res: Vec<pg_bigdecimal::PgNumeric> = thread::spawn(move || {
-            let mut client = Client::connect(&config, NoTls).unwrap();
-            let mut res: Vec<pg_bigdecimal::PgNumeric> = Vec::new();
-            // extract rows from query
-            for row in client.query(&query, &[]).unwrap() {
-                // extract features from row
-                for i in 0..row.len() {
-                    res.push(row.get(i));
+        // the synchronous client blocks on its own runtime, so it can't run on the thread of an async caller
+        let res = thread::spawn(
+            move || -> Result<(Vec<String>, Vec<Vec<FileSourceInner>>), String> {
+                let mut client = Client::connect(&config, NoTls)
+                    .map_err(|e| format!("failed to connect to postgres: {}", e))?;
+                let statement = client
+                    .prepare(&query)
+                    .map_err(|e| format!("failed to prepare postgres query: {}", e))?;
+                let names = statement
+                    .columns()
+                    .iter()
+                    .map(|c| c.name().to_string())
+                    .collect::<Vec<_>>();
+
+                let params: Vec<Box<dyn postgres::types::ToSql + Sync>> = params
+                    .into_iter()
+                    .map(|p| -> Box<dyn postgres::types::ToSql + Sync> {
+                        match p {
+                            SqlParam::Bool(x) => Box::new(x),
+                            SqlParam::Int(x) => Box::new(x),
+                            SqlParam::Float(x) => Box::new(x),
+                            SqlParam::Text(x) => Box::new(x),
+                        }
+                    })
+                    .collect();
+                let params: Vec<&(dyn postgres::types::ToSql + Sync)> =
+                    params.iter().map(|p| p.as_ref()).collect();
+
+                let rows = client
+                    .query(&statement, &params)
+                    .map_err(|e| format!("failed to run postgres query: {}", e))?
+                    .iter()
+                    .map(|row| {
+                        (0..row.len())
+                            .map(|i| Self::read_value(row, i))
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| format!("failed to read postgres rows: {}", e))?;
+                Ok((names, rows))
+            },
+        )
+        .join()
+        .map_err(|_| "failed to fetch data from postgres")??;
+
+        Ok(res)
+    }
+
+    fn query(&self) -> &str {
+        &self.query
+    }
+
+    fn columns(&self) -> &[Vec<Column>] {
+        &self.columns
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Inner elements of inputs/outputs coming from a SQLite database
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialOrd, PartialEq)]
+pub struct SqliteSource {
+    /// path to the sqlite database
+    pub sqlite: std::path::PathBuf,
+    /// query to execute, with `?` placeholders for the params
+    pub query: String,
+    /// parameters bound to the query
+    #[serde(default)]
+    pub params: Vec<SqlParam>,
+    /// the columns of each model input, if empty all the columns make up a single input
+    #[serde(default)]
+    pub columns: Vec<Vec<Column>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SqliteSource {
+    /// Create a new SqliteSource
+    pub fn new(sqlite: std::path::PathBuf, query: String, params: Vec<SqlParam>) -> Self {
+        SqliteSource {
+            sqlite,
+            query,
+            params,
+            columns: vec![],
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SqlSource for SqliteSource {
+    fn fetch_rows(
+        &self,
+        query: &str,
+    ) -> Result<(Vec<String>, Vec<Vec<FileSourceInner>>), Box<dyn std::error::Error>> {
+        use rusqlite::types::{Value, ValueRef};
+
+        let connection = rusqlite::Connection::open_with_flags(
+            &self.sqlite,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )
+        .map_err(|e| format!("failed to open sqlite at {}: {}", self.sqlite.display(), e))?;
+        let mut statement = connection.prepare(query)?;
+        let names = statement
+            .column_names()
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        // sqlite has no bool type, bools are stored as integers
+        let params = self.params.iter().map(|p| match p {
+            SqlParam::Bool(x) => Value::Integer(*x as i64),
+            SqlParam::Int(x) => Value::Integer(*x),
+            SqlParam::Float(x) => Value::Real(*x),
+            SqlParam::Text(x) => Value::Text(x.clone()),
+        });
+        let mut rows = statement.query(rusqlite::params_from_iter(params))?;
+
+        let mut data = vec![];
+        while let Some(row) = rows.next()? {
+            let values = (0..names.len())
+                .map(|i| -> Result<FileSourceInner, Box<dyn std::error::Error>> {
+                    match row.get_ref(i)? {
+                        ValueRef::Integer(x) => Ok(FileSourceInner::Float(x as f64)),
+                        ValueRef::Real(x) => Ok(FileSourceInner::Float(x)),
+                        ValueRef::Null => Err(format!("column {} is null", names[i]).into()),
+                        _ => Err(format!("column {} is not a number", names[i]).into()),
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            data.push(values);
+        }
+
+        Ok((names, data))
+    }
+
+    fn query(&self) -> &str {
+        &self.query
+    }
+
+    fn columns(&self) -> &[Vec<Column>] {
+        &self.columns
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// A column of a tabular (CSV or Parquet) data source, by index or by name
+#[derive(Clone, Debug, Deserialize, Serialize, PartialOrd, PartialEq)]
+#[serde(untagged)]
+pub enum Column {
+    /// index of the column
+    Index(usize),
+    /// name of the column (from the CSV header or the Parquet schema)
+    Name(String),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// A range of rows (or of the first axis of an array) to read from a data source
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialOrd, PartialEq)]
+pub struct RowRange {
+    /// first row to read
+    pub start: usize,
+    /// row to stop reading at (exclusive), all the remaining rows if None
+    #[serde(default)]
+    pub end: Option<usize>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl RowRange {
+    /// The `len` rows of a range starting at its row `start`
+    fn window(range: &Option<RowRange>, start: usize, len: usize) -> Option<RowRange> {
+        let (first, end) = RowRange::bounds(range);
+        let start = first.saturating_add(start);
+        Some(RowRange {
+            start,
+            end: Some(end.min(start.saturating_add(len))),
+        })
+    }
+
+    /// The first row of the range and the row it stops at (exclusive)
+    fn bounds(range: &Option<RowRange>) -> (usize, usize) {
+        match range {
+            Some(range) => (range.start, range.end.unwrap_or(usize::MAX)),
+            None => (0, usize::MAX),
+        }
+    }
+
+    /// Skips the rows before the range and stops at its end
+    fn apply<T>(
+        range: &Option<RowRange>,
+        rows: impl Iterator<Item = T>,
+    ) -> impl Iterator<Item = T> {
+        let (start, end) = RowRange::bounds(range);
+        rows.skip(start).take(end.saturating_sub(start))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Resolves the columns of each model input to column indices, all the columns are a single input if none are selected
+fn resolve_columns(
+    columns: &[Vec<Column>],
+    names: &[String],
+) -> Result<Vec<Vec<usize>>, Box<dyn std::error::Error>> {
+    if columns.is_empty() {
+        return Ok(vec![(0..names.len()).collect()]);
+    }
+    columns
+        .iter()
+        .map(|input| -> Result<Vec<usize>, Box<dyn std::error::Error>> {
+            input
+                .iter()
+                .map(|column| match column {
+                    Column::Index(i) if *i < names.len() => Ok(*i),
+                    Column::Index(i) => Err(format!("column {} is out of range", i).into()),
+                    Column::Name(name) => names
+                        .iter()
+                        .position(|n| n == name)
+                        .ok_or_else(|| format!("could not find column {}", name).into()),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// The rows of a file source read in order, each split into the data of every model input
+type Rows = Box<dyn Iterator<Item = Result<FileSource, Box<dyn std::error::Error>>>>;
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Reads the selected columns of every row out into the model inputs. Rows are consumed one at
+/// a time and only the selected cells are read out of them.
+fn select_cells<R>(
+    rows: impl Iterator<Item = Result<R, Box<dyn std::error::Error>>>,
+    inputs: Vec<Vec<usize>>,
+    cell: impl Fn(&R, usize) -> Result<FileSourceInner, Box<dyn std::error::Error>>,
+) -> impl Iterator<Item = Result<FileSource, Box<dyn std::error::Error>>> {
+    rows.map(move |row| {
+        let row = row?;
+        inputs
+            .iter()
+            .map(|columns| columns.iter().map(|column| cell(&row, *column)).collect())
+            .collect()
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Concatenates the rows into the model inputs (row-major)
+fn tabulate(
+    rows: impl Iterator<Item = Result<FileSource, Box<dyn std::error::Error>>>,
+    num_inputs: usize,
+) -> Result<FileSource, Box<dyn std::error::Error>> {
+    let mut data: FileSource = vec![vec![]; num_inputs];
+    for row in rows {
+        for (input, cells) in data.iter_mut().zip(row?) {
+            input.extend(cells);
+        }
+    }
+    Ok(data)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Inner elements of inputs coming from a CSV file. Each row holds a sample, and the selected
+/// columns of the rows make up the model inputs. Cells are numbers or `true`/`false`.
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialOrd, PartialEq)]
+pub struct CsvSource {
+    /// path to the csv file
+    pub csv: std::path::PathBuf,
+    /// the columns of each model input, if empty all the columns make up a single input
+    #[serde(default)]
+    pub columns: Vec<Vec<Column>>,
+    /// the rows to read (not counting the header), all the rows if None
+    #[serde(default)]
+    pub rows: Option<RowRange>,
+    /// whether the file has no header row, in which case columns can only be selected by index
+    #[serde(default)]
+    pub no_headers: bool,
+    /// the field delimiter, `,` if None
+    #[serde(default)]
+    pub delimiter: Option<char>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CsvSource {
+    /// Create a new CsvSource reading all the columns of all the rows
+    pub fn new(csv: std::path::PathBuf) -> Self {
+        CsvSource {
+            csv,
+            ..Default::default()
+        }
+    }
+
+    /// Read the csv file and format it as a FileSource
+    pub fn fetch_and_format_as_file(
+        &self,
+    ) -> Result<Vec<Vec<FileSourceInner>>, Box<dyn std::error::Error>> {
+        let (num_inputs, rows) = self.stream_rows()?;
+        tabulate(rows, num_inputs)
+    }
+
+    /// Opens the csv file to read its selected rows in order, returns the number of model inputs
+    /// along with the rows
+    fn stream_rows(&self) -> Result<(usize, Rows), Box<dyn std::error::Error>> {
+        let delimiter = self.delimiter.unwrap_or(',');
+        if !delimiter.is_ascii() {
+            return Err(format!("csv delimiter {} is not an ascii character", delimiter).into());
+        }
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(!self.no_headers)
+            .delimiter(delimiter as u8)
+            .from_path(&self.csv)
+            .map_err(|e| format!("failed to open csv at {}: {}", self.csv.display(), e))?;
+
+        let names: Vec<String> = if self.no_headers {
+            // columns are then only selectable by index
+            let width = reader.headers()?.len();
+            (0..width).map(|i| i.to_string()).collect()
+        } else {
+            reader.headers()?.iter().map(String::from).collect()
+        };
+        let inputs = resolve_columns(&self.columns, &names)?;
+
+        let num_inputs = inputs.len();
+
+        let records = RowRange::apply(&self.rows, reader.into_records())
+            .map(|record| -> Result<csv::StringRecord, Box<dyn std::error::Error>> { Ok(record?) });
+        // only the selected cells of a record are parsed
+        let rows = select_cells(records, inputs, |record: &csv::StringRecord, column| {
+            let cell = record
+                .get(column)
+                .ok_or_else(|| format!("row is missing column {}", column))?
+                .trim();
+            match cell {
+                "true" => Ok(FileSourceInner::Bool(true)),
+                "false" => Ok(FileSourceInner::Bool(false)),
+                _ => cell
+                    .parse::<f64>()
+                    .map(FileSourceInner::Float)
+                    .map_err(|_| format!("could not parse csv value {} as a number", cell).into()),
+            }
+        });
+        Ok((num_inputs, Box::new(rows)))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Inner elements of inputs coming from a NumPy `.npy` file (a single input) or `.npz` archive
+/// (an input per array). Arrays of bools, ints and floats are supported and are flattened (row-major).
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialOrd, PartialEq)]
+pub struct NpySource {
+    /// path to the .npy or .npz file
+    pub npy: std::path::PathBuf,
+    /// for .npz archives, the arrays making up each model input, all the arrays (in archive order) if empty
+    #[serde(default)]
+    pub arrays: Vec<String>,
+    /// the rows (along the first axis of every array) to read, all the rows if None
+    #[serde(default)]
+    pub rows: Option<RowRange>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl NpySource {
+    /// Create a new NpySource reading all the rows of all the arrays
+    pub fn new(npy: std::path::PathBuf) -> Self {
+        NpySource {
+            npy,
+            ..Default::default()
+        }
+    }
+
+    /// Read the npy / npz file and format it as a FileSource
+    pub fn fetch_and_format_as_file(
+        &self,
+    ) -> Result<Vec<Vec<FileSourceInner>>, Box<dyn std::error::Error>> {
+        let (num_inputs, rows) = self.stream_rows()?;
+        tabulate(rows, num_inputs)
+    }
+
+    /// Opens the npy / npz file to read the selected rows of its arrays in order, returns the
+    /// number of model inputs along with the rows. Arrays that run out of rows before the others
+    /// add no data to the remaining rows.
+    fn stream_rows(&self) -> Result<(usize, Rows), Box<dyn std::error::Error>> {
+        let is_npz = self.npy.extension().map_or(false, |ext| ext == "npz");
+        if !is_npz {
+            if !self.arrays.is_empty() {
+                return Err("arrays can only be selected from .npz archives".into());
+            }
+            let file = std::fs::File::open(&self.npy)
+                .map_err(|_| format!("failed to open npy at {}", self.npy.display()))?;
+            let array = npyz::NpyFile::new(std::io::BufReader::new(file))?;
+            let rows = self
+                .array_rows(array)?
+                .map(|row| -> Result<FileSource, Box<dyn std::error::Error>> { Ok(vec![row?]) });
+            return Ok((1, Box::new(rows)));
+        }
+
+        let mut archive = npyz::npz::NpzArchive::open(&self.npy)
+            .map_err(|_| format!("failed to open npz at {}", self.npy.display()))?;
+        let names: Vec<String> = if self.arrays.is_empty() {
+            archive.array_names().map(String::from).collect()
+        } else {
+            self.arrays.clone()
+        };
+        for name in &names {
+            if archive.by_name(name)?.is_none() {
+                return Err(format!("could not find array {} in the npz archive", name).into());
+            }
+        }
+
+        let mut arrays: Vec<_> = names
+            .into_iter()
+            .map(|name| self.stream_npz_array(name))
+            .collect();
+        let num_inputs = arrays.len();
+        let rows = std::iter::from_fn(move || {
+            let row: Vec<_> = arrays.iter_mut().map(|array| array.next()).collect();
+            if row.iter().all(Option::is_none) {
+                return None;
+            }
+            Some(
+                row.into_iter()
+                    .map(|cells| cells.unwrap_or_else(|| Ok(vec![])))
+                    .collect::<Result<FileSource, _>>(),
+            )
+        });
+        Ok((num_inputs, Box::new(rows)))
+    }
+
+    /// Streams the selected rows of an array of the npz archive. The entries of an archive can't
+    /// be read side by side, so each array is read on its own thread with its own handle on the
+    /// archive, at most [BATCH_WINDOW_ROWS] rows ahead of the reader.
+    fn stream_npz_array(
+        &self,
+        name: String,
+    ) -> impl Iterator<Item = Result<Vec<FileSourceInner>, Box<dyn std::error::Error>>> {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(BATCH_WINDOW_ROWS);
+        let source = self.clone();
+        thread::spawn(move || {
+            let read = || -> Result<(), Box<dyn std::error::Error>> {
+                let mut archive = npyz::npz::NpzArchive::open(&source.npy)?;
+                let array = archive
+                    .by_name(&name)?
+                    .ok_or_else(|| format!("could not find array {} in the npz archive", name))?;
+                for row in source.array_rows(array)? {
+                    // stops once the rows are no longer being read
+                    if sender.send(Ok(row?)).is_err() {
+                        break;
+                    }
                 }
+                Ok(())
+            };
+            if let Err(e) = read() {
+                let _ = sender.send(Err(e.to_string()));
             }
-            res
-        })
-        .join()
-        .map_err(|_| "failed to fetch data from postgres")?;
+        });
+        receiver.into_iter().map(|row| row.map_err(|e| e.into()))
+    }
+
+    /// Reads the selected rows of an array one at a time, converting its dtype. Only the
+    /// elements of the selected rows are kept.
+    fn array_rows<'a, R: std::io::Read + 'a>(
+        &self,
+        array: npyz::NpyFile<R>,
+    ) -> Result<
+        impl Iterator<Item = Result<Vec<FileSourceInner>, Box<dyn std::error::Error>>> + 'a,
+        Box<dyn std::error::Error>,
+    > {
+        // a 0-d array is a single row
+        let row_len = (array.shape().iter().skip(1).product::<u64>() as usize).max(1);
+        let (start, end) = RowRange::bounds(&self.rows);
+        let skip = start.saturating_mul(row_len);
+        let take = end.saturating_sub(start).saturating_mul(row_len);
+
+        macro_rules! read {
+            ($t:ty, $convert:expr) => {
+                Box::new(array.data::<$t>()?.map(
+                    |x| -> Result<FileSourceInner, Box<dyn std::error::Error>> {
+                        Ok(($convert)(x?))
+                    },
+                ))
+            };
+        }
+        macro_rules! floats {
+            ($t:ty) => {
+                read!($t, |x| FileSourceInner::Float(x as f64))
+            };
+        }
+        let values: Box<
+            dyn Iterator<Item = Result<FileSourceInner, Box<dyn std::error::Error>>> + 'a,
+        > = match array.dtype() {
+            npyz::DType::Plain(ty) => match (ty.type_char(), ty.size_field()) {
+                (npyz::TypeChar::Bool, _) => read!(bool, FileSourceInner::Bool),
+                (npyz::TypeChar::Float, 4) => floats!(f32),
+                (npyz::TypeChar::Float, 8) => floats!(f64),
+                (npyz::TypeChar::Int, 1) => floats!(i8),
+                (npyz::TypeChar::Int, 2) => floats!(i16),
+                (npyz::TypeChar::Int, 4) => floats!(i32),
+                (npyz::TypeChar::Int, 8) => floats!(i64),
+                (npyz::TypeChar::Uint, 1) => floats!(u8),
+                (npyz::TypeChar::Uint, 2) => floats!(u16),
+                (npyz::TypeChar::Uint, 4) => floats!(u32),
+                (npyz::TypeChar::Uint, 8) => floats!(u64),
+                _ => return Err(format!("unsupported npy dtype {}", ty).into()),
+            },
+            dtype => return Err(format!("unsupported npy dtype {:?}", dtype).into()),
+        };
+
+        let mut values = values.skip(skip).take(take);
+        Ok(std::iter::from_fn(move || {
+            match values.by_ref().take(row_len).collect::<Result<Vec<_>, _>>() {
+                Ok(row) if row.is_empty() => None,
+                row => Some(row),
+            }
+        }))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Inner elements of inputs coming from a Parquet file. Each row holds a sample, and the selected
+/// columns of the rows make up the model inputs. Bool, int and float columns are supported.
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialOrd, PartialEq)]
+pub struct ParquetSource {
+    /// path to the parquet file
+    pub parquet: std::path::PathBuf,
+    /// the columns of each model input, if empty all the columns make up a single input
+    #[serde(default)]
+    pub columns: Vec<Vec<Column>>,
+    /// the rows to read, all the rows if None
+    #[serde(default)]
+    pub rows: Option<RowRange>,
+}
 
-        Ok(vec![res])
+#[cfg(not(target_arch = "wasm32"))]
+impl ParquetSource {
+    /// Create a new ParquetSource reading all the columns of all the rows
+    pub fn new(parquet: std::path::PathBuf) -> Self {
+        ParquetSource {
+            parquet,
+            ..Default::default()
+        }
     }
 
-    /// Fetch data from postgres and format it as a FileSource
+    /// Read the parquet file and format it as a FileSource. Only the selected columns are
+    /// decoded, and row groups entirely outside of the selected rows are skipped.
     pub fn fetch_and_format_as_file(
         &self,
     ) -> Result<Vec<Vec<FileSourceInner>>, Box<dyn std::error::Error>> {
-        Ok(self
-            .fetch()?
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+        use parquet::record::{Field, Row};
+        use parquet::schema::types::Type;
+
+        let file = std::fs::File::open(&self.parquet)
+            .map_err(|_| format!("failed to open parquet at {}", self.parquet.display()))?;
+        let reader = SerializedFileReader::new(file)?;
+
+        let schema = reader.metadata().file_metadata().schema();
+        let fields = schema.get_fields();
+        let names: Vec<String> = fields.iter().map(|f| f.name().to_string()).collect();
+        let inputs = resolve_columns(&self.columns, &names)?;
+
+        // project the schema onto the selected columns, and index the columns within the projection
+        let mut selected: Vec<usize> = inputs.iter().flatten().copied().collect();
+        selected.sort_unstable();
+        selected.dedup();
+        let projection = Type::group_type_builder(schema.name())
+            .with_fields(selected.iter().map(|i| fields[*i].clone()).collect())
+            .build()?;
+        let inputs: Vec<Vec<usize>> = inputs
             .iter()
-            .map(|d| {
-                d.iter()
-                    .map(|d| {
-                        FileSourceInner::Float(
-                            d.n.as_ref()
-                                .unwrap()
-                                .to_f64()
-                                .ok_or("could not convert decimal to f64")
-                                .unwrap(),
-                        )
-                    })
+            .map(|columns| {
+                columns
+                    .iter()
+                    .map(|c| selected.partition_point(|s| s < c))
                     .collect()
             })
-            .collect())
+            .collect();
+
+        let (start, end) = RowRange::bounds(&self.rows);
+        let mut groups = vec![];
+        let mut group_start = 0;
+        for i in 0..reader.num_row_groups() {
+            let group_end = group_start + reader.metadata().row_group(i).num_rows() as usize;
+            if group_end > start && group_start < end {
+                groups.push((
+                    reader.get_row_group(i)?,
+                    start.saturating_sub(group_start),
+                    end.min(group_end) - start.max(group_start),
+                ));
+            }
+            group_start = group_end;
+        }
+        let mut group_rows = vec![];
+        for (group, skip, take) in &groups {
+            group_rows.push(
+                group
+                    .get_row_iter(Some(projection.clone()))?
+                    .skip(*skip)
+                    .take(*take),
+            );
+        }
+        let rows = group_rows
+            .into_iter()
+            .flatten()
+            .map(|row| -> Result<Row, Box<dyn std::error::Error>> { Ok(row?) });
+
+        let num_inputs = inputs.len();
+        let rows = select_cells(rows, inputs, |row: &Row, column| {
+            let (name, field) = row
+                .get_column_iter()
+                .nth(column)
+                .ok_or_else(|| format!("row is missing column {}", column))?;
+            match field {
+                Field::Bool(x) => Ok(FileSourceInner::Bool(*x)),
+                Field::Byte(x) => Ok(FileSourceInner::Float(*x as f64)),
+                Field::Short(x) => Ok(FileSourceInner::Float(*x as f64)),
+                Field::Int(x) => Ok(FileSourceInner::Float(*x as f64)),
+                Field::Long(x) => Ok(FileSourceInner::Float(*x as f64)),
+                Field::UByte(x) => Ok(FileSourceInner::Float(*x as f64)),
+                Field::UShort(x) => Ok(FileSourceInner::Float(*x as f64)),
+                Field::UInt(x) => Ok(FileSourceInner::Float(*x as f64)),
+                Field::ULong(x) => Ok(FileSourceInner::Float(*x as f64)),
+                Field::Float(x) => Ok(FileSourceInner::Float(*x as f64)),
+                Field::Double(x) => Ok(FileSourceInner::Float(*x)),
+                _ => Err(format!("unsupported parquet value {} in column {}", field, name).into()),
+            }
+        });
+        tabulate(rows, num_inputs)
+    }
+}
+
+/// The EIP-712 domain data is signed in, so that a signature can't be replayed on another chain,
+/// for another verifier or after the signer has moved on to a new nonce
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialOrd, PartialEq)]
+pub struct SignedDataDomain {
+    /// Id of the chain the data is attested on
+    pub chain_id: u64,
+    /// Hex encoded address of the verifier contract the data is attested for
+    pub verifier: String,
+    /// Nonce of the `DataAttestationSigned` contract the data is attested with
+    pub nonce: u64,
+}
+
+/// Inner elements of inputs/outputs signed off-chain (e.g. by an oracle). The signature is over the
+/// quantized data in the signing `domain`, see [crate::eth::signed_data_digest], and is checked
+/// on-chain by the `DataAttestationSigned` contract.
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialOrd, PartialEq)]
+pub struct SignedSource {
+    /// The signed data
+    pub data: FileSource,
+    /// Hex encoded 65 byte (r, s, v) ECDSA signature over the quantized data
+    pub signature: String,
+    /// Hex encoded address of the signer
+    pub signer: String,
+    /// The domain the data was signed in
+    pub domain: SignedDataDomain,
+}
+
+impl SignedSource {
+    /// Create a new SignedSource
+    pub fn new(
+        data: FileSource,
+        signature: String,
+        signer: String,
+        domain: SignedDataDomain,
+    ) -> Self {
+        SignedSource {
+            data,
+            signature,
+            signer,
+            domain,
+        }
     }
 }
 
@@ -297,7 +1121,8 @@ impl OnChainSource {
 
         let calls_to_accounts = test_on_chain_data(client.clone(), data).await?;
         debug!("Calls to accounts: {:?}", calls_to_accounts);
-        let inputs = read_on_chain_inputs(client.clone(), address, &calls_to_accounts).await?;
+        let inputs =
+            read_on_chain_inputs(client.clone(), address, &calls_to_accounts, None).await?;
         debug!("Inputs: {:?}", inputs);
 
         let mut quantized_evm_inputs = vec![];
@@ -359,9 +1184,23 @@ pub enum DataSource {
     File(FileSource),
     /// On-chain data source. The first element is the calls to the account, and the second is the RPC url.
     OnChain(OnChainSource),
+    /// Data signed off-chain, attested on-chain by checking the signature.
+    Signed(SignedSource),
     /// Postgres DB
     #[cfg(not(target_arch = "wasm32"))]
     DB(PostgresSource),
+    /// SQLite DB
+    #[cfg(not(target_arch = "wasm32"))]
+    Sqlite(SqliteSource),
+    /// CSV file
+    #[cfg(not(target_arch = "wasm32"))]
+    Csv(CsvSource),
+    /// NumPy .npy file or .npz archive
+    #[cfg(not(target_arch = "wasm32"))]
+    Npy(NpySource),
+    /// Parquet file
+    #[cfg(not(target_arch = "wasm32"))]
+    Parquet(ParquetSource),
 }
 
 impl Default for DataSource {
@@ -402,6 +1241,12 @@ impl From<OnChainSource> for DataSource {
     }
 }
 
+impl From<SignedSource> for DataSource {
+    fn from(data: SignedSource) -> Self {
+        DataSource::Signed(data)
+    }
+}
+
 // !!! ALWAYS USE JSON SERIALIZATION FOR GRAPH INPUT
 // UNTAGGED ENUMS WONT WORK :( as highlighted here:
 impl<'de> Deserialize<'de> for DataSource {
@@ -420,12 +1265,32 @@ impl<'de> Deserialize<'de> for DataSource {
         if let Ok(t) = second_try {
             return Ok(DataSource::OnChain(t));
         }
+        let signed_try: Result<SignedSource, _> = serde_json::from_str(this_json.get());
+        if let Ok(t) = signed_try {
+            return Ok(DataSource::Signed(t));
+        }
         #[cfg(not(target_arch = "wasm32"))]
         {
             let third_try: Result<PostgresSource, _> = serde_json::from_str(this_json.get());
             if let Ok(t) = third_try {
                 return Ok(DataSource::DB(t));
             }
+            let sqlite_try: Result<SqliteSource, _> = serde_json::from_str(this_json.get());
+            if let Ok(t) = sqlite_try {
+                return Ok(DataSource::Sqlite(t));
+            }
+            let csv_try: Result<CsvSource, _> = serde_json::from_str(this_json.get());
+            if let Ok(t) = csv_try {
+                return Ok(DataSource::Csv(t));
+            }
+            let npy_try: Result<NpySource, _> = serde_json::from_str(this_json.get());
+            if let Ok(t) = npy_try {
+                return Ok(DataSource::Npy(t));
+            }
+            let parquet_try: Result<ParquetSource, _> = serde_json::from_str(this_json.get());
+            if let Ok(t) = parquet_try {
+                return Ok(DataSource::Parquet(t));
+            }
         }
 
         Err(serde::de::Error::custom("failed to deserialize DataSource"))
@@ -440,6 +1305,19 @@ pub struct GraphData {
     pub input_data: DataSource,
     /// Outputs of the model / computational graph (can be empty vectors if outputs are coming from on-chain).
     pub output_data: Option<DataSource>,
+    /// The recipient encrypted values are delivered to (overrides the one in the settings).
+    #[serde(default)]
+    pub elgamal_recipient: Option<ElGamalRecipient>,
+    /// The salt absorbed into salted hashes, shared by inputs, params and outputs (if none, gen-witness draws
+    /// one and saves it here).
+    #[serde(default)]
+    pub hash_salt: Option<Fp>,
+    /// The paths `merkle/<depth>` inputs are opened along (one per input), eg. to draw them from a published dataset.
+    #[serde(default)]
+    pub merkle_proofs: Option<Vec<MerkleProof>>,
+    /// The data provider's signatures over `signed` inputs (one per input).
+    #[serde(default)]
+    pub signatures: Option<Vec<EddsaSignature>>,
 }
 
 impl UnwindSafe for GraphData {}
@@ -450,6 +1328,10 @@ impl GraphData {
         GraphData {
             input_data,
             output_data: None,
+            elgamal_recipient: None,
+            hash_salt: None,
+            merkle_proofs: None,
+            signatures: None,
         }
     }
 
@@ -462,90 +1344,215 @@ impl GraphData {
         serde_json::from_str(&data).map_err(|e| e.into())
     }
 
+    /// The signed sources of the input and output data (in that order)
+    pub fn signed_sources(&self) -> Vec<&SignedSource> {
+        let mut sources = vec![];
+        if let DataSource::Signed(source) = &self.input_data {
+            sources.push(source);
+        }
+        if let Some(DataSource::Signed(source)) = &self.output_data {
+            sources.push(source);
+        }
+        sources
+    }
+
     /// Save the model input to a file
     pub fn save(&self, path: std::path::PathBuf) -> Result<(), Box<dyn std::error::Error>> {
         serde_json::to_writer(std::fs::File::create(path)?, &self).map_err(|e| e.into())
     }
 
-    ///
+    /// Splits the input data into batches of the model's `input_shapes`
     pub fn split_into_batches(
         &self,
         input_shapes: Vec<Vec<usize>>,
     ) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
-        // split input data into batches
-        let mut batched_inputs = vec![];
-
-        let iterable = match self {
-            GraphData {
-                input_data: DataSource::File(data),
-                output_data: _,
-            } => data.clone(),
-            GraphData {
-                input_data: DataSource::OnChain(_),
-                output_data: _,
-            } => {
-                return Err(Box::new(GraphError::InvalidDims(
-                    0,
-                    "on-chain data cannot be split into batches".to_string(),
-                )))
-            }
-            #[cfg(not(target_arch = "wasm32"))]
-            GraphData {
-                input_data: DataSource::DB(data),
-                output_data: _,
-            } => data.fetch_and_format_as_file()?,
+        let mut batches = self.batches(input_shapes)?.collect::<Result<Vec<_>, _>>()?;
+        if batches.is_empty() {
+            batches.push(GraphData::new(DataSource::File(vec![vec![]])));
+        }
+        Ok(batches)
+    }
+
+    /// Lazily splits the input data into batches of the model's `input_shapes`. File and database
+    /// sources are read [BATCH_WINDOW_ROWS] rows at a time, so that only the rows of the batches
+    /// being split (rather than the whole source) are held in memory. CSV and NumPy files are
+    /// opened once and read forward. Going over the batches again (e.g. by calling this again)
+    /// reads the source again.
+    pub fn batches(
+        &self,
+        input_shapes: Vec<Vec<usize>>,
+    ) -> Result<Batches, Box<dyn std::error::Error>> {
+        if let DataSource::OnChain(_) = self.input_data {
+            return Err(Box::new(GraphError::InvalidDims(
+                0,
+                "on-chain data cannot be split into batches".to_string(),
+            )));
+        }
+
+        let input_sizes = input_shapes
+            .iter()
+            .map(|shape| shape.iter().product::<usize>())
+            .collect::<Vec<_>>();
+        if input_sizes.contains(&0) {
+            return Err(Box::new(GraphError::InvalidDims(
+                0,
+                "calibration data length must be evenly divisible by the original input_size"
+                    .to_string(),
+            )));
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let rows = match &self.input_data {
+            DataSource::Csv(source) => Some(source.stream_rows()?),
+            DataSource::Npy(source) => Some(source.stream_rows()?),
+            _ => None,
         };
 
-        for (i, shape) in input_shapes.iter().enumerate() {
-            // ensure the input is evenly divisible by batch_size
-            let input_size = shape.clone().iter().product::<usize>();
-            let input = &iterable[i];
-            if input.len() % input_size != 0 {
-                return Err(Box::new(GraphError::InvalidDims(
-                    0,
-                    "calibration data length must be evenly divisible by the original input_size"
-                        .to_string(),
-                )));
+        Ok(Batches {
+            input_data: self.input_data.clone(),
+            buffered: vec![VecDeque::new(); input_sizes.len()],
+            input_sizes,
+            next_row: 0,
+            #[cfg(not(target_arch = "wasm32"))]
+            rows,
+            exhausted: false,
+        })
+    }
+}
+
+/// The number of rows read at a time from file and database sources when splitting them into batches
+pub const BATCH_WINDOW_ROWS: usize = 1024;
+
+/// The batches of a [GraphData], see [GraphData::batches]
+pub struct Batches {
+    input_data: DataSource,
+    input_sizes: Vec<usize>,
+    /// data read from the source that hasn't been split into batches yet, for each input
+    buffered: Vec<VecDeque<FileSourceInner>>,
+    /// the next row of the source to read
+    next_row: usize,
+    /// the number of inputs and the open rows of sources that are read forward
+    #[cfg(not(target_arch = "wasm32"))]
+    rows: Option<(usize, Rows)>,
+    exhausted: bool,
+}
+
+impl std::fmt::Debug for Batches {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Batches")
+            .field("input_data", &self.input_data)
+            .field("input_sizes", &self.input_sizes)
+            .field("buffered", &self.buffered)
+            .field("next_row", &self.next_row)
+            .field("exhausted", &self.exhausted)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Batches {
+    /// Reads the next [BATCH_WINDOW_ROWS] rows of the input data, None once all the rows have been
+    /// read. Sources that aren't made of rows are read whole as the first window.
+    fn fetch_window(&mut self) -> Result<Option<FileSource>, Box<dyn std::error::Error>> {
+        let (start, len) = (self.next_row, BATCH_WINDOW_ROWS);
+        self.next_row += len;
+        let window = match &self.input_data {
+            DataSource::File(data) if start == 0 => data.clone(),
+            DataSource::Signed(source) if start == 0 => source.data.clone(),
+            DataSource::File(_) | DataSource::Signed(_) => return Ok(None),
+            DataSource::OnChain(_) => {
+                return Err("on-chain data cannot be split into batches".into());
             }
-            let mut batches = vec![];
-            for batch in input.chunks(input_size) {
-                batches.push(batch.to_vec());
+            #[cfg(not(target_arch = "wasm32"))]
+            DataSource::DB(source) => source.fetch_window_as_file(start, len)?,
+            #[cfg(not(target_arch = "wasm32"))]
+            DataSource::Sqlite(source) => source.fetch_window_as_file(start, len)?,
+            #[cfg(not(target_arch = "wasm32"))]
+            DataSource::Csv(_) | DataSource::Npy(_) => {
+                let (num_inputs, rows) = self
+                    .rows
+                    .as_mut()
+                    .ok_or("the rows of the source were not opened")?;
+                tabulate(rows.take(len), *num_inputs)?
             }
-            batched_inputs.push(batches);
-        }
-
-        // now merge all the batches for each input into a vector of batches
-        // first assert each input has the same number of batches
-        let num_batches = if batched_inputs.is_empty() {
-            0
-        } else {
-            let num_batches = batched_inputs[0].len();
-            for input in batched_inputs.iter() {
-                assert_eq!(input.len(), num_batches);
+            #[cfg(not(target_arch = "wasm32"))]
+            DataSource::Parquet(source) => ParquetSource {
+                rows: RowRange::window(&source.rows, start, len),
+                ..source.clone()
             }
-            num_batches
+            .fetch_and_format_as_file()?,
         };
-        // now merge the batches
-        let mut input_batches = vec![];
-        for i in 0..num_batches {
-            let mut batch = vec![];
-            for input in batched_inputs.iter() {
-                batch.push(input[i].clone());
-            }
-            input_batches.push(DataSource::File(batch));
+        // a window without any data is past the last row
+        if window.iter().all(|input| input.is_empty()) {
+            return Ok(None);
         }
+        Ok(Some(window))
+    }
+}
 
-        if input_batches.is_empty() {
-            input_batches.push(DataSource::File(vec![vec![]]));
-        }
+impl Iterator for Batches {
+    type Item = Result<GraphData, Box<dyn std::error::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let full = self
+                .buffered
+                .iter()
+                .zip(&self.input_sizes)
+                .all(|(input, size)| input.len() >= *size);
+            if full && !self.input_sizes.is_empty() {
+                let batch = self
+                    .buffered
+                    .iter_mut()
+                    .zip(&self.input_sizes)
+                    .map(|(input, size)| input.drain(..*size).collect())
+                    .collect();
+                return Some(Ok(GraphData::new(DataSource::File(batch))));
+            }
 
-        // create a new GraphWitness for each batch
-        let batches = input_batches
-            .into_iter()
-            .map(GraphData::new)
-            .collect::<Vec<GraphData>>();
+            if self.exhausted {
+                if self.buffered.iter().all(|input| input.is_empty()) {
+                    return None;
+                }
+                let uneven = self
+                    .buffered
+                    .iter()
+                    .zip(&self.input_sizes)
+                    .any(|(input, size)| input.len() % size != 0);
+                self.buffered.iter_mut().for_each(|input| input.clear());
+                let err = if uneven {
+                    "calibration data length must be evenly divisible by the original input_size"
+                } else {
+                    "all the inputs must have the same number of batches"
+                };
+                return Some(Err(Box::new(GraphError::InvalidDims(0, err.to_string()))));
+            }
 
-        Ok(batches)
+            match self.fetch_window() {
+                Ok(Some(window)) => {
+                    if window.len() < self.input_sizes.len() {
+                        self.exhausted = true;
+                        self.buffered.iter_mut().for_each(|input| input.clear());
+                        return Some(Err(Box::new(GraphError::InvalidDims(
+                            0,
+                            format!(
+                                "the data has {} inputs but the model has {}",
+                                window.len(),
+                                self.input_sizes.len()
+                            ),
+                        ))));
+                    }
+                    for (input, data) in self.buffered.iter_mut().zip(window) {
+                        input.extend(data);
+                    }
+                }
+                Ok(None) => self.exhausted = true,
+                Err(e) => {
+                    self.exhausted = true;
+                    self.buffered.iter_mut().for_each(|input| input.clear());
+                    return Some(Err(e));
+                }
+            }
+        }
     }
 }
 
@@ -559,6 +1566,27 @@ impl ToPyObject for CallsToAccount {
     }
 }
 
+#[cfg(feature = "python-bindings")]
+impl ToPyObject for GraphData {
+    fn to_object(&self, py: Python) -> PyObject {
+        let dict = PyDict::new(py);
+        dict.set_item("input_data", &self.input_data).unwrap();
+        dict.set_item("output_data", &self.output_data).unwrap();
+        dict.to_object(py)
+    }
+}
+
+#[cfg(feature = "python-bindings")]
+impl ToPyObject for SignedDataDomain {
+    fn to_object(&self, py: Python) -> PyObject {
+        let dict = PyDict::new(py);
+        dict.set_item("chain_id", self.chain_id).unwrap();
+        dict.set_item("verifier", &self.verifier).unwrap();
+        dict.set_item("nonce", self.nonce).unwrap();
+        dict.to_object(py)
+    }
+}
+
 #[cfg(feature = "python-bindings")]
 impl ToPyObject for DataSource {
     fn to_object(&self, py: Python) -> PyObject {
@@ -570,6 +1598,14 @@ impl ToPyObject for DataSource {
                 dict.set_item("calls_to_accounts", &source.calls).unwrap();
                 dict.to_object(py)
             }
+            DataSource::Signed(source) => {
+                let dict = PyDict::new(py);
+                dict.set_item("data", &source.data).unwrap();
+                dict.set_item("signature", &source.signature).unwrap();
+                dict.set_item("signer", &source.signer).unwrap();
+                dict.set_item("domain", &source.domain).unwrap();
+                dict.to_object(py)
+            }
             DataSource::DB(source) => {
                 let dict = PyDict::new(py);
                 dict.set_item("host", &source.host).unwrap();
@@ -577,10 +1613,59 @@ impl ToPyObject for DataSource {
                 dict.set_item("query", &source.query).unwrap();
                 dict.to_object(py)
             }
+            DataSource::Sqlite(source) => {
+                let dict = PyDict::new(py);
+                dict.set_item("sqlite", &source.sqlite).unwrap();
+                dict.set_item("query", &source.query).unwrap();
+                dict.to_object(py)
+            }
+            DataSource::Csv(source) => {
+                let dict = PyDict::new(py);
+                dict.set_item("csv", &source.csv).unwrap();
+                dict.set_item("columns", &source.columns).unwrap();
+                dict.set_item("rows", &source.rows).unwrap();
+                dict.set_item("no_headers", source.no_headers).unwrap();
+                dict.set_item("delimiter", source.delimiter).unwrap();
+                dict.to_object(py)
+            }
+            DataSource::Npy(source) => {
+                let dict = PyDict::new(py);
+                dict.set_item("npy", &source.npy).unwrap();
+                dict.set_item("arrays", &source.arrays).unwrap();
+                dict.set_item("rows", &source.rows).unwrap();
+                dict.to_object(py)
+            }
+            DataSource::Parquet(source) => {
+                let dict = PyDict::new(py);
+                dict.set_item("parquet", &source.parquet).unwrap();
+                dict.set_item("columns", &source.columns).unwrap();
+                dict.set_item("rows", &source.rows).unwrap();
+                dict.to_object(py)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "python-bindings")]
+impl ToPyObject for Column {
+    fn to_object(&self, py: Python) -> PyObject {
+        match self {
+            Column::Index(i) => i.to_object(py),
+            Column::Name(name) => name.to_object(py),
         }
     }
 }
 
+#[cfg(feature = "python-bindings")]
+impl ToPyObject for RowRange {
+    fn to_object(&self, py: Python) -> PyObject {
+        let dict = PyDict::new(py);
+        dict.set_item("start", self.start).unwrap();
+        dict.set_item("end", self.end).unwrap();
+        dict.to_object(py)
+    }
+}
+
 #[cfg(feature = "python-bindings")]
 use crate::pfsys::field_to_vecu64_montgomery;
 
@@ -600,9 +1685,21 @@ impl Serialize for GraphData {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("GraphData", 4)?;
+        let mut state = serializer.serialize_struct("GraphData", 7)?;
         state.serialize_field("input_data", &self.input_data)?;
         state.serialize_field("output_data", &self.output_data)?;
+        if let Some(recipient) = &self.elgamal_recipient {
+            state.serialize_field("elgamal_recipient", recipient)?;
+        }
+        if let Some(salt) = &self.hash_salt {
+            state.serialize_field("hash_salt", salt)?;
+        }
+        if let Some(proofs) = &self.merkle_proofs {
+            state.serialize_field("merkle_proofs", proofs)?;
+        }
+        if let Some(signatures) = &self.signatures {
+            state.serialize_field("signatures", signatures)?;
+        }
         state.end()
     }
 }
@@ -650,6 +1747,445 @@ mod tests {
         assert_eq!(graph_input3, file);
     }
 
+    #[test]
+    fn test_elgamal_recipient_serialization_round_trip() {
+        use halo2curves::group::cofactor::CofactorCurveAffine;
+
+        let mut file = GraphData::new(DataSource::from(vec![vec![0.5]]));
+        file.elgamal_recipient = Some(ElGamalRecipient::new(
+            halo2curves::bn256::G1Affine::generator(),
+        ));
+
+        let serialized = serde_json::to_string(&file).unwrap();
+        let deserialized = serde_json::from_str::<GraphData>(&serialized).unwrap();
+        assert_eq!(deserialized, file);
+    }
+
+    #[test]
+    fn test_on_chain_block_instances() {
+        let block = OnChainBlock {
+            number: 7,
+            hash: format!("0x{}{}", "00".repeat(15) + "01", "00".repeat(15) + "02"),
+        };
+        assert_eq!(
+            block.instances().unwrap(),
+            [Fp::from(7), Fp::from(1), Fp::from(2)]
+        );
+
+        let short = OnChainBlock {
+            number: 7,
+            hash: "0x01".to_string(),
+        };
+        assert!(short.instances().is_err());
+    }
+
+    #[test]
+    fn test_hash_salt_serialization_round_trip() {
+        let mut file = GraphData::new(DataSource::from(vec![vec![0.5]]));
+        file.hash_salt = Some(Fp::from(42));
+
+        let serialized = serde_json::to_string(&file).unwrap();
+        let deserialized = serde_json::from_str::<GraphData>(&serialized).unwrap();
+        assert_eq!(deserialized, file);
+    }
+
+    #[test]
+    fn test_signed_source_serialization_round_trip() {
+        let source = DataSource::from(SignedSource::new(
+            vec![vec![FileSourceInner::Float(0.5)]],
+            "ab".repeat(65),
+            "f39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string(),
+            SignedDataDomain {
+                chain_id: 31337,
+                verifier: "5fbdb2315678afecb367f032d93f642f64180aa3".to_string(),
+                nonce: 1,
+            },
+        ));
+
+        let serialized = serde_json::to_string(&source).unwrap();
+        let expect = serde_json::from_str::<DataSource>(&serialized)
+            .map_err(|e| e.to_string())
+            .unwrap();
+
+        assert_eq!(expect, source);
+    }
+
+    #[test]
+    fn test_file_format_source_deserialization() {
+        const JSON: &str = r#"{"csv":"data.csv","columns":[["a",1]],"rows":{"start":1,"end":3}}"#;
+        let source = serde_json::from_str::<DataSource>(JSON).unwrap();
+        assert_eq!(
+            source,
+            DataSource::Csv(CsvSource {
+                csv: "data.csv".into(),
+                columns: vec![vec![Column::Name("a".to_string()), Column::Index(1)]],
+                rows: Some(RowRange {
+                    start: 1,
+                    end: Some(3)
+                }),
+                no_headers: false,
+                delimiter: None,
+            })
+        );
+
+        const NPZ_JSON: &str = r#"{"npy":"data.npz","arrays":["x"]}"#;
+        let source = serde_json::from_str::<DataSource>(NPZ_JSON).unwrap();
+        assert!(matches!(source, DataSource::Npy(_)));
+
+        const PARQUET_JSON: &str = r#"{"parquet":"data.parquet"}"#;
+        let source = serde_json::from_str::<DataSource>(PARQUET_JSON).unwrap();
+        assert_eq!(
+            source,
+            DataSource::Parquet(ParquetSource::new("data.parquet".into()))
+        );
+    }
+
+    #[test]
+    fn test_csv_source() {
+        let mut path = std::env::temp_dir();
+        path.push("ezkl_test_csv_source.csv");
+        std::fs::write(&path, "a,b,c\n1,2.5,true\n3,-4,false\n5,6,true\n").unwrap();
+
+        let mut source = CsvSource::new(path.clone());
+        source.columns = vec![vec![Column::Name("b".to_string())], vec![Column::Index(2)]];
+        source.rows = Some(RowRange {
+            start: 1,
+            end: None,
+        });
+
+        let data = source.fetch_and_format_as_file().unwrap();
+        assert_eq!(
+            data,
+            vec![
+                vec![FileSourceInner::Float(-4.0), FileSourceInner::Float(6.0)],
+                vec![FileSourceInner::Bool(false), FileSourceInner::Bool(true)],
+            ]
+        );
+
+        // all the columns of all the rows make up a single input
+        let data = CsvSource::new(path.clone())
+            .fetch_and_format_as_file()
+            .unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0].len(), 9);
+
+        source.columns = vec![vec![Column::Name("d".to_string())]];
+        assert!(source.fetch_and_format_as_file().is_err());
+    }
+
+    /// Writes a little endian f4 npy file of the given shape
+    fn write_npy(
+        name: &str,
+        shape: &str,
+        values: impl IntoIterator<Item = f32>,
+    ) -> std::path::PathBuf {
+        let mut header = format!(
+            "{{'descr': '<f4', 'fortran_order': False, 'shape': {}, }}",
+            shape
+        );
+        while (10 + header.len() + 1) % 64 != 0 {
+            header.push(' ');
+        }
+        header.push('\n');
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend((header.len() as u16).to_le_bytes());
+        bytes.extend(header.as_bytes());
+        for x in values {
+            bytes.extend(x.to_le_bytes());
+        }
+
+        let mut path = std::env::temp_dir();
+        path.push(name);
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_npy_source() {
+        let path = write_npy(
+            "ezkl_test_npy_source.npy",
+            "(3, 2)",
+            [1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+        );
+
+        let mut source = NpySource::new(path);
+        source.rows = Some(RowRange {
+            start: 1,
+            end: Some(2),
+        });
+        let data = source.fetch_and_format_as_file().unwrap();
+        assert_eq!(
+            data,
+            vec![vec![
+                FileSourceInner::Float(3.0),
+                FileSourceInner::Float(4.0)
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_parquet_source() {
+        use parquet::data_type::{BoolType, DoubleType, Int64Type};
+        use parquet::file::properties::WriterProperties;
+        use parquet::file::writer::SerializedFileWriter;
+        use parquet::schema::parser::parse_message_type;
+        use std::sync::Arc;
+
+        let mut path = std::env::temp_dir();
+        path.push("ezkl_test_parquet_source.parquet");
+        let schema = parse_message_type(
+            "message schema { REQUIRED INT64 a; REQUIRED DOUBLE b; REQUIRED BOOLEAN c; }",
+        )
+        .unwrap();
+        let mut writer = SerializedFileWriter::new(
+            std::fs::File::create(&path).unwrap(),
+            Arc::new(schema),
+            Arc::new(WriterProperties::builder().build()),
+        )
+        .unwrap();
+        // two row groups, of 2 and 1 rows
+        for (a, b, c) in [
+            (vec![1i64, 3], vec![2.5f64, -4.0], vec![true, false]),
+            (vec![5], vec![6.0], vec![true]),
+        ] {
+            let mut group = writer.next_row_group().unwrap();
+            let mut column = group.next_column().unwrap().unwrap();
+            column
+                .typed::<Int64Type>()
+                .write_batch(&a, None, None)
+                .unwrap();
+            column.close().unwrap();
+            let mut column = group.next_column().unwrap().unwrap();
+            column
+                .typed::<DoubleType>()
+                .write_batch(&b, None, None)
+                .unwrap();
+            column.close().unwrap();
+            let mut column = group.next_column().unwrap().unwrap();
+            column
+                .typed::<BoolType>()
+                .write_batch(&c, None, None)
+                .unwrap();
+            column.close().unwrap();
+            group.close().unwrap();
+        }
+        writer.close().unwrap();
+
+        let mut source = ParquetSource::new(path.clone());
+        source.columns = vec![vec![Column::Name("c".to_string())], vec![Column::Index(1)]];
+        source.rows = Some(RowRange {
+            start: 1,
+            end: None,
+        });
+        let data = source.fetch_and_format_as_file().unwrap();
+        assert_eq!(
+            data,
+            vec![
+                vec![FileSourceInner::Bool(false), FileSourceInner::Bool(true)],
+                vec![FileSourceInner::Float(-4.0), FileSourceInner::Float(6.0)],
+            ]
+        );
+
+        // the first row group is skipped entirely
+        source.columns = vec![vec![Column::Name("a".to_string())]];
+        source.rows = Some(RowRange {
+            start: 2,
+            end: Some(3),
+        });
+        let data = source.fetch_and_format_as_file().unwrap();
+        assert_eq!(data, vec![vec![FileSourceInner::Float(5.0)]]);
+
+        // all the columns of all the rows make up a single input
+        let data = ParquetSource::new(path.clone())
+            .fetch_and_format_as_file()
+            .unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0].len(), 9);
+
+        source.columns = vec![vec![Column::Name("d".to_string())]];
+        assert!(source.fetch_and_format_as_file().is_err());
+    }
+
+    #[test]
+    fn test_sqlite_source() {
+        let mut path = std::env::temp_dir();
+        path.push("ezkl_test_sqlite_source.db");
+        let _ = std::fs::remove_file(&path);
+        let connection = rusqlite::Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE samples (id INTEGER, x REAL, y REAL, label INTEGER);
+                 INSERT INTO samples VALUES (1, 0.5, 1.5, 0), (2, -2.0, 3, 1), (3, 4.25, 5, 1);",
+            )
+            .unwrap();
+        drop(connection);
+
+        let mut source = SqliteSource::new(
+            path.clone(),
+            "SELECT x, y, label FROM samples WHERE id >= ? ORDER BY id".to_string(),
+            vec![SqlParam::Int(2)],
+        );
+        source.columns = vec![
+            vec![Column::Name("x".to_string()), Column::Name("y".to_string())],
+            vec![Column::Index(2)],
+        ];
+
+        let data = source.fetch_and_format_as_file().unwrap();
+        assert_eq!(
+            data,
+            vec![
+                vec![
+                    FileSourceInner::Float(-2.0),
+                    FileSourceInner::Float(3.0),
+                    FileSourceInner::Float(4.25),
+                    FileSourceInner::Float(5.0),
+                ],
+                vec![FileSourceInner::Float(1.0), FileSourceInner::Float(1.0)],
+            ]
+        );
+
+        // every row is a batch
+        let batches = GraphData::new(DataSource::Sqlite(source.clone()))
+            .split_into_batches(vec![vec![2], vec![1]])
+            .unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(
+            batches[1].input_data,
+            DataSource::File(vec![
+                vec![FileSourceInner::Float(4.25), FileSourceInner::Float(5.0)],
+                vec![FileSourceInner::Float(1.0)],
+            ])
+        );
+
+        // the rows of queries read in windows must be ordered
+        let unordered = |query: &str| {
+            let source = SqliteSource {
+                query: query.to_string(),
+                ..source.clone()
+            };
+            GraphData::new(DataSource::Sqlite(source))
+                .split_into_batches(vec![vec![2], vec![1]])
+                .is_err()
+        };
+        assert!(unordered("SELECT x, y, label FROM samples WHERE id >= ?"));
+        assert!(unordered(
+            "SELECT * FROM (SELECT x, y, label FROM samples WHERE id >= ? ORDER BY id) AS s"
+        ));
+        assert!(!unordered(
+            "select x, y, label from samples where id >= ?\norder  by id desc"
+        ));
+
+        // errors are propagated rather than panicking
+        source.query = "SELECT z FROM samples".to_string();
+        assert!(source.fetch_and_format_as_file().is_err());
+        source.query = "SELECT NULL AS x".to_string();
+        source.columns = vec![];
+        assert!(source.fetch_and_format_as_file().is_err());
+    }
+
+    #[test]
+    fn test_batches() {
+        let data = GraphData::new(DataSource::from(vec![
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ]));
+
+        let batches = data
+            .batches(vec![vec![1, 2], vec![1]])
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches.len(), 3);
+        assert_eq!(
+            batches,
+            data.split_into_batches(vec![vec![1, 2], vec![1]]).unwrap()
+        );
+        assert_eq!(
+            batches.last().unwrap().input_data,
+            DataSource::from(vec![vec![5.0, 6.0], vec![9.0]])
+        );
+
+        let split = |shapes| {
+            data.batches(shapes)
+                .and_then(|batches| batches.collect::<Result<Vec<_>, _>>())
+        };
+        // the inputs don't have the same number of batches
+        assert!(split(vec![vec![3], vec![1]]).is_err());
+        // the data isn't evenly divisible by the input size
+        assert!(split(vec![vec![4], vec![1]]).is_err());
+        // the data has fewer inputs than the model
+        assert!(split(vec![vec![1], vec![1], vec![1]]).is_err());
+    }
+
+    #[test]
+    fn test_batches_stream_windows() {
+        // more rows than are read at a time, so batches straddle the windows
+        let num_rows = 2 * BATCH_WINDOW_ROWS + 7;
+        let mut csv = "a,b\n".to_string();
+        for i in 0..num_rows {
+            csv.push_str(&format!("{},{}\n", i, 2 * i));
+        }
+        let mut path = std::env::temp_dir();
+        path.push("ezkl_test_batches_stream_windows.csv");
+        std::fs::write(&path, csv).unwrap();
+
+        let mut source = CsvSource::new(path);
+        source.columns = vec![vec![Column::Name("a".to_string())]];
+        let data = GraphData::new(DataSource::Csv(source.clone()));
+
+        let batches = data
+            .batches(vec![vec![3]])
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches.len(), num_rows / 3);
+        for (i, batch) in batches.iter().enumerate() {
+            let expected = (3 * i..3 * i + 3).map(|x| x as f64).collect::<Vec<_>>();
+            assert_eq!(batch.input_data, DataSource::from(vec![expected]));
+        }
+
+        // the selected rows are streamed too
+        source.columns = vec![
+            vec![Column::Name("a".to_string())],
+            vec![Column::Name("b".to_string())],
+        ];
+        source.rows = Some(RowRange {
+            start: BATCH_WINDOW_ROWS - 1,
+            end: Some(BATCH_WINDOW_ROWS + 1),
+        });
+        let batches = GraphData::new(DataSource::Csv(source))
+            .split_into_batches(vec![vec![1], vec![1]])
+            .unwrap();
+        let first = (BATCH_WINDOW_ROWS - 1) as f64;
+        assert_eq!(
+            batches
+                .iter()
+                .map(|batch| batch.input_data.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                DataSource::from(vec![vec![first], vec![2.0 * first]]),
+                DataSource::from(vec![vec![first + 1.0], vec![2.0 * first + 2.0]]),
+            ]
+        );
+
+        // npy files are read forward too, a row at a time
+        let path = write_npy(
+            "ezkl_test_batches_stream_windows.npy",
+            &format!("({}, 2)", num_rows),
+            (0..2 * num_rows).map(|x| x as f32),
+        );
+        let batches = GraphData::new(DataSource::Npy(NpySource::new(path)))
+            .batches(vec![vec![6]])
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches.len(), num_rows / 3);
+        for (i, batch) in batches.iter().enumerate() {
+            let expected = (6 * i..6 * i + 6).map(|x| x as f64).collect::<Vec<_>>();
+            assert_eq!(batch.input_data, DataSource::from(vec![expected]));
+        }
+    }
+
     //  test for the compatibility with the serialized elements from the mclbn256 library
     #[test]
     fn test_python_compat() {
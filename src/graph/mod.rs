@@ -6,6 +6,8 @@ pub mod model;
 pub mod modules;
 /// Inner elements of a computational graph that represent a single operation / constraints.
 pub mod node;
+/// Declarative preprocessing of a computational graph's inputs.
+pub mod preprocessing;
 /// Helper functions
 pub mod utilities;
 /// Representations of a computational graph's variables.
@@ -17,24 +19,29 @@ use halo2_proofs::poly::kzg::commitment::ParamsKZG;
 pub use input::DataSource;
 use itertools::Itertools;
 
+use self::input::{FileSource, GraphData, OnChainBlock, ON_CHAIN_BLOCK_INSTANCES};
 #[cfg(not(target_arch = "wasm32"))]
-use self::input::OnChainSource;
-use self::input::{FileSource, GraphData};
+use self::input::{OnChainSource, SqlSource};
 use self::modules::{
     GraphModules, ModuleConfigs, ModuleForwardResult, ModuleSettings, ModuleSizes,
 };
+use self::preprocessing::{preprocess, InputPreprocessing};
 use crate::circuit::lookup::LookupOp;
+use crate::circuit::modules::eddsa::EddsaSignature;
+use crate::circuit::modules::elgamal::ElGamalRecipient;
+use crate::circuit::modules::merkle::MerkleProof;
 use crate::circuit::modules::ModulePlanner;
 use crate::circuit::table::{Table, RANGE_MULTIPLIER, RESERVED_BLINDING_ROWS_PAD};
 use crate::circuit::{CheckMode, InputType};
+use crate::pfsys::container::{hash_bytes, ContainerError, SettingsDigest, SettingsHash};
 use crate::tensor::{Tensor, ValTensor};
 use crate::RunArgs;
 use halo2_proofs::{
     circuit::Layouter,
-    plonk::{Circuit, ConstraintSystem, Error as PlonkError},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error as PlonkError},
 };
 use halo2curves::bn256::{self, Bn256, Fr as Fp, G1Affine};
-use halo2curves::ff::PrimeField;
+use halo2curves::ff::{Field, PrimeField};
 use log::{debug, error, info, trace, warn};
 pub use model::*;
 pub use node::*;
@@ -140,6 +147,9 @@ pub struct GraphWitness {
     pub max_lookup_inputs: i128,
     /// max lookup input
     pub min_lookup_inputs: i128,
+    /// The block on-chain inputs were read at
+    #[serde(default)]
+    pub on_chain_block: Option<OnChainBlock>,
 }
 
 impl GraphWitness {
@@ -153,6 +163,7 @@ impl GraphWitness {
             processed_outputs: None,
             max_lookup_inputs: 0,
             min_lookup_inputs: 0,
+            on_chain_block: None,
         }
     }
 
@@ -177,6 +188,18 @@ impl GraphWitness {
         commitments
     }
 
+    /// The salt the salted hashes were computed with, the same for inputs, params and outputs
+    pub fn hash_salt(&self) -> Option<Fp> {
+        [
+            &self.processed_inputs,
+            &self.processed_params,
+            &self.processed_outputs,
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|processed| processed.salt)
+    }
+
     /// Export the ezkl witness as json
     pub fn as_json(&self) -> Result<String, Box<dyn std::error::Error>> {
         let serialized = match serde_json::to_string(&self) {
@@ -252,6 +275,15 @@ impl ToPyObject for GraphWitness {
             if let Some(processed_inputs_poseidon_hash) = &processed_inputs.poseidon_hash {
                 insert_poseidon_hash_pydict(&dict_inputs, &processed_inputs_poseidon_hash);
             }
+            if let Some(processed_inputs_salt) = &processed_inputs.salt {
+                insert_salt_pydict(&dict_inputs, processed_inputs_salt);
+            }
+            if let Some(processed_inputs_merkle_root) = &processed_inputs.merkle_root {
+                insert_merkle_root_pydict(&dict_inputs, processed_inputs_merkle_root);
+            }
+            if let Some(processed_inputs_public_keys) = &processed_inputs.public_keys {
+                insert_public_keys_pydict(&dict_inputs, processed_inputs_public_keys);
+            }
             if let Some(processed_inputs_elgamal) = &processed_inputs.elgamal {
                 insert_elgamal_results_pydict(py, dict_inputs, processed_inputs_elgamal);
             }
@@ -266,6 +298,12 @@ impl ToPyObject for GraphWitness {
             if let Some(processed_params_poseidon_hash) = &processed_params.poseidon_hash {
                 insert_poseidon_hash_pydict(dict_params, &processed_params_poseidon_hash);
             }
+            if let Some(processed_params_salt) = &processed_params.salt {
+                insert_salt_pydict(dict_params, processed_params_salt);
+            }
+            if let Some(processed_params_merkle_root) = &processed_params.merkle_root {
+                insert_merkle_root_pydict(dict_params, processed_params_merkle_root);
+            }
             if let Some(processed_params_elgamal) = &processed_params.elgamal {
                 insert_elgamal_results_pydict(py, dict_params, processed_params_elgamal);
             }
@@ -280,6 +318,12 @@ impl ToPyObject for GraphWitness {
             if let Some(processed_outputs_poseidon_hash) = &processed_outputs.poseidon_hash {
                 insert_poseidon_hash_pydict(dict_outputs, &processed_outputs_poseidon_hash);
             }
+            if let Some(processed_outputs_salt) = &processed_outputs.salt {
+                insert_salt_pydict(dict_outputs, processed_outputs_salt);
+            }
+            if let Some(processed_outputs_merkle_root) = &processed_outputs.merkle_root {
+                insert_merkle_root_pydict(dict_outputs, processed_outputs_merkle_root);
+            }
             if let Some(processed_outputs_elgamal) = &processed_outputs.elgamal {
                 insert_elgamal_results_pydict(py, dict_outputs, processed_outputs_elgamal);
             }
@@ -290,6 +334,15 @@ impl ToPyObject for GraphWitness {
             dict.set_item("processed_outputs", dict_outputs).unwrap();
         }
 
+        if let Some(on_chain_block) = &self.on_chain_block {
+            let dict_block = PyDict::new(py);
+            dict_block
+                .set_item("number", on_chain_block.number)
+                .unwrap();
+            dict_block.set_item("hash", &on_chain_block.hash).unwrap();
+            dict.set_item("on_chain_block", dict_block).unwrap();
+        }
+
         dict.to_object(py)
     }
 }
@@ -303,6 +356,36 @@ fn insert_poseidon_hash_pydict(pydict: &PyDict, poseidon_hash: &Vec<Fp>) {
     pydict.set_item("poseidon_hash", poseidon_hash).unwrap();
 }
 
+#[cfg(feature = "python-bindings")]
+fn insert_salt_pydict(pydict: &PyDict, salt: &Fp) {
+    pydict
+        .set_item("salt", field_to_vecu64_montgomery(salt))
+        .unwrap();
+}
+
+#[cfg(feature = "python-bindings")]
+fn insert_merkle_root_pydict(pydict: &PyDict, merkle_root: &[Fp]) {
+    let merkle_root: Vec<[u64; 4]> = merkle_root.iter().map(field_to_vecu64_montgomery).collect();
+    pydict.set_item("merkle_root", merkle_root).unwrap();
+}
+
+#[cfg(feature = "python-bindings")]
+fn insert_public_keys_pydict(
+    pydict: &PyDict,
+    public_keys: &[crate::circuit::modules::eddsa::EdwardsPoint],
+) {
+    let public_keys: Vec<[[u64; 4]; 2]> = public_keys
+        .iter()
+        .map(|pk| {
+            [
+                field_to_vecu64_montgomery(&pk.x),
+                field_to_vecu64_montgomery(&pk.y),
+            ]
+        })
+        .collect();
+    pydict.set_item("public_keys", public_keys).unwrap();
+}
+
 #[cfg(feature = "python-bindings")]
 fn insert_kzg_commit_pydict(pydict: &PyDict, commits: &Vec<Vec<G1Affine>>) {
     use crate::python::PyG1Affine;
@@ -342,7 +425,11 @@ fn insert_elgamal_results_pydict(py: Python, pydict: &PyDict, elgamal_results: &
         .set_item("encrypted_messages", encrypted_messages)
         .unwrap();
 
-    let variables: crate::python::PyElGamalVariables = elgamal_results.variables.clone().into();
+    let variables: Vec<crate::python::PyElGamalVariables> = elgamal_results
+        .variables
+        .iter()
+        .map(|v| v.clone().into())
+        .collect();
 
     results_dict.set_item("variables", variables).unwrap();
 
@@ -378,6 +465,12 @@ pub struct GraphSettings {
     pub version: String,
     /// num blinding factors
     pub num_blinding_factors: Option<usize>,
+    /// preprocessing applied to each input before it is quantized
+    #[serde(default)]
+    pub preprocessing: Vec<InputPreprocessing>,
+    /// the recipient values with encrypted visibility are encrypted to
+    #[serde(default)]
+    pub elgamal_recipient: Option<ElGamalRecipient>,
 }
 
 impl GraphSettings {
@@ -389,6 +482,9 @@ impl GraphSettings {
             .map(|x| x.iter().product())
             .collect();
         instances.extend(self.module_sizes.num_instances());
+        if self.run_args.commit_on_chain_block {
+            instances.push(ON_CHAIN_BLOCK_INSTANCES);
+        }
 
         instances
     }
@@ -448,11 +544,83 @@ impl GraphSettings {
     }
 }
 
+/// Version of the subset of [GraphSettings] hashed into a [SettingsHash]. Bump it whenever
+/// [SettingsDigestV1] stops being the set of fields which determine the circuit.
+const SETTINGS_DIGEST_VERSION: u8 = 1;
+
+/// The settings that determine the circuit (and hence its keys). Fields like the ezkl version,
+/// check mode, multi-open scheme, input preprocessing or elgamal recipient are left out as the
+/// same keys are used whatever their value, and new settings aren't hashed unless added here.
+#[derive(Serialize)]
+struct SettingsDigestV1<'a> {
+    tolerance: crate::circuit::Tolerance,
+    input_scale: crate::Scale,
+    param_scale: crate::Scale,
+    scale_rebase_multiplier: u32,
+    lookup_range: (i128, i128),
+    logrows: u32,
+    num_inner_cols: usize,
+    variables: &'a [(String, usize)],
+    input_visibility: &'a Visibility,
+    output_visibility: &'a Visibility,
+    param_visibility: &'a Visibility,
+    input_visibilities: &'a [Visibility],
+    output_visibilities: &'a [Visibility],
+    param_visibilities: &'a [Visibility],
+    commitment: crate::pfsys::Commitments,
+    commit_on_chain_block: bool,
+    num_rows: usize,
+    total_assignments: usize,
+    total_const_size: usize,
+    model_instance_shapes: &'a [Vec<usize>],
+    model_output_scales: &'a [crate::Scale],
+    model_input_scales: &'a [crate::Scale],
+    module_sizes: &'a ModuleSizes,
+    required_lookups: &'a [LookupOp],
+}
+
+impl SettingsDigest for GraphSettings {
+    fn settings_hash(&self) -> Result<SettingsHash, ContainerError> {
+        let run_args = &self.run_args;
+        let digest = SettingsDigestV1 {
+            tolerance: run_args.tolerance,
+            input_scale: run_args.input_scale,
+            param_scale: run_args.param_scale,
+            scale_rebase_multiplier: run_args.scale_rebase_multiplier,
+            lookup_range: run_args.lookup_range,
+            logrows: run_args.logrows,
+            num_inner_cols: run_args.num_inner_cols,
+            variables: &run_args.variables,
+            input_visibility: &run_args.input_visibility,
+            output_visibility: &run_args.output_visibility,
+            param_visibility: &run_args.param_visibility,
+            input_visibilities: &run_args.input_visibilities,
+            output_visibilities: &run_args.output_visibilities,
+            param_visibilities: &run_args.param_visibilities,
+            commitment: run_args.commitment,
+            commit_on_chain_block: run_args.commit_on_chain_block,
+            num_rows: self.num_rows,
+            total_assignments: self.total_assignments,
+            total_const_size: self.total_const_size,
+            model_instance_shapes: &self.model_instance_shapes,
+            model_output_scales: &self.model_output_scales,
+            model_input_scales: &self.model_input_scales,
+            module_sizes: &self.module_sizes,
+            required_lookups: &self.required_lookups,
+        };
+        let mut encoded = vec![SETTINGS_DIGEST_VERSION];
+        bincode::serialize_into(&mut encoded, &digest)?;
+        Ok(hash_bytes(&encoded))
+    }
+}
+
 /// Configuration for a computational graph / model loaded from a `.onnx` file.
 #[derive(Clone, Debug)]
 pub struct GraphConfig {
     model_config: ModelConfig,
     module_configs: ModuleConfigs,
+    /// the column the committed on-chain block is copied into from the instances
+    block_column: Option<Column<Advice>>,
 }
 
 /// Defines the circuit for a computational graph / model loaded from a `.onnx` file.
@@ -473,6 +641,15 @@ pub struct GraphCircuit {
     pub graph_witness: GraphWitness,
     /// The settings of the model's modules.
     pub module_settings: ModuleSettings,
+    /// The salt for salted hashes, supplied with the input data (a fresh one is drawn if none).
+    #[serde(default)]
+    pub hash_salt: Option<Fp>,
+    /// The paths merkle inputs are opened along, supplied with the input data.
+    #[serde(default)]
+    pub merkle_proofs: Option<Vec<MerkleProof>>,
+    /// The signatures over signed inputs, supplied with the input data.
+    #[serde(default)]
+    pub signatures: Option<Vec<EddsaSignature>>,
 }
 
 impl GraphCircuit {
@@ -569,18 +746,11 @@ impl GraphCircuit {
         let module_settings = ModuleSettings::default();
         let mut settings = model.gen_params(run_args, CheckMode::UNSAFE)?;
 
-        let mut num_params = 0;
-        if !model.const_shapes().is_empty() {
-            for shape in model.const_shapes() {
-                num_params += shape.iter().product::<usize>();
-            }
-        }
-
         let sizes = GraphModules::num_constraints_and_instances(
             model.graph.input_shapes(),
-            vec![vec![num_params]],
+            model.const_shapes(),
             model.graph.output_shapes(),
-            VarVisibility::from_args(run_args).unwrap(),
+            VarVisibility::from_args(run_args)?,
         );
 
         // number of instances used by modules
@@ -598,6 +768,9 @@ impl GraphCircuit {
             core,
             graph_witness: GraphWitness::new(inputs, vec![]),
             module_settings,
+            hash_salt: None,
+            merkle_proofs: None,
+            signatures: None,
         })
     }
 
@@ -628,6 +801,9 @@ impl GraphCircuit {
             core,
             graph_witness: GraphWitness::new(inputs, vec![]),
             module_settings,
+            hash_salt: None,
+            merkle_proofs: None,
+            signatures: None,
         })
     }
 
@@ -649,12 +825,12 @@ impl GraphCircuit {
         data: &GraphWitness,
     ) -> Result<Vec<Fp>, Box<dyn std::error::Error>> {
         // quantize the supplied data using the provided scale.
-        // the ordering here is important, we want the inputs to come before the outputs
-        // as they are configured in that order as Column<Instances>
+        // the ordering here is important, the instances have to be in the order they are laid out
+        // in the Column<Instances>: those of the input and params modules, then the public inputs and
+        // outputs of the model, then those of the output module, then the committed on-chain block
+        let visibility = VarVisibility::from_args(&self.settings().run_args)?;
         let mut public_inputs: Vec<Fp> = vec![];
-        if self.settings().run_args.input_visibility.is_public() {
-            public_inputs.extend(self.graph_witness.inputs.clone().into_iter().flatten())
-        } else if let Some(processed_inputs) = &data.processed_inputs {
+        if let Some(processed_inputs) = &data.processed_inputs {
             public_inputs.extend(processed_inputs.get_instances().into_iter().flatten());
         }
 
@@ -662,12 +838,29 @@ impl GraphCircuit {
             public_inputs.extend(processed_params.get_instances().into_iter().flatten());
         }
 
-        if self.settings().run_args.output_visibility.is_public() {
-            public_inputs.extend(self.graph_witness.outputs.clone().into_iter().flatten());
-        } else if let Some(processed_outputs) = &data.processed_outputs {
+        for (i, input) in self.graph_witness.inputs.iter().enumerate() {
+            if visibility.input_at(i).is_public() {
+                public_inputs.extend(input.iter().copied());
+            }
+        }
+
+        for (i, output) in self.graph_witness.outputs.iter().enumerate() {
+            if visibility.output_at(i).is_public() {
+                public_inputs.extend(output.iter().copied());
+            }
+        }
+
+        if let Some(processed_outputs) = &data.processed_outputs {
             public_inputs.extend(processed_outputs.get_instances().into_iter().flatten());
         }
 
+        if self.settings().run_args.commit_on_chain_block {
+            let block = data.on_chain_block.as_ref().ok_or(
+                "the circuit commits the on-chain block but the witness wasn't generated from on-chain inputs",
+            )?;
+            public_inputs.extend(block.instances()?);
+        }
+
         debug!("public inputs: {:?}", public_inputs);
 
         Ok(public_inputs)
@@ -679,12 +872,25 @@ impl GraphCircuit {
         &mut self,
         data: &GraphData,
     ) -> Result<Vec<Tensor<Fp>>, Box<dyn std::error::Error>> {
+        self.load_module_data(data);
         let shapes = self.model().graph.input_shapes();
         let scales = self.model().graph.get_input_scales();
         let input_types = self.model().graph.get_input_types()?;
         self.process_data_source(&data.input_data, shapes, scales, input_types)
     }
 
+    /// Loads what the modules need from the input data besides the inputs themselves (the salt,
+    /// merkle proofs, signatures and recipient)
+    pub fn load_module_data(&mut self, data: &GraphData) {
+        // a recipient supplied with the input data takes precedence over the one in the settings
+        if let Some(recipient) = &data.elgamal_recipient {
+            self.settings_mut().elgamal_recipient = Some(recipient.clone());
+        }
+        self.hash_salt = data.hash_salt;
+        self.merkle_proofs = data.merkle_proofs.clone();
+        self.signatures = data.signatures.clone();
+    }
+
     ///
     pub fn load_graph_from_file_exclusively(
         &mut self,
@@ -709,6 +915,7 @@ impl GraphCircuit {
         &mut self,
         data: &GraphData,
     ) -> Result<Vec<Tensor<Fp>>, Box<dyn std::error::Error>> {
+        self.load_module_data(data);
         let shapes = self.model().graph.input_shapes();
         let scales = self.model().graph.get_input_scales();
         let input_types = self.model().graph.get_input_types()?;
@@ -734,6 +941,10 @@ impl GraphCircuit {
             DataSource::OnChain(_) => {
                 Err("Cannot use on-chain data source as input for this method.".into())
             }
+            // the signature is checked on-chain
+            DataSource::Signed(source) => {
+                self.load_file_data(&source.data, &shapes, scales, input_types)
+            }
         }
     }
 
@@ -758,10 +969,33 @@ impl GraphCircuit {
             DataSource::File(file_data) => {
                 self.load_file_data(file_data, &shapes, scales, input_types)
             }
+            DataSource::Signed(source) => {
+                let inputs = self.load_file_data(&source.data, &shapes, scales, input_types)?;
+                // fail early rather than generating a proof the attestation contract will reject
+                let quantized: Vec<Fp> = inputs.iter().flat_map(|t| t.iter().cloned()).collect();
+                crate::eth::check_data_signature(&quantized, source)?;
+                Ok(inputs)
+            }
             DataSource::DB(pg) => {
                 let data = pg.fetch_and_format_as_file()?;
                 self.load_file_data(&data, &shapes, scales, input_types)
             }
+            DataSource::Sqlite(source) => {
+                let data = source.fetch_and_format_as_file()?;
+                self.load_file_data(&data, &shapes, scales, input_types)
+            }
+            DataSource::Csv(source) => {
+                let data = source.fetch_and_format_as_file()?;
+                self.load_file_data(&data, &shapes, scales, input_types)
+            }
+            DataSource::Npy(source) => {
+                let data = source.fetch_and_format_as_file()?;
+                self.load_file_data(&data, &shapes, scales, input_types)
+            }
+            DataSource::Parquet(source) => {
+                let data = source.fetch_and_format_as_file()?;
+                self.load_file_data(&data, &shapes, scales, input_types)
+            }
         }
     }
 
@@ -773,9 +1007,13 @@ impl GraphCircuit {
         shapes: &Vec<Vec<usize>>,
         scales: Vec<crate::Scale>,
     ) -> Result<Vec<Tensor<Fp>>, Box<dyn std::error::Error>> {
-        use crate::eth::{evm_quantize, read_on_chain_inputs, setup_eth_backend};
+        use crate::eth::{
+            evm_quantize, on_chain_block_id, read_on_chain_inputs, setup_eth_backend,
+        };
         let (_, client) = setup_eth_backend(Some(&source.rpc), None).await?;
-        let inputs = read_on_chain_inputs(client.clone(), client.address(), &source.calls).await?;
+        let block = on_chain_block_id(&source)?;
+        let inputs =
+            read_on_chain_inputs(client.clone(), client.address(), &source.calls, block).await?;
         // quantize the supplied data using the provided scale + QuantizeData.sol
         let quantized_evm_inputs = evm_quantize(client, scales, &inputs).await?;
         // on-chain data has already been quantized at this point. Just need to reshape it and push into tensor vector
@@ -797,6 +1035,7 @@ impl GraphCircuit {
         scales: Vec<crate::Scale>,
         input_types: Vec<InputType>,
     ) -> Result<Vec<Tensor<Fp>>, Box<dyn std::error::Error>> {
+        let file_data = preprocess(&self.settings().preprocessing, file_data)?;
         // quantize the supplied data using the provided scale.
         let mut data: Vec<Tensor<Fp>> = vec![];
         for (((d, shape), scale), input_type) in file_data
@@ -842,16 +1081,17 @@ impl GraphCircuit {
         (ASSUMED_BLINDING_FACTORS + RESERVED_BLINDING_ROWS_PAD) as f64
     }
 
-    fn calc_safe_range(res: &GraphWitness) -> (i128, i128) {
+    fn calc_safe_range(min_lookup_inputs: i128, max_lookup_inputs: i128) -> (i128, i128) {
         (
-            RANGE_MULTIPLIER * res.min_lookup_inputs,
-            RANGE_MULTIPLIER * res.max_lookup_inputs,
+            RANGE_MULTIPLIER * min_lookup_inputs,
+            RANGE_MULTIPLIER * max_lookup_inputs,
         )
     }
 
     fn calc_min_logrows(
         &mut self,
-        res: &GraphWitness,
+        min_lookup_inputs: i128,
+        max_lookup_inputs: i128,
         max_logrows: Option<u32>,
     ) -> Result<(), Box<dyn std::error::Error>> {
         // load the max logrows
@@ -861,14 +1101,14 @@ impl GraphCircuit {
 
         let reserved_blinding_rows = Self::reserved_blinding_rows();
         // check if has overflowed i128 max
-        if res.max_lookup_inputs > i128::MAX / RANGE_MULTIPLIER
-            || res.min_lookup_inputs < i128::MIN / RANGE_MULTIPLIER
+        if max_lookup_inputs > i128::MAX / RANGE_MULTIPLIER
+            || min_lookup_inputs < i128::MIN / RANGE_MULTIPLIER
         {
-            let err_string = format!("max lookup input ({}) is too large", res.max_lookup_inputs);
+            let err_string = format!("max lookup input ({}) is too large", max_lookup_inputs);
             return Err(err_string.into());
         }
 
-        let safe_range = Self::calc_safe_range(res);
+        let safe_range = Self::calc_safe_range(min_lookup_inputs, max_lookup_inputs);
 
         let max_col_size =
             Table::<Fp>::cal_col_size(max_logrows as usize, reserved_blinding_rows as usize);
@@ -894,9 +1134,7 @@ impl GraphCircuit {
         let mut logrows = std::cmp::max(min_bits, min_rows_from_constraints);
 
         // if public input then public inputs col will have public inputs len
-        if self.settings().run_args.input_visibility.is_public()
-            || self.settings().run_args.output_visibility.is_public()
-        {
+        if !self.model().instance_shapes().is_empty() {
             let mut max_instance_len = self
                 .model()
                 .instance_shapes()
@@ -960,7 +1198,19 @@ impl GraphCircuit {
         max_logrows: Option<u32>,
     ) -> Result<(), Box<dyn std::error::Error>> {
         let res = self.forward(&mut input.to_vec(), None, None)?;
-        self.calc_min_logrows(&res, max_logrows)
+        self.calc_min_logrows(res.min_lookup_inputs, res.max_lookup_inputs, max_logrows)
+    }
+
+    /// Calibrate the circuit to the range of lookup inputs of forward passes over some data, e.g.
+    /// accumulated over the batches of a dataset (see [GraphWitness::min_lookup_inputs] and
+    /// [GraphWitness::max_lookup_inputs]).
+    pub fn calibrate_from_min_max(
+        &mut self,
+        min_lookup_inputs: i128,
+        max_lookup_inputs: i128,
+        max_logrows: Option<u32>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.calc_min_logrows(min_lookup_inputs, max_lookup_inputs, max_logrows)
     }
 
     /// Runs the forward pass of the model / graph of computations and any associated hashing.
@@ -973,68 +1223,98 @@ impl GraphCircuit {
         let original_inputs = inputs.to_vec();
 
         let visibility = VarVisibility::from_args(&self.settings().run_args)?;
+        let recipient = self.settings().elgamal_recipient.as_ref();
+        // one salt for every salted group, a fresh one is drawn if none is supplied (it is returned
+        // in the witness so the commitments can be opened)
+        let salt = match self.hash_salt {
+            None if [&visibility.input, &visibility.params, &visibility.output]
+                .iter()
+                .any(|v| v.is_salted()) =>
+            {
+                Some(Fp::random(&mut rand::thread_rng()))
+            }
+            salt => salt,
+        };
         let mut processed_inputs = None;
         let mut processed_params = None;
         let mut processed_outputs = None;
 
-        if visibility.input.requires_processing() {
-            let module_outlets = visibility.input.overwrites_inputs();
-            if !module_outlets.is_empty() {
-                let mut module_inputs = vec![];
-                for outlet in &module_outlets {
-                    module_inputs.push(inputs[*outlet].clone());
-                }
-                let res = GraphModules::forward(&module_inputs, visibility.input.clone(), vk, srs)?;
-                processed_inputs = Some(res.clone());
+        let input_outlets = visibility.input_outlets(inputs.len());
+        if !input_outlets.is_empty() {
+            let module_inputs = input_outlets
+                .iter()
+                .map(|outlet| inputs[*outlet].clone())
+                .collect_vec();
+            let res = GraphModules::forward(
+                &module_inputs,
+                visibility.input.clone(),
+                vk,
+                srs,
+                recipient,
+                salt,
+                self.merkle_proofs.as_deref(),
+                self.signatures.as_deref(),
+            )?;
+            // private hashes are fed to the model in place of the inputs
+            if visibility.input.is_hashed_private() {
                 let module_results = res.get_result(visibility.input.clone());
-
-                for (i, outlet) in module_outlets.iter().enumerate() {
+                for (i, outlet) in input_outlets.iter().enumerate() {
                     inputs[*outlet] = Tensor::from(module_results[i].clone().into_iter());
                 }
-            } else {
-                processed_inputs = Some(GraphModules::forward(inputs, visibility.input, vk, srs)?);
             }
+            processed_inputs = Some(res);
         }
 
-        if visibility.params.requires_processing() {
-            let params = self.model().get_all_params();
-            if !params.is_empty() {
-                let flattened_params = Tensor::new(Some(&params), &[params.len()])?.combine()?;
-                processed_params = Some(GraphModules::forward(
-                    &[flattened_params],
-                    visibility.params,
-                    vk,
-                    srs,
-                )?);
-            }
+        let params = if visibility.params.requires_processing() {
+            self.model().get_all_params()
+        } else {
+            vec![]
+        };
+        let param_outlets = visibility.param_outlets(params.len());
+        if !param_outlets.is_empty() {
+            let params = param_outlets
+                .iter()
+                .map(|outlet| params[*outlet].clone())
+                .collect_vec();
+            let flattened_params = Tensor::new(Some(&params), &[params.len()])?.combine()?;
+            processed_params = Some(GraphModules::forward(
+                &[flattened_params],
+                visibility.params.clone(),
+                vk,
+                srs,
+                recipient,
+                salt,
+                None,
+                None,
+            )?);
         }
 
         let mut model_results = self.model().forward(inputs)?;
 
-        if visibility.output.requires_processing() {
-            let module_outlets = visibility.output.overwrites_inputs();
-            if !module_outlets.is_empty() {
-                let mut module_inputs = vec![];
-                for outlet in &module_outlets {
-                    module_inputs.push(model_results.outputs[*outlet].clone());
-                }
-                let res =
-                    GraphModules::forward(&module_inputs, visibility.output.clone(), vk, srs)?;
-                processed_outputs = Some(res.clone());
+        let output_outlets = visibility.output_outlets(model_results.outputs.len());
+        if !output_outlets.is_empty() {
+            let module_inputs = output_outlets
+                .iter()
+                .map(|outlet| model_results.outputs[*outlet].clone())
+                .collect_vec();
+            let res = GraphModules::forward(
+                &module_inputs,
+                visibility.output.clone(),
+                vk,
+                srs,
+                recipient,
+                salt,
+                None,
+                None,
+            )?;
+            if visibility.output.is_hashed_private() {
                 let module_results = res.get_result(visibility.output.clone());
-
-                for (i, outlet) in module_outlets.iter().enumerate() {
+                for (i, outlet) in output_outlets.iter().enumerate() {
                     model_results.outputs[*outlet] =
                         Tensor::from(module_results[i].clone().into_iter());
                 }
-            } else {
-                processed_outputs = Some(GraphModules::forward(
-                    &model_results.outputs,
-                    visibility.output,
-                    vk,
-                    srs,
-                )?);
             }
+            processed_outputs = Some(res);
         }
 
         let witness = GraphWitness {
@@ -1052,6 +1332,7 @@ impl GraphCircuit {
             processed_outputs,
             max_lookup_inputs: model_results.max_lookup_inputs,
             min_lookup_inputs: model_results.min_lookup_inputs,
+            on_chain_block: None,
         };
 
         #[cfg(not(target_arch = "wasm32"))]
@@ -1069,6 +1350,7 @@ impl GraphCircuit {
         run_args: &RunArgs,
         model_path: &std::path::Path,
     ) -> Result<Self, Box<dyn std::error::Error>> {
+        run_args.validate()?;
         let model = Model::from_run_args(run_args, model_path)?;
         Self::new(model, run_args)
     }
@@ -1093,13 +1375,14 @@ impl GraphCircuit {
         test_on_chain_data: TestOnChainData,
     ) -> Result<(), Box<dyn std::error::Error>> {
         // Set up local anvil instance for reading on-chain data
+        let visibility = VarVisibility::from_args(&self.settings().run_args)?;
 
         if matches!(
             test_on_chain_data.data_sources.input,
             TestDataSource::OnChain
         ) {
             // if not public then fail
-            if self.settings().run_args.input_visibility.is_private() {
+            if (0..self.model().graph.inputs.len()).any(|i| visibility.input_at(i).is_private()) {
                 return Err("Cannot use on-chain data source as private data".into());
             }
 
@@ -1128,7 +1411,7 @@ impl GraphCircuit {
             TestDataSource::OnChain
         ) {
             // if not public then fail
-            if self.settings().run_args.output_visibility.is_private() {
+            if (0..self.model().graph.outputs.len()).any(|i| visibility.output_at(i).is_private()) {
                 return Err("Cannot use on-chain data source as private data".into());
             }
 
@@ -1250,6 +1533,15 @@ impl Circuit<Fp> for GraphCircuit {
 
         let model_config = ModelConfig { base, vars };
 
+        // the block instances are copied into an advice column, so that they're constrained
+        let block_column = if params.run_args.commit_on_chain_block {
+            let column = cs.advice_column();
+            cs.enable_equality(column);
+            Some(column)
+        } else {
+            None
+        };
+
         trace!(
             "log2_ceil of degrees {:?}",
             (cs.degree() as f32).log2().ceil()
@@ -1268,6 +1560,7 @@ impl Circuit<Fp> for GraphCircuit {
         GraphConfig {
             model_config,
             module_configs,
+            block_column,
         }
     }
 
@@ -1281,8 +1574,10 @@ impl Circuit<Fp> for GraphCircuit {
         mut layouter: impl Layouter<Fp>,
     ) -> Result<(), PlonkError> {
         trace!("Setting input in synthesize");
-        let input_vis = &self.settings().run_args.input_visibility;
-        let output_vis = &self.settings().run_args.output_visibility;
+        let visibility = VarVisibility::from_args(&self.settings().run_args).map_err(|e| {
+            log::error!("{}", e);
+            PlonkError::Synthesis
+        })?;
         let mut graph_modules = GraphModules::new();
 
         let mut config = config.clone();
@@ -1291,8 +1586,9 @@ impl Circuit<Fp> for GraphCircuit {
             .graph_witness
             .get_input_tensor()
             .iter_mut()
-            .map(|i| {
-                i.set_visibility(input_vis);
+            .enumerate()
+            .map(|(idx, i)| {
+                i.set_visibility(visibility.input_at(idx));
                 ValTensor::try_from(i.clone()).map_err(|e| {
                     log::error!("failed to convert input to valtensor: {:?}", e);
                     PlonkError::Synthesis
@@ -1304,8 +1600,9 @@ impl Circuit<Fp> for GraphCircuit {
             .graph_witness
             .get_output_tensor()
             .iter_mut()
-            .map(|i| {
-                i.set_visibility(output_vis);
+            .enumerate()
+            .map(|(idx, i)| {
+                i.set_visibility(visibility.output_at(idx));
                 ValTensor::try_from(i.clone()).map_err(|e| {
                     log::error!("failed to convert output to valtensor: {:?}", e);
                     PlonkError::Synthesis
@@ -1316,47 +1613,42 @@ impl Circuit<Fp> for GraphCircuit {
         let mut instance_offset = 0;
         trace!("running input module layout");
 
-        let input_visibility = &self.settings().run_args.input_visibility;
-        let outlets = input_visibility.overwrites_inputs();
-
-        if !outlets.is_empty() {
-            let mut input_outlets = vec![];
-            for outlet in &outlets {
-                input_outlets.push(inputs[*outlet].clone());
-            }
-            graph_modules.layout(
-                &mut layouter,
-                &mut config.module_configs,
-                &mut input_outlets,
-                input_visibility,
-                &mut instance_offset,
-                &self.module_settings.input,
-            )?;
-            // replace inputs with the outlets
-            for (i, outlet) in outlets.iter().enumerate() {
-                inputs[*outlet] = input_outlets[i].clone();
-            }
-        } else {
-            graph_modules.layout(
-                &mut layouter,
-                &mut config.module_configs,
-                &mut inputs,
-                input_visibility,
-                &mut instance_offset,
-                &self.module_settings.input,
-            )?;
+        let outlets = visibility.input_outlets(inputs.len());
+        let mut input_outlets = outlets
+            .iter()
+            .map(|outlet| inputs[*outlet].clone())
+            .collect_vec();
+        graph_modules.layout(
+            &mut layouter,
+            &mut config.module_configs,
+            &mut input_outlets,
+            &visibility.input,
+            &mut instance_offset,
+            &self.module_settings.input,
+        )?;
+        // replace inputs with the outlets
+        for (i, outlet) in outlets.iter().enumerate() {
+            inputs[*outlet] = input_outlets[i].clone();
         }
 
         // now we need to assign the flattened params to the model
         let mut model = self.model().clone();
-        let param_visibility = &self.settings().run_args.param_visibility;
         trace!("running params module layout");
-        if !self.model().get_all_params().is_empty() && param_visibility.requires_processing() {
-            // now we need to flatten the params
-            let consts = self.model().get_all_params();
+        let consts = if visibility.params.requires_processing() {
+            self.model().get_all_params()
+        } else {
+            vec![]
+        };
+        let outlets = visibility.param_outlets(consts.len());
+        if !outlets.is_empty() {
+            // now we need to flatten the processed params
+            let processed_consts = outlets
+                .iter()
+                .map(|outlet| consts[*outlet].clone())
+                .collect_vec();
 
             let mut flattened_params = {
-                let mut t = Tensor::new(Some(&consts), &[consts.len()])
+                let mut t = Tensor::new(Some(&processed_consts), &[processed_consts.len()])
                     .map_err(|_| {
                         log::error!("failed to flatten params");
                         PlonkError::Synthesis
@@ -1366,7 +1658,7 @@ impl Circuit<Fp> for GraphCircuit {
                         log::error!("failed to combine params");
                         PlonkError::Synthesis
                     })?;
-                t.set_visibility(param_visibility);
+                t.set_visibility(&visibility.params);
                 vec![t.try_into().map_err(|_| {
                     log::error!("failed to convert params to valtensor");
                     PlonkError::Synthesis
@@ -1378,20 +1670,41 @@ impl Circuit<Fp> for GraphCircuit {
                 &mut layouter,
                 &mut config.module_configs,
                 &mut flattened_params,
-                param_visibility,
+                &visibility.params,
                 &mut instance_offset,
                 &self.module_settings.params,
             )?;
 
-            let shapes = self.model().const_shapes();
+            let shapes = processed_consts
+                .iter()
+                .map(|c| c.dims().to_vec())
+                .collect_vec();
             trace!("replacing processed consts");
-            let split_params = split_valtensor(&flattened_params[0], shapes).map_err(|_| {
-                log::error!("failed to split params");
-                PlonkError::Synthesis
-            })?;
+            let mut split_params = split_valtensor(&flattened_params[0], shapes)
+                .map_err(|_| {
+                    log::error!("failed to split params");
+                    PlonkError::Synthesis
+                })?
+                .into_iter();
+
+            // consts that aren't processed are assigned as they would be otherwise
+            let consts = consts
+                .into_iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    if outlets.contains(&i) {
+                        split_params.next().ok_or(PlonkError::Synthesis)
+                    } else {
+                        c.try_into().map_err(|_| {
+                            log::error!("failed to convert params to valtensor");
+                            PlonkError::Synthesis
+                        })
+                    }
+                })
+                .collect::<Result<Vec<ValTensor<Fp>>, PlonkError>>()?;
 
             // now the flattened_params have been assigned to and we-assign them to the model consts such that they are constrained to be equal
-            model.replace_consts(&split_params);
+            model.replace_consts(&consts);
         }
 
         // create a new module for the model (space 2)
@@ -1416,38 +1729,46 @@ impl Circuit<Fp> for GraphCircuit {
             })?;
         trace!("running output module layout");
 
-        let output_visibility = &self.settings().run_args.output_visibility;
-        let outlets = output_visibility.overwrites_inputs();
+        let outlets = visibility.output_outlets(outputs.len());
 
         instance_offset += vars.get_instance_len();
 
-        if !outlets.is_empty() {
-            let mut output_outlets = vec![];
-            for outlet in &outlets {
-                output_outlets.push(outputs[*outlet].clone());
-            }
-            // this will re-enter module 0
-            graph_modules.layout(
-                &mut layouter,
-                &mut config.module_configs,
-                &mut output_outlets,
-                &self.settings().run_args.output_visibility,
-                &mut instance_offset,
-                &self.module_settings.output,
-            )?;
+        let mut output_outlets = outlets
+            .iter()
+            .map(|outlet| outputs[*outlet].clone())
+            .collect_vec();
+        // this will re-enter module 0
+        graph_modules.layout(
+            &mut layouter,
+            &mut config.module_configs,
+            &mut output_outlets,
+            &visibility.output,
+            &mut instance_offset,
+            &self.module_settings.output,
+        )?;
+
+        // replace outputs with the outlets
+        for (i, outlet) in outlets.iter().enumerate() {
+            outputs[*outlet] = output_outlets[i].clone();
+        }
 
-            // replace outputs with the outlets
-            for (i, outlet) in outlets.iter().enumerate() {
-                outputs[*outlet] = output_outlets[i].clone();
-            }
-        } else {
-            graph_modules.layout(
-                &mut layouter,
-                &mut config.module_configs,
-                &mut outputs,
-                &self.settings().run_args.output_visibility,
-                &mut instance_offset,
-                &self.module_settings.output,
+        if let Some(block_column) = config.block_column {
+            trace!("committing the on-chain block");
+            let instance = *vars.get_instance_col().ok_or(PlonkError::Synthesis)?;
+            layouter.assign_region(
+                || "on_chain_block",
+                |mut region| {
+                    for i in 0..ON_CHAIN_BLOCK_INSTANCES {
+                        region.assign_advice_from_instance(
+                            || "block",
+                            instance,
+                            instance_offset + i,
+                            block_column,
+                            i,
+                        )?;
+                    }
+                    Ok(())
+                },
             )?;
         }
 
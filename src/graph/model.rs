@@ -402,7 +402,17 @@ impl Model {
 
         let graph = Self::load_onnx_model(reader, run_args, &visibility)?;
 
-        let om = Model { graph, visibility };
+        let mut om = Model { graph, visibility };
+
+        om.visibility.check_num_tensors(
+            om.graph.inputs.len(),
+            om.const_shapes().len(),
+            om.graph.outputs.len(),
+        )?;
+        if !om.visibility.per_param.is_empty() {
+            let per_param = om.visibility.per_param.clone();
+            om.set_param_visibilities(&per_param);
+        }
 
         debug!("\n {}", om.table_nodes());
 
@@ -478,6 +488,8 @@ impl Model {
             check_mode,
             version: env!("CARGO_PKG_VERSION").to_string(),
             num_blinding_factors: None,
+            preprocessing: vec![],
+            elgamal_recipient: None,
         })
     }
 
@@ -1059,7 +1071,7 @@ impl Model {
 
         let input_shapes = self.graph.input_shapes();
         for (i, input_idx) in self.graph.inputs.iter().enumerate() {
-            if self.visibility.input.is_public() {
+            if self.visibility.input_at(i).is_public() {
                 results.insert(*input_idx, vec![vars.instance.as_ref().unwrap().clone()]);
                 vars.increment_instance_idx();
             } else {
@@ -1091,39 +1103,41 @@ impl Model {
                         halo2_proofs::plonk::Error::Synthesis
                     })?;
 
-                if run_args.output_visibility.is_public() || run_args.output_visibility.is_fixed() {
-                    let output_scales = self.graph.get_output_scales();
-                    let res = outputs
-                        .iter()
-                        .enumerate()
-                        .map(|(i, output)| {
-                            let mut tolerance = run_args.tolerance;
-                            tolerance.scale = scale_to_multiplier(output_scales[i]).into();
-
-                            let comparators = if run_args.output_visibility == Visibility::Public {
-                                let res = vars.instance.as_ref().unwrap().clone();
-                                vars.increment_instance_idx();
-                                res
-                            } else {
-                                // if witnessed_outputs is of len less than i  error
-                                if witnessed_outputs.len() <= i {
-                                    return Err("you provided insufficient witness values to generate a fixed output".into());
-                                }
-                                witnessed_outputs[i].clone()
-                            };
+                let output_scales = self.graph.get_output_scales();
+                let res = outputs
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| {
+                        let output_visibility = self.visibility.output_at(*i);
+                        output_visibility.is_public() || output_visibility.is_fixed()
+                    })
+                    .map(|(i, output)| {
+                        let mut tolerance = run_args.tolerance;
+                        tolerance.scale = scale_to_multiplier(output_scales[i]).into();
+
+                        let comparators = if self.visibility.output_at(i).is_public() {
+                            let res = vars.instance.as_ref().unwrap().clone();
+                            vars.increment_instance_idx();
+                            res
+                        } else {
+                            // if witnessed_outputs is of len less than i  error
+                            if witnessed_outputs.len() <= i {
+                                return Err("you provided insufficient witness values to generate a fixed output".into());
+                            }
+                            witnessed_outputs[i].clone()
+                        };
 
-                            config.base.layout(
-                                &mut thread_safe_region,
-                                &[output.clone(), comparators],
-                                Box::new(HybridOp::RangeCheck(tolerance)),
-                            )
-                        })
-                        .collect::<Result<Vec<_>,_>>();
-                    res.map_err(|e| {
-                        error!("{}", e);
-                        halo2_proofs::plonk::Error::Synthesis
-                    })?;
-                }
+                        config.base.layout(
+                            &mut thread_safe_region,
+                            &[output.clone(), comparators],
+                            Box::new(HybridOp::RangeCheck(tolerance)),
+                        )
+                    })
+                    .collect::<Result<Vec<_>,_>>();
+                res.map_err(|e| {
+                    error!("{}", e);
+                    halo2_proofs::plonk::Error::Synthesis
+                })?;
                 num_rows = thread_safe_region.row();
                 linear_coord = thread_safe_region.linear_coord();
                 total_const_size = thread_safe_region.total_constants();
@@ -1332,17 +1346,20 @@ impl Model {
         let start_time = instant::Instant::now();
 
         let mut results = BTreeMap::<usize, Vec<ValTensor<Fp>>>::new();
-        let default_value = if !self.visibility.input.is_fixed() {
-            ValType::Value(Value::<Fp>::unknown())
-        } else {
-            ValType::Constant(Fp::ONE)
+        let default_value = |visibility: &Visibility| {
+            if !visibility.is_fixed() {
+                ValType::Value(Value::<Fp>::unknown())
+            } else {
+                ValType::Constant(Fp::ONE)
+            }
         };
 
         let inputs: Vec<ValTensor<Fp>> = input_shapes
             .iter()
-            .map(|shape| {
+            .enumerate()
+            .map(|(i, shape)| {
                 let mut t: ValTensor<Fp> =
-                    vec![default_value.clone(); shape.iter().product()].into();
+                    vec![default_value(self.visibility.input_at(i)); shape.iter().product()].into();
                 t.reshape(shape).unwrap();
                 t
             })
@@ -1363,36 +1380,22 @@ impl Model {
 
         let outputs = self.layout_nodes(&mut model_config, &mut region, &mut results)?;
 
-        if self.visibility.output.is_public() || self.visibility.output.is_fixed() {
-            let default_value = if !self.visibility.output.is_fixed() {
-                ValType::Value(Value::<Fp>::unknown())
-            } else {
-                ValType::Constant(Fp::ONE)
-            };
-
-            let comparator = outputs
-                .iter()
-                .map(|x| {
-                    let mut v: ValTensor<Fp> =
-                        vec![default_value.clone(); x.dims().iter().product::<usize>()].into();
-                    v.reshape(x.dims()).unwrap();
-                    v
-                })
-                .collect_vec();
-
-            let _ = outputs
-                .into_iter()
-                .zip(comparator)
-                .map(|(o, c)| {
-                    dummy_config
-                        .layout(
-                            &mut region,
-                            &[o, c],
-                            Box::new(HybridOp::RangeCheck(run_args.tolerance)),
-                        )
-                        .unwrap()
-                })
-                .collect_vec();
+        for (i, output) in outputs.into_iter().enumerate() {
+            let output_visibility = self.visibility.output_at(i);
+            if output_visibility.is_public() || output_visibility.is_fixed() {
+                let mut comparator: ValTensor<Fp> =
+                    vec![default_value(output_visibility); output.dims().iter().product::<usize>()]
+                        .into();
+                comparator.reshape(output.dims()).unwrap();
+
+                dummy_config
+                    .layout(
+                        &mut region,
+                        &[output, comparator],
+                        Box::new(HybridOp::RangeCheck(run_args.tolerance)),
+                    )
+                    .unwrap();
+            }
         }
 
         let duration = start_time.elapsed();
@@ -1478,14 +1481,38 @@ impl Model {
         const_idx
     }
 
+    /// Sets the visibility of each of the model's constants (in order of indexing), returns the number of consts
+    pub fn set_param_visibilities(&mut self, visibilities: &[Visibility]) -> usize {
+        let mut const_idx = 0;
+        for node in self.graph.nodes.values_mut() {
+            match node {
+                NodeType::Node(n) => {
+                    if let SupportedOp::Constant(c) = &mut n.opkind {
+                        c.quantized_values.set_visibility(&visibilities[const_idx]);
+                        const_idx += 1;
+                    }
+                }
+                NodeType::SubGraph { model, .. } => {
+                    let total_consts = model.set_param_visibilities(&visibilities[const_idx..]);
+                    const_idx += total_consts;
+                }
+            }
+        }
+        const_idx
+    }
+
     /// Shapes of the computational graph's public inputs (if any)
     pub fn instance_shapes(&self) -> Vec<Vec<usize>> {
         let mut instance_shapes = vec![];
-        if self.visibility.input.is_public() {
-            instance_shapes.extend(self.graph.input_shapes());
+        for (i, shape) in self.graph.input_shapes().into_iter().enumerate() {
+            if self.visibility.input_at(i).is_public() {
+                instance_shapes.push(shape);
+            }
         }
-        if self.visibility.output.is_public() {
-            instance_shapes.extend(self.graph.output_shapes());
+        for (i, shape) in self.graph.output_shapes().into_iter().enumerate() {
+            if self.visibility.output_at(i).is_public() {
+                instance_shapes.push(shape);
+            }
         }
         instance_shapes
     }
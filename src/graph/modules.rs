@@ -1,13 +1,21 @@
-use crate::circuit::modules::elgamal::{ElGamalConfig, ElGamalGadget, ElGamalVariables};
+use crate::circuit::modules::eddsa::{EddsaChip, EddsaConfig, EddsaSignature, EdwardsPoint};
+use crate::circuit::modules::elgamal::{
+    ElGamalConfig, ElGamalGadget, ElGamalRecipient, ElGamalVariables,
+};
 use crate::circuit::modules::kzg::{KZGChip, KZGConfig};
-use crate::circuit::modules::poseidon::spec::{PoseidonSpec, POSEIDON_RATE, POSEIDON_WIDTH};
-use crate::circuit::modules::poseidon::{PoseidonChip, PoseidonConfig};
+use crate::circuit::modules::merkle::{MerkleChip, MerkleConfig, MerkleProof};
+use crate::circuit::modules::poseidon::spec::{
+    PoseidonSpec, PoseidonSpecT5, POSEIDON_RATE, POSEIDON_T5_RATE, POSEIDON_T5_WIDTH,
+    POSEIDON_WIDTH,
+};
+use crate::circuit::modules::poseidon::{PoseidonChip, PoseidonConfig, PoseidonParams};
 use crate::circuit::modules::Module;
 use crate::tensor::{Tensor, ValTensor, ValType};
 use halo2_proofs::circuit::{Layouter, Value};
 use halo2_proofs::plonk::{Column, ConstraintSystem, Error, Instance, VerifyingKey};
 use halo2_proofs::poly::kzg::commitment::ParamsKZG;
 use halo2curves::bn256::{Bn256, Fr as Fp, G1Affine};
+use halo2curves::ff::Field;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
@@ -21,12 +29,58 @@ pub const POSEIDON_LEN_GRAPH: usize = 32;
 pub const ELGAMAL_INSTANCES: usize = 4;
 /// Poseidon number of instancess
 pub const POSEIDON_INSTANCES: usize = 1;
+/// EdDSA number of instances (the coordinates of the signer's public key)
+pub const EDDSA_INSTANCES: usize = 2;
 
 /// Poseidon module type
 pub type ModulePoseidon =
     PoseidonChip<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, POSEIDON_LEN_GRAPH>;
-/// Poseidon module config
-pub type ModulePoseidonConfig = PoseidonConfig<POSEIDON_WIDTH, POSEIDON_RATE>;
+/// Poseidon module type for the width 5 parameters
+pub type ModulePoseidonT5 =
+    PoseidonChip<PoseidonSpecT5, POSEIDON_T5_WIDTH, POSEIDON_T5_RATE, POSEIDON_LEN_GRAPH>;
+
+/// Poseidon module config, for each of the [PoseidonParams]
+#[derive(Clone, Debug)]
+pub enum ModulePoseidonConfig {
+    /// width 2
+    T2(PoseidonConfig<POSEIDON_WIDTH, POSEIDON_RATE>),
+    /// width 5
+    T5(PoseidonConfig<POSEIDON_T5_WIDTH, POSEIDON_T5_RATE>),
+}
+
+impl ModulePoseidonConfig {
+    /// Configure the module with its own instance column
+    fn configure(cs: &mut ConstraintSystem<Fp>, params: PoseidonParams) -> Self {
+        match params {
+            PoseidonParams::T2 => Self::T2(ModulePoseidon::configure(cs, ())),
+            PoseidonParams::T5 => Self::T5(ModulePoseidonT5::configure(cs, ())),
+        }
+    }
+
+    /// Configure the module with an optional (shared) instance column
+    fn configure_with_optional_instance(
+        cs: &mut ConstraintSystem<Fp>,
+        params: PoseidonParams,
+        instance: Option<Column<Instance>>,
+    ) -> Self {
+        match params {
+            PoseidonParams::T2 => Self::T2(ModulePoseidon::configure_with_optional_instance(
+                cs, instance,
+            )),
+            PoseidonParams::T5 => Self::T5(ModulePoseidonT5::configure_with_optional_instance(
+                cs, instance,
+            )),
+        }
+    }
+
+    /// The instance column of the module
+    fn instance(&self) -> Option<Column<Instance>> {
+        match self {
+            Self::T2(config) => config.instance,
+            Self::T5(config) => config.instance,
+        }
+    }
+}
 
 ///
 #[derive(Clone, Debug, Default)]
@@ -37,6 +91,10 @@ pub struct ModuleConfigs {
     poseidon: Option<ModulePoseidonConfig>,
     /// ElGamal
     elgamal: Option<ElGamalConfig>,
+    /// Merkle
+    merkle: Option<MerkleConfig>,
+    /// EdDSA
+    eddsa: Option<EddsaConfig>,
     /// Instance
     pub instance: Option<Column<Instance>>,
 }
@@ -79,52 +137,99 @@ impl ModuleConfigs {
             || visibility.params.is_hashed())
             && module_size.poseidon.1[0] > 0
         {
+            let params = visibility.poseidon_params();
             if visibility.input.is_hashed_public()
                 || visibility.output.is_hashed_public()
                 || visibility.params.is_hashed_public()
             {
                 if let Some(inst) = self.instance {
-                    self.poseidon = Some(ModulePoseidon::configure_with_optional_instance(
+                    self.poseidon = Some(ModulePoseidonConfig::configure_with_optional_instance(
                         cs,
+                        params,
                         Some(inst),
                     ));
                 } else {
-                    let poseidon = ModulePoseidon::configure(cs, ());
-                    self.instance = poseidon.instance;
+                    let poseidon = ModulePoseidonConfig::configure(cs, params);
+                    self.instance = poseidon.instance();
                     self.poseidon = Some(poseidon);
                 }
             } else if visibility.input.is_hashed_private()
                 || visibility.output.is_hashed_private()
                 || visibility.params.is_hashed_private()
             {
-                self.poseidon = Some(ModulePoseidon::configure_with_optional_instance(cs, None));
+                self.poseidon = Some(ModulePoseidonConfig::configure_with_optional_instance(
+                    cs, params, None,
+                ));
+            }
+        };
+
+        // merkle roots are counted alongside the poseidon hashes
+        if (visibility.input.is_merkle()
+            || visibility.output.is_merkle()
+            || visibility.params.is_merkle())
+            && module_size.poseidon.1[0] > 0
+        {
+            if let Some(inst) = self.instance {
+                self.merkle = Some(MerkleChip::configure_with_optional_instance(cs, Some(inst)));
+            } else {
+                let merkle = MerkleChip::configure(cs, ());
+                self.instance = merkle.instance;
+                self.merkle = Some(merkle);
+            }
+        };
+
+        // signers' public keys are counted alongside the poseidon hashes
+        if visibility.input.is_signed() && module_size.poseidon.1[0] > 0 {
+            if let Some(inst) = self.instance {
+                self.eddsa = Some(EddsaChip::configure_with_optional_instance(cs, Some(inst)));
+            } else {
+                let eddsa = EddsaChip::configure(cs, ());
+                self.instance = eddsa.instance;
+                self.eddsa = Some(eddsa);
             }
         };
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 /// Module variable settings
 pub struct ModuleVarSettings {
-    ///
-    elgamal: Option<ElGamalVariables>,
+    /// The variables each encrypted tensor is encrypted with
+    #[serde(default)]
+    elgamal: Vec<ElGamalVariables>,
+    /// The salt absorbed into salted hashes
+    #[serde(default)]
+    salt: Option<Fp>,
+    /// The paths merkle leaves are opened along
+    #[serde(default)]
+    merkle_proofs: Option<Vec<MerkleProof>>,
+    /// The signatures verified over signed inputs
+    #[serde(default)]
+    signatures: Option<Vec<EddsaSignature>>,
 }
 
 impl ModuleVarSettings {
     /// Create new module variable settings
-    pub fn new(elgamal: ElGamalVariables) -> Self {
+    pub fn new(elgamal: Vec<ElGamalVariables>) -> Self {
         ModuleVarSettings {
-            elgamal: Some(elgamal),
+            elgamal,
+            salt: None,
+            merkle_proofs: None,
+            signatures: None,
         }
     }
 }
 
-impl Default for ModuleVarSettings {
-    fn default() -> Self {
-        let dummy_elgamal = ElGamalVariables::default();
-        ModuleVarSettings {
-            elgamal: Some(dummy_elgamal),
+impl From<&ModuleForwardResult> for ModuleVarSettings {
+    fn from(result: &ModuleForwardResult) -> Self {
+        let mut settings = Self::default();
+        if let Some(elgamal_result) = &result.elgamal {
+            settings.elgamal = elgamal_result.variables.clone();
         }
+        settings.salt = result.salt;
+        settings.merkle_proofs = result.merkle_proofs.clone();
+        settings.signatures = result.signatures.clone();
+        settings
     }
 }
 
@@ -144,19 +249,13 @@ impl From<&GraphWitness> for ModuleSettings {
         let mut settings = Self::default();
 
         if let Some(processed_inputs) = &graph_input.processed_inputs {
-            if let Some(elgamal_result) = &processed_inputs.elgamal {
-                settings.input = ModuleVarSettings::new(elgamal_result.variables.clone());
-            }
+            settings.input = processed_inputs.into();
         }
         if let Some(processed_params) = &graph_input.processed_params {
-            if let Some(elgamal_result) = &processed_params.elgamal {
-                settings.params = ModuleVarSettings::new(elgamal_result.variables.clone());
-            }
+            settings.params = processed_params.into();
         }
         if let Some(processed_outputs) = &graph_input.processed_outputs {
-            if let Some(elgamal_result) = &processed_outputs.elgamal {
-                settings.output = ModuleVarSettings::new(elgamal_result.variables.clone());
-            }
+            settings.output = processed_outputs.into();
         }
 
         settings
@@ -166,8 +265,8 @@ impl From<&GraphWitness> for ModuleSettings {
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 /// Result from ElGamal
 pub struct ElGamalResult {
-    /// ElGamal variables
-    pub variables: ElGamalVariables,
+    /// ElGamal variables, one set per encrypted tensor
+    pub variables: Vec<ElGamalVariables>,
     /// ElGamal ciphertexts
     pub ciphertexts: Vec<Vec<Fp>>,
     /// ElGamal encrypted message
@@ -183,6 +282,21 @@ pub struct ModuleForwardResult {
     pub elgamal: Option<ElGamalResult>,
     /// The outputs of the forward pass for KZG
     pub kzg_commit: Option<Vec<Vec<G1Affine>>>,
+    /// The salt absorbed into the poseidon hashes, if the visibility is salted
+    #[serde(default)]
+    pub salt: Option<Fp>,
+    /// The merkle roots committing to each input of the forward pass
+    #[serde(default)]
+    pub merkle_root: Option<Vec<Fp>>,
+    /// The paths the inputs are opened along, if they are leaves of a larger tree
+    #[serde(default)]
+    pub merkle_proofs: Option<Vec<MerkleProof>>,
+    /// The public keys of the signers of each input of the forward pass
+    #[serde(default)]
+    pub public_keys: Option<Vec<EdwardsPoint>>,
+    /// The signatures over each input of the forward pass
+    #[serde(default)]
+    pub signatures: Option<Vec<EddsaSignature>>,
 }
 
 impl ModuleForwardResult {
@@ -206,6 +320,10 @@ impl ModuleForwardResult {
     pub fn get_instances(&self) -> Vec<Vec<Fp>> {
         if let Some(poseidon) = &self.poseidon_hash {
             poseidon.iter().map(|x| vec![*x]).collect()
+        } else if let Some(merkle_root) = &self.merkle_root {
+            merkle_root.iter().map(|x| vec![*x]).collect()
+        } else if let Some(public_keys) = &self.public_keys {
+            public_keys.iter().map(|pk| vec![pk.x, pk.y]).collect()
         } else if let Some(elgamal) = &self.elgamal {
             elgamal.ciphertexts.clone()
         } else {
@@ -283,10 +401,23 @@ impl GraphModules {
                 if visibility.is_kzgcommit() {
                     // 1 constraint for each kzg commitment
                     sizes.kzg.push(total_len);
-                } else if visibility.is_hashed() {
-                    sizes.poseidon.0 += ModulePoseidon::num_rows(total_len);
+                } else if let Some(params) = visibility.poseidon_params() {
+                    // the salt is hashed as an extra element of the message
+                    let hash_len = total_len + visibility.is_salted() as usize;
+                    sizes.poseidon.0 += params.num_rows::<POSEIDON_LEN_GRAPH>(hash_len);
                     // 1 constraints for hash
                     sizes.poseidon.1[0] += 1;
+                } else if visibility.is_merkle() {
+                    sizes.poseidon.0 += match visibility.merkle_depth() {
+                        Some(depth) => MerkleChip::num_rows_with_depth(total_len, depth),
+                        None => MerkleChip::num_rows(total_len),
+                    };
+                    // 1 constraint for the root
+                    sizes.poseidon.1[0] += 1;
+                } else if visibility.is_signed() {
+                    sizes.poseidon.0 += EddsaChip::num_rows(total_len);
+                    // 2 constraints for the coordinates of the public key
+                    sizes.poseidon.1[0] += EDDSA_INSTANCES;
                 } else if visibility.is_encrypted() {
                     // add the 1 time fixed cost of maingate + ecc chips
                     sizes.elgamal.0 += ElGamalGadget::num_rows(total_len);
@@ -296,7 +427,8 @@ impl GraphModules {
             }
         }
     }
-    /// Get the number of constraints and instances for the module
+    /// Get the number of constraints and instances for the module, given the shapes of the
+    /// model's inputs, constants and outputs (only those the modules process are counted)
     pub fn num_constraints_and_instances(
         input_shapes: Vec<Vec<usize>>,
         params_shapes: Vec<Vec<usize>>,
@@ -305,8 +437,29 @@ impl GraphModules {
     ) -> ModuleSizes {
         let mut module_sizes = ModuleSizes::new();
 
+        let processed = |shapes: &[Vec<usize>], outlets: Vec<usize>| {
+            outlets.iter().map(|o| shapes[*o].clone()).collect_vec()
+        };
+        let input_shapes = processed(&input_shapes, visibility.input_outlets(input_shapes.len()));
+        // the processed params are flattened into a single tensor
+        let num_params = processed(
+            &params_shapes,
+            visibility.param_outlets(params_shapes.len()),
+        )
+        .iter()
+        .map(|shape| shape.iter().product::<usize>())
+        .sum::<usize>();
+        let output_shapes = processed(
+            &output_shapes,
+            visibility.output_outlets(output_shapes.len()),
+        );
+
         Self::num_constraint_given_shapes(visibility.input, input_shapes, &mut module_sizes);
-        Self::num_constraint_given_shapes(visibility.params, params_shapes, &mut module_sizes);
+        Self::num_constraint_given_shapes(
+            visibility.params,
+            vec![vec![num_params]],
+            &mut module_sizes,
+        );
         Self::num_constraint_given_shapes(visibility.output, output_shapes, &mut module_sizes);
 
         module_sizes
@@ -372,13 +525,37 @@ impl GraphModules {
             if let Some(config) = &mut configs.poseidon {
                 // reserve module 0 for poseidon modules
                 layouter.assign_region(|| "_enter_module_0", |_| Ok(()))?;
-                // create the module
-                let chip = ModulePoseidon::new(config.clone());
-                // concat values and sk to get the inputs
-                let mut inputs = values.iter_mut().map(|x| vec![x.clone()]).collect_vec();
+                // concat values and the salt (if any) to get the inputs
+                let mut inputs = if element_visibility.is_salted() {
+                    let salt: Tensor<ValType<Fp>> = Tensor::new(
+                        Some(&[Value::known(module_settings.salt.unwrap_or_default()).into()]),
+                        &[1],
+                    )
+                    .unwrap();
+                    values
+                        .iter_mut()
+                        .map(|x| vec![x.clone(), salt.clone().into()])
+                        .collect_vec()
+                } else {
+                    values.iter_mut().map(|x| vec![x.clone()]).collect_vec()
+                };
                 // layout the module
                 inputs.iter_mut().for_each(|x| {
-                    Self::layout_module(&chip, layouter, x, instance_offset).unwrap();
+                    match config {
+                        ModulePoseidonConfig::T2(config) => Self::layout_module(
+                            &ModulePoseidon::new(config.clone()),
+                            layouter,
+                            x,
+                            instance_offset,
+                        ),
+                        ModulePoseidonConfig::T5(config) => Self::layout_module(
+                            &ModulePoseidonT5::new(config.clone()),
+                            layouter,
+                            x,
+                            instance_offset,
+                        ),
+                    }
+                    .unwrap();
                 });
                 // replace the inputs with the outputs
                 values.iter_mut().enumerate().for_each(|(i, x)| {
@@ -388,6 +565,63 @@ impl GraphModules {
                 log::error!("Poseidon config not initialized");
                 return Err(Error::Synthesis);
             }
+        // If the module is committed to with a merkle root, then we need to build the tree
+        } else if element_visibility.is_merkle() && !values.is_empty() {
+            if let Some(config) = &configs.merkle {
+                // merkle trees share module 0 with the poseidon modules
+                layouter.assign_region(|| "_enter_module_0", |_| Ok(()))?;
+                // create the module
+                let mut chip = MerkleChip::new(config.clone());
+                let mut inputs = values.iter_mut().map(|x| vec![x.clone()]).collect_vec();
+                // layout the module
+                for (i, x) in inputs.iter_mut().enumerate() {
+                    // leaves of a larger tree are opened along their path, without a witness
+                    // (eg. at keygen) a dummy path of the same depth is used
+                    if let Some(depth) = element_visibility.merkle_depth() {
+                        let proof = module_settings
+                            .merkle_proofs
+                            .as_ref()
+                            .and_then(|proofs| proofs.get(i).cloned())
+                            .unwrap_or_else(|| MerkleProof::dummy(depth));
+                        chip.load_proof(proof);
+                    }
+                    Self::layout_module(&chip, layouter, x, instance_offset)?;
+                }
+                // replace the inputs with the outputs
+                values.iter_mut().enumerate().for_each(|(i, x)| {
+                    x.clone_from(&inputs[i][0]);
+                });
+            } else {
+                log::error!("Merkle config not initialized");
+                return Err(Error::Synthesis);
+            }
+        // If the module is signed, then we need to verify the signatures
+        } else if element_visibility.is_signed() && !values.is_empty() {
+            if let Some(config) = &configs.eddsa {
+                // signatures share module 0 with the poseidon modules
+                layouter.assign_region(|| "_enter_module_0", |_| Ok(()))?;
+                // create the module
+                let mut chip = EddsaChip::new(config.clone());
+                let mut inputs = values.iter_mut().map(|x| vec![x.clone()]).collect_vec();
+                // layout the module
+                for (i, x) in inputs.iter_mut().enumerate() {
+                    // without a witness (eg. at keygen) the default signature is used
+                    let signature = module_settings
+                        .signatures
+                        .as_ref()
+                        .and_then(|signatures| signatures.get(i).cloned())
+                        .unwrap_or_default();
+                    chip.load_signature(signature);
+                    Self::layout_module(&chip, layouter, x, instance_offset)?;
+                }
+                // replace the inputs with the outputs
+                values.iter_mut().enumerate().for_each(|(i, x)| {
+                    x.clone_from(&inputs[i][0]);
+                });
+            } else {
+                log::error!("EdDSA config not initialized");
+                return Err(Error::Synthesis);
+            }
         // If the module is encrypted, then we need to encrypt the inputs
         } else if element_visibility.is_encrypted() && !values.is_empty() {
             if let Some(config) = &mut configs.elgamal {
@@ -395,22 +629,18 @@ impl GraphModules {
                 layouter.assign_region(|| "_enter_module_1", |_| Ok(()))?;
                 // create the module
                 let mut chip = ElGamalGadget::new(config.clone());
-                // load the variables
-                let variables = module_settings.elgamal.as_ref().unwrap().clone();
-                chip.load_variables(variables.clone());
-                // load the sk:
-                let sk: Tensor<ValType<Fp>> =
-                    Tensor::new(Some(&[Value::known(variables.sk).into()]), &[1]).unwrap();
-                // concat values and sk to get the inputs
-                let mut inputs = values
-                    .iter_mut()
-                    .map(|x| vec![x.clone(), sk.clone().into()])
-                    .collect_vec();
-                // layout the module
-                inputs.iter_mut().for_each(|x| {
-                    Self::layout_module(&chip, layouter, x, instance_offset).unwrap();
+                let mut inputs = values.iter_mut().map(|x| vec![x.clone()]).collect_vec();
+                // layout the module, each tensor is encrypted with its own randomness
+                for (i, x) in inputs.iter_mut().enumerate() {
+                    let variables = module_settings.elgamal.get(i).cloned().unwrap_or_default();
+                    // concat values and randomness to get the inputs
+                    let r: Tensor<ValType<Fp>> =
+                        Tensor::new(Some(&[Value::known(variables.r).into()]), &[1]).unwrap();
+                    x.push(r.into());
+                    chip.load_variables(variables);
+                    Self::layout_module(&chip, layouter, x, instance_offset)?;
                     chip.config.initialized = true;
-                });
+                }
                 // replace the inputs with the outputs
                 values.iter_mut().enumerate().for_each(|(i, x)| {
                     x.clone_from(&inputs[i][0]);
@@ -432,21 +662,91 @@ impl GraphModules {
         element_visibility: Visibility,
         vk: Option<&VerifyingKey<G1Affine>>,
         srs: Option<&ParamsKZG<Bn256>>,
+        recipient: Option<&ElGamalRecipient>,
+        salt: Option<Fp>,
+        merkle_proofs: Option<&[MerkleProof]>,
+        signatures: Option<&[EddsaSignature]>,
     ) -> Result<ModuleForwardResult, Box<dyn std::error::Error>> {
         let mut rng = &mut rand::thread_rng();
         let mut poseidon_hash = None;
         let mut elgamal = None;
         let mut kzg_commit = None;
-
-        if element_visibility.is_hashed() {
+        let mut hash_salt = None;
+        let mut merkle_root = None;
+        let mut merkle_paths = None;
+        let mut public_keys = None;
+        let mut input_signatures = None;
+
+        if let Some(params) = element_visibility.poseidon_params() {
+            // a salted hash absorbs the salt (drawn once per circuit) before the message
+            if element_visibility.is_salted() {
+                hash_salt = Some(salt.ok_or("salted hashes need a salt")?);
+            }
             let field_elements = inputs.iter().fold(vec![], |mut acc, x| {
-                let res = ModulePoseidon::run(x.to_vec()).unwrap()[0].clone();
+                let message = hash_salt.iter().copied().chain(x.iter().copied()).collect();
+                let res = params.hash::<POSEIDON_LEN_GRAPH>(message).unwrap()[0].clone();
                 acc.extend(res);
                 acc
             });
             poseidon_hash = Some(field_elements);
         }
 
+        if element_visibility.is_merkle() {
+            // leaves of a larger tree need a path of the configured depth each
+            if let Some(depth) = element_visibility.merkle_depth() {
+                let proofs = merkle_proofs.unwrap_or_default();
+                if proofs.len() != inputs.len() || proofs.iter().any(|p| p.depth() != depth) {
+                    return Err(format!(
+                        "expected a merkle proof of depth {} for each of the {} inputs, got {:?}",
+                        depth,
+                        inputs.len(),
+                        proofs.iter().map(|p| p.depth()).collect_vec()
+                    )
+                    .into());
+                }
+                merkle_paths = Some(proofs.to_vec());
+            }
+            let roots = inputs
+                .iter()
+                .enumerate()
+                .map(|(i, x)| {
+                    let proof = merkle_paths
+                        .as_ref()
+                        .map(|p: &Vec<MerkleProof>| p[i].clone());
+                    Ok(MerkleChip::run((x.to_vec(), proof))?[0][0])
+                })
+                .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+            merkle_root = Some(roots);
+        }
+
+        if element_visibility.is_signed() {
+            // the signatures are the data provider's, they can't be made up here
+            let signatures = signatures.unwrap_or_default();
+            if signatures.len() != inputs.len() {
+                return Err(format!(
+                    "expected a signature for each of the {} signed inputs in the input data's `signatures`, got {}",
+                    inputs.len(),
+                    signatures.len()
+                )
+                .into());
+            }
+            let signatures = signatures.to_vec();
+            // checks each signature verifies over its input
+            let keys = inputs
+                .iter()
+                .zip(signatures.iter())
+                .map(|(x, signature)| {
+                    let pk = EddsaChip::run((x.to_vec(), signature.clone()))?;
+                    Ok(EdwardsPoint {
+                        x: pk[0][0],
+                        y: pk[0][1],
+                    })
+                })
+                .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+            public_keys = Some(keys);
+            input_signatures = Some(signatures);
+        }
+
         if element_visibility.is_kzgcommit() {
             if let Some(vk) = vk {
                 if let Some(srs) = srs {
@@ -472,18 +772,36 @@ impl GraphModules {
         }
 
         if element_visibility.is_encrypted() {
-            let variables = ElGamalVariables::gen_random(&mut rng);
-            let ciphertexts = inputs.iter().fold(vec![], |mut acc, x| {
-                let res = ElGamalGadget::run((x.to_vec(), variables.clone())).unwrap();
-                acc.extend(res);
-                acc
-            });
-
-            let encrypted_messages = inputs.iter().fold(vec![], |mut acc, x| {
-                let res = ElGamalGadget::encrypt(variables.pk, x.to_vec(), variables.r).c2;
-                acc.push(res);
-                acc
-            });
+            // only the recipient can decrypt, so there is no sensible key to fall back to
+            let key = recipient
+                .ok_or("encrypted values need a recipient, add their public key to the data's `elgamal_recipient`")?
+                .variables(&mut rng);
+            // draw fresh randomness for every tensor, reusing r would leak the difference
+            // between plaintexts
+            let variables = inputs
+                .iter()
+                .map(|_| ElGamalVariables {
+                    r: Fp::random(&mut rng),
+                    ..key.clone()
+                })
+                .collect_vec();
+
+            let ciphertexts = inputs.iter().zip(&variables).try_fold(
+                vec![],
+                |mut acc, (x, variables)| -> Result<_, Box<dyn std::error::Error>> {
+                    let res = ElGamalGadget::run((x.to_vec(), variables.clone()))?;
+                    acc.extend(res);
+                    Ok(acc)
+                },
+            )?;
+
+            let encrypted_messages = inputs
+                .iter()
+                .zip(&variables)
+                .map(|(x, variables)| {
+                    ElGamalGadget::encrypt(variables.pk, x.to_vec(), variables.r).c2
+                })
+                .collect_vec();
 
             elgamal = Some(ElGamalResult {
                 variables,
@@ -496,6 +814,97 @@ impl GraphModules {
             poseidon_hash,
             elgamal,
             kzg_commit,
+            salt: hash_salt,
+            merkle_root,
+            merkle_proofs: merkle_paths,
+            public_keys,
+            signatures: input_signatures,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypted_tensors_use_fresh_randomness() {
+        let recipient_key = ElGamalVariables::gen_random(&mut rand::thread_rng());
+        let recipient = ElGamalRecipient::new(recipient_key.pk);
+
+        let message = Tensor::new(Some(&[Fp::from(1), Fp::from(2)]), &[2]).unwrap();
+        let inputs = vec![message.clone(), message];
+
+        let res = GraphModules::forward(
+            &inputs,
+            Visibility::Encrypted,
+            None,
+            None,
+            Some(&recipient),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let elgamal = res.elgamal.unwrap();
+
+        assert_eq!(elgamal.variables.len(), 2);
+        assert_ne!(elgamal.variables[0].r, elgamal.variables[1].r);
+        // equal plaintexts must not encrypt to equal ciphertexts
+        assert_ne!(elgamal.encrypted_messages[0], elgamal.encrypted_messages[1]);
+        for variables in &elgamal.variables {
+            assert_eq!(variables.pk, recipient_key.pk);
+            assert_eq!(variables.sk, None);
+        }
+    }
+
+    #[test]
+    fn test_encrypted_tensors_need_a_recipient() {
+        let message = Tensor::new(Some(&[Fp::from(1), Fp::from(2)]), &[2]).unwrap();
+
+        let res = GraphModules::forward(
+            &[message],
+            Visibility::Encrypted,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_signed_inputs_need_signatures() {
+        let message = Tensor::new(Some(&[Fp::from(1), Fp::from(2)]), &[2]).unwrap();
+        let inputs = vec![message.clone(), message];
+
+        let forward = |signatures: Option<&[EddsaSignature]>| {
+            GraphModules::forward(
+                &inputs,
+                Visibility::Signed,
+                None,
+                None,
+                None,
+                None,
+                None,
+                signatures,
+            )
+        };
+        // missing signatures aren't made up
+        assert!(forward(None).is_err());
+
+        let mut rng = rand::thread_rng();
+        let secret_key = EddsaSignature::gen_secret_key(&mut rng);
+        let signatures = inputs
+            .iter()
+            .map(|x| EddsaSignature::sign(&secret_key, x.to_vec(), &mut rng).unwrap())
+            .collect_vec();
+        assert!(forward(Some(&signatures[..1])).is_err());
+
+        let res = forward(Some(&signatures)).unwrap();
+        let public_key = EddsaSignature::public_key(&secret_key);
+        assert_eq!(res.public_keys, Some(vec![public_key; 2]));
+    }
+}
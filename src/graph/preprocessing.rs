@@ -0,0 +1,461 @@
+use super::input::{FileSource, FileSourceInner};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// Errors raised when preprocessing graph inputs
+#[derive(Debug, Error)]
+pub enum PreprocessingError {
+    /// Field elements have already been quantized and can't be preprocessed
+    #[error("input {0} contains field elements, which can't be preprocessed")]
+    FieldElements(usize),
+    /// The data doesn't have the number of elements the declared shape expects
+    #[error("input {0} has {1} elements but its shape {2:?} expects {3}")]
+    ShapeMismatch(usize, usize, Vec<usize>, usize),
+    /// A step references an axis the data doesn't have
+    #[error("input {0}: axis {1} is out of range for shape {2:?}")]
+    InvalidAxis(usize, usize, Vec<usize>),
+    /// The normalization parameters don't line up with the normalized axis
+    #[error("input {0}: expected 1 or {1} normalization parameters, got {2} means and {3} stds")]
+    NormalizationParams(usize, usize, usize, usize),
+    /// A standard deviation of zero can't be divided by
+    #[error("input {0}: standard deviations must be non-zero")]
+    ZeroStd(usize),
+    /// A transpose permutation that isn't a permutation of the axes
+    #[error("input {0}: {1:?} is not a permutation of the axes of shape {2:?}")]
+    InvalidPermutation(usize, Vec<usize>, Vec<usize>),
+    /// A categorical value that can't be one-hot encoded
+    #[error("input {0}: categorical value {1} is not an integer in [0, {2})")]
+    InvalidCategory(usize, f64, usize),
+}
+
+/// The type an input is cast to during preprocessing
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CastType {
+    /// Leave the values as floats
+    Float,
+    /// Round the values to the nearest integer
+    Int,
+    /// Map non-zero values to 1 and zero to 0
+    Bool,
+}
+
+/// A single preprocessing step, applied to the raw (unquantized) values of an input
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(remote = "Self", tag = "op", rename_all = "snake_case")]
+pub enum PreprocessStep {
+    /// Computes `(x - mean) / std`, either with a single mean and std or with one per index
+    /// along `axis` (defaults to the last axis)
+    Normalize {
+        /// the means to subtract
+        mean: Vec<f64>,
+        /// the standard deviations to divide by
+        std: Vec<f64>,
+        /// the axis the parameters are indexed along
+        #[serde(default)]
+        axis: Option<usize>,
+    },
+    /// Clamps the values to `[min, max]`
+    Clip {
+        /// lower bound
+        #[serde(default)]
+        min: Option<f64>,
+        /// upper bound
+        #[serde(default)]
+        max: Option<f64>,
+    },
+    /// Casts the values to the given type
+    Cast {
+        /// the type to cast to
+        to: CastType,
+    },
+    /// Reshapes the input, keeping the number of elements
+    Reshape {
+        /// the new shape
+        shape: Vec<usize>,
+    },
+    /// Permutes the axes of the input
+    Transpose {
+        /// `perm[i]` is the axis of the input that becomes axis `i` of the output
+        perm: Vec<usize>,
+    },
+    /// Replaces the categorical field at index `field` of the last axis by `classes` one-hot
+    /// columns. Fields after it shift by `classes - 1` for subsequent steps.
+    OneHot {
+        /// index of the categorical field along the last axis
+        field: usize,
+        /// number of categories
+        classes: usize,
+    },
+}
+
+// Binary formats (e.g. bincode, which compiled circuits are saved in) can't deserialize internally
+// tagged enums, as they don't describe their own structure, so steps are embedded as json in them.
+impl Serialize for PreprocessStep {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            PreprocessStep::serialize(self, serializer)
+        } else {
+            let mut json = vec![];
+            PreprocessStep::serialize(self, &mut serde_json::Serializer::new(&mut json))
+                .map_err(serde::ser::Error::custom)?;
+            serializer.serialize_bytes(&json)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PreprocessStep {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            PreprocessStep::deserialize(deserializer)
+        } else {
+            let json = <Vec<u8>>::deserialize(deserializer)?;
+            PreprocessStep::deserialize(&mut serde_json::Deserializer::from_slice(&json))
+                .map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// The preprocessing applied to a single input before it is quantized
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct InputPreprocessing {
+    /// The shape of the raw input. Defaults to a flat vector. Needed when the steps change
+    /// the number of elements (e.g. one-hot encoding) so raw data can be split into batches.
+    #[serde(default)]
+    pub shape: Option<Vec<usize>>,
+    /// The steps to apply, in order
+    #[serde(default)]
+    pub steps: Vec<PreprocessStep>,
+}
+
+fn strides(dims: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; dims.len()];
+    for i in (0..dims.len().saturating_sub(1)).rev() {
+        strides[i] = strides[i + 1] * dims[i + 1];
+    }
+    strides
+}
+
+impl InputPreprocessing {
+    /// Applies the steps to the raw values of input `idx`
+    pub fn apply(
+        &self,
+        idx: usize,
+        data: &[FileSourceInner],
+    ) -> Result<Vec<FileSourceInner>, PreprocessingError> {
+        if self.steps.is_empty() {
+            return Ok(data.to_vec());
+        }
+        if data.iter().any(|x| x.is_field()) {
+            return Err(PreprocessingError::FieldElements(idx));
+        }
+
+        let mut values: Vec<f64> = data.iter().map(|x| x.to_float()).collect();
+        let mut dims = self.shape.clone().unwrap_or_else(|| vec![values.len()]);
+        if dims.iter().product::<usize>() != values.len() {
+            return Err(PreprocessingError::ShapeMismatch(
+                idx,
+                values.len(),
+                dims.clone(),
+                dims.iter().product(),
+            ));
+        }
+
+        let mut is_bool = false;
+        for step in &self.steps {
+            is_bool = false;
+            match step {
+                PreprocessStep::Normalize { mean, std, axis } => {
+                    let axis = axis.unwrap_or(dims.len().saturating_sub(1));
+                    if axis >= dims.len() {
+                        return Err(PreprocessingError::InvalidAxis(idx, axis, dims));
+                    }
+                    let len = dims[axis];
+                    let valid = |v: &Vec<f64>| v.len() == 1 || v.len() == len;
+                    if !valid(mean) || !valid(std) {
+                        return Err(PreprocessingError::NormalizationParams(
+                            idx,
+                            len,
+                            mean.len(),
+                            std.len(),
+                        ));
+                    }
+                    if std.iter().any(|s| *s == 0.0) {
+                        return Err(PreprocessingError::ZeroStd(idx));
+                    }
+                    let stride = strides(&dims)[axis];
+                    for (j, v) in values.iter_mut().enumerate() {
+                        let k = (j / stride) % len;
+                        let m = mean[k.min(mean.len() - 1)];
+                        let s = std[k.min(std.len() - 1)];
+                        *v = (*v - m) / s;
+                    }
+                }
+                PreprocessStep::Clip { min, max } => {
+                    for v in values.iter_mut() {
+                        if let Some(min) = min {
+                            *v = v.max(*min);
+                        }
+                        if let Some(max) = max {
+                            *v = v.min(*max);
+                        }
+                    }
+                }
+                PreprocessStep::Cast { to } => match to {
+                    CastType::Float => {}
+                    CastType::Int => values.iter_mut().for_each(|v| *v = v.round()),
+                    CastType::Bool => {
+                        values
+                            .iter_mut()
+                            .for_each(|v| *v = if *v != 0.0 { 1.0 } else { 0.0 });
+                        is_bool = true;
+                    }
+                },
+                PreprocessStep::Reshape { shape } => {
+                    let expected = shape.iter().product::<usize>();
+                    if expected != values.len() {
+                        return Err(PreprocessingError::ShapeMismatch(
+                            idx,
+                            values.len(),
+                            shape.clone(),
+                            expected,
+                        ));
+                    }
+                    dims = shape.clone();
+                }
+                PreprocessStep::Transpose { perm } => {
+                    let mut sorted = perm.clone();
+                    sorted.sort();
+                    if sorted != (0..dims.len()).collect::<Vec<_>>() {
+                        return Err(PreprocessingError::InvalidPermutation(
+                            idx,
+                            perm.clone(),
+                            dims,
+                        ));
+                    }
+                    let in_strides = strides(&dims);
+                    let out_dims: Vec<usize> = perm.iter().map(|p| dims[*p]).collect();
+                    let out_strides = strides(&out_dims);
+                    let mut out = vec![0.0; values.len()];
+                    for (j, o) in out.iter_mut().enumerate() {
+                        let src: usize = perm
+                            .iter()
+                            .enumerate()
+                            .map(|(k, p)| ((j / out_strides[k]) % out_dims[k]) * in_strides[*p])
+                            .sum();
+                        *o = values[src];
+                    }
+                    values = out;
+                    dims = out_dims;
+                }
+                PreprocessStep::OneHot { field, classes } => {
+                    let width = *dims.last().unwrap_or(&0);
+                    if *field >= width {
+                        return Err(PreprocessingError::InvalidAxis(idx, *field, dims));
+                    }
+                    let mut out = Vec::with_capacity(values.len() / width * (width + classes - 1));
+                    for row in values.chunks(width) {
+                        let category = row[*field];
+                        if category.fract() != 0.0 || category < 0.0 || category >= *classes as f64
+                        {
+                            return Err(PreprocessingError::InvalidCategory(
+                                idx, category, *classes,
+                            ));
+                        }
+                        out.extend_from_slice(&row[..*field]);
+                        out.extend((0..*classes).map(|c| (c == category as usize) as u8 as f64));
+                        out.extend_from_slice(&row[*field + 1..]);
+                    }
+                    values = out;
+                    if let Some(last) = dims.last_mut() {
+                        *last = width + classes - 1;
+                    }
+                }
+            }
+        }
+
+        Ok(values
+            .into_iter()
+            .map(|v| {
+                if is_bool {
+                    FileSourceInner::new_bool(v != 0.0)
+                } else {
+                    FileSourceInner::new_float(v)
+                }
+            })
+            .collect())
+    }
+}
+
+/// Applies the preprocessing declared for each input. Inputs without a spec are left as is.
+pub fn preprocess(
+    spec: &[InputPreprocessing],
+    data: &FileSource,
+) -> Result<FileSource, PreprocessingError> {
+    data.iter()
+        .enumerate()
+        .map(|(i, d)| match spec.get(i) {
+            Some(s) => s.apply(i, d),
+            None => Ok(d.clone()),
+        })
+        .collect()
+}
+
+/// The shapes of the raw inputs, i.e. before preprocessing, falling back to the model's input
+/// shapes for inputs that don't declare one
+pub fn raw_input_shapes(spec: &[InputPreprocessing], shapes: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    shapes
+        .into_iter()
+        .enumerate()
+        .map(|(i, shape)| spec.get(i).and_then(|s| s.shape.clone()).unwrap_or(shape))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn floats(v: &[f64]) -> Vec<FileSourceInner> {
+        v.iter().map(|x| FileSourceInner::new_float(*x)).collect()
+    }
+
+    #[test]
+    fn test_deserialize_preprocessing() {
+        let json = r#"[{"shape": [2, 3], "steps": [
+            {"op": "normalize", "mean": [1.0], "std": [2.0]},
+            {"op": "clip", "min": -1.0},
+            {"op": "cast", "to": "int"},
+            {"op": "transpose", "perm": [1, 0]},
+            {"op": "reshape", "shape": [6]},
+            {"op": "one_hot", "field": 0, "classes": 3}
+        ]}, {}]"#;
+        let spec: Vec<InputPreprocessing> = serde_json::from_str(json).unwrap();
+        assert_eq!(spec.len(), 2);
+        assert_eq!(spec[0].steps.len(), 6);
+        assert_eq!(
+            spec[0].steps[1],
+            PreprocessStep::Clip {
+                min: Some(-1.0),
+                max: None
+            }
+        );
+        assert_eq!(spec[1], InputPreprocessing::default());
+    }
+
+    #[test]
+    fn test_compiled_circuit_roundtrip() {
+        // compiled circuits are saved with bincode, which must round trip the settings' steps
+        let run_args = crate::RunArgs {
+            input_scale: 7,
+            param_scale: 7,
+            scale_rebase_multiplier: 1,
+            lookup_range: (-32768, 32768),
+            logrows: 17,
+            num_inner_cols: 2,
+            variables: vec![("batch_size".to_string(), 1)],
+            ..Default::default()
+        };
+        let mut circuit = crate::graph::GraphCircuit::from_run_args(
+            &run_args,
+            std::path::Path::new("examples/onnx/1l_average/network.onnx"),
+        )
+        .unwrap();
+        circuit.settings_mut().preprocessing = vec![InputPreprocessing {
+            shape: Some(vec![3, 4]),
+            steps: vec![
+                PreprocessStep::Normalize {
+                    mean: vec![0.5],
+                    std: vec![2.0],
+                    axis: Some(1),
+                },
+                PreprocessStep::Clip {
+                    min: Some(-1.0),
+                    max: None,
+                },
+                PreprocessStep::Cast { to: CastType::Int },
+                PreprocessStep::Transpose { perm: vec![1, 0] },
+                PreprocessStep::Reshape { shape: vec![12] },
+            ],
+        }];
+
+        let mut path = std::env::temp_dir();
+        path.push("ezkl_test_compiled_circuit_roundtrip.compiled");
+        circuit.save(path.clone()).unwrap();
+        let loaded = crate::graph::GraphCircuit::load(path).unwrap();
+        assert_eq!(loaded.settings(), circuit.settings());
+        assert_eq!(loaded.model(), circuit.model());
+    }
+
+    #[test]
+    fn test_preprocess() {
+        // per-column normalization then clipping
+        let spec = InputPreprocessing {
+            shape: Some(vec![2, 2]),
+            steps: vec![
+                PreprocessStep::Normalize {
+                    mean: vec![1.0, 10.0],
+                    std: vec![1.0, 5.0],
+                    axis: None,
+                },
+                PreprocessStep::Clip {
+                    min: None,
+                    max: Some(1.5),
+                },
+            ],
+        };
+        let out = spec.apply(0, &floats(&[2.0, 20.0, 4.0, 5.0])).unwrap();
+        assert_eq!(out, floats(&[1.0, 1.5, 1.5, -1.0]));
+
+        // transposing a 2x3 matrix
+        let spec = InputPreprocessing {
+            shape: Some(vec![2, 3]),
+            steps: vec![PreprocessStep::Transpose { perm: vec![1, 0] }],
+        };
+        let out = spec
+            .apply(0, &floats(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]))
+            .unwrap();
+        assert_eq!(out, floats(&[0.0, 3.0, 1.0, 4.0, 2.0, 5.0]));
+
+        // one-hot encoding the first of two fields in each row
+        let spec = InputPreprocessing {
+            shape: Some(vec![2, 2]),
+            steps: vec![PreprocessStep::OneHot {
+                field: 0,
+                classes: 3,
+            }],
+        };
+        let out = spec.apply(0, &floats(&[2.0, 0.5, 0.0, 0.7])).unwrap();
+        assert_eq!(out, floats(&[0.0, 0.0, 1.0, 0.5, 1.0, 0.0, 0.0, 0.7]));
+        assert!(spec.apply(0, &floats(&[3.0, 0.5, 0.0, 0.7])).is_err());
+
+        // casting to bools
+        let spec = InputPreprocessing {
+            shape: None,
+            steps: vec![PreprocessStep::Cast { to: CastType::Bool }],
+        };
+        let out = spec.apply(0, &floats(&[0.0, 2.0])).unwrap();
+        assert_eq!(
+            out,
+            vec![
+                FileSourceInner::new_bool(false),
+                FileSourceInner::new_bool(true)
+            ]
+        );
+
+        // mismatched shapes and inputs without a spec
+        let spec = vec![InputPreprocessing {
+            shape: Some(vec![3]),
+            steps: vec![PreprocessStep::Cast { to: CastType::Int }],
+        }];
+        assert!(preprocess(&spec, &vec![floats(&[1.0, 2.0])]).is_err());
+        let data = vec![floats(&[1.4, 2.6, 3.0]), floats(&[0.3])];
+        assert_eq!(
+            preprocess(&spec, &data).unwrap(),
+            vec![floats(&[1.0, 3.0, 3.0]), floats(&[0.3])]
+        );
+        assert_eq!(
+            raw_input_shapes(&spec, vec![vec![1, 3], vec![1]]),
+            vec![vec![3], vec![1]]
+        );
+    }
+}
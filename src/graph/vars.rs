@@ -1,5 +1,6 @@
 use std::error::Error;
 
+use crate::circuit::modules::poseidon::PoseidonParams;
 use crate::tensor::TensorType;
 use crate::tensor::{ValTensor, VarTensor};
 use crate::RunArgs;
@@ -33,47 +34,108 @@ pub enum Visibility {
         hash_is_public: bool,
         ///
         outlets: Vec<usize>,
+        /// The Poseidon parameters the hash is computed with, set with an `@` suffix (eg. `hashed/public@t5`)
+        #[serde(default)]
+        params: PoseidonParams,
+        /// Whether a random salt is absorbed into the hash, making it a hiding commitment, set with a `+salted` suffix (eg. `hashed/public+salted`)
+        #[serde(default)]
+        salted: bool,
     },
     /// Mark an item as publicly committed to (KZG commitment sent in the proof submitted for verification)
     KZGCommit,
+    /// Mark an item as committed to by the root of a Poseidon Merkle tree (root sent in the proof submitted for verification)
+    Merkle {
+        /// If set (eg. `merkle/10`), the item is instead a leaf of a tree of this depth (a dataset), and is opened
+        /// against its root with a (private) authentication path supplied with the input data
+        #[serde(default)]
+        depth: Option<usize>,
+    },
+    /// Mark an item as signed by a data provider, the EdDSA signature over its hash is verified in the circuit
+    /// (signer's public key sent in the proof submitted for verification, the signature is private).
+    /// Signatures are made with ezkl's `eddsa_sign`, circomlib's EdDSA signatures don't verify
+    Signed,
     /// Mark an item as encrypted (public key and encrypted message sent in the proof submitted for verificatio)
     Encrypted,
     /// assigned as a constant in the circuit
     Fixed,
 }
 
-impl<'a> From<&'a str> for Visibility {
-    fn from(s: &'a str) -> Self {
-        if s.contains("hashed/private") {
-            // split on last occurence of '/'
-            let (_, outlets) = s.split_at(s.rfind('/').unwrap());
+/// Splits the suffixes of a hashed visibility off, in either order: the Poseidon parameter set
+/// (eg. `hashed/public@t5`) and the salt flag (eg. `hashed/public+salted`)
+fn split_hash_suffixes(s: &str) -> Result<(&str, Option<PoseidonParams>, bool), String> {
+    let mut rest = s;
+    let mut params = None;
+    let mut salted = false;
+    loop {
+        if let Some(r) = rest.strip_suffix("+salted") {
+            if salted {
+                return Err(format!("repeated +salted suffix in visibility {}", s));
+            }
+            salted = true;
+            rest = r;
+        } else if let Some((r, p)) = rest.rsplit_once('@') {
+            if params.is_some() {
+                return Err(format!("repeated @ suffix in visibility {}", s));
+            }
+            params = Some(p.parse()?);
+            rest = r;
+        } else {
+            return Ok((rest, params, salted));
+        }
+    }
+}
+
+impl std::str::FromStr for Visibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (base, params, salted) = split_hash_suffixes(s)?;
+        let hashed = |hash_is_public: bool, outlets: Vec<usize>| Visibility::Hashed {
+            hash_is_public,
+            outlets,
+            params: params.unwrap_or_default(),
+            salted,
+        };
+
+        let visibility = if let Some(outlets) = base.strip_prefix("hashed/private") {
+            // the outlets are optional (eg. for a per-tensor visibility)
             let outlets = outlets
                 .trim_start_matches('/')
                 .split(',')
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect_vec();
-
-            return Visibility::Hashed {
-                hash_is_public: false,
-                outlets,
-            };
-        }
-        match s {
-            "private" => Visibility::Private,
-            "public" => Visibility::Public,
-            "kzgcommit" => Visibility::KZGCommit,
-            "fixed" => Visibility::Fixed,
-            "hashed" | "hashed/public" => Visibility::Hashed {
-                hash_is_public: true,
-                outlets: vec![],
-            },
-            "encrypted" => Visibility::Encrypted,
-            _ => {
-                log::error!("Invalid value for Visibility: {}", s);
-                log::warn!("Defaulting to private");
-                Visibility::Private
+                .filter(|s| !s.is_empty())
+                .map(|s| {
+                    s.parse::<usize>()
+                        .map_err(|_| format!("invalid hashed outlet: {}", s))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            hashed(false, outlets)
+        } else if let Some(depth) = base.strip_prefix("merkle/") {
+            let depth = depth
+                .parse::<usize>()
+                .map_err(|_| format!("invalid merkle depth: {}", depth))?;
+            Visibility::Merkle { depth: Some(depth) }
+        } else {
+            match base {
+                "private" => Visibility::Private,
+                "public" => Visibility::Public,
+                "kzgcommit" => Visibility::KZGCommit,
+                "merkle" => Visibility::Merkle { depth: None },
+                "signed" => Visibility::Signed,
+                "fixed" => Visibility::Fixed,
+                "hashed" | "hashed/public" => hashed(true, vec![]),
+                "encrypted" => Visibility::Encrypted,
+                _ => return Err(format!("invalid value for visibility: {}", s)),
             }
+        };
+
+        // the suffixes only apply to hashes
+        if !visibility.is_hashed() && (params.is_some() || salted) {
+            return Err(format!(
+                "only hashed visibilities take a poseidon parameter set or a salt, got {}",
+                s
+            ));
         }
+        Ok(visibility)
     }
 }
 
@@ -81,28 +143,7 @@ impl<'a> From<&'a str> for Visibility {
 /// Converts Visibility into a PyObject (Required for Visibility to be compatible with Python)
 impl IntoPy<PyObject> for Visibility {
     fn into_py(self, py: Python) -> PyObject {
-        match self {
-            Visibility::Private => "private".to_object(py),
-            Visibility::Public => "public".to_object(py),
-            Visibility::Fixed => "fixed".to_object(py),
-            Visibility::KZGCommit => "kzgcommit".to_object(py),
-            Visibility::Hashed {
-                hash_is_public,
-                outlets,
-            } => {
-                if hash_is_public {
-                    "hashed/public".to_object(py)
-                } else {
-                    let outlets = outlets
-                        .iter()
-                        .map(|o| o.to_string())
-                        .collect_vec()
-                        .join(",");
-                    format!("hashed/private/{}", outlets).to_object(py)
-                }
-            }
-            Visibility::Encrypted => "encrypted".to_object(py),
-        }
+        self.to_string().to_object(py)
     }
 }
 
@@ -113,39 +154,7 @@ impl<'source> FromPyObject<'source> for Visibility {
         let trystr = <PyString as PyTryFrom>::try_from(ob)?;
         let strval = trystr.to_string();
 
-        let strval = strval.as_str();
-
-        if strval.contains("hashed/private") {
-            // split on last occurence of '/'
-            let (_, outlets) = strval.split_at(strval.rfind('/').unwrap());
-            let outlets = outlets
-                .trim_start_matches('/')
-                .split(',')
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect_vec();
-
-            return Ok(Visibility::Hashed {
-                hash_is_public: false,
-                outlets,
-            });
-        }
-
-        match strval.to_lowercase().as_str() {
-            "private" => Ok(Visibility::Private),
-            "public" => Ok(Visibility::Public),
-            "kzgcommit" => Ok(Visibility::KZGCommit),
-            "hashed" => Ok(Visibility::Hashed {
-                hash_is_public: true,
-                outlets: vec![],
-            }),
-            "hashed/public" => Ok(Visibility::Hashed {
-                hash_is_public: true,
-                outlets: vec![],
-            }),
-            "fixed" => Ok(Visibility::Fixed),
-            "encrypted" => Ok(Visibility::Encrypted),
-            _ => Err(PyValueError::new_err("Invalid value for Visibility")),
-        }
+        strval.to_lowercase().parse().map_err(PyValueError::new_err)
     }
 }
 
@@ -171,6 +180,21 @@ impl Visibility {
     pub fn is_kzgcommit(&self) -> bool {
         matches!(&self, Visibility::KZGCommit)
     }
+    #[allow(missing_docs)]
+    pub fn is_merkle(&self) -> bool {
+        matches!(&self, Visibility::Merkle { .. })
+    }
+    /// The depth of the tree a merkle visibility opens its item against, if it is a leaf of one
+    pub fn merkle_depth(&self) -> Option<usize> {
+        if let Visibility::Merkle { depth } = self {
+            return *depth;
+        }
+        None
+    }
+    #[allow(missing_docs)]
+    pub fn is_signed(&self) -> bool {
+        matches!(&self, Visibility::Signed)
+    }
 
     #[allow(missing_docs)]
     pub fn is_hashed_public(&self) -> bool {
@@ -203,6 +227,19 @@ impl Visibility {
         matches!(&self, Visibility::Encrypted)
             | matches!(&self, Visibility::Hashed { .. })
             | matches!(&self, Visibility::KZGCommit)
+            | matches!(&self, Visibility::Merkle { .. })
+            | matches!(&self, Visibility::Signed)
+    }
+    /// Whether a hashed visibility absorbs a salt into the hash
+    pub fn is_salted(&self) -> bool {
+        matches!(&self, Visibility::Hashed { salted: true, .. })
+    }
+    /// The Poseidon parameters of a hashed visibility
+    pub fn poseidon_params(&self) -> Option<PoseidonParams> {
+        if let Visibility::Hashed { params, .. } = self {
+            return Some(*params);
+        }
+        None
     }
     #[allow(missing_docs)]
     pub fn overwrites_inputs(&self) -> Vec<usize> {
@@ -216,10 +253,33 @@ impl std::fmt::Display for Visibility {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             Visibility::KZGCommit => write!(f, "kzgcommit"),
+            Visibility::Merkle { depth: None } => write!(f, "merkle"),
+            Visibility::Merkle { depth: Some(depth) } => write!(f, "merkle/{}", depth),
+            Visibility::Signed => write!(f, "signed"),
             Visibility::Private => write!(f, "private"),
             Visibility::Public => write!(f, "public"),
             Visibility::Fixed => write!(f, "fixed"),
-            Visibility::Hashed { .. } => write!(f, "hashed"),
+            Visibility::Hashed {
+                hash_is_public,
+                outlets,
+                params,
+                salted,
+            } => {
+                if *hash_is_public {
+                    write!(f, "hashed/public")?;
+                } else if outlets.is_empty() {
+                    write!(f, "hashed/private")?;
+                } else {
+                    write!(f, "hashed/private/{}", outlets.iter().join(","))?;
+                }
+                if !params.is_default() {
+                    write!(f, "@{}", params)?;
+                }
+                if *salted {
+                    write!(f, "+salted")?;
+                }
+                Ok(())
+            }
             Visibility::Encrypted => write!(f, "encrypted"),
         }
     }
@@ -261,13 +321,137 @@ impl VarScales {
 /// Represents whether the model input, model parameters, and model output are Public or Private to the prover.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, PartialOrd)]
 pub struct VarVisibility {
-    /// Input to the model or computational graph
+    /// Input to the model or computational graph (if set per input, the visibility the processed inputs share)
     pub input: Visibility,
-    /// Parameters, such as weights and biases, in the model
+    /// Parameters, such as weights and biases, in the model (if set per tensor, the visibility the processed params share)
     pub params: Visibility,
-    /// Output of the model or computational graph
+    /// Output of the model or computational graph (if set per output, the visibility the processed outputs share)
     pub output: Visibility,
+    /// The visibility of each input, if set per input
+    #[serde(default)]
+    pub per_input: Vec<Visibility>,
+    /// The visibility of each param tensor, if set per tensor
+    #[serde(default)]
+    pub per_param: Vec<Visibility>,
+    /// The visibility of each output, if set per output
+    #[serde(default)]
+    pub per_output: Vec<Visibility>,
+}
+impl VarVisibility {
+    /// The Poseidon parameters hashed variables are hashed with
+    pub fn poseidon_params(&self) -> PoseidonParams {
+        [&self.input, &self.params, &self.output]
+            .iter()
+            .find_map(|v| v.poseidon_params())
+            .unwrap_or_default()
+    }
+
+    /// The visibility of the input at `idx`
+    pub fn input_at(&self, idx: usize) -> &Visibility {
+        self.per_input.get(idx).unwrap_or(&self.input)
+    }
+
+    /// The visibility of the output at `idx`
+    pub fn output_at(&self, idx: usize) -> &Visibility {
+        self.per_output.get(idx).unwrap_or(&self.output)
+    }
+
+    /// The indices of the inputs processed by the input module (eg. hashed), out of `num_inputs`
+    pub fn input_outlets(&self, num_inputs: usize) -> Vec<usize> {
+        Self::outlets(&self.input, &self.per_input, num_inputs)
+    }
+
+    /// The indices of the param tensors processed by the params module, out of `num_params`
+    pub fn param_outlets(&self, num_params: usize) -> Vec<usize> {
+        Self::outlets(&self.params, &self.per_param, num_params)
+    }
+
+    /// The indices of the outputs processed by the output module, out of `num_outputs`
+    pub fn output_outlets(&self, num_outputs: usize) -> Vec<usize> {
+        Self::outlets(&self.output, &self.per_output, num_outputs)
+    }
+
+    fn outlets(shared: &Visibility, per_tensor: &[Visibility], num: usize) -> Vec<usize> {
+        if !per_tensor.is_empty() {
+            (0..num)
+                .filter(|i| per_tensor.get(*i).unwrap_or(shared).requires_processing())
+                .collect()
+        } else if !shared.requires_processing() {
+            vec![]
+        } else if !shared.overwrites_inputs().is_empty() {
+            shared.overwrites_inputs()
+        } else {
+            (0..num).collect()
+        }
+    }
+
+    /// Whether any visibility is set per tensor
+    pub fn is_per_tensor(&self) -> bool {
+        !self.per_input.is_empty() || !self.per_param.is_empty() || !self.per_output.is_empty()
+    }
+
+    /// Checks a visibility is set for every tensor of the model, when they are set per tensor
+    pub fn check_num_tensors(
+        &self,
+        num_inputs: usize,
+        num_params: usize,
+        num_outputs: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        for (name, per_tensor, num) in [
+            ("input", &self.per_input, num_inputs),
+            ("param", &self.per_param, num_params),
+            ("output", &self.per_output, num_outputs),
+        ] {
+            if !per_tensor.is_empty() && per_tensor.len() != num {
+                return Err(format!(
+                    "the model has {} {} tensors but {} {} visibilities were set",
+                    num,
+                    name,
+                    per_tensor.len(),
+                    name
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// The visibility the processed tensors of a category share, given the visibility of each of them.
+    /// Tensors of a category are processed together, so can't be processed in different ways.
+    fn shared_visibility(
+        name: &str,
+        default: &Visibility,
+        per_tensor: &[Visibility],
+    ) -> Result<Visibility, Box<dyn Error>> {
+        if per_tensor.is_empty() {
+            return Ok(default.clone());
+        }
+        if per_tensor.iter().any(|v| !v.overwrites_inputs().is_empty()) {
+            return Err(format!(
+                "the outlets of a per-{} visibility are given by its position, use `hashed/private` without outlets",
+                name
+            )
+            .into());
+        }
+        let processed = per_tensor
+            .iter()
+            .filter(|v| v.requires_processing())
+            .sorted()
+            .dedup()
+            .collect_vec();
+        match processed[..] {
+            [] => Ok(Visibility::Private),
+            [shared] => Ok((*shared).clone()),
+            _ => Err(format!(
+                "{} tensors can only be processed one way, got {}",
+                name,
+                processed.iter().join(", ")
+            )
+            .into()),
+        }
+    }
 }
+
 impl std::fmt::Display for VarVisibility {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
@@ -282,38 +466,68 @@ impl VarVisibility {
     /// Read from cli args whether the model input, model parameters, and model output are Public or Private to the prover.
     /// Place in [VarVisibility] struct.
     pub fn from_args(args: &RunArgs) -> Result<Self, Box<dyn Error>> {
-        let input_vis = &args.input_visibility;
-        let params_vis = &args.param_visibility;
-        let output_vis = &args.output_visibility;
-
-        if params_vis.is_public() {
+        let input_vis =
+            &Self::shared_visibility("input", &args.input_visibility, &args.input_visibilities)?;
+        let params_vis =
+            &Self::shared_visibility("param", &args.param_visibility, &args.param_visibilities)?;
+        let output_vis =
+            &Self::shared_visibility("output", &args.output_visibility, &args.output_visibilities)?;
+
+        if params_vis.is_public() || args.param_visibilities.iter().any(|v| v.is_public()) {
             return Err(
                 "public visibility for params is deprecated, please use `fixed` instead".into(),
             );
         }
 
-        if !output_vis.is_public()
-            & !params_vis.is_public()
-            & !input_vis.is_public()
-            & !output_vis.is_fixed()
-            & !params_vis.is_fixed()
-            & !input_vis.is_fixed()
-            & !output_vis.is_hashed()
-            & !params_vis.is_hashed()
-            & !input_vis.is_hashed()
-            & !output_vis.is_encrypted()
-            & !params_vis.is_encrypted()
-            & !input_vis.is_encrypted()
-            & !output_vis.is_kzgcommit()
-            & !params_vis.is_kzgcommit()
-            & !input_vis.is_kzgcommit()
+        // every variable can't be private
+        if [input_vis, params_vis, output_vis]
+            .into_iter()
+            .chain(&args.input_visibilities)
+            .chain(&args.param_visibilities)
+            .chain(&args.output_visibilities)
+            .all(|v| *v == Visibility::Private)
         {
             return Err(Box::new(GraphError::Visibility));
         }
+
+        // every hashed variable, per-tensor ones included, shares a single poseidon chip
+        let poseidon_params = [input_vis, params_vis, output_vis]
+            .into_iter()
+            .chain(&args.input_visibilities)
+            .chain(&args.param_visibilities)
+            .chain(&args.output_visibilities)
+            .filter_map(|v| v.poseidon_params())
+            .sorted()
+            .dedup()
+            .collect_vec();
+        if poseidon_params.len() > 1 {
+            return Err(format!(
+                "hashed variables must use the same poseidon parameters, got {}",
+                poseidon_params.iter().join(", ")
+            )
+            .into());
+        }
+
+        // only inputs can be drawn from a larger (published) tree
+        if params_vis.merkle_depth().is_some() || output_vis.merkle_depth().is_some() {
+            return Err(
+                "merkle membership (merkle/<depth>) is only supported for inputs, use `merkle` to commit to params or outputs"
+                    .into(),
+            );
+        }
+
+        // signatures are supplied with the input data
+        if params_vis.is_signed() || output_vis.is_signed() {
+            return Err("signed visibility is only supported for inputs".into());
+        }
+
         Ok(Self {
             input: input_vis.clone(),
             params: params_vis.clone(),
             output: output_vis.clone(),
+            per_input: args.input_visibilities.clone(),
+            per_param: args.param_visibilities.clone(),
+            per_output: args.output_visibilities.clone(),
         })
     }
 }
@@ -438,3 +652,110 @@ impl<F: PrimeField + TensorType + PartialOrd> ModelVars<F> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visibility_from_str() {
+        assert_eq!(
+            "hashed/public@t5".parse::<Visibility>(),
+            Ok(Visibility::Hashed {
+                hash_is_public: true,
+                outlets: vec![],
+                params: PoseidonParams::T5,
+                salted: false,
+            })
+        );
+        assert_eq!(
+            "hashed/private/0,1+salted".parse::<Visibility>(),
+            Ok(Visibility::Hashed {
+                hash_is_public: false,
+                outlets: vec![0, 1],
+                params: PoseidonParams::default(),
+                salted: true,
+            })
+        );
+        assert_eq!(
+            "merkle/3".parse::<Visibility>(),
+            Ok(Visibility::Merkle { depth: Some(3) })
+        );
+        assert_eq!(
+            Visibility::Merkle { depth: Some(3) }.to_string(),
+            "merkle/3"
+        );
+        // a bad parameter set is an error rather than a fallback to the default
+        assert!("hashed@t4".parse::<Visibility>().is_err());
+        assert!("hashed@t3".parse::<Visibility>().is_err());
+        assert!("merkle/deep".parse::<Visibility>().is_err());
+        assert!("secret".parse::<Visibility>().is_err());
+    }
+
+    #[test]
+    fn test_visibility_suffixes() {
+        let salted_t5 = Visibility::Hashed {
+            hash_is_public: true,
+            outlets: vec![],
+            params: PoseidonParams::T5,
+            salted: true,
+        };
+        // the suffixes go in either order
+        assert_eq!(
+            "hashed@t5+salted".parse::<Visibility>(),
+            Ok(salted_t5.clone())
+        );
+        assert_eq!("hashed+salted@t5".parse::<Visibility>(), Ok(salted_t5));
+
+        // only hashes take suffixes
+        for s in [
+            "public@t5",
+            "private+salted",
+            "merkle/4+salted",
+            "merkle@t5",
+            "kzgcommit+salted",
+            "signed@t2",
+        ] {
+            assert!(s.parse::<Visibility>().is_err(), "{} should not parse", s);
+        }
+        // nor can they be repeated or garbled
+        for s in ["hashed+salted+salted", "hashed@t5@t5", "hashed@t5+pepper"] {
+            assert!(s.parse::<Visibility>().is_err(), "{} should not parse", s);
+        }
+    }
+
+    #[test]
+    fn test_visibility_display_round_trips() {
+        for s in [
+            "private",
+            "public",
+            "fixed",
+            "kzgcommit",
+            "merkle",
+            "merkle/3",
+            "signed",
+            "encrypted",
+            "hashed/public",
+            "hashed/private",
+            "hashed/private/0,1",
+            "hashed/public@t5",
+            "hashed/private/2+salted",
+            "hashed/public@t5+salted",
+        ] {
+            let visibility = s.parse::<Visibility>().unwrap();
+            assert_eq!(visibility.to_string(), s);
+            assert_eq!(visibility.to_string().parse::<Visibility>(), Ok(visibility));
+        }
+    }
+
+    #[test]
+    fn test_per_tensor_poseidon_params_must_agree() {
+        let args = |output: &str| RunArgs {
+            input_visibilities: vec!["hashed/public@t5".parse().unwrap(), Visibility::Public],
+            output_visibility: output.parse().unwrap(),
+            ..RunArgs::default()
+        };
+        assert!(VarVisibility::from_args(&args("hashed/public@t5")).is_ok());
+        assert!(VarVisibility::from_args(&args("hashed/public")).is_err());
+    }
+}
@@ -32,6 +32,7 @@
 use circuit::Tolerance;
 use clap::Args;
 use graph::Visibility;
+use pfsys::{Commitments, MultiOpenScheme};
 use serde::{Deserialize, Serialize};
 
 /// Methods for configuring tensor operations and assigning values to them in a Halo2 circuit.
@@ -62,6 +63,8 @@ pub mod hub;
 pub mod logger;
 /// Tools for proofs and verification used by cli
 pub mod pfsys;
+/// Opt-in wall time and peak memory profiling of the proving pipeline
+pub mod profiler;
 /// Python bindings
 #[cfg(feature = "python-bindings")]
 pub mod python;
@@ -110,6 +113,35 @@ pub struct RunArgs {
     /// Flags whether params are public, private, hashed
     #[arg(long, default_value = "private")]
     pub param_visibility: Visibility,
+    /// The visibility of each input, in order (repeat the flag once per input), overrides input_visibility.
+    /// The processed inputs (eg. hashed, encrypted) must all be processed the same way, mixing eg. hashed and kzgcommit is rejected
+    #[arg(long)]
+    #[serde(default)]
+    pub input_visibilities: Vec<Visibility>,
+    /// The visibility of each output, in order (repeat the flag once per output), overrides output_visibility.
+    /// The processed outputs (eg. hashed, encrypted) must all be processed the same way, mixing eg. hashed and kzgcommit is rejected
+    #[arg(long)]
+    #[serde(default)]
+    pub output_visibilities: Vec<Visibility>,
+    /// The visibility of each param tensor, in order (repeat the flag once per param tensor), overrides param_visibility.
+    /// The processed params (eg. hashed, encrypted) must all be processed the same way, mixing eg. hashed and kzgcommit is rejected
+    #[arg(long)]
+    #[serde(default)]
+    pub param_visibilities: Vec<Visibility>,
+    /// The polynomial commitment scheme to use (kzg requires a trusted setup, ipa does not)
+    #[arg(long, default_value = "kzg")]
+    #[serde(default)]
+    pub commitment: Commitments,
+    /// The kzg multi-open scheme to use for proving and verification. Proof aggregation only supports
+    /// shplonk, the aggregation circuit and its accumulator verify shplonk openings
+    #[arg(long, default_value = "shplonk")]
+    #[serde(default)]
+    pub multi_open: MultiOpenScheme,
+    /// Commit the block on-chain inputs are read at (its number and hash) to the public instances,
+    /// so that the data attestation contract can check it with `blockhash`
+    #[arg(long, default_value = "false")]
+    #[serde(default)]
+    pub commit_on_chain_block: bool,
 }
 
 impl RunArgs {
@@ -127,6 +159,29 @@ impl RunArgs {
         if self.num_inner_cols < 1 {
             return Err("num_inner_cols must be >= 1".into());
         }
+        if self.commitment == Commitments::IPA
+            && [
+                &self.input_visibility,
+                &self.param_visibility,
+                &self.output_visibility,
+            ]
+            .into_iter()
+            .chain(&self.input_visibilities)
+            .chain(&self.param_visibilities)
+            .chain(&self.output_visibilities)
+            .any(|v| v.is_kzgcommit())
+        {
+            return Err(Box::new(pfsys::PfSysError::UnsupportedCommitment(
+                "kzgcommit visibility".to_string(),
+                Commitments::KZG,
+            )));
+        }
+        if self.commitment == Commitments::IPA && self.multi_open != MultiOpenScheme::default() {
+            return Err(Box::new(pfsys::PfSysError::UnsupportedCommitment(
+                format!("the {} multi-open scheme", self.multi_open),
+                Commitments::KZG,
+            )));
+        }
         Ok(())
     }
 
@@ -0,0 +1,230 @@
+use crate::pfsys::TranscriptType;
+use halo2curves::{CurveAffine, CurveExt};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{BufRead, Read, Write};
+use thiserror::Error;
+
+/// Magic bytes prefixing every ezkl binary container
+pub const MAGIC: [u8; 4] = *b"EZKL";
+/// Version of the container layout itself (not of ezkl)
+pub const FORMAT_VERSION: u8 = 1;
+
+/// A sha256 digest of the circuit defining settings a key or proof was generated for
+pub type SettingsHash = [u8; 32];
+
+/// Types that can be digested into a [SettingsHash]
+pub trait SettingsDigest {
+    /// Hash of the settings that determine the circuit (and hence its keys)
+    fn settings_hash(&self) -> Result<SettingsHash, ContainerError>;
+}
+
+impl SettingsDigest for () {
+    fn settings_hash(&self) -> Result<SettingsHash, ContainerError> {
+        Ok(hash_bytes(&[]))
+    }
+}
+
+/// sha256 of a byte string, used to compute [SettingsHash]es
+pub fn hash_bytes(bytes: &[u8]) -> SettingsHash {
+    Sha256::digest(bytes).into()
+}
+
+/// The artifact stored in a container
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContainerKind {
+    /// A [crate::pfsys::Snark]
+    Proof,
+    /// A halo2 verifying key
+    VerifyingKey,
+    /// A halo2 proving key
+    ProvingKey,
+}
+
+#[derive(Error, Debug)]
+/// Errors related to binary containers
+pub enum ContainerError {
+    /// Failed to read or write the container
+    #[error("[io] {0}")]
+    Io(#[from] std::io::Error),
+    /// Failed to (de)serialize the header or payload
+    #[error("failed to (de)serialize container: {0}")]
+    Serialization(#[from] bincode::Error),
+    /// The container layout is newer than this version of ezkl understands
+    #[error("unsupported container format version {0}")]
+    FormatVersion(u8),
+    /// The container holds a different kind of artifact
+    #[error("expected a {expected:?} container but found a {found:?}")]
+    Kind {
+        /// the kind we wanted
+        expected: ContainerKind,
+        /// the kind we found
+        found: ContainerKind,
+    },
+    /// The container was generated over a different curve
+    #[error("container was generated over the {found} curve but {expected} was expected")]
+    Curve {
+        /// the curve we wanted
+        expected: String,
+        /// the curve we found
+        found: String,
+    },
+    /// The container was generated for different settings
+    #[error("{0:?} was generated for different circuit settings, regenerate it or use the settings it was generated with")]
+    SettingsMismatch(ContainerKind),
+}
+
+/// Header prefixed to ezkl proofs, verifying keys and proving keys
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContainerHeader {
+    /// version of the container layout
+    pub format_version: u8,
+    /// the artifact stored after the header
+    pub kind: ContainerKind,
+    /// ezkl version the artifact was generated with
+    pub ezkl_version: String,
+    /// hash of the settings the artifact was generated for (if known)
+    pub settings_hash: Option<SettingsHash>,
+    /// transcript type (for proofs)
+    pub transcript_type: Option<TranscriptType>,
+    /// the curve the artifact is defined over
+    pub curve: String,
+}
+
+/// Identifier of the curve `C`, as recorded in container headers
+pub fn curve_id<C: CurveAffine>() -> String {
+    <C::CurveExt as CurveExt>::CURVE_ID.to_string()
+}
+
+impl ContainerHeader {
+    /// Create a header for an artifact over the curve `C`
+    pub fn new<C: CurveAffine>(
+        kind: ContainerKind,
+        settings_hash: Option<SettingsHash>,
+        transcript_type: Option<TranscriptType>,
+    ) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            kind,
+            ezkl_version: env!("CARGO_PKG_VERSION").to_string(),
+            settings_hash,
+            transcript_type,
+            curve: curve_id::<C>(),
+        }
+    }
+
+    /// Writes the magic bytes and header to `writer`
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), ContainerError> {
+        let encoded = bincode::serialize(self)?;
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&(encoded.len() as u32).to_le_bytes())?;
+        writer.write_all(&encoded)?;
+        Ok(())
+    }
+
+    /// Reads a header from `reader`, leaving it positioned at the payload.
+    /// Returns `None` (and consumes nothing) for legacy artifacts that have no header.
+    pub fn read<R: BufRead>(reader: &mut R) -> Result<Option<Self>, ContainerError> {
+        if !reader.fill_buf()?.starts_with(&MAGIC) {
+            return Ok(None);
+        }
+        reader.consume(MAGIC.len());
+
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        let mut encoded = vec![0u8; u32::from_le_bytes(len) as usize];
+        reader.read_exact(&mut encoded)?;
+
+        let header: Self = bincode::deserialize(&encoded)?;
+        if header.format_version > FORMAT_VERSION {
+            return Err(ContainerError::FormatVersion(header.format_version));
+        }
+        Ok(Some(header))
+    }
+
+    /// Splits an in-memory artifact into its (optional) header and payload
+    pub fn split(bytes: &[u8]) -> Result<(Option<Self>, &[u8]), ContainerError> {
+        let mut reader = bytes;
+        let header = Self::read(&mut reader)?;
+        Ok((header, reader))
+    }
+
+    /// Checks that the header describes a `kind` artifact over `C` generated for `settings_hash`
+    pub fn validate<C: CurveAffine>(
+        &self,
+        kind: ContainerKind,
+        settings_hash: Option<SettingsHash>,
+    ) -> Result<(), ContainerError> {
+        if self.kind != kind {
+            return Err(ContainerError::Kind {
+                expected: kind,
+                found: self.kind,
+            });
+        }
+        let curve = curve_id::<C>();
+        if self.curve != curve {
+            return Err(ContainerError::Curve {
+                expected: curve,
+                found: self.curve.clone(),
+            });
+        }
+        if let (Some(expected), Some(found)) = (settings_hash, self.settings_hash) {
+            if expected != found {
+                return Err(ContainerError::SettingsMismatch(kind));
+            }
+        }
+        if self.ezkl_version != env!("CARGO_PKG_VERSION") {
+            warn!(
+                "{:?} was generated with ezkl {} but this is ezkl {}",
+                kind,
+                self.ezkl_version,
+                env!("CARGO_PKG_VERSION")
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2curves::bn256::G1Affine;
+
+    #[test]
+    fn test_header_roundtrip() {
+        let header = ContainerHeader::new::<G1Affine>(
+            ContainerKind::Proof,
+            Some(hash_bytes(b"settings")),
+            Some(TranscriptType::EVM),
+        );
+        let mut bytes = vec![];
+        header.write(&mut bytes).unwrap();
+        bytes.extend_from_slice(&[1, 2, 3]);
+
+        let (read, payload) = ContainerHeader::split(&bytes).unwrap();
+        assert_eq!(read, Some(header.clone()));
+        assert_eq!(payload, &[1, 2, 3]);
+
+        assert!(header
+            .validate::<G1Affine>(ContainerKind::Proof, Some(hash_bytes(b"settings")))
+            .is_ok());
+        assert!(matches!(
+            header.validate::<G1Affine>(ContainerKind::VerifyingKey, None),
+            Err(ContainerError::Kind { .. })
+        ));
+        assert!(matches!(
+            header.validate::<G1Affine>(ContainerKind::Proof, Some(hash_bytes(b"other"))),
+            Err(ContainerError::SettingsMismatch(ContainerKind::Proof))
+        ));
+    }
+
+    #[test]
+    fn test_legacy_artifacts_have_no_header() {
+        // raw halo2 keys start with the (big endian) domain size
+        let bytes = [0u8, 0, 0, 17, 42];
+        let (header, payload) = ContainerHeader::split(&bytes).unwrap();
+        assert!(header.is_none());
+        assert_eq!(payload, &bytes);
+    }
+}
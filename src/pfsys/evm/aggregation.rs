@@ -1,3 +1,6 @@
+use crate::graph::input::ON_CHAIN_BLOCK_INSTANCES;
+use crate::graph::modules::{EDDSA_INSTANCES, ELGAMAL_INSTANCES, POSEIDON_INSTANCES};
+use crate::graph::{GraphSettings, VarVisibility, Visibility};
 use crate::pfsys::{Snark, SnarkWitness};
 use halo2_proofs::circuit::AssignedCell;
 use halo2_proofs::plonk::{self};
@@ -18,6 +21,7 @@ use halo2curves::ff::PrimeField;
 use itertools::Itertools;
 use log::trace;
 use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
 use snark_verifier::loader::native::NativeLoader;
 use snark_verifier::loader::EcPointLoader;
 use snark_verifier::{
@@ -70,6 +74,236 @@ pub enum AggregationError {
     /// proof creation errors
     #[error("Failed to create proof")]
     ProofCreate,
+    /// the instances of an aggregated snark don't match its settings
+    #[error("Invalid instance layout: {0}")]
+    InstanceLayout(String),
+}
+
+/// A named range of the public instances of a proof
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct InstanceRange {
+    /// name of the range, e.g. `input_0`, `output_0_hash` or `params`
+    pub name: String,
+    /// offset of the range in the proof's instances
+    pub offset: usize,
+    /// number of instances in the range
+    pub len: usize,
+    /// shape of the tensor the range holds (for public inputs and outputs)
+    pub shape: Option<Vec<usize>>,
+    /// fixed point scale of the tensor the range holds (for public inputs and outputs)
+    pub scale: Option<crate::Scale>,
+}
+
+/// The instances of one of the aggregated snarks
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SegmentLayout {
+    /// offset of the snark's instances in the aggregate proof's instances
+    pub offset: usize,
+    /// number of instances of the snark
+    pub len: usize,
+    /// the named ranges of the snark's instances, in the order they are laid out
+    pub ranges: Vec<InstanceRange>,
+}
+
+impl SegmentLayout {
+    /// The instances of a snark with the circuit `settings`, starting at `offset`. As they are laid out in the circuit
+    /// the hashes (or commitments) of the inputs come first, then those of the params, then the public inputs and outputs
+    /// of the model, then the hashes (or commitments) of the outputs, then the committed on-chain block
+    pub fn new(settings: &GraphSettings, offset: usize) -> Result<Self, AggregationError> {
+        let visibility = VarVisibility::from_args(&settings.run_args)
+            .map_err(|e| AggregationError::InstanceLayout(e.to_string()))?;
+        let num_inputs = settings.model_input_scales.len();
+        let num_outputs = settings.model_output_scales.len();
+        let len: usize = settings.total_instances().iter().sum();
+
+        // the instances of the module processing the tensors at `outlets`
+        let module_ranges =
+            |prefix: &str,
+             shared: &Visibility,
+             outlets: Vec<usize>|
+             -> Vec<(String, usize, Option<Vec<usize>>, Option<crate::Scale>)> {
+                let (suffix, len) = if shared.is_hashed_public() {
+                    ("hash", POSEIDON_INSTANCES)
+                } else if shared.is_merkle() {
+                    ("root", POSEIDON_INSTANCES)
+                } else if shared.is_signed() {
+                    ("public_key", EDDSA_INSTANCES)
+                } else if shared.is_encrypted() {
+                    ("ciphertext", ELGAMAL_INSTANCES)
+                } else {
+                    return vec![];
+                };
+                outlets
+                    .into_iter()
+                    .map(|i| (format!("{}_{}_{}", prefix, i, suffix), len, None, None))
+                    .collect()
+            };
+
+        // the model instance shapes hold the public inputs followed by the public outputs
+        let mut model_shapes = settings.model_instance_shapes.iter();
+        let mut public_ranges =
+            |prefix: &str,
+             public: Vec<usize>,
+             scales: &[crate::Scale]|
+             -> Vec<(String, usize, Option<Vec<usize>>, Option<crate::Scale>)> {
+                public
+                    .into_iter()
+                    .filter_map(|i| {
+                        model_shapes.next().map(|shape| {
+                            (
+                                format!("{}_{}", prefix, i),
+                                shape.iter().product(),
+                                Some(shape.clone()),
+                                scales.get(i).copied(),
+                            )
+                        })
+                    })
+                    .collect()
+            };
+
+        let input_modules = module_ranges(
+            "input",
+            &visibility.input,
+            visibility.input_outlets(num_inputs),
+        );
+        let inputs = public_ranges(
+            "input",
+            (0..num_inputs)
+                .filter(|i| visibility.input_at(*i).is_public())
+                .collect(),
+            &settings.model_input_scales,
+        );
+        let outputs = public_ranges(
+            "output",
+            (0..num_outputs)
+                .filter(|i| visibility.output_at(*i).is_public())
+                .collect(),
+            &settings.model_output_scales,
+        );
+        let output_modules = module_ranges(
+            "output",
+            &visibility.output,
+            visibility.output_outlets(num_outputs),
+        );
+
+        let block = if settings.run_args.commit_on_chain_block {
+            vec![(
+                "on_chain_block".to_string(),
+                ON_CHAIN_BLOCK_INSTANCES,
+                None,
+                None,
+            )]
+        } else {
+            vec![]
+        };
+
+        // whatever is left holds the (hashed or encrypted) params
+        let io_len: usize = [&input_modules, &inputs, &outputs, &output_modules, &block]
+            .iter()
+            .flat_map(|ranges| ranges.iter())
+            .map(|r| r.1)
+            .sum();
+        let params_len = len.checked_sub(io_len).ok_or_else(|| {
+            AggregationError::InstanceLayout(format!(
+                "the snark has {} instances but its public inputs and outputs need {}",
+                len, io_len
+            ))
+        })?;
+        let params = if params_len > 0 {
+            vec![("params".to_string(), params_len, None, None)]
+        } else {
+            vec![]
+        };
+
+        let mut range_offset = offset;
+        let ranges = input_modules
+            .into_iter()
+            .chain(params)
+            .chain(inputs)
+            .chain(outputs)
+            .chain(output_modules)
+            .chain(block)
+            .map(|(name, len, shape, scale)| {
+                let range = InstanceRange {
+                    name,
+                    offset: range_offset,
+                    len,
+                    shape,
+                    scale,
+                };
+                range_offset += len;
+                range
+            })
+            .collect();
+
+        Ok(SegmentLayout {
+            offset,
+            len,
+            ranges,
+        })
+    }
+}
+
+/// The layout of the public instances of an aggregate proof. For sequential split proofs
+/// the `inputs` are those of the first segment and the `outputs` those of the last segment.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AggregateInstanceLayout {
+    /// total number of instances of the aggregate proof
+    pub num_instances: usize,
+    /// the limbs of the KZG accumulator, which come first
+    pub accumulator: InstanceRange,
+    /// the instances of each aggregated snark, in order
+    pub segments: Vec<SegmentLayout>,
+    /// the public inputs (or their hashes) of the first segment
+    pub inputs: Vec<InstanceRange>,
+    /// the public outputs (or their hashes) of the last segment
+    pub outputs: Vec<InstanceRange>,
+}
+
+impl AggregateInstanceLayout {
+    /// The layout of the instances of an aggregate proof over snarks with the circuit `settings`, in order
+    pub fn new(settings: &[GraphSettings]) -> Result<Self, AggregationError> {
+        let accumulator_len = 4 * LIMBS;
+        let mut offset = accumulator_len;
+        let mut segments = vec![];
+        for s in settings {
+            let segment = SegmentLayout::new(s, offset)?;
+            offset += segment.len;
+            segments.push(segment);
+        }
+
+        let named = |segment: Option<&SegmentLayout>, prefix: &str| match segment {
+            Some(segment) => segment
+                .ranges
+                .iter()
+                .filter(|r| r.name.starts_with(prefix))
+                .cloned()
+                .collect(),
+            None => vec![],
+        };
+        let inputs = named(segments.first(), "input");
+        let outputs = named(segments.last(), "output");
+
+        Ok(AggregateInstanceLayout {
+            num_instances: offset,
+            accumulator: InstanceRange {
+                name: "accumulator".to_string(),
+                offset: 0,
+                len: accumulator_len,
+                shape: None,
+                scale: None,
+            },
+            segments,
+            inputs,
+            outputs,
+        })
+    }
+
+    /// Saves the layout as json to `path`
+    pub fn save(&self, path: &std::path::PathBuf) -> Result<(), std::io::Error> {
+        serde_json::to_writer_pretty(&std::fs::File::create(path)?, self)
+            .map_err(std::io::Error::from)
+    }
 }
 
 type AggregationResult<'a> = (
@@ -418,3 +652,42 @@ impl Circuit<Fr> for AggregationCircuit {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::modules::GraphModules;
+
+    #[test]
+    fn test_segment_layout_encrypts_each_input() {
+        let mut settings = GraphSettings::default();
+        settings.run_args.input_visibility = Visibility::Encrypted;
+        settings.run_args.param_visibility = Visibility::Private;
+        settings.run_args.output_visibility = Visibility::Public;
+        settings.model_input_scales = vec![7, 7];
+        settings.model_output_scales = vec![7];
+        settings.model_instance_shapes = vec![vec![1, 3]];
+        settings.module_sizes = GraphModules::num_constraints_and_instances(
+            vec![vec![1, 2], vec![1, 2]],
+            vec![],
+            vec![vec![1, 3]],
+            VarVisibility::from_args(&settings.run_args).unwrap(),
+        );
+
+        let layout = SegmentLayout::new(&settings, 0).unwrap();
+        let ranges = layout
+            .ranges
+            .iter()
+            .map(|r| (r.name.as_str(), r.offset, r.len))
+            .collect_vec();
+        assert_eq!(
+            ranges,
+            [
+                ("input_0_ciphertext", 0, ELGAMAL_INSTANCES),
+                ("input_1_ciphertext", ELGAMAL_INSTANCES, ELGAMAL_INSTANCES),
+                ("output_0", 2 * ELGAMAL_INSTANCES, 3),
+            ]
+        );
+        assert_eq!(layout.len, 2 * ELGAMAL_INSTANCES + 3);
+    }
+}
@@ -26,6 +26,9 @@ pub enum EvmVerificationError {
     /// Invalid Visibilit
     #[error("Invalid visibility")]
     InvalidVisibility,
+    /// A feature of the verifier that can't be used when its vk is deployed separately
+    #[error("{0} is not supported for verifiers with a separate vk")]
+    SeparateVk(&'static str),
 }
 /// YulCode type which is just an alias of string
 pub type YulCode = String;
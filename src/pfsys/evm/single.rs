@@ -1,17 +1,19 @@
 use crate::pfsys::evm::YulCode;
+use crate::pfsys::MultiOpenScheme;
 use halo2_proofs::poly::commitment::ParamsProver;
 use halo2_proofs::{plonk::VerifyingKey, poly::kzg::commitment::ParamsKZG};
 use halo2curves::bn256::{Bn256, Fq, Fr, G1Affine};
 use snark_verifier::{
     loader::evm::EvmLoader,
-    pcs::kzg::{Gwc19, KzgAs},
+    pcs::kzg::{Bdfg21, Gwc19, KzgAs},
     system::halo2::{compile, transcript::evm::EvmTranscript, Config},
     verifier::{self, SnarkVerifier},
 };
 use std::rc::Rc;
 use thiserror::Error;
 
-type PlonkVerifier = verifier::plonk::PlonkVerifier<KzgAs<Bn256, Gwc19>>;
+type GwcVerifier = verifier::plonk::PlonkVerifier<KzgAs<Bn256, Gwc19>>;
+type ShplonkVerifier = verifier::plonk::PlonkVerifier<KzgAs<Bn256, Bdfg21>>;
 
 #[derive(Error, Debug)]
 /// Errors related to simple evm verifier generation
@@ -24,11 +26,12 @@ pub enum SimpleError {
     ProofVerify,
 }
 
-/// Create EVM verifier yulcode
+/// Create EVM verifier yulcode for the given kzg multi-open scheme
 pub fn gen_evm_verifier(
     params: &ParamsKZG<Bn256>,
     vk: &VerifyingKey<G1Affine>,
     num_instance: usize,
+    multi_open: MultiOpenScheme,
 ) -> Result<YulCode, SimpleError> {
     let protocol = compile(
         params,
@@ -42,10 +45,20 @@ pub fn gen_evm_verifier(
     let mut transcript = EvmTranscript::<_, Rc<EvmLoader>, _, _>::new(&loader);
 
     let instances = transcript.load_instances(vec![num_instance]);
-    let proof = PlonkVerifier::read_proof(&vk, &protocol, &instances, &mut transcript)
-        .map_err(|_| SimpleError::ProofRead)?;
-    PlonkVerifier::verify(&vk, &protocol, &instances, &proof)
-        .map_err(|_| SimpleError::ProofVerify)?;
+    match multi_open {
+        MultiOpenScheme::SHPLONK => {
+            let proof = ShplonkVerifier::read_proof(&vk, &protocol, &instances, &mut transcript)
+                .map_err(|_| SimpleError::ProofRead)?;
+            ShplonkVerifier::verify(&vk, &protocol, &instances, &proof)
+                .map_err(|_| SimpleError::ProofVerify)?;
+        }
+        MultiOpenScheme::GWC => {
+            let proof = GwcVerifier::read_proof(&vk, &protocol, &instances, &mut transcript)
+                .map_err(|_| SimpleError::ProofRead)?;
+            GwcVerifier::verify(&vk, &protocol, &instances, &proof)
+                .map_err(|_| SimpleError::ProofVerify)?;
+        }
+    }
 
     let yul_code = &loader.yul_code();
 
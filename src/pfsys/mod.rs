@@ -4,9 +4,16 @@ pub mod evm;
 /// SRS generation, processing, verification and downloading
 pub mod srs;
 
+/// Versioned binary containers for proofs and keys
+pub mod container;
+
 use crate::circuit::CheckMode;
 use crate::graph::GraphWitness;
+use crate::pfsys::container::{
+    ContainerHeader, ContainerKind, SettingsDigest, SettingsHash, MAGIC,
+};
 use crate::pfsys::evm::aggregation::PoseidonTranscript;
+use crate::profiler;
 use crate::tensor::TensorType;
 use clap::ValueEnum;
 use halo2_proofs::circuit::Value;
@@ -14,15 +21,17 @@ use halo2_proofs::plonk::{
     create_proof, keygen_pk, keygen_vk, verify_proof, Circuit, ProvingKey, VerifyingKey,
 };
 use halo2_proofs::poly::commitment::{CommitmentScheme, Params, ParamsProver, Prover, Verifier};
+use halo2_proofs::poly::ipa::commitment::{IPACommitmentScheme, ParamsIPA};
+use halo2_proofs::poly::ipa::multiopen::{ProverIPA, VerifierIPA};
 use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
-use halo2_proofs::poly::kzg::multiopen::{ProverSHPLONK, VerifierSHPLONK};
+use halo2_proofs::poly::kzg::multiopen::{ProverGWC, ProverSHPLONK, VerifierGWC, VerifierSHPLONK};
 use halo2_proofs::poly::VerificationStrategy;
 use halo2_proofs::transcript::{EncodedChallenge, TranscriptReadBuffer, TranscriptWriterBuffer};
 use halo2curves::ff::{FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
 use halo2curves::serde::SerdeObject;
 use halo2curves::CurveAffine;
 use instant::Instant;
-use log::{debug, info, trace};
+use log::{debug, info, trace, warn};
 #[cfg(not(feature = "det-prove"))]
 use rand::rngs::OsRng;
 #[cfg(feature = "det-prove")]
@@ -42,6 +51,135 @@ use thiserror::Error as thisError;
 
 use halo2curves::bn256::{Bn256, Fr, G1Affine};
 
+/// The polynomial commitment scheme used by a circuit
+#[derive(
+    ValueEnum, Copy, Clone, Default, Debug, PartialEq, Eq, Deserialize, Serialize, PartialOrd,
+)]
+pub enum Commitments {
+    /// KZG commitments, requires a trusted setup (SRS) but has cheap (and EVM compatible) verification
+    #[default]
+    KZG,
+    /// Inner product argument commitments, requires no trusted setup but has linear verifier cost
+    IPA,
+}
+
+impl std::fmt::Display for Commitments {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
+
+impl Commitments {
+    /// The [Config] used to compile the snark protocol for this commitment scheme
+    pub fn protocol_config(&self) -> Config {
+        match self {
+            Commitments::KZG => Config::kzg(),
+            Commitments::IPA => Config::ipa(),
+        }
+    }
+}
+
+#[cfg(feature = "python-bindings")]
+impl ToPyObject for Commitments {
+    fn to_object(&self, py: Python) -> PyObject {
+        match self {
+            Commitments::KZG => "kzg".to_object(py),
+            Commitments::IPA => "ipa".to_object(py),
+        }
+    }
+}
+
+#[cfg(feature = "python-bindings")]
+/// Converts Commitments into a PyObject (Required for Commitments to be compatible with Python)
+impl pyo3::IntoPy<PyObject> for Commitments {
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+#[cfg(feature = "python-bindings")]
+/// Obtains Commitments from PyObject (Required for Commitments to be compatible with Python)
+impl<'source> pyo3::FromPyObject<'source> for Commitments {
+    fn extract(ob: &'source pyo3::PyAny) -> pyo3::PyResult<Self> {
+        let trystr = <pyo3::types::PyString as pyo3::PyTryFrom>::try_from(ob)?;
+        let strval = trystr.to_string();
+        match strval.to_lowercase().as_str() {
+            "kzg" => Ok(Commitments::KZG),
+            "ipa" => Ok(Commitments::IPA),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(
+                "Invalid value for Commitments",
+            )),
+        }
+    }
+}
+
+/// The multi-open (batch opening) scheme used by KZG proofs
+#[derive(
+    ValueEnum, Copy, Clone, Default, Debug, PartialEq, Eq, Deserialize, Serialize, PartialOrd,
+)]
+pub enum MultiOpenScheme {
+    /// SHPLONK (BDFG21), smaller proofs
+    #[default]
+    SHPLONK,
+    /// GWC19, cheaper to verify in some settings
+    GWC,
+}
+
+impl std::fmt::Display for MultiOpenScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
+
+impl From<MultiOpenScheme> for halo2_solidity_verifier::BatchOpenScheme {
+    fn from(val: MultiOpenScheme) -> Self {
+        match val {
+            MultiOpenScheme::SHPLONK => halo2_solidity_verifier::BatchOpenScheme::Bdfg21,
+            MultiOpenScheme::GWC => halo2_solidity_verifier::BatchOpenScheme::Gwc19,
+        }
+    }
+}
+
+#[cfg(feature = "python-bindings")]
+impl ToPyObject for MultiOpenScheme {
+    fn to_object(&self, py: Python) -> PyObject {
+        match self {
+            MultiOpenScheme::SHPLONK => "shplonk".to_object(py),
+            MultiOpenScheme::GWC => "gwc".to_object(py),
+        }
+    }
+}
+
+#[cfg(feature = "python-bindings")]
+/// Converts MultiOpenScheme into a PyObject (Required for MultiOpenScheme to be compatible with Python)
+impl pyo3::IntoPy<PyObject> for MultiOpenScheme {
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+#[cfg(feature = "python-bindings")]
+/// Obtains MultiOpenScheme from PyObject (Required for MultiOpenScheme to be compatible with Python)
+impl<'source> pyo3::FromPyObject<'source> for MultiOpenScheme {
+    fn extract(ob: &'source pyo3::PyAny) -> pyo3::PyResult<Self> {
+        let trystr = <pyo3::types::PyString as pyo3::PyTryFrom>::try_from(ob)?;
+        let strval = trystr.to_string();
+        match strval.to_lowercase().as_str() {
+            "shplonk" => Ok(MultiOpenScheme::SHPLONK),
+            "gwc" => Ok(MultiOpenScheme::GWC),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(
+                "Invalid value for MultiOpenScheme",
+            )),
+        }
+    }
+}
+
 #[allow(missing_docs)]
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, PartialOrd)]
 pub enum ProofType {
@@ -139,6 +277,15 @@ pub enum PfSysError {
     /// Packing exponent is too large
     #[error("largest packing exponent exceeds max. try reducing the scale")]
     PackingExponent,
+    /// The requested feature is only available for a given commitment scheme
+    #[error("{0} is only supported with the {1} commitment scheme")]
+    UnsupportedCommitment(String, Commitments),
+    /// The requested feature is only available for a given multi-open scheme
+    #[error("{0} is only supported with the {1} multi-open scheme")]
+    UnsupportedMultiOpen(String, MultiOpenScheme),
+    /// The proof was generated with a different multi-open scheme than expected
+    #[error("proof was generated with the {0} multi-open scheme but {1} was expected")]
+    MultiOpenMismatch(MultiOpenScheme, MultiOpenScheme),
 }
 
 #[allow(missing_docs)]
@@ -213,6 +360,10 @@ where
     pub transcript_type: TranscriptType,
     /// the split proof
     pub split: Option<ProofSplitCommit>,
+    /// the commitment scheme used to generate the proof (None for legacy proofs, which are all KZG)
+    pub commitment: Option<Commitments>,
+    /// the kzg multi-open scheme used to generate the proof (None for legacy proofs, which are all SHPLONK)
+    pub multi_open: Option<MultiOpenScheme>,
 }
 
 #[cfg(feature = "python-bindings")]
@@ -235,6 +386,8 @@ where
         dict.set_item("proof", &hex_proof).unwrap();
         dict.set_item("transcript_type", &self.transcript_type)
             .unwrap();
+        dict.set_item("commitment", &self.commitment).unwrap();
+        dict.set_item("multi_open", &self.multi_open).unwrap();
         dict.to_object(py)
     }
 }
@@ -254,6 +407,8 @@ where
         proof: Vec<u8>,
         transcript_type: TranscriptType,
         split: Option<ProofSplitCommit>,
+        commitment: Option<Commitments>,
+        multi_open: Option<MultiOpenScheme>,
     ) -> Self {
         Self {
             protocol: Some(protocol),
@@ -261,9 +416,29 @@ where
             proof,
             transcript_type,
             split,
+            commitment,
+            multi_open,
         }
     }
 
+    /// The commitment scheme the proof was generated with
+    pub fn commitment(&self) -> Commitments {
+        self.commitment.unwrap_or_default()
+    }
+
+    /// The kzg multi-open scheme the proof was generated with
+    pub fn multi_open(&self) -> MultiOpenScheme {
+        self.multi_open.unwrap_or_default()
+    }
+
+    /// Checks that the proof was generated with the `expected` multi-open scheme
+    pub fn check_multi_open(&self, expected: MultiOpenScheme) -> Result<(), PfSysError> {
+        if self.multi_open() != expected {
+            return Err(PfSysError::MultiOpenMismatch(self.multi_open(), expected));
+        }
+        Ok(())
+    }
+
     /// Saves the Proof to a specified `proof_path`.
     pub fn save(&self, proof_path: &PathBuf) -> Result<(), Box<dyn Error>> {
         let file = std::fs::File::create(proof_path)?;
@@ -272,16 +447,71 @@ where
         Ok(())
     }
 
-    /// Load a json serialized proof from the provided path.
+    /// Load a proof from the provided path, either json serialized or in the binary container format.
+    /// If `settings_hash` is set, a binary proof must have been generated for those settings.
     pub fn load<Scheme: CommitmentScheme<Curve = C, Scalar = F>>(
         proof_path: &PathBuf,
+        settings_hash: Option<SettingsHash>,
     ) -> Result<Self, Box<dyn Error>>
     where
         <C as CurveAffine>::ScalarExt: FromUniformBytes<64>,
     {
         trace!("reading proof");
-        let data = std::fs::read_to_string(proof_path)?;
-        serde_json::from_str(&data).map_err(|e| e.into())
+        let data = std::fs::read(proof_path)?;
+        Self::from_bytes(&data, settings_hash)
+    }
+
+    /// Deserializes a proof that is either json serialized or in the binary container format.
+    pub fn from_bytes(
+        bytes: &[u8],
+        settings_hash: Option<SettingsHash>,
+    ) -> Result<Self, Box<dyn Error>> {
+        if bytes.starts_with(&MAGIC) {
+            return Self::from_container_bytes(bytes, settings_hash);
+        }
+        serde_json::from_slice(bytes).map_err(|e| e.into())
+    }
+
+    /// Serializes the proof into the compact binary container format.
+    pub fn to_container_bytes(
+        &self,
+        settings_hash: Option<SettingsHash>,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut bytes = vec![];
+        ContainerHeader::new::<C>(
+            ContainerKind::Proof,
+            settings_hash,
+            Some(self.transcript_type),
+        )
+        .write(&mut bytes)?;
+        bincode::serialize_into(&mut bytes, &self)?;
+        Ok(bytes)
+    }
+
+    /// Deserializes a proof from the binary container format, checking it was generated for
+    /// `settings_hash` (if set).
+    pub fn from_container_bytes(
+        bytes: &[u8],
+        settings_hash: Option<SettingsHash>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let (header, payload) = ContainerHeader::split(bytes)?;
+        let header = header.ok_or("proof is not in the binary container format")?;
+        header.validate::<C>(ContainerKind::Proof, settings_hash)?;
+        let snark: Self = bincode::deserialize(payload)?;
+        if header.transcript_type != Some(snark.transcript_type) {
+            return Err("proof transcript type does not match its container header".into());
+        }
+        Ok(snark)
+    }
+
+    /// Saves the proof to a specified `proof_path` in the binary container format.
+    pub fn save_binary(
+        &self,
+        proof_path: &PathBuf,
+        settings_hash: Option<SettingsHash>,
+    ) -> Result<(), Box<dyn Error>> {
+        std::fs::write(proof_path, self.to_container_bytes(settings_hash)?)?;
+        Ok(())
     }
 }
 
@@ -396,13 +626,15 @@ where
     // Initialize verifying key
     let now = Instant::now();
     trace!("preparing VK");
-    let vk = keygen_vk(params, &empty_circuit)?;
+    let vk = profiler::phase(profiler::KEYGEN_VK, || keygen_vk(params, &empty_circuit))?;
     let elapsed = now.elapsed();
     info!("VK took {}.{}", elapsed.as_secs(), elapsed.subsec_millis());
 
     // Initialize the proving key
     let now = Instant::now();
-    let pk = keygen_pk(params, vk, &empty_circuit)?;
+    let pk = profiler::phase(profiler::KEYGEN_PK, || {
+        keygen_pk(params, vk, &empty_circuit)
+    })?;
     let elapsed = now.elapsed();
     info!("PK took {}.{}", elapsed.as_secs(), elapsed.subsec_millis());
     Ok(pk)
@@ -430,6 +662,8 @@ pub fn create_proof_circuit<
     check_mode: CheckMode,
     transcript_type: TranscriptType,
     split: Option<ProofSplitCommit>,
+    commitment: Commitments,
+    multi_open: Option<MultiOpenScheme>,
 ) -> Result<Snark<Scheme::Scalar, Scheme::Curve>, Box<dyn Error>>
 where
     C: Circuit<Scheme::Scalar>,
@@ -453,7 +687,9 @@ where
     let protocol = compile(
         params,
         pk.get_vk(),
-        Config::kzg().with_num_instance(number_instance),
+        commitment
+            .protocol_config()
+            .with_num_instance(number_instance),
     );
 
     let pi_inner = instances
@@ -471,28 +707,40 @@ where
     // not wasm32 unknown
     let now = Instant::now();
 
-    create_proof::<Scheme, P, _, _, TW, _>(
-        params,
-        pk,
-        &[circuit],
-        pi_inner,
-        &mut rng,
-        &mut transcript,
-    )?;
+    profiler::phase(profiler::CREATE_PROOF, || {
+        create_proof::<Scheme, P, _, _, TW, _>(
+            params,
+            pk,
+            &[circuit],
+            pi_inner,
+            &mut rng,
+            &mut transcript,
+        )
+    })?;
     let proof = transcript.finalize();
 
-    let checkable_pf = Snark::new(protocol, instances, proof, transcript_type, split);
+    let checkable_pf = Snark::new(
+        protocol,
+        instances,
+        proof,
+        transcript_type,
+        split,
+        Some(commitment),
+        multi_open,
+    );
 
     // sanity check that the generated proof is valid
     if check_mode == CheckMode::SAFE {
         debug!("verifying generated proof");
         let verifier_params = params.verifier_params();
-        verify_proof_circuit::<F, V, Scheme, Strategy, E, TR>(
-            &checkable_pf,
-            verifier_params,
-            pk.get_vk(),
-            strategy,
-        )?;
+        profiler::phase(profiler::SELF_VERIFY, || {
+            verify_proof_circuit::<F, V, Scheme, Strategy, E, TR>(
+                &checkable_pf,
+                verifier_params,
+                pk.get_vk(),
+                strategy,
+            )
+        })?;
     }
     let elapsed = now.elapsed();
     info!(
@@ -608,6 +856,7 @@ pub fn load_vk<Scheme: CommitmentScheme, F: PrimeField + TensorType, C: Circuit<
 ) -> Result<VerifyingKey<Scheme::Curve>, Box<dyn Error>>
 where
     C: Circuit<Scheme::Scalar>,
+    <C as Circuit<Scheme::Scalar>>::Params: SettingsDigest,
     Scheme::Curve: SerdeObject + CurveAffine,
     Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
 {
@@ -615,6 +864,11 @@ where
     let f =
         File::open(path.clone()).map_err(|_| format!("failed to load vk at {}", path.display()))?;
     let mut reader = BufReader::new(f);
+    check_key_header::<Scheme::Curve, _>(
+        &mut reader,
+        ContainerKind::VerifyingKey,
+        params.settings_hash()?,
+    )?;
     VerifyingKey::<Scheme::Curve>::read::<_, C>(
         &mut reader,
         halo2_proofs::SerdeFormat::RawBytes,
@@ -623,13 +877,16 @@ where
     .map_err(Box::<dyn Error>::from)
 }
 
-/// Loads a [ProvingKey] at `path`.
+/// Loads a [ProvingKey] at `path`. The whole key is read into memory: halo2 only reads (and
+/// builds) a [ProvingKey] as a whole, so its fixed columns and permutation polynomials can't be
+/// loaded lazily or regenerated separately.
 pub fn load_pk<Scheme: CommitmentScheme, F: PrimeField + TensorType, C: Circuit<F>>(
     path: PathBuf,
     params: <C as Circuit<Scheme::Scalar>>::Params,
 ) -> Result<ProvingKey<Scheme::Curve>, Box<dyn Error>>
 where
     C: Circuit<Scheme::Scalar>,
+    <C as Circuit<Scheme::Scalar>>::Params: SettingsDigest,
     Scheme::Curve: SerdeObject + CurveAffine,
     Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
 {
@@ -637,6 +894,11 @@ where
     let f =
         File::open(path.clone()).map_err(|_| format!("failed to load pk at {}", path.display()))?;
     let mut reader = BufReader::new(f);
+    check_key_header::<Scheme::Curve, _>(
+        &mut reader,
+        ContainerKind::ProvingKey,
+        params.settings_hash()?,
+    )?;
     ProvingKey::<Scheme::Curve>::read::<_, C>(
         &mut reader,
         halo2_proofs::SerdeFormat::RawBytes,
@@ -645,11 +907,28 @@ where
     .map_err(Box::<dyn Error>::from)
 }
 
-/// Saves a [ProvingKey] to `path`.
+/// Reads the container header of a key (if any) and checks it matches the expected `kind` and `settings_hash`.
+pub fn check_key_header<C: CurveAffine, R: std::io::BufRead>(
+    reader: &mut R,
+    kind: ContainerKind,
+    settings_hash: SettingsHash,
+) -> Result<(), Box<dyn Error>> {
+    match ContainerHeader::read(reader)? {
+        Some(header) => header.validate::<C>(kind, Some(settings_hash))?,
+        None => warn!(
+            "{:?} has no header (eg. it predates the container format), it can't be checked against the settings",
+            kind
+        ),
+    }
+    Ok(())
+}
+
+/// Saves a [ProvingKey] to `path`, prefixed with a header recording the `settings` it was generated for.
 pub fn save_pk<Scheme: CommitmentScheme>(
     path: &PathBuf,
     vk: &ProvingKey<Scheme::Curve>,
-) -> Result<(), io::Error>
+    settings: &impl SettingsDigest,
+) -> Result<(), Box<dyn Error>>
 where
     Scheme::Curve: SerdeObject + CurveAffine,
     Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
@@ -657,16 +936,23 @@ where
     info!("saving proving key 💾");
     let f = File::create(path)?;
     let mut writer = BufWriter::new(f);
+    ContainerHeader::new::<Scheme::Curve>(
+        ContainerKind::ProvingKey,
+        Some(settings.settings_hash()?),
+        None,
+    )
+    .write(&mut writer)?;
     vk.write(&mut writer, halo2_proofs::SerdeFormat::RawBytes)?;
     writer.flush()?;
     Ok(())
 }
 
-/// Saves a [VerifyingKey] to `path`.
+/// Saves a [VerifyingKey] to `path`, prefixed with a header recording the `settings` it was generated for.
 pub fn save_vk<Scheme: CommitmentScheme>(
     path: &PathBuf,
     vk: &VerifyingKey<Scheme::Curve>,
-) -> Result<(), io::Error>
+    settings: &impl SettingsDigest,
+) -> Result<(), Box<dyn Error>>
 where
     Scheme::Curve: SerdeObject + CurveAffine,
     Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
@@ -674,6 +960,12 @@ where
     info!("saving verification key 💾");
     let f = File::create(path)?;
     let mut writer = BufWriter::new(f);
+    ContainerHeader::new::<Scheme::Curve>(
+        ContainerKind::VerifyingKey,
+        Some(settings.settings_hash()?),
+        None,
+    )
+    .write(&mut writer)?;
     vk.write(&mut writer, halo2_proofs::SerdeFormat::RawBytes)?;
     writer.flush()?;
     Ok(())
@@ -697,7 +989,8 @@ pub fn save_params<Scheme: CommitmentScheme>(
 pub fn create_proof_circuit_kzg<
     'params,
     C: Circuit<Fr>,
-    Strategy: VerificationStrategy<'params, KZGCommitmentScheme<Bn256>, VerifierSHPLONK<'params, Bn256>>,
+    Strategy: VerificationStrategy<'params, KZGCommitmentScheme<Bn256>, VerifierSHPLONK<'params, Bn256>>
+        + VerificationStrategy<'params, KZGCommitmentScheme<Bn256>, VerifierGWC<'params, Bn256>>,
 >(
     circuit: C,
     params: &'params ParamsKZG<Bn256>,
@@ -707,6 +1000,7 @@ pub fn create_proof_circuit_kzg<
     strategy: Strategy,
     check_mode: CheckMode,
     split: Option<ProofSplitCommit>,
+    multi_open: MultiOpenScheme,
 ) -> Result<Snark<Fr, G1Affine>, Box<dyn Error>> {
     let public_inputs = if let Some(public_inputs) = public_inputs {
         if !public_inputs.is_empty() {
@@ -718,8 +1012,8 @@ pub fn create_proof_circuit_kzg<
         vec![]
     };
 
-    match transcript {
-        TranscriptType::EVM => create_proof_circuit::<
+    match (transcript, multi_open) {
+        (TranscriptType::EVM, MultiOpenScheme::SHPLONK) => create_proof_circuit::<
             KZGCommitmentScheme<_>,
             Fr,
             _,
@@ -738,9 +1032,11 @@ pub fn create_proof_circuit_kzg<
             check_mode,
             transcript,
             split,
+            Commitments::KZG,
+            Some(multi_open),
         )
         .map_err(Box::<dyn Error>::from),
-        TranscriptType::Poseidon => create_proof_circuit::<
+        (TranscriptType::Poseidon, MultiOpenScheme::SHPLONK) => create_proof_circuit::<
             KZGCommitmentScheme<_>,
             Fr,
             _,
@@ -759,26 +1055,208 @@ pub fn create_proof_circuit_kzg<
             check_mode,
             transcript,
             split,
+            Commitments::KZG,
+            Some(multi_open),
+        )
+        .map_err(Box::<dyn Error>::from),
+        (TranscriptType::EVM, MultiOpenScheme::GWC) => create_proof_circuit::<
+            KZGCommitmentScheme<_>,
+            Fr,
+            _,
+            ProverGWC<_>,
+            VerifierGWC<_>,
+            _,
+            _,
+            EvmTranscript<G1Affine, _, _, _>,
+            EvmTranscript<G1Affine, _, _, _>,
+        >(
+            circuit,
+            public_inputs,
+            params,
+            pk,
+            strategy,
+            check_mode,
+            transcript,
+            split,
+            Commitments::KZG,
+            Some(multi_open),
+        )
+        .map_err(Box::<dyn Error>::from),
+        (TranscriptType::Poseidon, MultiOpenScheme::GWC) => create_proof_circuit::<
+            KZGCommitmentScheme<_>,
+            Fr,
+            _,
+            ProverGWC<_>,
+            VerifierGWC<_>,
+            _,
+            _,
+            PoseidonTranscript<NativeLoader, _>,
+            PoseidonTranscript<NativeLoader, _>,
+        >(
+            circuit,
+            public_inputs,
+            params,
+            pk,
+            strategy,
+            check_mode,
+            transcript,
+            split,
+            Commitments::KZG,
+            Some(multi_open),
         )
         .map_err(Box::<dyn Error>::from),
     }
 }
 
-#[allow(unused)]
-/// helper function
+/// helper function, the multi-open scheme is read from the proof
 pub(crate) fn verify_proof_circuit_kzg<
     'params,
-    Strategy: VerificationStrategy<'params, KZGCommitmentScheme<Bn256>, VerifierSHPLONK<'params, Bn256>>,
+    Strategy: VerificationStrategy<
+            'params,
+            KZGCommitmentScheme<Bn256>,
+            VerifierSHPLONK<'params, Bn256>,
+            Output = Output,
+        > + VerificationStrategy<
+            'params,
+            KZGCommitmentScheme<Bn256>,
+            VerifierGWC<'params, Bn256>,
+            Output = Output,
+        >,
+    Output,
 >(
     params: &'params ParamsKZG<Bn256>,
     proof: Snark<Fr, G1Affine>,
     vk: &VerifyingKey<G1Affine>,
     strategy: Strategy,
+) -> Result<Output, halo2_proofs::plonk::Error> {
+    match (proof.transcript_type, proof.multi_open()) {
+        (TranscriptType::EVM, MultiOpenScheme::SHPLONK) => verify_proof_circuit::<
+            Fr,
+            VerifierSHPLONK<'_, Bn256>,
+            _,
+            _,
+            _,
+            EvmTranscript<G1Affine, _, _, _>,
+        >(&proof, params, vk, strategy),
+        (TranscriptType::Poseidon, MultiOpenScheme::SHPLONK) => {
+            verify_proof_circuit::<
+                Fr,
+                VerifierSHPLONK<'_, Bn256>,
+                _,
+                _,
+                _,
+                PoseidonTranscript<NativeLoader, _>,
+            >(&proof, params, vk, strategy)
+        }
+        (TranscriptType::EVM, MultiOpenScheme::GWC) => verify_proof_circuit::<
+            Fr,
+            VerifierGWC<'_, Bn256>,
+            _,
+            _,
+            _,
+            EvmTranscript<G1Affine, _, _, _>,
+        >(&proof, params, vk, strategy),
+        (TranscriptType::Poseidon, MultiOpenScheme::GWC) => verify_proof_circuit::<
+            Fr,
+            VerifierGWC<'_, Bn256>,
+            _,
+            _,
+            _,
+            PoseidonTranscript<NativeLoader, _>,
+        >(&proof, params, vk, strategy),
+    }
+}
+
+/// helper function
+#[allow(clippy::too_many_arguments)]
+pub fn create_proof_circuit_ipa<
+    'params,
+    C: Circuit<Fr>,
+    Strategy: VerificationStrategy<'params, IPACommitmentScheme<G1Affine>, VerifierIPA<'params, G1Affine>>,
+>(
+    circuit: C,
+    params: &'params ParamsIPA<G1Affine>,
+    public_inputs: Option<Vec<Fr>>,
+    pk: &ProvingKey<G1Affine>,
+    transcript: TranscriptType,
+    strategy: Strategy,
+    check_mode: CheckMode,
+    split: Option<ProofSplitCommit>,
+) -> Result<Snark<Fr, G1Affine>, Box<dyn Error>> {
+    let public_inputs = if let Some(public_inputs) = public_inputs {
+        if !public_inputs.is_empty() {
+            vec![public_inputs]
+        } else {
+            vec![vec![]]
+        }
+    } else {
+        vec![]
+    };
+
+    match transcript {
+        TranscriptType::EVM => create_proof_circuit::<
+            IPACommitmentScheme<_>,
+            Fr,
+            _,
+            ProverIPA<_>,
+            VerifierIPA<_>,
+            _,
+            _,
+            EvmTranscript<G1Affine, _, _, _>,
+            EvmTranscript<G1Affine, _, _, _>,
+        >(
+            circuit,
+            public_inputs,
+            params,
+            pk,
+            strategy,
+            check_mode,
+            transcript,
+            split,
+            Commitments::IPA,
+            None,
+        )
+        .map_err(Box::<dyn Error>::from),
+        TranscriptType::Poseidon => create_proof_circuit::<
+            IPACommitmentScheme<_>,
+            Fr,
+            _,
+            ProverIPA<_>,
+            VerifierIPA<_>,
+            _,
+            _,
+            PoseidonTranscript<NativeLoader, _>,
+            PoseidonTranscript<NativeLoader, _>,
+        >(
+            circuit,
+            public_inputs,
+            params,
+            pk,
+            strategy,
+            check_mode,
+            transcript,
+            split,
+            Commitments::IPA,
+            None,
+        )
+        .map_err(Box::<dyn Error>::from),
+    }
+}
+
+/// helper function
+pub(crate) fn verify_proof_circuit_ipa<
+    'params,
+    Strategy: VerificationStrategy<'params, IPACommitmentScheme<G1Affine>, VerifierIPA<'params, G1Affine>>,
+>(
+    params: &'params ParamsIPA<G1Affine>,
+    proof: Snark<Fr, G1Affine>,
+    vk: &VerifyingKey<G1Affine>,
+    strategy: Strategy,
 ) -> Result<Strategy::Output, halo2_proofs::plonk::Error> {
     match proof.transcript_type {
         TranscriptType::EVM => verify_proof_circuit::<
             Fr,
-            VerifierSHPLONK<'_, Bn256>,
+            VerifierIPA<'_, G1Affine>,
             _,
             _,
             _,
@@ -786,7 +1264,7 @@ pub(crate) fn verify_proof_circuit_kzg<
         >(&proof, params, vk, strategy),
         TranscriptType::Poseidon => verify_proof_circuit::<
             Fr,
-            VerifierSHPLONK<'_, Bn256>,
+            VerifierIPA<'_, G1Affine>,
             _,
             _,
             _,
@@ -850,6 +1328,8 @@ mod tests {
             transcript_type: TranscriptType::EVM,
             protocol: None,
             split: None,
+            commitment: None,
+            multi_open: None,
         };
 
         snark
@@ -857,10 +1337,106 @@ mod tests {
             .unwrap();
         let snark2 = Snark::<Fr, G1Affine>::load::<KZGCommitmentScheme<Bn256>>(
             &"test_snark_serialization_roundtrip.json".into(),
+            None,
         )
         .unwrap();
         assert_eq!(snark.instances, snark2.instances);
         assert_eq!(snark.proof, snark2.proof);
         assert_eq!(snark.transcript_type, snark2.transcript_type);
+        // legacy proofs without a commitment field are KZG proofs
+        assert_eq!(snark2.commitment(), Commitments::KZG);
+        // legacy proofs without a multi-open field are SHPLONK proofs
+        assert_eq!(snark2.multi_open(), MultiOpenScheme::SHPLONK);
+    }
+
+    #[test]
+    fn test_snark_container_roundtrip() {
+        let snark = Snark::<Fr, G1Affine> {
+            proof: vec![1, 2, 3, 4, 5, 6, 7, 8],
+            instances: vec![vec![Fr::from(1)], vec![Fr::from(2)]],
+            transcript_type: TranscriptType::Poseidon,
+            protocol: None,
+            split: None,
+            commitment: Some(Commitments::KZG),
+            multi_open: Some(MultiOpenScheme::GWC),
+        };
+
+        let bytes = snark.to_container_bytes(None).unwrap();
+        assert!(bytes.starts_with(&MAGIC));
+        assert!(bytes.len() < serde_json::to_vec(&snark).unwrap().len());
+
+        let snark2 = Snark::<Fr, G1Affine>::from_bytes(&bytes, None).unwrap();
+        assert_eq!(snark.instances, snark2.instances);
+        assert_eq!(snark.proof, snark2.proof);
+        assert_eq!(snark.transcript_type, snark2.transcript_type);
+        assert_eq!(snark2.multi_open(), MultiOpenScheme::GWC);
+
+        // json proofs are still accepted
+        let json = serde_json::to_vec(&snark).unwrap();
+        let snark3 = Snark::<Fr, G1Affine>::from_bytes(&json, None).unwrap();
+        assert_eq!(snark.instances, snark3.instances);
+    }
+
+    #[test]
+    fn test_snark_container_settings_mismatch_is_rejected() {
+        let snark = Snark::<Fr, G1Affine> {
+            proof: vec![1, 2, 3, 4],
+            instances: vec![vec![Fr::from(1)]],
+            transcript_type: TranscriptType::EVM,
+            protocol: None,
+            split: None,
+            commitment: None,
+            multi_open: None,
+        };
+
+        let bytes = snark
+            .to_container_bytes(Some(container::hash_bytes(b"settings")))
+            .unwrap();
+        assert!(Snark::<Fr, G1Affine>::from_bytes(
+            &bytes,
+            Some(container::hash_bytes(b"settings"))
+        )
+        .is_ok());
+        assert!(
+            Snark::<Fr, G1Affine>::from_bytes(&bytes, Some(container::hash_bytes(b"other")))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_multi_open_mismatch_is_rejected() {
+        let mut snark = Snark::<Fr, G1Affine> {
+            proof: vec![1, 2, 3, 4],
+            instances: vec![vec![Fr::from(1)]],
+            transcript_type: TranscriptType::EVM,
+            protocol: None,
+            split: None,
+            commitment: Some(Commitments::KZG),
+            multi_open: Some(MultiOpenScheme::GWC),
+        };
+
+        assert!(snark.check_multi_open(MultiOpenScheme::GWC).is_ok());
+        assert!(matches!(
+            snark.check_multi_open(MultiOpenScheme::SHPLONK),
+            Err(PfSysError::MultiOpenMismatch(
+                MultiOpenScheme::GWC,
+                MultiOpenScheme::SHPLONK
+            ))
+        ));
+
+        snark.multi_open = None;
+        assert!(snark.check_multi_open(MultiOpenScheme::SHPLONK).is_ok());
+        assert!(snark.check_multi_open(MultiOpenScheme::GWC).is_err());
+    }
+
+    #[test]
+    fn test_can_load_saved_ipa_srs() {
+        let tmp_dir = Builder::new().prefix("example").tempdir().unwrap();
+        let fname = tmp_dir.path().join("ipa.params");
+        let srs = srs::gen_srs::<IPACommitmentScheme<G1Affine>>(1);
+        let res = save_params::<IPACommitmentScheme<G1Affine>>(&fname, &srs);
+        assert!(res.is_ok());
+        let res = srs::load_srs::<IPACommitmentScheme<G1Affine>>(fname);
+        assert!(res.is_ok())
     }
 }
@@ -0,0 +1,194 @@
+use instant::Instant;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fs::File;
+use std::path::PathBuf;
+
+/// Model loading (deserializing the compiled circuit)
+pub const MODEL_LOAD: &str = "model_load";
+/// Laying out the witness (forward pass / loading the witness into the circuit)
+pub const WITNESS_LAYOUT: &str = "witness_layout";
+/// Loading the structured reference string
+pub const SRS_LOAD: &str = "srs_load";
+/// Loading the proving key
+pub const PK_LOAD: &str = "pk_load";
+/// Generating the verifying key
+pub const KEYGEN_VK: &str = "keygen_vk";
+/// Generating the proving key
+pub const KEYGEN_PK: &str = "keygen_pk";
+/// Creating the proof
+pub const CREATE_PROOF: &str = "create_proof";
+/// Verifying the freshly created proof (when running in safe mode)
+pub const SELF_VERIFY: &str = "self_verify";
+
+/// Wall time and memory usage of a single phase
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PhaseReport {
+    /// name of the phase
+    pub name: String,
+    /// wall time in seconds
+    pub wall_time_secs: f64,
+    /// peak resident set size during the phase in bytes (if the platform reports it)
+    pub peak_rss_bytes: Option<u64>,
+}
+
+/// Profile of a single ezkl command
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ProfileReport {
+    /// the command that was profiled
+    pub command: String,
+    /// ezkl version that generated the report
+    pub ezkl_version: String,
+    /// total wall time of the command in seconds
+    pub total_wall_time_secs: f64,
+    /// peak resident set size over all phases in bytes (if the platform reports it)
+    pub peak_rss_bytes: Option<u64>,
+    /// the profiled phases, in the order they ran
+    pub phases: Vec<PhaseReport>,
+}
+
+impl ProfileReport {
+    /// Saves the report as json to `path`
+    pub fn save(&self, path: &PathBuf) -> Result<(), std::io::Error> {
+        serde_json::to_writer_pretty(&File::create(path)?, self).map_err(std::io::Error::from)
+    }
+}
+
+struct Profile {
+    start: Instant,
+    depth: usize,
+    report: ProfileReport,
+}
+
+thread_local!(
+    static PROFILE: RefCell<Option<Profile>> = RefCell::new(None)
+);
+
+/// Starts profiling `command` on the current thread, discarding any unfinished profile
+pub fn start(command: &str) {
+    PROFILE.with(|p| {
+        *p.borrow_mut() = Some(Profile {
+            start: Instant::now(),
+            depth: 0,
+            report: ProfileReport {
+                command: command.to_string(),
+                ezkl_version: env!("CARGO_PKG_VERSION").to_string(),
+                total_wall_time_secs: 0.0,
+                peak_rss_bytes: None,
+                phases: vec![],
+            },
+        })
+    });
+}
+
+/// Whether profiling is enabled on the current thread
+pub fn is_enabled() -> bool {
+    PROFILE.with(|p| p.borrow().is_some())
+}
+
+/// Runs `f` as the phase `name`, recording its wall time and peak memory if profiling is enabled.
+/// Peak memory of nested phases includes the part of the outer phase that ran before them.
+pub fn phase<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let outermost = PROFILE.with(|p| match p.borrow_mut().as_mut() {
+        Some(profile) => {
+            profile.depth += 1;
+            Some(profile.depth == 1)
+        }
+        None => None,
+    });
+    let outermost = match outermost {
+        Some(outermost) => outermost,
+        None => return f(),
+    };
+
+    if outermost {
+        reset_peak_rss();
+    }
+    let now = Instant::now();
+    let res = f();
+    let wall_time_secs = now.elapsed().as_secs_f64();
+    let peak_rss_bytes = peak_rss();
+
+    PROFILE.with(|p| {
+        if let Some(profile) = p.borrow_mut().as_mut() {
+            profile.depth -= 1;
+            profile.report.phases.push(PhaseReport {
+                name: name.to_string(),
+                wall_time_secs,
+                peak_rss_bytes,
+            });
+        }
+    });
+    res
+}
+
+/// Stops profiling on the current thread and returns the report (if profiling was started)
+pub fn finish() -> Option<ProfileReport> {
+    PROFILE.with(|p| p.borrow_mut().take()).map(|profile| {
+        let mut report = profile.report;
+        report.total_wall_time_secs = profile.start.elapsed().as_secs_f64();
+        report.peak_rss_bytes = report
+            .phases
+            .iter()
+            .filter_map(|p| p.peak_rss_bytes)
+            .chain(peak_rss())
+            .max();
+        report
+    })
+}
+
+/// Resets the kernel's peak rss (high water mark) counter so it can be read per phase
+#[cfg(target_os = "linux")]
+fn reset_peak_rss() {
+    // supported since linux 4.0, if it fails the peak is measured since the process started
+    let _ = std::fs::write("/proc/self/clear_refs", "5");
+}
+
+#[cfg(not(target_os = "linux"))]
+fn reset_peak_rss() {}
+
+/// Peak resident set size of the process in bytes
+#[cfg(target_os = "linux")]
+fn peak_rss() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kb = status
+        .lines()
+        .find_map(|l| l.strip_prefix("VmHWM:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_rss() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_are_only_recorded_when_enabled() {
+        assert_eq!(phase(KEYGEN_VK, || 1), 1);
+        assert!(finish().is_none());
+
+        start("setup");
+        assert!(is_enabled());
+        let res = phase(KEYGEN_PK, || phase(KEYGEN_VK, || 2) + 1);
+        assert_eq!(res, 3);
+        let report = finish().unwrap();
+        assert!(!is_enabled());
+
+        assert_eq!(report.command, "setup");
+        // inner phases finish first
+        let names: Vec<_> = report.phases.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec![KEYGEN_VK, KEYGEN_PK]);
+        assert!(report.total_wall_time_secs >= report.phases[1].wall_time_secs);
+        #[cfg(target_os = "linux")]
+        assert!(report.peak_rss_bytes.is_some());
+    }
+}
@@ -1,12 +1,10 @@
+use crate::circuit::modules::eddsa::EddsaSignature;
 use crate::circuit::modules::elgamal::{ElGamalCipher, ElGamalVariables};
 use crate::circuit::modules::kzg::KZGChip;
-use crate::circuit::modules::poseidon::{
-    spec::{PoseidonSpec, POSEIDON_RATE, POSEIDON_WIDTH},
-    PoseidonChip,
-};
-use crate::circuit::modules::Module;
+use crate::circuit::modules::merkle::MerkleTree;
+use crate::circuit::modules::poseidon::PoseidonParams;
 use crate::circuit::{CheckMode, Tolerance};
-use crate::commands::CalibrationTarget;
+use crate::commands::{CalibrationTarget, EvmCalldataType};
 use crate::fieldutils::{felt_to_i128, i128_to_felt};
 use crate::graph::modules::POSEIDON_LEN_GRAPH;
 use crate::graph::{
@@ -14,14 +12,14 @@ use crate::graph::{
 };
 use crate::pfsys::evm::aggregation::AggregationCircuit;
 use crate::pfsys::{
-    load_pk, load_vk, save_params, save_vk, srs::gen_srs as ezkl_gen_srs, srs::load_srs, ProofType,
-    Snark, TranscriptType,
+    load_pk, load_vk, save_vk, srs::load_srs, Commitments, MultiOpenScheme, ProofType, Snark,
+    TranscriptType,
 };
 use crate::RunArgs;
 use ethers::types::H160;
 use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
 use halo2curves::bn256::{Bn256, Fq, Fr, G1Affine, G1};
-use pyo3::exceptions::{PyIOError, PyRuntimeError};
+use pyo3::exceptions::{PyIOError, PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
 use pyo3_log;
@@ -164,7 +162,7 @@ pub struct PyElGamalVariables {
     #[pyo3(get, set)]
     pk: PyG1Affine,
     #[pyo3(get, set)]
-    sk: PyFelt,
+    sk: Option<PyFelt>,
     #[pyo3(get, set)]
     window_size: usize,
     #[pyo3(get, set)]
@@ -179,7 +177,9 @@ impl From<PyElGamalVariables> for ElGamalVariables {
                 x: crate::pfsys::vecu64_to_field_montgomery::<Fq>(&py_elgamal_variables.pk.x),
                 y: crate::pfsys::vecu64_to_field_montgomery::<Fq>(&py_elgamal_variables.pk.y),
             },
-            sk: crate::pfsys::vecu64_to_field_montgomery::<Fr>(&py_elgamal_variables.sk),
+            sk: py_elgamal_variables
+                .sk
+                .map(|sk| crate::pfsys::vecu64_to_field_montgomery::<Fr>(&sk)),
             window_size: py_elgamal_variables.window_size,
             aux_generator: G1Affine {
                 x: crate::pfsys::vecu64_to_field_montgomery::<Fq>(
@@ -201,7 +201,9 @@ impl From<ElGamalVariables> for PyElGamalVariables {
                 x: crate::pfsys::field_to_vecu64_montgomery::<Fq>(&elgamal_variables.pk.x),
                 y: crate::pfsys::field_to_vecu64_montgomery::<Fq>(&elgamal_variables.pk.y),
             },
-            sk: crate::pfsys::field_to_vecu64_montgomery::<Fr>(&elgamal_variables.sk),
+            sk: elgamal_variables
+                .sk
+                .map(|sk| crate::pfsys::field_to_vecu64_montgomery::<Fr>(&sk)),
             window_size: elgamal_variables.window_size,
             aux_generator: PyG1Affine {
                 x: crate::pfsys::field_to_vecu64_montgomery::<Fq>(
@@ -261,7 +263,19 @@ struct PyRunArgs {
     #[pyo3(get, set)]
     pub param_visibility: Visibility,
     #[pyo3(get, set)]
+    pub input_visibilities: Vec<Visibility>,
+    #[pyo3(get, set)]
+    pub output_visibilities: Vec<Visibility>,
+    #[pyo3(get, set)]
+    pub param_visibilities: Vec<Visibility>,
+    #[pyo3(get, set)]
     pub variables: Vec<(String, usize)>,
+    #[pyo3(get, set)]
+    pub commitment: Commitments,
+    #[pyo3(get, set)]
+    pub multi_open: MultiOpenScheme,
+    #[pyo3(get, set)]
+    pub commit_on_chain_block: bool,
 }
 
 /// default instantiation of PyRunArgs
@@ -280,7 +294,13 @@ impl PyRunArgs {
             input_visibility: Visibility::Private,
             output_visibility: Visibility::Public,
             param_visibility: Visibility::Private,
+            input_visibilities: vec![],
+            output_visibilities: vec![],
+            param_visibilities: vec![],
             variables: vec![("batch_size".to_string(), 1)],
+            commitment: Commitments::KZG,
+            multi_open: MultiOpenScheme::SHPLONK,
+            commit_on_chain_block: false,
         }
     }
 }
@@ -299,7 +319,13 @@ impl From<PyRunArgs> for RunArgs {
             input_visibility: py_run_args.input_visibility,
             output_visibility: py_run_args.output_visibility,
             param_visibility: py_run_args.param_visibility,
+            input_visibilities: py_run_args.input_visibilities,
+            output_visibilities: py_run_args.output_visibilities,
+            param_visibilities: py_run_args.param_visibilities,
             variables: py_run_args.variables,
+            commitment: py_run_args.commitment,
+            multi_open: py_run_args.multi_open,
+            commit_on_chain_block: py_run_args.commit_on_chain_block,
         }
     }
 }
@@ -317,7 +343,13 @@ impl Into<PyRunArgs> for RunArgs {
             input_visibility: self.input_visibility,
             output_visibility: self.output_visibility,
             param_visibility: self.param_visibility,
+            input_visibilities: self.input_visibilities,
+            output_visibilities: self.output_visibilities,
+            param_visibilities: self.param_visibilities,
             variables: self.variables,
+            commitment: self.commitment,
+            multi_open: self.multi_open,
+            commit_on_chain_block: self.commit_on_chain_block,
         }
     }
 }
@@ -426,20 +458,21 @@ fn buffer_to_felts(buffer: Vec<u8>) -> PyResult<Vec<String>> {
     Ok(field_elements)
 }
 
-/// Generate a poseidon hash.
+/// Generate a poseidon hash, with one of the named parameter sets (`t2` or `t5`).
 #[pyfunction(signature = (
     message,
+    params=String::from("t2"),
     ))]
-fn poseidon_hash(message: Vec<PyFelt>) -> PyResult<Vec<PyFelt>> {
+fn poseidon_hash(message: Vec<PyFelt>, params: String) -> PyResult<Vec<PyFelt>> {
+    let params: PoseidonParams = params.parse().map_err(PyValueError::new_err)?;
+
     let message: Vec<Fr> = message
         .iter()
         .map(|x| crate::pfsys::vecu64_to_field_montgomery::<Fr>(&x))
         .collect::<Vec<_>>();
 
-    let output =
-        PoseidonChip::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, POSEIDON_LEN_GRAPH>::run(
-            message.clone(),
-        )
+    let output = params
+        .hash::<POSEIDON_LEN_GRAPH>(message.clone())
         .map_err(|_| PyIOError::new_err("Failed to run poseidon"))?;
 
     let hash = output[0]
@@ -449,6 +482,93 @@ fn poseidon_hash(message: Vec<PyFelt>) -> PyResult<Vec<PyFelt>> {
     Ok(hash)
 }
 
+/// Builds a poseidon merkle tree over a dataset, one leaf per record.
+fn merkle_tree(records: Vec<Vec<PyFelt>>) -> PyResult<MerkleTree> {
+    let records: Vec<Vec<Fr>> = records
+        .iter()
+        .map(|record| {
+            record
+                .iter()
+                .map(|x| crate::pfsys::vecu64_to_field_montgomery::<Fr>(x))
+                .collect()
+        })
+        .collect();
+
+    MerkleTree::from_records(&records)
+        .map_err(|_| PyIOError::new_err("Failed to build merkle tree"))
+}
+
+/// Generate the root of a poseidon merkle tree over a dataset, one leaf per record.
+#[pyfunction(signature = (
+    records,
+    ))]
+fn merkle_root(records: Vec<Vec<PyFelt>>) -> PyResult<PyFelt> {
+    let tree = merkle_tree(records)?;
+    Ok(crate::pfsys::field_to_vecu64_montgomery::<Fr>(&tree.root()))
+}
+
+/// Generate the root a `merkle` visibility commits a message to, the message is split into leaves of
+/// `MERKLE_LEAF_LEN` elements (as in the circuit) before the tree is built.
+#[pyfunction(signature = (
+    message,
+    ))]
+fn merkle_commit(message: Vec<PyFelt>) -> PyResult<PyFelt> {
+    let message: Vec<Fr> = message
+        .iter()
+        .map(|x| crate::pfsys::vecu64_to_field_montgomery::<Fr>(x))
+        .collect();
+
+    let tree = MerkleTree::from_elements(&message)
+        .map_err(|_| PyIOError::new_err("Failed to build merkle tree"))?;
+    Ok(crate::pfsys::field_to_vecu64_montgomery::<Fr>(&tree.root()))
+}
+
+/// Generate the path the record at `index` of a dataset is opened along by a `merkle/<depth>` input,
+/// as json to add to the input data's `merkle_proofs`.
+#[pyfunction(signature = (
+    records,
+    index,
+    ))]
+fn merkle_proof(records: Vec<Vec<PyFelt>>, index: usize) -> PyResult<String> {
+    let tree = merkle_tree(records)?;
+    let proof = tree
+        .proof(index)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    serde_json::to_string(&proof).map_err(|_| PyIOError::new_err("Failed to serialize proof"))
+}
+
+/// Generate the (BabyJubJub) public key `signed` inputs are verified against, from an eddsa secret key.
+#[pyfunction(signature = (
+    secret_key,
+    ))]
+fn eddsa_public_key(secret_key: PyFelt) -> PyResult<Vec<PyFelt>> {
+    let secret_key = crate::pfsys::vecu64_to_field_montgomery::<Fr>(&secret_key);
+    let public_key = EddsaSignature::public_key(&secret_key);
+    Ok(vec![
+        crate::pfsys::field_to_vecu64_montgomery::<Fr>(&public_key.x),
+        crate::pfsys::field_to_vecu64_montgomery::<Fr>(&public_key.y),
+    ])
+}
+
+/// Sign a message (eg. a record from a sensor) with an eddsa secret key, as json to add to the
+/// input data's `signatures` for a `signed` input. The scheme is ezkl's own (circomlib signatures don't verify).
+#[pyfunction(signature = (
+    message,
+    secret_key,
+    ))]
+fn eddsa_sign(message: Vec<PyFelt>, secret_key: PyFelt) -> PyResult<String> {
+    let message: Vec<Fr> = message
+        .iter()
+        .map(|x| crate::pfsys::vecu64_to_field_montgomery::<Fr>(x))
+        .collect();
+    let secret_key = crate::pfsys::vecu64_to_field_montgomery::<Fr>(&secret_key);
+
+    let signature = EddsaSignature::sign(&secret_key, message, &mut rand::thread_rng())
+        .map_err(|_| PyIOError::new_err("Failed to sign message"))?;
+    serde_json::to_string(&signature)
+        .map_err(|_| PyIOError::new_err("Failed to serialize signature"))
+}
+
 /// Generate a kzg commitment.
 #[pyfunction(signature = (
     message,
@@ -566,13 +686,13 @@ fn gen_vk_from_pk_single(
     let settings = GraphSettings::load(&circuit_settings_path)
         .map_err(|_| PyIOError::new_err("Failed to load circuit settings"))?;
 
-    let pk = load_pk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(path_to_pk, settings)
+    let pk = load_pk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(path_to_pk, settings.clone())
         .map_err(|_| PyIOError::new_err("Failed to load pk"))?;
 
     let vk = pk.get_vk();
 
     // now save
-    save_vk::<KZGCommitmentScheme<Bn256>>(&vk_output_path, vk)
+    save_vk::<KZGCommitmentScheme<Bn256>>(&vk_output_path, vk, &settings)
         .map_err(|_| PyIOError::new_err("Failed to save vk"))?;
 
     Ok(true)
@@ -590,7 +710,7 @@ fn gen_vk_from_pk_aggr(path_to_pk: PathBuf, vk_output_path: PathBuf) -> PyResult
     let vk = pk.get_vk();
 
     // now save
-    save_vk::<KZGCommitmentScheme<Bn256>>(&vk_output_path, vk)
+    save_vk::<KZGCommitmentScheme<Bn256>>(&vk_output_path, vk, &())
         .map_err(|_| PyIOError::new_err("Failed to save vk"))?;
 
     Ok(true)
@@ -616,10 +736,13 @@ fn table(model: String, py_run_args: Option<PyRunArgs>) -> PyResult<String> {
 #[pyfunction(signature = (
     srs_path,
     logrows,
+    commitment = Commitments::KZG,
 ))]
-fn gen_srs(srs_path: PathBuf, logrows: usize) -> PyResult<()> {
-    let params = ezkl_gen_srs::<KZGCommitmentScheme<Bn256>>(logrows as u32);
-    save_params::<KZGCommitmentScheme<Bn256>>(&srs_path, &params)?;
+fn gen_srs(srs_path: PathBuf, logrows: usize, commitment: Commitments) -> PyResult<()> {
+    crate::execute::gen_srs_cmd(srs_path, logrows as u32, commitment).map_err(|e| {
+        let err_str = format!("Failed to generate srs: {}", e);
+        PyRuntimeError::new_err(err_str)
+    })?;
     Ok(())
 }
 
@@ -654,15 +777,17 @@ fn get_srs(
     model,
     output,
     py_run_args = None,
+    preprocessing = None,
 ))]
 fn gen_settings(
     model: PathBuf,
     output: PathBuf,
     py_run_args: Option<PyRunArgs>,
+    preprocessing: Option<PathBuf>,
 ) -> Result<bool, PyErr> {
     let run_args: RunArgs = py_run_args.unwrap_or_else(PyRunArgs::new).into();
 
-    crate::execute::gen_circuit_settings(model, output, run_args).map_err(|e| {
+    crate::execute::gen_circuit_settings(model, output, run_args, preprocessing).map_err(|e| {
         let err_str = format!("Failed to generate settings: {}", e);
         PyRuntimeError::new_err(err_str)
     })?;
@@ -678,6 +803,8 @@ fn gen_settings(
     target,
     scales = None,
     max_logrows = None,
+    num_samples = None,
+    max_memory = None,
 ))]
 fn calibrate_settings(
     data: PathBuf,
@@ -686,11 +813,23 @@ fn calibrate_settings(
     target: Option<CalibrationTarget>,
     scales: Option<Vec<crate::Scale>>,
     max_logrows: Option<u32>,
+    num_samples: Option<usize>,
+    max_memory: Option<usize>,
 ) -> Result<bool, PyErr> {
     let target = target.unwrap_or(CalibrationTarget::Resources {
         col_overflow: false,
     });
-    crate::execute::calibrate(model, data, settings, target, scales, max_logrows).map_err(|e| {
+    crate::execute::calibrate(
+        model,
+        data,
+        settings,
+        target,
+        scales,
+        max_logrows,
+        num_samples,
+        max_memory,
+    )
+    .map_err(|e| {
         let err_str = format!("Failed to calibrate settings: {}", e);
         PyRuntimeError::new_err(err_str)
     })?;
@@ -743,13 +882,21 @@ fn mock(witness: PathBuf, model: PathBuf) -> PyResult<bool> {
     aggregation_snarks,
     logrows,
     split_proofs = false,
+    settings_paths = None,
 ))]
 fn mock_aggregate(
     aggregation_snarks: Vec<PathBuf>,
     logrows: u32,
     split_proofs: bool,
+    settings_paths: Option<Vec<PathBuf>>,
 ) -> PyResult<bool> {
-    crate::execute::mock_aggregate(aggregation_snarks, logrows, split_proofs).map_err(|e| {
+    crate::execute::mock_aggregate(
+        aggregation_snarks,
+        settings_paths.unwrap_or_default(),
+        logrows,
+        split_proofs,
+    )
+    .map_err(|e| {
         let err_str = format!("Failed to run mock: {}", e);
         PyRuntimeError::new_err(err_str)
     })?;
@@ -842,6 +989,7 @@ fn verify(
     srs_path,
     logrows,
     split_proofs = false,
+    settings_paths = None,
 ))]
 fn setup_aggregate(
     sample_snarks: Vec<PathBuf>,
@@ -850,9 +998,11 @@ fn setup_aggregate(
     srs_path: PathBuf,
     logrows: u32,
     split_proofs: bool,
+    settings_paths: Option<Vec<PathBuf>>,
 ) -> Result<bool, PyErr> {
     crate::execute::setup_aggregate(
         sample_snarks,
+        settings_paths.unwrap_or_default(),
         vk_path,
         pk_path,
         srs_path,
@@ -895,6 +1045,7 @@ fn compile_circuit(
     logrows,
     check_mode,
     split_proofs = false,
+    settings_paths = None,
 ))]
 fn aggregate(
     proof_path: PathBuf,
@@ -905,11 +1056,13 @@ fn aggregate(
     logrows: u32,
     check_mode: CheckMode,
     split_proofs: bool,
+    settings_paths: Option<Vec<PathBuf>>,
 ) -> Result<bool, PyErr> {
     // the K used for the aggregation circuit
     crate::execute::aggregate(
         proof_path,
         aggregation_snarks,
+        settings_paths.unwrap_or_default(),
         vk_path,
         srs_path,
         transcript,
@@ -952,7 +1105,9 @@ fn verify_aggr(
     srs_path,
     settings_path,
     sol_code_path,
-    abi_path
+    abi_path,
+    render_vk_separately = false,
+    bind_commitments = false
 ))]
 fn create_evm_verifier(
     vk_path: PathBuf,
@@ -960,16 +1115,89 @@ fn create_evm_verifier(
     settings_path: PathBuf,
     sol_code_path: PathBuf,
     abi_path: PathBuf,
+    render_vk_separately: bool,
+    bind_commitments: bool,
+) -> Result<bool, PyErr> {
+    crate::execute::create_evm_verifier(
+        vk_path,
+        srs_path,
+        settings_path,
+        sol_code_path,
+        abi_path,
+        render_vk_separately,
+        bind_commitments,
+    )
+    .map_err(|e| {
+        let err_str = format!("Failed to run create_evm_verifier: {}", e);
+        PyRuntimeError::new_err(err_str)
+    })?;
+
+    Ok(true)
+}
+
+/// creates an EVM verifying key contract for a verifier rendered with `render_vk_separately`, you will need solc installed in your environment to run this
+#[pyfunction(signature = (
+    vk_path,
+    srs_path,
+    settings_path,
+    sol_code_path,
+    abi_path
+))]
+fn create_evm_vk(
+    vk_path: PathBuf,
+    srs_path: PathBuf,
+    settings_path: PathBuf,
+    sol_code_path: PathBuf,
+    abi_path: PathBuf,
 ) -> Result<bool, PyErr> {
-    crate::execute::create_evm_verifier(vk_path, srs_path, settings_path, sol_code_path, abi_path)
+    crate::execute::create_evm_vk(vk_path, srs_path, settings_path, sol_code_path, abi_path)
         .map_err(|e| {
-            let err_str = format!("Failed to run create_evm_verifier: {}", e);
+            let err_str = format!("Failed to run create_evm_vk: {}", e);
             PyRuntimeError::new_err(err_str)
         })?;
 
     Ok(true)
 }
 
+/// quantizes and signs the input (and output) data of a data file, for signed data attestation, returns the signed data
+#[pyfunction(signature = (
+    data,
+    model,
+    private_key,
+    addr_verifier,
+    chain_id,
+    output,
+    nonce=0
+))]
+fn sign_data(
+    data: PathBuf,
+    model: PathBuf,
+    private_key: String,
+    addr_verifier: &str,
+    chain_id: u64,
+    output: PathBuf,
+    nonce: u64,
+) -> PyResult<PyObject> {
+    let addr_verifier = H160::from_str(addr_verifier).map_err(|e| {
+        let err_str = format!("address is invalid: {}", e);
+        PyRuntimeError::new_err(err_str)
+    })?;
+    let output = crate::execute::sign_data(
+        data,
+        model,
+        private_key,
+        addr_verifier,
+        chain_id,
+        nonce,
+        output,
+    )
+    .map_err(|e| {
+        let err_str = format!("Failed to run sign_data: {}", e);
+        PyRuntimeError::new_err(err_str)
+    })?;
+    Python::with_gil(|py| Ok(output.to_object(py)))
+}
+
 // creates an EVM compatible data attestation verifier, you will need solc installed in your environment to run this
 #[pyfunction(signature = (
     vk_path,
@@ -1003,12 +1231,32 @@ fn create_evm_data_attestation(
     Ok(true)
 }
 
+/// The CREATE2 factory to deploy through if `create2` is set, the deterministic deployment proxy
+/// unless a `create2_factory` is given
+fn parse_create2_factory(
+    create2: bool,
+    create2_factory: Option<&str>,
+) -> Result<Option<H160>, PyErr> {
+    if !create2 {
+        return Ok(None);
+    }
+    let factory = create2_factory.unwrap_or(crate::eth::DEFAULT_CREATE2_FACTORY);
+    let factory = H160::from_str(factory).map_err(|e| {
+        let err_str = format!("address is invalid: {}", e);
+        PyRuntimeError::new_err(err_str)
+    })?;
+    Ok(Some(factory))
+}
+
 #[pyfunction(signature = (
     addr_path,
     sol_code_path,
     rpc_url=None,
     optimizer_runs=1,
-    private_key=None
+    private_key=None,
+    reuse_addr=None,
+    create2=false,
+    create2_factory=None
 ))]
 fn deploy_evm(
     addr_path: PathBuf,
@@ -1016,7 +1264,21 @@ fn deploy_evm(
     rpc_url: Option<String>,
     optimizer_runs: usize,
     private_key: Option<String>,
+    reuse_addr: Option<&str>,
+    create2: bool,
+    create2_factory: Option<&str>,
 ) -> Result<bool, PyErr> {
+    let create2_factory = parse_create2_factory(create2, create2_factory)?;
+    let reuse_addr = if let Some(reuse_addr) = reuse_addr {
+        let reuse_addr = H160::from_str(reuse_addr).map_err(|e| {
+            let err_str = format!("address is invalid: {}", e);
+            PyRuntimeError::new_err(err_str)
+        })?;
+        Some(reuse_addr)
+    } else {
+        None
+    };
+
     Runtime::new()
         .unwrap()
         .block_on(crate::execute::deploy_evm(
@@ -1025,6 +1287,8 @@ fn deploy_evm(
             addr_path,
             optimizer_runs,
             private_key,
+            reuse_addr,
+            create2_factory,
         ))
         .map_err(|e| {
             let err_str = format!("Failed to run deploy_evm: {}", e);
@@ -1034,6 +1298,43 @@ fn deploy_evm(
     Ok(true)
 }
 
+#[pyfunction(signature = (
+    addr_path,
+    sol_code_path,
+    rpc_url=None,
+    optimizer_runs=1,
+    private_key=None,
+    create2=false,
+    create2_factory=None
+))]
+fn deploy_vk_evm(
+    addr_path: PathBuf,
+    sol_code_path: PathBuf,
+    rpc_url: Option<String>,
+    optimizer_runs: usize,
+    private_key: Option<String>,
+    create2: bool,
+    create2_factory: Option<&str>,
+) -> Result<bool, PyErr> {
+    let create2_factory = parse_create2_factory(create2, create2_factory)?;
+    Runtime::new()
+        .unwrap()
+        .block_on(crate::execute::deploy_evm_vk(
+            sol_code_path,
+            rpc_url,
+            addr_path,
+            optimizer_runs,
+            private_key,
+            create2_factory,
+        ))
+        .map_err(|e| {
+            let err_str = format!("Failed to run deploy_vk_evm: {}", e);
+            PyRuntimeError::new_err(err_str)
+        })?;
+
+    Ok(true)
+}
+
 #[pyfunction(signature = (
     addr_path,
     input_data,
@@ -1041,7 +1342,9 @@ fn deploy_evm(
     sol_code_path,
     rpc_url=None,
     optimizer_runs=1,
-    private_key=None
+    private_key=None,
+    create2=false,
+    create2_factory=None
 ))]
 fn deploy_da_evm(
     addr_path: PathBuf,
@@ -1051,7 +1354,10 @@ fn deploy_da_evm(
     rpc_url: Option<String>,
     optimizer_runs: usize,
     private_key: Option<String>,
+    create2: bool,
+    create2_factory: Option<&str>,
 ) -> Result<bool, PyErr> {
+    let create2_factory = parse_create2_factory(create2, create2_factory)?;
     Runtime::new()
         .unwrap()
         .block_on(crate::execute::deploy_da_evm(
@@ -1062,6 +1368,7 @@ fn deploy_da_evm(
             addr_path,
             optimizer_runs,
             private_key,
+            create2_factory,
         ))
         .map_err(|e| {
             let err_str = format!("Failed to run deploy_da_evm: {}", e);
@@ -1070,18 +1377,72 @@ fn deploy_da_evm(
 
     Ok(true)
 }
+
+/// computes the address a contract is deployed at with `create2=True`, without connecting to a node.
+/// For a data attestation contract pass the `data` and `settings_path` it is deployed with and its `deployer`
+#[pyfunction(signature = (
+    sol_code_path,
+    contract_name=None,
+    optimizer_runs=1,
+    create2_factory=None,
+    data=None,
+    settings_path=None,
+    deployer=None
+))]
+fn create2_address(
+    sol_code_path: PathBuf,
+    contract_name: Option<String>,
+    optimizer_runs: usize,
+    create2_factory: Option<&str>,
+    data: Option<PathBuf>,
+    settings_path: Option<PathBuf>,
+    deployer: Option<&str>,
+) -> Result<String, PyErr> {
+    let create2_factory = parse_create2_factory(true, create2_factory)?.unwrap();
+    let deployer = match deployer {
+        Some(deployer) => Some(H160::from_str(deployer).map_err(|e| {
+            let err_str = format!("deployer address is invalid: {}", e);
+            PyRuntimeError::new_err(err_str)
+        })?),
+        None => None,
+    };
+    let addr = crate::execute::create2_address(
+        sol_code_path,
+        contract_name.unwrap_or("Halo2Verifier".to_string()),
+        optimizer_runs,
+        create2_factory,
+        None,
+        data,
+        settings_path,
+        deployer,
+    )
+    .map_err(|e| {
+        let err_str = format!("Failed to run create2_address: {}", e);
+        PyRuntimeError::new_err(err_str)
+    })?;
+
+    Ok(format!("{:#?}", addr))
+}
 /// verifies an evm compatible proof, you will need solc installed in your environment to run this
 #[pyfunction(signature = (
     proof_path,
     addr_verifier,
     rpc_url=None,
     addr_da = None,
+    addr_vk = None,
+    data_path = None,
+    witness_path = None,
+    settings_path = None,
 ))]
 fn verify_evm(
     proof_path: PathBuf,
     addr_verifier: &str,
     rpc_url: Option<String>,
     addr_da: Option<&str>,
+    addr_vk: Option<&str>,
+    data_path: Option<PathBuf>,
+    witness_path: Option<PathBuf>,
+    settings_path: Option<PathBuf>,
 ) -> Result<bool, PyErr> {
     let addr_verifier = H160::from_str(addr_verifier).map_err(|e| {
         let err_str = format!("address is invalid: {}", e);
@@ -1096,6 +1457,15 @@ fn verify_evm(
     } else {
         None
     };
+    let addr_vk = if let Some(addr_vk) = addr_vk {
+        let addr_vk = H160::from_str(addr_vk).map_err(|e| {
+            let err_str = format!("address is invalid: {}", e);
+            PyRuntimeError::new_err(err_str)
+        })?;
+        Some(addr_vk)
+    } else {
+        None
+    };
 
     Runtime::new()
         .unwrap()
@@ -1104,6 +1474,10 @@ fn verify_evm(
             addr_verifier,
             rpc_url,
             addr_da,
+            addr_vk,
+            data_path,
+            witness_path,
+            settings_path,
         ))
         .map_err(|e| {
             let err_str = format!("Failed to run verify_evm: {}", e);
@@ -1113,13 +1487,55 @@ fn verify_evm(
     Ok(true)
 }
 
+/// creates a solidity library decoding the public outputs of a verified proof into signed fixed point integers
+#[pyfunction(signature = (
+    settings_path,
+    sol_code_path
+))]
+fn create_evm_output_decoder(
+    settings_path: PathBuf,
+    sol_code_path: PathBuf,
+) -> Result<bool, PyErr> {
+    crate::execute::create_evm_output_decoder(settings_path, sol_code_path).map_err(|e| {
+        let err_str = format!("Failed to run create_evm_output_decoder: {}", e);
+        PyRuntimeError::new_err(err_str)
+    })?;
+    Ok(true)
+}
+
+/// decodes the public outputs of a proof with an output decoder library, you will need solc installed in your environment to run this
+#[pyfunction(signature = (
+    proof_path,
+    sol_code_path,
+    rpc_url=None
+))]
+fn decode_evm_outputs(
+    proof_path: PathBuf,
+    sol_code_path: PathBuf,
+    rpc_url: Option<String>,
+) -> Result<Vec<Vec<i128>>, PyErr> {
+    Runtime::new()
+        .unwrap()
+        .block_on(crate::execute::decode_evm_outputs(
+            proof_path,
+            sol_code_path,
+            rpc_url,
+        ))
+        .map_err(|e| {
+            let err_str = format!("Failed to run decode_evm_outputs: {}", e);
+            PyRuntimeError::new_err(err_str)
+        })
+}
+
 /// creates an evm compatible aggregate verifier, you will need solc installed in your environment to run this
 #[pyfunction(signature = (
     vk_path,
     srs_path,
     sol_code_path,
     abi_path,
-    aggregation_settings
+    aggregation_settings,
+    split_proofs = false,
+    layout_path = None
 ))]
 fn create_evm_verifier_aggr(
     vk_path: PathBuf,
@@ -1127,6 +1543,8 @@ fn create_evm_verifier_aggr(
     sol_code_path: PathBuf,
     abi_path: PathBuf,
     aggregation_settings: Vec<PathBuf>,
+    split_proofs: bool,
+    layout_path: Option<PathBuf>,
 ) -> Result<bool, PyErr> {
     crate::execute::create_evm_aggregate_verifier(
         vk_path,
@@ -1134,6 +1552,8 @@ fn create_evm_verifier_aggr(
         sol_code_path,
         abi_path,
         aggregation_settings,
+        split_proofs,
+        layout_path,
     )
     .map_err(|e| {
         let err_str = format!("Failed to run create_evm_verifier_aggr: {}", e);
@@ -1142,10 +1562,58 @@ fn create_evm_verifier_aggr(
     Ok(true)
 }
 
+/// encodes the calldata to verify a proof on chain, returning it as a 0x prefixed hex string
+#[pyfunction(signature = (
+    proof_path,
+    calldata_path=None,
+    calldata_type=None,
+    addr_verifier=None,
+    addr_vk=None,
+    selector=None,
+    settings_path=None,
+))]
+fn encode_evm_calldata(
+    proof_path: PathBuf,
+    calldata_path: Option<PathBuf>,
+    calldata_type: Option<EvmCalldataType>,
+    addr_verifier: Option<&str>,
+    addr_vk: Option<&str>,
+    selector: Option<String>,
+    settings_path: Option<PathBuf>,
+) -> Result<String, PyErr> {
+    let parse_addr = |addr: Option<&str>| -> Result<Option<H160>, PyErr> {
+        addr.map(|addr| {
+            H160::from_str(addr).map_err(|e| {
+                let err_str = format!("address is invalid: {}", e);
+                PyRuntimeError::new_err(err_str)
+            })
+        })
+        .transpose()
+    };
+    let addr_verifier = parse_addr(addr_verifier)?;
+    let addr_vk = parse_addr(addr_vk)?;
+
+    let calldata = crate::execute::encode_evm_calldata(
+        proof_path,
+        calldata_path,
+        calldata_type.unwrap_or_default(),
+        addr_verifier,
+        addr_vk,
+        selector,
+        settings_path,
+    )
+    .map_err(|e| {
+        let err_str = format!("Failed to run encode_evm_calldata: {}", e);
+        PyRuntimeError::new_err(err_str)
+    })?;
+
+    Ok(format!("0x{}", hex::encode(calldata)))
+}
+
 /// print hex representation of a proof
 #[pyfunction(signature = (proof_path))]
 fn print_proof_hex(proof_path: PathBuf) -> Result<String, PyErr> {
-    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path)
+    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path, None)
         .map_err(|_| PyIOError::new_err("Failed to load proof"))?;
 
     Ok(hex::encode(proof.proof))
@@ -1257,6 +1725,11 @@ fn ezkl(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(kzg_commit, m)?)?;
     m.add_function(wrap_pyfunction!(swap_proof_commitments, m)?)?;
     m.add_function(wrap_pyfunction!(poseidon_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(merkle_root, m)?)?;
+    m.add_function(wrap_pyfunction!(merkle_commit, m)?)?;
+    m.add_function(wrap_pyfunction!(merkle_proof, m)?)?;
+    m.add_function(wrap_pyfunction!(eddsa_public_key, m)?)?;
+    m.add_function(wrap_pyfunction!(eddsa_sign, m)?)?;
     m.add_function(wrap_pyfunction!(elgamal_encrypt, m)?)?;
     m.add_function(wrap_pyfunction!(elgamal_decrypt, m)?)?;
     m.add_function(wrap_pyfunction!(elgamal_gen_random, m)?)?;
@@ -1280,12 +1753,19 @@ fn ezkl(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(compile_circuit, m)?)?;
     m.add_function(wrap_pyfunction!(verify_aggr, m)?)?;
     m.add_function(wrap_pyfunction!(create_evm_verifier, m)?)?;
+    m.add_function(wrap_pyfunction!(create_evm_vk, m)?)?;
     m.add_function(wrap_pyfunction!(deploy_evm, m)?)?;
+    m.add_function(wrap_pyfunction!(deploy_vk_evm, m)?)?;
     m.add_function(wrap_pyfunction!(deploy_da_evm, m)?)?;
+    m.add_function(wrap_pyfunction!(create2_address, m)?)?;
     m.add_function(wrap_pyfunction!(verify_evm, m)?)?;
+    m.add_function(wrap_pyfunction!(create_evm_output_decoder, m)?)?;
+    m.add_function(wrap_pyfunction!(decode_evm_outputs, m)?)?;
+    m.add_function(wrap_pyfunction!(encode_evm_calldata, m)?)?;
     m.add_function(wrap_pyfunction!(print_proof_hex, m)?)?;
     m.add_function(wrap_pyfunction!(create_evm_verifier_aggr, m)?)?;
     m.add_function(wrap_pyfunction!(create_evm_data_attestation, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_data, m)?)?;
     m.add_function(wrap_pyfunction!(create_hub_artifact, m)?)?;
     m.add_function(wrap_pyfunction!(prove_hub, m)?)?;
     m.add_function(wrap_pyfunction!(get_hub_proof, m)?)?;
@@ -1,7 +1,5 @@
 use crate::circuit::modules::elgamal::ElGamalCipher;
-use crate::circuit::modules::poseidon::spec::{PoseidonSpec, POSEIDON_RATE, POSEIDON_WIDTH};
-use crate::circuit::modules::poseidon::PoseidonChip;
-use crate::circuit::modules::Module;
+use crate::circuit::modules::poseidon::PoseidonParams;
 use crate::fieldutils::felt_to_i128;
 use crate::fieldutils::i128_to_felt;
 use crate::graph::modules::POSEIDON_LEN_GRAPH;
@@ -9,6 +7,10 @@ use crate::graph::quantize_float;
 use crate::graph::scale_to_multiplier;
 use halo2_proofs::plonk::*;
 use halo2_proofs::poly::commitment::{CommitmentScheme, ParamsProver};
+use halo2_proofs::poly::ipa::{
+    commitment::{IPACommitmentScheme, ParamsIPA},
+    strategy::SingleStrategy as IPASingleStrategy,
+};
 use halo2_proofs::poly::kzg::{
     commitment::{KZGCommitmentScheme, ParamsKZG},
     strategy::SingleStrategy as KZGSingleStrategy,
@@ -41,7 +43,11 @@ pub fn init_panic_hook() {
 }
 
 use crate::graph::{GraphCircuit, GraphSettings};
-use crate::pfsys::{create_proof_circuit_kzg, verify_proof_circuit_kzg};
+use crate::pfsys::container::{ContainerHeader, ContainerKind, SettingsDigest};
+use crate::pfsys::{
+    check_key_header, create_proof_circuit_ipa, create_proof_circuit_kzg, verify_proof_circuit_ipa,
+    verify_proof_circuit_kzg, Commitments,
+};
 
 /// Wrapper around the halo2 encode call data method
 #[wasm_bindgen]
@@ -50,8 +56,9 @@ pub fn encodeVerifierCalldata(
     proof: wasm_bindgen::Clamped<Vec<u8>>,
     vk_address: Option<Vec<u8>>,
 ) -> Result<Vec<u8>, JsError> {
-    let snark: crate::pfsys::Snark<Fr, G1Affine> = serde_json::from_slice(&proof[..])
-        .map_err(|e| JsError::new(&format!("Failed to deserialize proof: {}", e)))?;
+    let snark: crate::pfsys::Snark<Fr, G1Affine> =
+        crate::pfsys::Snark::from_bytes(&proof[..], None)
+            .map_err(|e| JsError::new(&format!("Failed to deserialize proof: {}", e)))?;
 
     let vk_address: Option<[u8; 20]> = if let Some(vk_address) = vk_address {
         let array: [u8; 20] = serde_json::from_slice(&vk_address[..])
@@ -183,13 +190,22 @@ pub fn bufferToVecOfVecU64(
 pub fn poseidonHash(
     message: wasm_bindgen::Clamped<Vec<u8>>,
 ) -> Result<wasm_bindgen::Clamped<Vec<u8>>, JsError> {
+    poseidonHashWithParams(message, PoseidonParams::default().to_string())
+}
+
+/// Generate a poseidon hash in browser, with one of the named parameter sets (`t2` or `t5`).
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn poseidonHashWithParams(
+    message: wasm_bindgen::Clamped<Vec<u8>>,
+    params: String,
+) -> Result<wasm_bindgen::Clamped<Vec<u8>>, JsError> {
+    let params: PoseidonParams = params.parse().map_err(|e: String| JsError::new(&e))?;
     let message: Vec<Fr> = serde_json::from_slice(&message[..])
         .map_err(|e| JsError::new(&format!("Failed to deserialize message: {}", e)))?;
 
-    let output =
-        PoseidonChip::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, POSEIDON_LEN_GRAPH>::run(
-            message.clone(),
-        )
+    let output = params
+        .hash::<POSEIDON_LEN_GRAPH>(message.clone())
         .map_err(|e| JsError::new(&format!("{}", e)))?;
 
     Ok(wasm_bindgen::Clamped(serde_json::to_vec(&output).map_err(
@@ -285,21 +301,43 @@ pub fn genVk(
     compiled_circuit: wasm_bindgen::Clamped<Vec<u8>>,
     params_ser: wasm_bindgen::Clamped<Vec<u8>>,
 ) -> Result<Vec<u8>, JsError> {
-    // Read in kzg params
-    let mut reader = std::io::BufReader::new(&params_ser[..]);
-    let params: ParamsKZG<Bn256> =
-        halo2_proofs::poly::commitment::Params::<'_, G1Affine>::read(&mut reader)
-            .map_err(|e| JsError::new(&format!("Failed to deserialize params: {}", e)))?;
     // Read in compiled circuit
     let circuit: crate::graph::GraphCircuit = bincode::deserialize(&compiled_circuit[..])
         .map_err(|e| JsError::new(&format!("Failed to deserialize compiled model: {}", e)))?;
 
-    // Create verifying key
-    let vk = create_vk_wasm::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(&circuit, &params)
-        .map_err(Box::<dyn std::error::Error>::from)
-        .map_err(|e| JsError::new(&format!("Failed to create verifying key: {}", e)))?;
+    // Read in params and create verifying key
+    let mut reader = std::io::BufReader::new(&params_ser[..]);
+    let vk = match circuit.settings().run_args.commitment {
+        Commitments::KZG => {
+            let params: ParamsKZG<Bn256> =
+                halo2_proofs::poly::commitment::Params::<'_, G1Affine>::read(&mut reader)
+                    .map_err(|e| JsError::new(&format!("Failed to deserialize params: {}", e)))?;
+            create_vk_wasm::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(&circuit, &params)
+        }
+        Commitments::IPA => {
+            let params: ParamsIPA<G1Affine> =
+                halo2_proofs::poly::commitment::Params::<'_, G1Affine>::read(&mut reader)
+                    .map_err(|e| JsError::new(&format!("Failed to deserialize params: {}", e)))?;
+            create_vk_wasm::<IPACommitmentScheme<G1Affine>, Fr, GraphCircuit>(&circuit, &params)
+        }
+    }
+    .map_err(Box::<dyn std::error::Error>::from)
+    .map_err(|e| JsError::new(&format!("Failed to create verifying key: {}", e)))?;
 
+    // the same container header as keys saved by the cli, so the key is checked against the settings
     let mut serialized_vk = Vec::new();
+    ContainerHeader::new::<G1Affine>(
+        ContainerKind::VerifyingKey,
+        Some(
+            circuit
+                .settings()
+                .settings_hash()
+                .map_err(|e| JsError::new(&format!("Failed to hash settings: {}", e)))?,
+        ),
+        None,
+    )
+    .write(&mut serialized_vk)
+    .map_err(|e| JsError::new(&format!("Failed to serialize vk: {}", e)))?;
     vk.write(&mut serialized_vk, halo2_proofs::SerdeFormat::RawBytes)
         .map_err(|e| JsError::new(&format!("Failed to serialize vk: {}", e)))?;
 
@@ -314,29 +352,59 @@ pub fn genPk(
     compiled_circuit: wasm_bindgen::Clamped<Vec<u8>>,
     params_ser: wasm_bindgen::Clamped<Vec<u8>>,
 ) -> Result<Vec<u8>, JsError> {
-    // Read in kzg params
-    let mut reader = std::io::BufReader::new(&params_ser[..]);
-    let params: ParamsKZG<Bn256> =
-        halo2_proofs::poly::commitment::Params::<'_, G1Affine>::read(&mut reader)
-            .map_err(|e| JsError::new(&format!("Failed to deserialize params: {}", e)))?;
     // Read in compiled circuit
     let circuit: crate::graph::GraphCircuit = bincode::deserialize(&compiled_circuit[..])
         .map_err(|e| JsError::new(&format!("Failed to deserialize compiled model: {}", e)))?;
 
     // Read in verifying key
     let mut reader = std::io::BufReader::new(&vk[..]);
+    check_key_header::<G1Affine, _>(
+        &mut reader,
+        ContainerKind::VerifyingKey,
+        circuit
+            .settings()
+            .settings_hash()
+            .map_err(|e| JsError::new(&format!("Failed to hash settings: {}", e)))?,
+    )
+    .map_err(|e| JsError::new(&format!("Failed to deserialize verifying key: {}", e)))?;
     let vk = VerifyingKey::<G1Affine>::read::<_, GraphCircuit>(
         &mut reader,
         halo2_proofs::SerdeFormat::RawBytes,
         circuit.settings().clone(),
     )
     .map_err(|e| JsError::new(&format!("Failed to deserialize verifying key: {}", e)))?;
-    // Create proving key
-    let pk = create_pk_wasm::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(vk, &circuit, &params)
-        .map_err(Box::<dyn std::error::Error>::from)
-        .map_err(|e| JsError::new(&format!("Failed to create proving key: {}", e)))?;
+    // Read in params and create proving key
+    let mut reader = std::io::BufReader::new(&params_ser[..]);
+    let pk = match circuit.settings().run_args.commitment {
+        Commitments::KZG => {
+            let params: ParamsKZG<Bn256> =
+                halo2_proofs::poly::commitment::Params::<'_, G1Affine>::read(&mut reader)
+                    .map_err(|e| JsError::new(&format!("Failed to deserialize params: {}", e)))?;
+            create_pk_wasm::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(vk, &circuit, &params)
+        }
+        Commitments::IPA => {
+            let params: ParamsIPA<G1Affine> =
+                halo2_proofs::poly::commitment::Params::<'_, G1Affine>::read(&mut reader)
+                    .map_err(|e| JsError::new(&format!("Failed to deserialize params: {}", e)))?;
+            create_pk_wasm::<IPACommitmentScheme<G1Affine>, Fr, GraphCircuit>(vk, &circuit, &params)
+        }
+    }
+    .map_err(Box::<dyn std::error::Error>::from)
+    .map_err(|e| JsError::new(&format!("Failed to create proving key: {}", e)))?;
 
     let mut serialized_pk = Vec::new();
+    ContainerHeader::new::<G1Affine>(
+        ContainerKind::ProvingKey,
+        Some(
+            circuit
+                .settings()
+                .settings_hash()
+                .map_err(|e| JsError::new(&format!("Failed to hash settings: {}", e)))?,
+        ),
+        None,
+    )
+    .write(&mut serialized_pk)
+    .map_err(|e| JsError::new(&format!("Failed to serialize pk: {}", e)))?;
     pk.write(&mut serialized_pk, halo2_proofs::SerdeFormat::RawBytes)
         .map_err(|e| JsError::new(&format!("Failed to serialize pk: {}", e)))?;
 
@@ -351,18 +419,35 @@ pub fn verify(
     settings: wasm_bindgen::Clamped<Vec<u8>>,
     srs: wasm_bindgen::Clamped<Vec<u8>>,
 ) -> Result<bool, JsError> {
-    let mut reader = std::io::BufReader::new(&srs[..]);
-    let params: ParamsKZG<Bn256> =
-        halo2_proofs::poly::commitment::Params::<'_, G1Affine>::read(&mut reader)
-            .map_err(|e| JsError::new(&format!("Failed to deserialize params: {}", e)))?;
-
     let circuit_settings: GraphSettings = serde_json::from_slice(&settings[..])
         .map_err(|e| JsError::new(&format!("Failed to deserialize settings: {}", e)))?;
-
-    let snark: crate::pfsys::Snark<Fr, G1Affine> = serde_json::from_slice(&proof_js[..])
-        .map_err(|e| JsError::new(&format!("Failed to deserialize proof: {}", e)))?;
+    let settings_hash = circuit_settings
+        .settings_hash()
+        .map_err(|e| JsError::new(&format!("Failed to hash settings: {}", e)))?;
+
+    let snark: crate::pfsys::Snark<Fr, G1Affine> =
+        crate::pfsys::Snark::from_bytes(&proof_js[..], Some(settings_hash))
+            .map_err(|e| JsError::new(&format!("Failed to deserialize proof: {}", e)))?;
+
+    let commitment = circuit_settings.run_args.commitment;
+    let multi_open = circuit_settings.run_args.multi_open;
+    if snark.commitment() != commitment {
+        return Err(JsError::new(&format!(
+            "proof was generated with {} commitments but the settings specify {}",
+            snark.commitment(),
+            commitment
+        )));
+    }
 
     let mut reader = std::io::BufReader::new(&vk[..]);
+    check_key_header::<G1Affine, _>(
+        &mut reader,
+        ContainerKind::VerifyingKey,
+        circuit_settings
+            .settings_hash()
+            .map_err(|e| JsError::new(&format!("Failed to hash settings: {}", e)))?,
+    )
+    .map_err(|e| JsError::new(&format!("Failed to deserialize vk: {}", e)))?;
     let vk = VerifyingKey::<G1Affine>::read::<_, GraphCircuit>(
         &mut reader,
         halo2_proofs::SerdeFormat::RawBytes,
@@ -370,9 +455,26 @@ pub fn verify(
     )
     .map_err(|e| JsError::new(&format!("Failed to deserialize vk: {}", e)))?;
 
-    let strategy = KZGSingleStrategy::new(params.verifier_params());
-
-    let result = verify_proof_circuit_kzg(params.verifier_params(), snark, &vk, strategy);
+    let mut reader = std::io::BufReader::new(&srs[..]);
+    let result = match commitment {
+        Commitments::KZG => {
+            snark
+                .check_multi_open(multi_open)
+                .map_err(|e| JsError::new(&format!("{}", e)))?;
+            let params: ParamsKZG<Bn256> =
+                halo2_proofs::poly::commitment::Params::<'_, G1Affine>::read(&mut reader)
+                    .map_err(|e| JsError::new(&format!("Failed to deserialize params: {}", e)))?;
+            let strategy = KZGSingleStrategy::new(params.verifier_params());
+            verify_proof_circuit_kzg(params.verifier_params(), snark, &vk, strategy).map(|_| ())
+        }
+        Commitments::IPA => {
+            let params: ParamsIPA<G1Affine> =
+                halo2_proofs::poly::commitment::Params::<'_, G1Affine>::read(&mut reader)
+                    .map_err(|e| JsError::new(&format!("Failed to deserialize params: {}", e)))?;
+            let strategy = IPASingleStrategy::new(params.verifier_params());
+            verify_proof_circuit_ipa(params.verifier_params(), snark, &vk, strategy)
+        }
+    };
 
     match result {
         Ok(_) => Ok(true),
@@ -392,12 +494,6 @@ pub fn prove(
     log::set_max_level(log::LevelFilter::Debug);
     #[cfg(not(feature = "det-prove"))]
     log::set_max_level(log::LevelFilter::Info);
-    // read in kzg params
-    let mut reader = std::io::BufReader::new(&srs[..]);
-    let params: ParamsKZG<Bn256> =
-        halo2_proofs::poly::commitment::Params::<'_, G1Affine>::read(&mut reader)
-            .map_err(|e| JsError::new(&format!("Failed to deserialize srs: {}", e)))?;
-
     // read in circuit
     let mut circuit: crate::graph::GraphCircuit = bincode::deserialize(&compiled_circuit[..])
         .map_err(|e| JsError::new(&format!("Failed to deserialize circuit: {}", e)))?;
@@ -408,6 +504,15 @@ pub fn prove(
 
     // read in proving key
     let mut reader = std::io::BufReader::new(&pk[..]);
+    check_key_header::<G1Affine, _>(
+        &mut reader,
+        ContainerKind::ProvingKey,
+        circuit
+            .settings()
+            .settings_hash()
+            .map_err(|e| JsError::new(&format!("Failed to hash settings: {}", e)))?,
+    )
+    .map_err(|e| JsError::new(&format!("Failed to deserialize proving key: {}", e)))?;
     let pk = ProvingKey::<G1Affine>::read::<_, GraphCircuit>(
         &mut reader,
         halo2_proofs::SerdeFormat::RawBytes,
@@ -424,17 +529,44 @@ pub fn prove(
         .map_err(|e| JsError::new(&format!("{}", e)))?;
     let proof_split_commits: Option<crate::pfsys::ProofSplitCommit> = data.into();
 
-    let strategy = KZGSingleStrategy::new(&params);
-    let proof = create_proof_circuit_kzg(
-        circuit,
-        &params,
-        Some(public_inputs),
-        &pk,
-        crate::pfsys::TranscriptType::EVM,
-        strategy,
-        crate::circuit::CheckMode::UNSAFE,
-        proof_split_commits,
-    )
+    // read in params
+    let multi_open = circuit.settings().run_args.multi_open;
+    let mut reader = std::io::BufReader::new(&srs[..]);
+    let proof = match circuit.settings().run_args.commitment {
+        Commitments::KZG => {
+            let params: ParamsKZG<Bn256> =
+                halo2_proofs::poly::commitment::Params::<'_, G1Affine>::read(&mut reader)
+                    .map_err(|e| JsError::new(&format!("Failed to deserialize srs: {}", e)))?;
+            let strategy = KZGSingleStrategy::new(&params);
+            create_proof_circuit_kzg(
+                circuit,
+                &params,
+                Some(public_inputs),
+                &pk,
+                crate::pfsys::TranscriptType::EVM,
+                strategy,
+                crate::circuit::CheckMode::UNSAFE,
+                proof_split_commits,
+                multi_open,
+            )
+        }
+        Commitments::IPA => {
+            let params: ParamsIPA<G1Affine> =
+                halo2_proofs::poly::commitment::Params::<'_, G1Affine>::read(&mut reader)
+                    .map_err(|e| JsError::new(&format!("Failed to deserialize srs: {}", e)))?;
+            let strategy = IPASingleStrategy::new(&params);
+            create_proof_circuit_ipa(
+                circuit,
+                &params,
+                Some(public_inputs),
+                &pk,
+                crate::pfsys::TranscriptType::EVM,
+                strategy,
+                crate::circuit::CheckMode::UNSAFE,
+                proof_split_commits,
+            )
+        }
+    }
     .map_err(|e| JsError::new(&format!("{}", e)))?;
 
     Ok(serde_json::to_string(&proof)
@@ -446,8 +578,9 @@ pub fn prove(
 #[wasm_bindgen]
 #[allow(non_snake_case)]
 pub fn printProofHex(proof: wasm_bindgen::Clamped<Vec<u8>>) -> Result<String, JsError> {
-    let proof: crate::pfsys::Snark<Fr, G1Affine> = serde_json::from_slice(&proof[..])
-        .map_err(|e| JsError::new(&format!("Failed to deserialize proof: {}", e)))?;
+    let proof: crate::pfsys::Snark<Fr, G1Affine> =
+        crate::pfsys::Snark::from_bytes(&proof[..], None)
+            .map_err(|e| JsError::new(&format!("Failed to deserialize proof: {}", e)))?;
     Ok(hex::encode(proof.proof))
 }
 // VALIDATION FUNCTIONS
@@ -485,7 +618,7 @@ pub fn inputValidation(input: wasm_bindgen::Clamped<Vec<u8>>) -> Result<bool, Js
 #[wasm_bindgen]
 #[allow(non_snake_case)]
 pub fn proofValidation(proof: wasm_bindgen::Clamped<Vec<u8>>) -> Result<bool, JsError> {
-    let _: crate::pfsys::Snark<Fr, G1Affine> = serde_json::from_slice(&proof[..])
+    let _: crate::pfsys::Snark<Fr, G1Affine> = crate::pfsys::Snark::from_bytes(&proof[..], None)
         .map_err(|e| JsError::new(&format!("Failed to deserialize proof: {}", e)))?;
 
     Ok(true)
@@ -500,6 +633,14 @@ pub fn vkValidation(
     let circuit_settings: GraphSettings = serde_json::from_slice(&settings[..])
         .map_err(|e| JsError::new(&format!("Failed to deserialize settings: {}", e)))?;
     let mut reader = std::io::BufReader::new(&vk[..]);
+    check_key_header::<G1Affine, _>(
+        &mut reader,
+        ContainerKind::VerifyingKey,
+        circuit_settings
+            .settings_hash()
+            .map_err(|e| JsError::new(&format!("Failed to hash settings: {}", e)))?,
+    )
+    .map_err(|e| JsError::new(&format!("Failed to deserialize vk: {}", e)))?;
     let _ = VerifyingKey::<G1Affine>::read::<_, GraphCircuit>(
         &mut reader,
         halo2_proofs::SerdeFormat::RawBytes,
@@ -519,6 +660,14 @@ pub fn pkValidation(
     let circuit_settings: GraphSettings = serde_json::from_slice(&settings[..])
         .map_err(|e| JsError::new(&format!("Failed to deserialize settings: {}", e)))?;
     let mut reader = std::io::BufReader::new(&pk[..]);
+    check_key_header::<G1Affine, _>(
+        &mut reader,
+        ContainerKind::ProvingKey,
+        circuit_settings
+            .settings_hash()
+            .map_err(|e| JsError::new(&format!("Failed to hash settings: {}", e)))?,
+    )
+    .map_err(|e| JsError::new(&format!("Failed to deserialize proving key: {}", e)))?;
     let _ = ProvingKey::<G1Affine>::read::<_, GraphCircuit>(
         &mut reader,
         halo2_proofs::SerdeFormat::RawBytes,
@@ -3,8 +3,13 @@
 mod native_tests {
 
     // use ezkl::circuit::table::RESERVED_BLINDING_ROWS_PAD;
+    use ezkl::circuit::modules::eddsa::EddsaSignature;
+    use ezkl::circuit::modules::elgamal::{ElGamalRecipient, ElGamalVariables};
+    use ezkl::circuit::modules::merkle::{hash_leaf, MerkleProof};
     use ezkl::graph::input::{FileSource, FileSourceInner, GraphData};
-    use ezkl::graph::{DataSource, GraphSettings, GraphWitness};
+    use ezkl::graph::{DataSource, GraphCircuit, GraphSettings, GraphWitness, Model};
+    use ezkl::RunArgs;
+    use halo2curves::bn256::Fr;
     use lazy_static::lazy_static;
     use rand::Rng;
     use std::env::var;
@@ -34,6 +39,9 @@ mod native_tests {
         static ref LIMITLESS_ANVIL_URL: String = "http://localhost:8545".to_string();
         static ref ANVIL_DEFAULT_PRIVATE_KEY: String =
             "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string();
+        // the account of ANVIL_DEFAULT_PRIVATE_KEY
+        static ref ANVIL_DEFAULT_ADDRESS: String =
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string();
     }
 
     fn start_anvil(limitless: bool, hardfork: Hardfork) -> Child {
@@ -472,6 +480,7 @@ mod native_tests {
             use crate::native_tests::kzg_fuzz;
             use crate::native_tests::render_circuit;
             use crate::native_tests::model_serialization_different_binaries;
+            use crate::native_tests::mock_per_tensor_visibility;
             use tempdir::TempDir;
 
             #[test]
@@ -485,6 +494,27 @@ mod native_tests {
                 test_dir.close().unwrap();
             }
 
+            #[test]
+            fn mock_per_tensor_visibility_() {
+                let test = "rounding_ops";
+                crate::native_tests::init_binary();
+                let test_dir = TempDir::new(test).unwrap();
+                let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                mock_per_tensor_visibility(path, test.to_string(), &["public", "private", "hashed"], &[], &["public", "private", "hashed"]);
+                test_dir.close().unwrap();
+            }
+
+            #[test]
+            fn mock_per_tensor_param_visibility_() {
+                let test = "1l_mlp";
+                crate::native_tests::init_binary();
+                let test_dir = TempDir::new(test).unwrap();
+                let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                let param_visibilities = crate::native_tests::per_param_visibilities(path, test, &["public", "private", "hashed"]);
+                mock_per_tensor_visibility(path, test.to_string(), &[], &param_visibilities, &[]);
+                test_dir.close().unwrap();
+            }
+
             seq!(N in 0..=4 {
             #(#[test_case(ACCURACY_CAL_TESTS[N])])*
             fn mock_accuracy_cal_tests(test: &str) {
@@ -603,6 +633,72 @@ mod native_tests {
                 test_dir.close().unwrap();
             }
 
+            #(#[test_case(TESTS[N])])*
+            fn mock_salted_hashed_input_(test: &str) {
+                crate::native_tests::init_binary();
+                let test_dir = TempDir::new(test).unwrap();
+                let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                mock(path, test.to_string(), "hashed+salted", "private", "public", 1, "resources", None);
+                test_dir.close().unwrap();
+            }
+
+            #(#[test_case(TESTS[N])])*
+            fn mock_salted_hashed_all_(test: &str) {
+                crate::native_tests::init_binary();
+                let test_dir = TempDir::new(test).unwrap();
+                let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                mock(path, test.to_string(), "hashed+salted", "hashed+salted", "hashed+salted", 1, "resources", None);
+                check_salt_regenerates(path, test);
+                test_dir.close().unwrap();
+            }
+
+            #(#[test_case(TESTS[N])])*
+            fn mock_hashed_input_t5_(test: &str) {
+                crate::native_tests::init_binary();
+                let test_dir = TempDir::new(test).unwrap();
+                let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                mock(path, test.to_string(), "hashed@t5", "private", "public", 1, "resources", None);
+                test_dir.close().unwrap();
+            }
+
+            #(#[test_case(TESTS[N])])*
+            fn mock_hashed_output_t5_(test: &str) {
+                crate::native_tests::init_binary();
+                let test_dir = TempDir::new(test).unwrap();
+                let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                mock(path, test.to_string(), "public", "private", "hashed@t5", 1, "resources", None);
+                test_dir.close().unwrap();
+            }
+
+            #(#[test_case(TESTS[N])])*
+            fn mock_merkle_params_(test: &str) {
+                crate::native_tests::init_binary();
+                let test_dir = TempDir::new(test).unwrap();
+                let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                mock(path, test.to_string(), "private", "merkle", "public", 1, "resources", None);
+                test_dir.close().unwrap();
+            }
+
+            #(#[test_case(TESTS[N])])*
+            fn mock_merkle_depth_input_(test: &str) {
+                crate::native_tests::init_binary();
+                let test_dir = TempDir::new(test).unwrap();
+                let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                let proofs = add_merkle_proofs(path, test, 3);
+                mock(path, test.to_string(), "merkle/3", "private", "public", 1, "resources", None);
+                check_merkle_roots(path, test, &proofs);
+                test_dir.close().unwrap();
+            }
+
+            #(#[test_case(TESTS[N])])*
+            fn mock_signed_input_(test: &str) {
+                crate::native_tests::init_binary();
+                let test_dir = TempDir::new(test).unwrap();
+                let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                mock_signed_input(path, test.to_string());
+                test_dir.close().unwrap();
+            }
+
             #(#[test_case(TESTS[N])])*
             fn mock_kzg_input_(test: &str) {
                 crate::native_tests::init_binary();
@@ -854,6 +950,24 @@ mod native_tests {
                test_dir.close().unwrap();
             }
 
+            #(#[test_case(TESTS[N])])*
+            fn kzg_gwc_prove_and_verify_(test: &str) {
+                crate::native_tests::init_binary();
+                let test_dir = TempDir::new(test).unwrap();
+                let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                kzg_gwc_prove_and_verify(path, test.to_string());
+                test_dir.close().unwrap();
+            }
+
+            #(#[test_case(TESTS[N])])*
+            fn ipa_prove_and_verify_(test: &str) {
+                crate::native_tests::init_binary();
+                let test_dir = TempDir::new(test).unwrap();
+                let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                ipa_prove_and_verify(path, test.to_string());
+                test_dir.close().unwrap();
+            }
+
             #(#[test_case(TESTS[N])])*
             fn kzg_fuzz_(test: &str) {
                 crate::native_tests::init_binary();
@@ -863,6 +977,15 @@ mod native_tests {
                 test_dir.close().unwrap();
             }
 
+            #(#[test_case(TESTS[N])])*
+            fn kzg_profile_prove_and_verify_(test: &str) {
+                crate::native_tests::init_binary();
+                let test_dir = TempDir::new(test).unwrap();
+                let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                kzg_profile_prove_and_verify(path, test.to_string());
+                test_dir.close().unwrap();
+            }
+
             });
 
             seq!(N in 0..=47 {
@@ -930,8 +1053,10 @@ mod native_tests {
             use crate::native_tests::TESTS_EVM_AGGR;
             use test_case::test_case;
             use crate::native_tests::kzg_evm_prove_and_verify;
+            use crate::native_tests::kzg_evm_prove_and_verify_render_separately;
             use crate::native_tests::run_js_tests;
             use crate::native_tests::kzg_evm_on_chain_input_prove_and_verify;
+            use crate::native_tests::kzg_evm_signed_input_prove_and_verify;
             use crate::native_tests::kzg_evm_aggr_prove_and_verify;
             use crate::native_tests::kzg_fuzz;
             use tempdir::TempDir;
@@ -969,7 +1094,7 @@ mod native_tests {
                     let test_dir = TempDir::new(test).unwrap();
                     let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
                     let _anvil_child = crate::native_tests::start_anvil(true, hardfork);
-                    kzg_evm_on_chain_input_prove_and_verify(path, test.to_string(), "on-chain", "file", "public", "private");
+                    kzg_evm_on_chain_input_prove_and_verify(path, test.to_string(), "on-chain", "file", "public", "private", false);
                     // test_dir.close().unwrap();
                 }
 
@@ -979,7 +1104,7 @@ mod native_tests {
                     let test_dir = TempDir::new(test).unwrap();
                     let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
                     let _anvil_child = crate::native_tests::start_anvil(true, Hardfork::Latest);
-                    kzg_evm_on_chain_input_prove_and_verify(path, test.to_string(), "file", "on-chain", "private", "public");
+                    kzg_evm_on_chain_input_prove_and_verify(path, test.to_string(), "file", "on-chain", "private", "public", false);
                     // test_dir.close().unwrap();
                 }
 
@@ -989,7 +1114,27 @@ mod native_tests {
                     let test_dir = TempDir::new(test).unwrap();
                     let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
                     let _anvil_child = crate::native_tests::start_anvil(true, Hardfork::Latest);
-                    kzg_evm_on_chain_input_prove_and_verify(path, test.to_string(), "on-chain", "on-chain", "public", "public");
+                    kzg_evm_on_chain_input_prove_and_verify(path, test.to_string(), "on-chain", "on-chain", "public", "public", false);
+                    test_dir.close().unwrap();
+                }
+
+                #(#[test_case(TESTS_ON_CHAIN_INPUT[N])])*
+                fn kzg_evm_on_chain_input_hashed_on_chain_prove_and_verify_(test: &str) {
+                    crate::native_tests::init_binary();
+                    let test_dir = TempDir::new(test).unwrap();
+                    let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                    let _anvil_child = crate::native_tests::start_anvil(true, Hardfork::Latest);
+                    kzg_evm_on_chain_input_prove_and_verify(path, test.to_string(), "on-chain", "file", "hashed", "private", true);
+                    test_dir.close().unwrap();
+                }
+
+                #(#[test_case(TESTS_ON_CHAIN_INPUT[N])])*
+                fn kzg_evm_signed_input_prove_and_verify_(test: &str) {
+                    crate::native_tests::init_binary();
+                    let test_dir = TempDir::new(test).unwrap();
+                    let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                    let _anvil_child = crate::native_tests::start_anvil(true, Hardfork::Latest);
+                    kzg_evm_signed_input_prove_and_verify(path, test.to_string());
                     test_dir.close().unwrap();
                 }
 
@@ -999,7 +1144,7 @@ mod native_tests {
                     let test_dir = TempDir::new(test).unwrap();
                     let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
                     let _anvil_child = crate::native_tests::start_anvil(true, Hardfork::Latest);
-                    kzg_evm_on_chain_input_prove_and_verify(path, test.to_string(), "on-chain", "on-chain", "hashed", "hashed");
+                    kzg_evm_on_chain_input_prove_and_verify(path, test.to_string(), "on-chain", "on-chain", "hashed", "hashed", false);
                     test_dir.close().unwrap();
                 }
             });
@@ -1048,6 +1193,16 @@ mod native_tests {
 
                 }
 
+                #(#[test_case(TESTS_EVM[N])])*
+                fn kzg_evm_prove_and_verify_render_separately_(test: &str) {
+                    crate::native_tests::init_binary();
+                    let test_dir = TempDir::new(test).unwrap();
+                    let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                    let _anvil_child = crate::native_tests::start_anvil(false, Hardfork::Latest);
+                    kzg_evm_prove_and_verify_render_separately(path, test.to_string(), "private", "private", "public");
+                    test_dir.close().unwrap();
+                }
+
                 #[test]
                 #[ignore]
                 fn kzg_evm_prove_and_verify_encrypted_input_() {
@@ -1062,6 +1217,29 @@ mod native_tests {
                     test_dir.close().unwrap();
                 }
 
+                #[test]
+                fn kzg_evm_prove_and_verify_per_tensor_visibility_() {
+                    let test = "rounding_ops";
+                    crate::native_tests::init_binary();
+                    let test_dir = TempDir::new(test).unwrap();
+                    let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                    let _anvil_child = crate::native_tests::start_anvil(false, Hardfork::Latest);
+                    kzg_evm_prove_and_verify(path, test.to_string(), "public,private,hashed", "private", "public,private,hashed");
+                    test_dir.close().unwrap();
+                }
+
+                #[test]
+                fn kzg_evm_prove_and_verify_per_tensor_param_visibility_() {
+                    let test = "1l_mlp";
+                    crate::native_tests::init_binary();
+                    let test_dir = TempDir::new(test).unwrap();
+                    let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                    let _anvil_child = crate::native_tests::start_anvil(false, Hardfork::Latest);
+                    let param_visibilities = crate::native_tests::per_param_visibilities(path, test, &["public", "private", "hashed"]);
+                    kzg_evm_prove_and_verify(path, test.to_string(), "private", &param_visibilities.join(","), "public");
+                    test_dir.close().unwrap();
+                }
+
                 #(#[test_case(TESTS_EVM[N])])*
                 fn kzg_evm_hashed_input_prove_and_verify_(test: &str) {
                     crate::native_tests::init_binary();
@@ -1155,6 +1333,16 @@ mod native_tests {
                     test_dir.close().unwrap();
                 }
 
+                #(#[test_case(TESTS_EVM[N])])*
+                fn kzg_evm_kzg_input_bind_commitments_prove_and_verify_(test: &str) {
+                    crate::native_tests::init_binary();
+                    let test_dir = TempDir::new(test).unwrap();
+                    let path = test_dir.path().to_str().unwrap(); crate::native_tests::mv_test_(path, test);
+                    let _anvil_child = crate::native_tests::start_anvil(false, Hardfork::Latest);
+                    kzg_evm_bind_commitments_prove_and_verify(path, test.to_string());
+                    test_dir.close().unwrap();
+                }
+
 
 
                 #(#[test_case(TESTS_EVM[N])])*
@@ -1332,6 +1520,7 @@ mod native_tests {
             cal_target,
             scales_to_use,
             2,
+            ProofSystem::default(),
         );
 
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
@@ -1347,85 +1536,343 @@ mod native_tests {
         assert!(status.success());
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn gen_circuit_settings_and_witness(
-        test_dir: &str,
-        example_name: String,
-        input_visibility: &str,
-        param_visibility: &str,
-        output_visibility: &str,
-        batch_size: usize,
-        cal_target: &str,
-        scales_to_use: Option<Vec<u32>>,
-        num_inner_columns: usize,
-    ) {
+    // Adds a path of `depth` for each input to the input data, to open them as leaves of a larger tree
+    fn add_merkle_proofs(test_dir: &str, example_name: &str, depth: usize) -> Vec<MerkleProof> {
+        let data_path = format!("{}/{}/input.json", test_dir, example_name);
+        let mut data = GraphData::from_path(data_path.clone().into()).unwrap();
+        let num_inputs = match &data.input_data {
+            DataSource::File(inputs) => inputs.len(),
+            _ => panic!("merkle proofs are only added to file data sources"),
+        };
+
+        let proofs = (0..num_inputs)
+            .map(|i| MerkleProof {
+                index: i + 1,
+                siblings: (0..depth as u64).map(|j| Fr::from(j + 1)).collect(),
+            })
+            .collect::<Vec<_>>();
+        data.merkle_proofs = Some(proofs.clone());
+        data.save(data_path.into()).unwrap();
+        proofs
+    }
+
+    // Encrypts to a freshly generated recipient, supplied with the input data
+    fn add_elgamal_recipient(test_dir: &str, example_name: &str) {
+        let data_path = format!("{}/{}/input.json", test_dir, example_name);
+        let mut data = GraphData::from_path(data_path.clone().into()).unwrap();
+        let key = ElGamalVariables::gen_random(&mut rand::thread_rng());
+        data.elgamal_recipient = Some(ElGamalRecipient::new(key.pk));
+        data.save(data_path.into()).unwrap();
+    }
+
+    // Checks each input opens to the root published in the witness along its path
+    fn check_merkle_roots(test_dir: &str, example_name: &str, proofs: &[MerkleProof]) {
+        let witness_path = format!("{}/{}/witness.json", test_dir, example_name);
+        let witness = GraphWitness::from_path(witness_path.into()).unwrap();
+        let roots = witness.processed_inputs.unwrap().merkle_root.unwrap();
+
+        assert_eq!(roots.len(), proofs.len());
+        for ((input, proof), root) in witness.inputs.iter().zip(proofs).zip(roots) {
+            let leaf = hash_leaf(input.clone()).unwrap();
+            assert_eq!(proof.root(leaf).unwrap(), root);
+        }
+    }
+
+    // Checks the inputs, params and outputs are salted alike, with the salt kept in the input data,
+    // and that the witness is regenerated from the saved data
+    fn check_salt_regenerates(test_dir: &str, example_name: &str) {
+        let data_path = format!("{}/{}/input.json", test_dir, example_name);
+        let witness_path = format!("{}/{}/witness.json", test_dir, example_name);
+        let regenerated_path = format!("{}/{}/witness_regenerated.json", test_dir, example_name);
+
+        let witness = GraphWitness::from_path(witness_path.into()).unwrap();
+        let salt = witness.hash_salt().unwrap();
+        for processed in [
+            &witness.processed_inputs,
+            &witness.processed_params,
+            &witness.processed_outputs,
+        ] {
+            assert_eq!(processed.as_ref().unwrap().salt, Some(salt));
+        }
+        let data = GraphData::from_path(data_path.clone().into()).unwrap();
+        assert_eq!(data.hash_salt, Some(salt));
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "gen-witness",
+                "-D",
+                data_path.as_str(),
+                "-M",
+                &format!("{}/{}/network.compiled", test_dir, example_name),
+                "-O",
+                regenerated_path.as_str(),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let regenerated = GraphWitness::from_path(regenerated_path.into()).unwrap();
+        assert_eq!(regenerated.as_json().unwrap(), witness.as_json().unwrap());
+    }
+
+    // Mock prove with each input signed by a known key, checking the witness publishes its public key
+    fn mock_signed_input(test_dir: &str, example_name: String) {
+        let model_path = format!("{}/{}/network.onnx", test_dir, example_name);
+        let settings_path = format!("{}/{}/settings.json", test_dir, example_name);
+        let data_path = format!("{}/{}/input.json", test_dir, example_name);
+        let witness_path = format!("{}/{}/witness.json", test_dir, example_name);
+
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
             .args([
                 "gen-settings",
                 "-M",
-                format!("{}/{}/network.onnx", test_dir, example_name).as_str(),
-                &format!(
-                    "--settings-path={}/{}/settings.json",
-                    test_dir, example_name
-                ),
-                &format!("--variables=batch_size={}", batch_size),
-                &format!("--input-visibility={}", input_visibility),
-                &format!("--param-visibility={}", param_visibility),
-                &format!("--output-visibility={}", output_visibility),
-                &format!("--num-inner-cols={}", num_inner_columns),
+                model_path.as_str(),
+                &format!("--settings-path={}", settings_path),
+                "--input-visibility=signed",
+                "--output-visibility=public",
             ])
             .stdout(std::process::Stdio::null())
             .status()
             .expect("failed to execute process");
         assert!(status.success());
 
-        let mut calibrate_args = vec![
-            "calibrate-settings".to_string(),
-            "--data".to_string(),
-            format!("{}/{}/input.json", test_dir, example_name),
+        // the inputs are signed as they are quantized at the scale of the generated settings
+        let settings = GraphSettings::load(&settings_path.into()).unwrap();
+        let mut circuit =
+            GraphCircuit::from_run_args(&settings.run_args, model_path.as_ref()).unwrap();
+        let mut data = GraphData::from_path(data_path.clone().into()).unwrap();
+        let inputs = circuit.load_graph_from_file_exclusively(&data).unwrap();
+
+        let secret_key = Fr::from(42);
+        let mut rng = rand::thread_rng();
+        let signatures = inputs
+            .iter()
+            .map(|x| EddsaSignature::sign(&secret_key, x.to_vec(), &mut rng).unwrap())
+            .collect::<Vec<_>>();
+        data.signatures = Some(signatures);
+        data.save(data_path.into()).unwrap();
+
+        // calibrating at another input scale would change what the signatures are over
+        calibrate_compile_and_gen_witness(
+            test_dir,
+            example_name.clone(),
+            "resources",
+            Some(vec![settings.run_args.input_scale as u32]),
+        );
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "mock",
+                "-W",
+                witness_path.as_str(),
+                "-M",
+                format!("{}/{}/network.compiled", test_dir, example_name).as_str(),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let witness = GraphWitness::from_path(witness_path.into()).unwrap();
+        let public_key = EddsaSignature::public_key(&secret_key);
+        assert_eq!(
+            witness.processed_inputs.unwrap().public_keys,
+            Some(vec![public_key; inputs.len()])
+        );
+    }
+
+    // Cycles through `pattern` to set a visibility for each of the model's param tensors
+    fn per_param_visibilities(
+        test_dir: &str,
+        example_name: &str,
+        pattern: &[&'static str],
+    ) -> Vec<&'static str> {
+        let model_path = format!("{}/{}/network.onnx", test_dir, example_name);
+        let model = Model::from_run_args(&RunArgs::default(), model_path.as_ref()).unwrap();
+        pattern
+            .iter()
+            .cycle()
+            .take(model.const_shapes().len())
+            .copied()
+            .collect()
+    }
+
+    // The gen-settings flags for a visibility, a comma separated list sets it per tensor
+    fn visibility_args(category: &str, visibility: &str) -> Vec<String> {
+        if visibility.contains(',') {
+            visibility
+                .split(',')
+                .map(|v| format!("--{}-visibilities={}", category, v))
+                .collect()
+        } else {
+            vec![format!("--{}-visibility={}", category, visibility)]
+        }
+    }
+
+    // Mock prove with the visibility of each input, param and output set separately
+    fn mock_per_tensor_visibility(
+        test_dir: &str,
+        example_name: String,
+        input_visibilities: &[&str],
+        param_visibilities: &[&str],
+        output_visibilities: &[&str],
+    ) {
+        let mut args = vec![
+            "gen-settings".to_string(),
             "-M".to_string(),
             format!("{}/{}/network.onnx", test_dir, example_name),
             format!(
                 "--settings-path={}/{}/settings.json",
                 test_dir, example_name
             ),
-            format!("--target={}", cal_target),
         ];
-
-        if let Some(scales) = scales_to_use {
-            let scales = scales
-                .iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>()
-                .join(",");
-            calibrate_args.push("--scales".to_string());
-            calibrate_args.push(scales);
+        for visibility in input_visibilities {
+            args.push(format!("--input-visibilities={}", visibility));
+        }
+        for visibility in param_visibilities {
+            args.push(format!("--param-visibilities={}", visibility));
+        }
+        for visibility in output_visibilities {
+            args.push(format!("--output-visibilities={}", visibility));
         }
 
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
-            .args(calibrate_args)
+            .args(args)
+            .stdout(std::process::Stdio::null())
             .status()
             .expect("failed to execute process");
         assert!(status.success());
 
+        calibrate_compile_and_gen_witness(test_dir, example_name.clone(), "resources", None);
+
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
             .args([
-                "compile-circuit",
+                "mock",
+                "-W",
+                format!("{}/{}/witness.json", test_dir, example_name).as_str(),
                 "-M",
-                format!("{}/{}/network.onnx", test_dir, example_name).as_str(),
-                "--compiled-circuit",
                 format!("{}/{}/network.compiled", test_dir, example_name).as_str(),
-                &format!(
-                    "--settings-path={}/{}/settings.json",
-                    test_dir, example_name
-                ),
             ])
-            .stdout(std::process::Stdio::null())
             .status()
             .expect("failed to execute process");
         assert!(status.success());
+    }
 
-        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+    /// The proof system circuit settings are generated for
+    #[derive(Clone, Copy)]
+    struct ProofSystem {
+        commitment: &'static str,
+        multi_open: &'static str,
+    }
+
+    impl Default for ProofSystem {
+        fn default() -> Self {
+            ProofSystem {
+                commitment: "kzg",
+                multi_open: "shplonk",
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn gen_circuit_settings_and_witness(
+        test_dir: &str,
+        example_name: String,
+        input_visibility: &str,
+        param_visibility: &str,
+        output_visibility: &str,
+        batch_size: usize,
+        cal_target: &str,
+        scales_to_use: Option<Vec<u32>>,
+        num_inner_columns: usize,
+        proof_system: ProofSystem,
+    ) {
+        let mut args = vec![
+            "gen-settings".to_string(),
+            "-M".to_string(),
+            format!("{}/{}/network.onnx", test_dir, example_name),
+            format!(
+                "--settings-path={}/{}/settings.json",
+                test_dir, example_name
+            ),
+            format!("--variables=batch_size={}", batch_size),
+            format!("--num-inner-cols={}", num_inner_columns),
+            format!("--commitment={}", proof_system.commitment),
+            format!("--multi-open={}", proof_system.multi_open),
+        ];
+        args.extend(visibility_args("input", input_visibility));
+        args.extend(visibility_args("param", param_visibility));
+        args.extend(visibility_args("output", output_visibility));
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(args)
+            .stdout(std::process::Stdio::null())
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        if [input_visibility, param_visibility, output_visibility]
+            .iter()
+            .any(|v| v.contains("encrypted"))
+        {
+            add_elgamal_recipient(test_dir, &example_name);
+        }
+
+        calibrate_compile_and_gen_witness(test_dir, example_name, cal_target, scales_to_use);
+    }
+
+    fn calibrate_compile_and_gen_witness(
+        test_dir: &str,
+        example_name: String,
+        cal_target: &str,
+        scales_to_use: Option<Vec<u32>>,
+    ) {
+        let mut calibrate_args = vec![
+            "calibrate-settings".to_string(),
+            "--data".to_string(),
+            format!("{}/{}/input.json", test_dir, example_name),
+            "-M".to_string(),
+            format!("{}/{}/network.onnx", test_dir, example_name),
+            format!(
+                "--settings-path={}/{}/settings.json",
+                test_dir, example_name
+            ),
+            format!("--target={}", cal_target),
+        ];
+
+        if let Some(scales) = scales_to_use {
+            let scales = scales
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            calibrate_args.push("--scales".to_string());
+            calibrate_args.push(scales);
+        }
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(calibrate_args)
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "compile-circuit",
+                "-M",
+                format!("{}/{}/network.onnx", test_dir, example_name).as_str(),
+                "--compiled-circuit",
+                format!("{}/{}/network.compiled", test_dir, example_name).as_str(),
+                &format!(
+                    "--settings-path={}/{}/settings.json",
+                    test_dir, example_name
+                ),
+            ])
+            .stdout(std::process::Stdio::null())
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
             .args([
                 "gen-witness",
                 "-D",
@@ -1461,6 +1908,7 @@ mod native_tests {
             cal_target,
             None,
             2,
+            ProofSystem::default(),
         );
 
         println!(
@@ -1571,6 +2019,8 @@ mod native_tests {
                 "--logrows=23",
                 "--aggregation-snarks",
                 &format!("{}/{}/proof.pf", test_dir, example_name),
+                "--settings-paths",
+                &format!("{}/{}/settings.json", test_dir, example_name),
                 "--proof-path",
                 &format!("{}/{}/aggr.pf", test_dir, example_name),
                 "--pk-path",
@@ -1723,6 +2173,7 @@ mod native_tests {
             target_str,
             scales_to_use,
             num_inner_columns,
+            ProofSystem::default(),
         );
 
         let settings_path = format!("{}/{}/settings.json", test_dir, example_name);
@@ -1789,110 +2240,411 @@ mod native_tests {
             .status()
             .expect("failed to execute process");
         assert!(status.success());
+
+        // the compact binary proof format verifies just the same
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "convert-proof",
+                "--proof-path",
+                &format!("{}/{}/proof.pf", test_dir, example_name),
+                "--output-path",
+                &format!("{}/{}/proof.bin", test_dir, example_name),
+                format!("--settings-path={}", settings_path).as_str(),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "verify",
+                format!("--settings-path={}", settings_path).as_str(),
+                "--proof-path",
+                &format!("{}/{}/proof.bin", test_dir, example_name),
+                "--vk-path",
+                &format!("{}/{}/key.vk", test_dir, example_name),
+                &srs_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
     }
 
-    // prove-serialize-verify, the usual full path
-    fn kzg_fuzz(test_dir: &str, example_name: String, transcript: &str) {
+    // prove-verify with the gwc multi-open scheme, and check that verifying against shplonk settings fails
+    fn kzg_gwc_prove_and_verify(test_dir: &str, example_name: String) {
         gen_circuit_settings_and_witness(
             test_dir,
             example_name.clone(),
             "private",
-            "fixed",
+            "private",
             "public",
             1,
             "resources",
             None,
             2,
+            ProofSystem {
+                multi_open: "gwc",
+                ..Default::default()
+            },
         );
 
+        let settings_path = format!("{}/{}/settings.json", test_dir, example_name);
+
+        let srs_path = init_params(test_dir, settings_path.clone().into());
+        let srs_path = format!("--srs-path={}", srs_path);
+
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
             .args([
-                "fuzz",
+                "setup",
+                "-M",
+                &format!("{}/{}/network.compiled", test_dir, example_name),
+                "--pk-path",
+                &format!("{}/{}/key.pk", test_dir, example_name),
+                "--vk-path",
+                &format!("{}/{}/key.vk", test_dir, example_name),
+                &srs_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "prove",
                 "-W",
                 format!("{}/{}/witness.json", test_dir, example_name).as_str(),
                 "-M",
                 format!("{}/{}/network.compiled", test_dir, example_name).as_str(),
-                &format!("--num-runs={}", 5),
-                &format!("--transcript={}", transcript),
+                "--proof-path",
+                &format!("{}/{}/proof.pf", test_dir, example_name),
+                "--pk-path",
+                &format!("{}/{}/key.pk", test_dir, example_name),
+                &srs_path,
             ])
             .status()
             .expect("failed to execute process");
         assert!(status.success());
-    }
 
-    // prove-serialize-verify, the usual full path
-    fn kzg_evm_prove_and_verify(
-        test_dir: &str,
-        example_name: String,
-        input_visibility: &str,
-        param_visibility: &str,
-        output_visibility: &str,
-    ) {
-        let anvil_url = ANVIL_URL.as_str();
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "verify",
+                format!("--settings-path={}", settings_path).as_str(),
+                "--proof-path",
+                &format!("{}/{}/proof.pf", test_dir, example_name),
+                "--vk-path",
+                &format!("{}/{}/key.vk", test_dir, example_name),
+                &srs_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
 
-        kzg_prove_and_verify(
+        // the same proof checked against shplonk settings should be rejected
+        let settings = std::fs::read_to_string(&settings_path).expect("failed to read settings");
+        let mut settings: GraphSettings = serde_json::from_str(&settings).unwrap();
+        settings.run_args.multi_open = ezkl::pfsys::MultiOpenScheme::SHPLONK;
+        let shplonk_settings_path = format!("{}/{}/settings_shplonk.json", test_dir, example_name);
+        settings
+            .save(&shplonk_settings_path.clone().into())
+            .unwrap();
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "verify",
+                format!("--settings-path={}", shplonk_settings_path).as_str(),
+                "--proof-path",
+                &format!("{}/{}/proof.pf", test_dir, example_name),
+                "--vk-path",
+                &format!("{}/{}/key.vk", test_dir, example_name),
+                &srs_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(!status.success());
+    }
+
+    // prove-serialize-verify with the transparent ipa backend
+    fn ipa_prove_and_verify(test_dir: &str, example_name: String) {
+        gen_circuit_settings_and_witness(
             test_dir,
             example_name.clone(),
-            "safe",
-            input_visibility,
-            param_visibility,
-            output_visibility,
-            2,
+            "private",
+            "private",
+            "public",
+            1,
+            "resources",
             None,
-            false,
-            "single",
+            2,
+            ProofSystem {
+                commitment: "ipa",
+                ..Default::default()
+            },
         );
 
         let settings_path = format!("{}/{}/settings.json", test_dir, example_name);
-        let srs_path = init_params(test_dir, settings_path.clone().into());
-        let srs_path = format!("--srs-path={}", srs_path);
-
-        let vk_arg = format!("{}/{}/key.vk", test_dir, example_name);
-        let rpc_arg = format!("--rpc-url={}", anvil_url);
-        let addr_path_arg = format!("--addr-path={}/{}/addr.txt", test_dir, example_name);
-        let settings_arg = format!("--settings-path={}", settings_path);
-
-        // create the verifier
-        let mut args = vec![
-            "create-evm-verifier",
-            &srs_path,
-            "--vk-path",
-            &vk_arg,
-            &settings_arg,
-        ];
-
-        let sol_arg = format!("{}/{}/kzg.sol", test_dir, example_name);
-
-        // create everything to test the pipeline
-        args.push("--sol-code-path");
-        args.push(sol_arg.as_str());
+        let settings =
+            std::fs::read_to_string(&settings_path).expect("failed to read settings file");
+        let settings: GraphSettings = serde_json::from_str(&settings).unwrap();
+        let logrows = settings.run_args.logrows;
 
+        // ipa params are transparent, so we can generate them locally
+        let srs_path = format!("{}/ipa_{}.srs", test_dir, logrows);
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
-            .args(&args)
+            .args([
+                "gen-srs",
+                "--srs-path",
+                &srs_path,
+                "--logrows",
+                &logrows.to_string(),
+                "--commitment=ipa",
+            ])
             .status()
             .expect("failed to execute process");
         assert!(status.success());
-
-        // deploy the verifier
-        let mut args = vec![
-            "deploy-evm-verifier",
-            rpc_arg.as_str(),
-            addr_path_arg.as_str(),
-        ];
-
-        args.push("--sol-code-path");
-        args.push(sol_arg.as_str());
+        let srs_path = format!("--srs-path={}", srs_path);
 
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
-            .args(&args)
+            .args([
+                "setup",
+                "-M",
+                &format!("{}/{}/network.compiled", test_dir, example_name),
+                "--pk-path",
+                &format!("{}/{}/key.pk", test_dir, example_name),
+                "--vk-path",
+                &format!("{}/{}/key.vk", test_dir, example_name),
+                &srs_path,
+            ])
             .status()
             .expect("failed to execute process");
         assert!(status.success());
 
-        // read in the address
-        let addr = std::fs::read_to_string(format!("{}/{}/addr.txt", test_dir, example_name))
-            .expect("failed to read address file");
-
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "prove",
+                "-W",
+                format!("{}/{}/witness.json", test_dir, example_name).as_str(),
+                "-M",
+                format!("{}/{}/network.compiled", test_dir, example_name).as_str(),
+                "--proof-path",
+                &format!("{}/{}/proof.pf", test_dir, example_name),
+                "--pk-path",
+                &format!("{}/{}/key.pk", test_dir, example_name),
+                &srs_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "verify",
+                format!("--settings-path={}", settings_path).as_str(),
+                "--proof-path",
+                &format!("{}/{}/proof.pf", test_dir, example_name),
+                "--vk-path",
+                &format!("{}/{}/key.vk", test_dir, example_name),
+                &srs_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+    }
+
+    // prove-serialize-verify, recording a profile of each phase of setup and prove
+    fn kzg_profile_prove_and_verify(test_dir: &str, example_name: String) {
+        gen_circuit_settings_and_witness(
+            test_dir,
+            example_name.clone(),
+            "private",
+            "private",
+            "public",
+            1,
+            "resources",
+            None,
+            2,
+            ProofSystem::default(),
+        );
+
+        let settings_path = format!("{}/{}/settings.json", test_dir, example_name);
+
+        let srs_path = init_params(test_dir, settings_path.clone().into());
+        let srs_path = format!("--srs-path={}", srs_path);
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "setup",
+                "-M",
+                &format!("{}/{}/network.compiled", test_dir, example_name),
+                "--pk-path",
+                &format!("{}/{}/key.pk", test_dir, example_name),
+                "--vk-path",
+                &format!("{}/{}/key.vk", test_dir, example_name),
+                &srs_path,
+                "--profile-path",
+                &format!("{}/{}/setup_profile.json", test_dir, example_name),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "prove",
+                "-W",
+                format!("{}/{}/witness.json", test_dir, example_name).as_str(),
+                "-M",
+                format!("{}/{}/network.compiled", test_dir, example_name).as_str(),
+                "--proof-path",
+                &format!("{}/{}/proof.pf", test_dir, example_name),
+                "--pk-path",
+                &format!("{}/{}/key.pk", test_dir, example_name),
+                &srs_path,
+                "--profile-path",
+                &format!("{}/{}/profile.json", test_dir, example_name),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let phases = |profile_name: &str| {
+            let profile =
+                std::fs::read_to_string(format!("{}/{}/{}", test_dir, example_name, profile_name))
+                    .expect("failed to read profile");
+            let profile: serde_json::Value = serde_json::from_str(&profile).unwrap();
+            profile["phases"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|p| p["name"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        let setup_phases = phases("setup_profile.json");
+        for phase in ["model_load", "srs_load", "keygen_vk", "keygen_pk"] {
+            assert!(setup_phases.contains(&phase.to_string()));
+        }
+        let prove_phases = phases("profile.json");
+        for phase in ["model_load", "pk_load", "create_proof", "self_verify"] {
+            assert!(prove_phases.contains(&phase.to_string()));
+        }
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "verify",
+                format!("--settings-path={}", settings_path).as_str(),
+                "--proof-path",
+                &format!("{}/{}/proof.pf", test_dir, example_name),
+                "--vk-path",
+                &format!("{}/{}/key.vk", test_dir, example_name),
+                &srs_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+    }
+
+    // prove-serialize-verify, the usual full path
+    fn kzg_fuzz(test_dir: &str, example_name: String, transcript: &str) {
+        gen_circuit_settings_and_witness(
+            test_dir,
+            example_name.clone(),
+            "private",
+            "fixed",
+            "public",
+            1,
+            "resources",
+            None,
+            2,
+            ProofSystem::default(),
+        );
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "fuzz",
+                "-W",
+                format!("{}/{}/witness.json", test_dir, example_name).as_str(),
+                "-M",
+                format!("{}/{}/network.compiled", test_dir, example_name).as_str(),
+                &format!("--num-runs={}", 5),
+                &format!("--transcript={}", transcript),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+    }
+
+    // prove-serialize-verify, the usual full path
+    fn kzg_evm_prove_and_verify(
+        test_dir: &str,
+        example_name: String,
+        input_visibility: &str,
+        param_visibility: &str,
+        output_visibility: &str,
+    ) {
+        let anvil_url = ANVIL_URL.as_str();
+
+        kzg_prove_and_verify(
+            test_dir,
+            example_name.clone(),
+            "safe",
+            input_visibility,
+            param_visibility,
+            output_visibility,
+            2,
+            None,
+            false,
+            "single",
+        );
+
+        let settings_path = format!("{}/{}/settings.json", test_dir, example_name);
+        let srs_path = init_params(test_dir, settings_path.clone().into());
+        let srs_path = format!("--srs-path={}", srs_path);
+
+        let vk_arg = format!("{}/{}/key.vk", test_dir, example_name);
+        let rpc_arg = format!("--rpc-url={}", anvil_url);
+        let addr_path_arg = format!("--addr-path={}/{}/addr.txt", test_dir, example_name);
+        let settings_arg = format!("--settings-path={}", settings_path);
+
+        // create the verifier
+        let mut args = vec![
+            "create-evm-verifier",
+            &srs_path,
+            "--vk-path",
+            &vk_arg,
+            &settings_arg,
+        ];
+
+        let sol_arg = format!("{}/{}/kzg.sol", test_dir, example_name);
+
+        // create everything to test the pipeline
+        args.push("--sol-code-path");
+        args.push(sol_arg.as_str());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(&args)
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        // deploy the verifier
+        let mut args = vec![
+            "deploy-evm-verifier",
+            rpc_arg.as_str(),
+            addr_path_arg.as_str(),
+        ];
+
+        args.push("--sol-code-path");
+        args.push(sol_arg.as_str());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(&args)
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        // read in the address
+        let addr = std::fs::read_to_string(format!("{}/{}/addr.txt", test_dir, example_name))
+            .expect("failed to read address file");
+
         let deployed_addr_arg = format!("--addr-verifier={}", addr);
 
         // now verify the proof
@@ -1902,16 +2654,830 @@ mod native_tests {
             "--proof-path",
             pf_arg.as_str(),
             rpc_arg.as_str(),
-            deployed_addr_arg.as_str(),
+            deployed_addr_arg.as_str(),
+        ];
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(&args)
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+        // As sanity check, add example that should fail.
+        args[2] = PF_FAILURE;
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(args)
+            .status()
+            .expect("failed to execute process");
+        assert!(!status.success());
+
+        // estimate the deployment and verification costs on a fresh chain
+        let report_path = format!("{}/{}/evm_costs.json", test_dir, example_name);
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "estimate-evm",
+                "--proof-path",
+                &pf_arg,
+                "--sol-code-path",
+                &sol_arg,
+                "-O",
+                &report_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+        let report = std::fs::read_to_string(&report_path).expect("failed to read report");
+        let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+        assert_eq!(report[0]["contract"], "Halo2Verifier");
+        assert!(report[0]["verify_gas"].as_u64().unwrap() > 0);
+        assert!(report[0]["deploy_gas"].as_u64().unwrap() > 0);
+
+        // encode the calldata ourselves and submit it to the deployed verifier
+        let calldata_path = format!("{}/{}/calldata.hex", test_dir, example_name);
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "encode-evm-calldata",
+                "--proof-path",
+                &pf_arg,
+                "--calldata-path",
+                &calldata_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+        let calldata = std::fs::read_to_string(&calldata_path).expect("failed to read calldata");
+        let output = Command::new("cast")
+            .args(["call", addr.trim(), calldata.trim(), "--rpc-url", anvil_url])
+            .output()
+            .expect("failed to execute process");
+        assert!(output.status.success());
+        let result = String::from_utf8(output.stdout).unwrap();
+        assert!(result.trim().ends_with('1'));
+    }
+
+    // verify against externally supplied kzg commitments to the inputs
+    fn kzg_evm_bind_commitments_prove_and_verify(test_dir: &str, example_name: String) {
+        let anvil_url = ANVIL_URL.as_str();
+
+        kzg_prove_and_verify(
+            test_dir,
+            example_name.clone(),
+            "safe",
+            "kzgcommit",
+            "private",
+            "public",
+            2,
+            None,
+            false,
+            "single",
+        );
+
+        let settings_path = format!("{}/{}/settings.json", test_dir, example_name);
+        let srs_path = init_params(test_dir, settings_path.clone().into());
+        let model_path = format!("{}/{}/network.compiled", test_dir, example_name);
+        let vk_path = format!("{}/{}/key.vk", test_dir, example_name);
+        let witness_path = format!("{}/{}/witness_kzg.json", test_dir, example_name);
+        let pf_path = format!("{}/{}/proof_kzg.pf", test_dir, example_name);
+        let sol_path = format!("{}/{}/kzg_bind.sol", test_dir, example_name);
+        let addr_path = format!("{}/{}/addr_bind.txt", test_dir, example_name);
+        let rpc_arg = format!("--rpc-url={}", anvil_url);
+
+        // the witness only has kzg commitments if it is generated with the vk and srs
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "gen-witness",
+                "-D",
+                &format!("{}/{}/input.json", test_dir, example_name),
+                "-M",
+                &model_path,
+                "-O",
+                &witness_path,
+                "-V",
+                &vk_path,
+                "-P",
+                &srs_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "prove",
+                "-W",
+                &witness_path,
+                "-M",
+                &model_path,
+                "--proof-path",
+                &pf_path,
+                "--pk-path",
+                &format!("{}/{}/key.pk", test_dir, example_name),
+                &format!("--srs-path={}", srs_path),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "swap-proof-commitments",
+                "--proof-path",
+                &pf_path,
+                "--witness-path",
+                &witness_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "create-evm-verifier",
+                &format!("--srs-path={}", srs_path),
+                "--vk-path",
+                &vk_path,
+                &format!("--settings-path={}", settings_path),
+                "--sol-code-path",
+                &sol_path,
+                "--bind-commitments",
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "deploy-evm-verifier",
+                rpc_arg.as_str(),
+                &format!("--addr-path={}", addr_path),
+                "--sol-code-path",
+                &sol_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let addr = std::fs::read_to_string(&addr_path).expect("failed to read address file");
+        let deployed_addr_arg = format!("--addr-verifier={}", addr);
+
+        // the extended verifier still verifies proofs without any commitments
+        let mut args = vec![
+            "verify-evm",
+            "--proof-path",
+            pf_path.as_str(),
+            rpc_arg.as_str(),
+            deployed_addr_arg.as_str(),
+        ];
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(&args)
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        args.push("-W");
+        args.push(witness_path.as_str());
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(&args)
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        // commitments that don't match the proof's are rejected
+        let mut witness = GraphWitness::from_path(witness_path.clone().into()).unwrap();
+        let commitments = witness
+            .processed_inputs
+            .as_mut()
+            .unwrap()
+            .kzg_commit
+            .as_mut()
+            .unwrap();
+        commitments[0][0] = -commitments[0][0];
+        let forged_witness_path = format!("{}/{}/witness_forged.json", test_dir, example_name);
+        witness.save(forged_witness_path.clone().into()).unwrap();
+        args.pop();
+        args.push(forged_witness_path.as_str());
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(&args)
+            .status()
+            .expect("failed to execute process");
+        assert!(!status.success());
+    }
+
+    // verify with a shared verifier contract and a separate vk contract
+    fn kzg_evm_prove_and_verify_render_separately(
+        test_dir: &str,
+        example_name: String,
+        input_visibility: &str,
+        param_visibility: &str,
+        output_visibility: &str,
+    ) {
+        let anvil_url = ANVIL_URL.as_str();
+
+        kzg_prove_and_verify(
+            test_dir,
+            example_name.clone(),
+            "safe",
+            input_visibility,
+            param_visibility,
+            output_visibility,
+            2,
+            None,
+            false,
+            "single",
+        );
+
+        let settings_path = format!("{}/{}/settings.json", test_dir, example_name);
+        let srs_path = init_params(test_dir, settings_path.clone().into());
+        let srs_path = format!("--srs-path={}", srs_path);
+
+        let vk_arg = format!("{}/{}/key.vk", test_dir, example_name);
+        let rpc_arg = format!("--rpc-url={}", anvil_url);
+        let addr_path_arg = format!("--addr-path={}/{}/addr.txt", test_dir, example_name);
+        let settings_arg = format!("--settings-path={}", settings_path);
+        let sol_arg = format!("--sol-code-path={}/{}/kzg.sol", test_dir, example_name);
+
+        // create the shared verifier
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "create-evm-verifier",
+                &srs_path,
+                "--vk-path",
+                &vk_arg,
+                &settings_arg,
+                &sol_arg,
+                "--render-vk-separately",
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        // create the vk contract
+        let vk_sol_arg = format!("--sol-code-path={}/{}/vk.sol", test_dir, example_name);
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "create-evm-vk",
+                &srs_path,
+                "--vk-path",
+                &vk_arg,
+                &settings_arg,
+                &vk_sol_arg,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        // deploy the verifier
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(["deploy-evm-verifier", &rpc_arg, &addr_path_arg, &sol_arg])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let addr = std::fs::read_to_string(format!("{}/{}/addr.txt", test_dir, example_name))
+            .expect("failed to read address file");
+
+        // deploying again with the existing verifier should reuse it
+        let reuse_arg = format!("--reuse-addr={}", addr);
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "deploy-evm-verifier",
+                &rpc_arg,
+                &addr_path_arg,
+                &sol_arg,
+                &reuse_arg,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+        let reused_addr =
+            std::fs::read_to_string(format!("{}/{}/addr.txt", test_dir, example_name))
+                .expect("failed to read address file");
+        assert_eq!(addr, reused_addr);
+
+        // deploy the vk
+        let vk_addr_path_arg = format!("--addr-path={}/{}/addr_vk.txt", test_dir, example_name);
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(["deploy-evm-vk", &rpc_arg, &vk_addr_path_arg, &vk_sol_arg])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let addr_vk = std::fs::read_to_string(format!("{}/{}/addr_vk.txt", test_dir, example_name))
+            .expect("failed to read address file");
+
+        let deployed_addr_arg = format!("--addr-verifier={}", addr);
+        let deployed_addr_vk_arg = format!("--addr-vk={}", addr_vk);
+
+        // now verify the proof
+        let pf_arg = format!("{}/{}/proof.pf", test_dir, example_name);
+        let mut args = vec![
+            "verify-evm",
+            "--proof-path",
+            pf_arg.as_str(),
+            rpc_arg.as_str(),
+            deployed_addr_arg.as_str(),
+            deployed_addr_vk_arg.as_str(),
+        ];
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(&args)
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+        // As sanity check, add example that should fail.
+        args[2] = PF_FAILURE;
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(args)
+            .status()
+            .expect("failed to execute process");
+        assert!(!status.success());
+    }
+
+    // run js browser evm verify tests for a given example
+    fn run_js_tests(test_dir: &str, example_name: String, js_test: &str) {
+        let status = Command::new("pnpm")
+            .args([
+                "run",
+                "test",
+                js_test,
+                &format!("--example={}", example_name),
+                &format!("--dir={}", test_dir),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+    }
+
+    fn kzg_evm_on_chain_input_prove_and_verify(
+        test_dir: &str,
+        example_name: String,
+        input_source: &str,
+        output_source: &str,
+        input_visibility: &str,
+        output_visibility: &str,
+        hash_on_chain: bool,
+    ) {
+        gen_circuit_settings_and_witness(
+            test_dir,
+            example_name.clone(),
+            input_visibility,
+            "private",
+            output_visibility,
+            1,
+            "resources",
+            // we need the accuracy
+            Some(vec![7, 8]),
+            1,
+            ProofSystem::default(),
+        );
+
+        let model_path = format!("{}/{}/network.compiled", test_dir, example_name);
+        let settings_path = format!("{}/{}/settings.json", test_dir, example_name);
+
+        let srs_path = init_params(test_dir, settings_path.clone().into());
+        let srs_path = format!("--srs-path={}", srs_path);
+
+        let data_path = format!("{}/{}/input.json", test_dir, example_name);
+        let witness_path = format!("{}/{}/witness.json", test_dir, example_name);
+        let test_on_chain_data_path = format!("{}/{}/on_chain_input.json", test_dir, example_name);
+        let rpc_arg = format!("--rpc-url={}", LIMITLESS_ANVIL_URL.as_str());
+        let private_key = format!("--private-key={}", *ANVIL_DEFAULT_PRIVATE_KEY);
+
+        let test_input_source = format!("--input-source={}", input_source);
+        let test_output_source = format!("--output-source={}", output_source);
+
+        // load witness
+        let witness: GraphWitness = GraphWitness::from_path(witness_path.clone().into()).unwrap();
+        let mut input: GraphData = GraphData::from_path(data_path.clone().into()).unwrap();
+
+        // when hashing on-chain the raw data is stored on-chain rather than its hash
+        if input_visibility == "hashed" && !hash_on_chain {
+            let hashes = witness.processed_inputs.unwrap().poseidon_hash.unwrap();
+            input.input_data = DataSource::File(
+                hashes
+                    .iter()
+                    .map(|h| vec![FileSourceInner::Field(*h)])
+                    .collect(),
+            );
+        }
+        if output_visibility == "hashed" && !hash_on_chain {
+            let hashes = witness.processed_outputs.unwrap().poseidon_hash.unwrap();
+            input.output_data = Some(DataSource::File(
+                hashes
+                    .iter()
+                    .map(|h| vec![FileSourceInner::Field(*h)])
+                    .collect(),
+            ));
+        }
+
+        println!("input is {:?}", input);
+
+        input.save(data_path.clone().into()).unwrap();
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "setup-test-evm-data",
+                "-D",
+                data_path.as_str(),
+                "-M",
+                &model_path,
+                "--test-data",
+                test_on_chain_data_path.as_str(),
+                rpc_arg.as_str(),
+                test_input_source.as_str(),
+                test_output_source.as_str(),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        if hash_on_chain {
+            let mut test_data: GraphData =
+                GraphData::from_path(test_on_chain_data_path.clone().into()).unwrap();
+            if let DataSource::OnChain(source) = &mut test_data.input_data {
+                source.hash_on_chain = input_visibility == "hashed";
+            }
+            if let Some(DataSource::OnChain(source)) = &mut test_data.output_data {
+                source.hash_on_chain = output_visibility == "hashed";
+            }
+            test_data
+                .save(test_on_chain_data_path.clone().into())
+                .unwrap();
+        }
+
+        // public on-chain inputs commit the block they were read at, which the data attestation contract checks
+        let commit_block = input_source == "on-chain" && input_visibility == "public";
+        if commit_block {
+            let mut settings = GraphSettings::load(&settings_path.clone().into()).unwrap();
+            settings.run_args.commit_on_chain_block = true;
+            settings.save(&settings_path.clone().into()).unwrap();
+
+            let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+                .args([
+                    "compile-circuit",
+                    "-M",
+                    format!("{}/{}/network.onnx", test_dir, example_name).as_str(),
+                    "--compiled-circuit",
+                    &model_path,
+                    &format!("--settings-path={}", settings_path),
+                ])
+                .status()
+                .expect("failed to execute process");
+            assert!(status.success());
+
+            let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+                .args([
+                    "gen-witness",
+                    "-D",
+                    test_on_chain_data_path.as_str(),
+                    "-M",
+                    &model_path,
+                    "-O",
+                    &witness_path,
+                ])
+                .status()
+                .expect("failed to execute process");
+            assert!(status.success());
+        }
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "setup",
+                "-M",
+                &model_path,
+                "--pk-path",
+                &format!("{}/{}/key.pk", test_dir, example_name),
+                "--vk-path",
+                &format!("{}/{}/key.vk", test_dir, example_name),
+                &srs_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "prove",
+                "-W",
+                &witness_path,
+                "-M",
+                &model_path,
+                "--proof-path",
+                &format!("{}/{}/proof.pf", test_dir, example_name),
+                "--pk-path",
+                &format!("{}/{}/key.pk", test_dir, example_name),
+                &srs_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let vk_arg = format!("{}/{}/key.vk", test_dir, example_name);
+
+        let settings_arg = format!("--settings-path={}", settings_path);
+
+        // create the verifier
+        let mut args = vec![
+            "create-evm-verifier",
+            &srs_path,
+            "--vk-path",
+            &vk_arg,
+            &settings_arg,
+        ];
+
+        let sol_arg = format!("{}/{}/kzg.sol", test_dir, example_name);
+
+        args.push("--sol-code-path");
+        args.push(sol_arg.as_str());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(&args)
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let addr_path_verifier_arg = format!(
+            "--addr-path={}/{}/addr_verifier.txt",
+            test_dir, example_name
+        );
+
+        // deploy the verifier
+        let mut args = vec![
+            "deploy-evm-verifier",
+            rpc_arg.as_str(),
+            addr_path_verifier_arg.as_str(),
+        ];
+
+        args.push("--sol-code-path");
+        args.push(sol_arg.as_str());
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(&args)
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let sol_arg = format!("{}/{}/kzg.sol", test_dir, example_name);
+
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "create-evm-da",
+                &settings_arg,
+                "--sol-code-path",
+                sol_arg.as_str(),
+                &srs_path,
+                "--vk-path",
+                &vk_arg,
+                "-D",
+                test_on_chain_data_path.as_str(),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let addr_path_da_arg = format!("--addr-path={}/{}/addr_da.txt", test_dir, example_name);
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "deploy-evm-da",
+                format!("--settings-path={}", settings_path).as_str(),
+                "-D",
+                test_on_chain_data_path.as_str(),
+                "--sol-code-path",
+                sol_arg.as_str(),
+                rpc_arg.as_str(),
+                addr_path_da_arg.as_str(),
+                private_key.as_str(),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        // the address of a data attestation contract deployed through a CREATE2 factory depends on its
+        // constructor arguments, which are derived from the data and the deployer (its admin)
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "create2-address",
+                "--sol-code-path",
+                sol_arg.as_str(),
+                "--contract-name",
+                "DataAttestation",
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(!status.success());
+
+        let create2_addr_path = format!("{}/{}/addr_da_create2.txt", test_dir, example_name);
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "create2-address",
+                "--sol-code-path",
+                sol_arg.as_str(),
+                "-D",
+                test_on_chain_data_path.as_str(),
+                &settings_arg,
+                "--deployer",
+                ANVIL_DEFAULT_ADDRESS.as_str(),
+                "--addr-path",
+                &create2_addr_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let deployed_create2_addr_path =
+            format!("{}/{}/addr_da_create2_deployed.txt", test_dir, example_name);
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "deploy-evm-da",
+                &settings_arg,
+                "-D",
+                test_on_chain_data_path.as_str(),
+                "--sol-code-path",
+                sol_arg.as_str(),
+                rpc_arg.as_str(),
+                &format!("--addr-path={}", deployed_create2_addr_path),
+                private_key.as_str(),
+                "--create2",
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+        let expected_addr =
+            std::fs::read_to_string(&create2_addr_path).expect("failed to read address file");
+        let deployed_addr = std::fs::read_to_string(&deployed_create2_addr_path)
+            .expect("failed to read address file");
+        assert_eq!(
+            expected_addr.trim().to_lowercase(),
+            deployed_addr.trim().to_lowercase()
+        );
+
+        let pf_arg = format!("{}/{}/proof.pf", test_dir, example_name);
+        // read in the verifier address
+        let addr_verifier =
+            std::fs::read_to_string(format!("{}/{}/addr_verifier.txt", test_dir, example_name))
+                .expect("failed to read address file");
+
+        let deployed_addr_verifier_arg = format!("--addr-verifier={}", addr_verifier);
+
+        // read in the da address
+        let addr_da = std::fs::read_to_string(format!("{}/{}/addr_da.txt", test_dir, example_name))
+            .expect("failed to read address file");
+
+        let deployed_addr_da_arg = format!("--addr-da={}", addr_da);
+
+        let args = vec![
+            "verify-evm",
+            "--proof-path",
+            pf_arg.as_str(),
+            deployed_addr_verifier_arg.as_str(),
+            deployed_addr_da_arg.as_str(),
+            rpc_arg.as_str(),
+        ];
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args(&args)
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        // regenerating the witness from the block the on-chain inputs were pinned to gives the same inputs,
+        // and a proof committing to a hash that isn't the block's is rejected by the data attestation contract
+        if commit_block {
+            let witness = GraphWitness::from_path(witness_path.clone().into()).unwrap();
+            let block = witness.on_chain_block.clone().unwrap();
+
+            let mut test_data: GraphData =
+                GraphData::from_path(test_on_chain_data_path.clone().into()).unwrap();
+            if let DataSource::OnChain(source) = &mut test_data.input_data {
+                source.block_number = Some(block.number);
+                source.block_hash = None;
+            }
+            test_data
+                .save(test_on_chain_data_path.clone().into())
+                .unwrap();
+
+            let pinned_witness_path = format!("{}/{}/pinned_witness.json", test_dir, example_name);
+            let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+                .args([
+                    "gen-witness",
+                    "-D",
+                    test_on_chain_data_path.as_str(),
+                    "-M",
+                    &model_path,
+                    "-O",
+                    &pinned_witness_path,
+                ])
+                .status()
+                .expect("failed to execute process");
+            assert!(status.success());
+            let pinned_witness = GraphWitness::from_path(pinned_witness_path.into()).unwrap();
+            assert_eq!(pinned_witness.on_chain_block, Some(block.clone()));
+            assert_eq!(pinned_witness.inputs, witness.inputs);
+
+            // flip the last bit of the committed hash and prove again
+            let mut tampered_witness = witness.clone();
+            let mut hash = block.hash.clone();
+            let last = hash.pop().unwrap().to_digit(16).unwrap() ^ 1;
+            hash.push(std::char::from_digit(last, 16).unwrap());
+            tampered_witness.on_chain_block =
+                Some(ezkl::graph::input::OnChainBlock { hash, ..block });
+            let tampered_witness_path =
+                format!("{}/{}/tampered_witness.json", test_dir, example_name);
+            tampered_witness
+                .save(tampered_witness_path.clone().into())
+                .unwrap();
+
+            let tampered_pf_path = format!("{}/{}/tampered_proof.pf", test_dir, example_name);
+            let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+                .args([
+                    "prove",
+                    "-W",
+                    &tampered_witness_path,
+                    "-M",
+                    &model_path,
+                    "--proof-path",
+                    &tampered_pf_path,
+                    "--pk-path",
+                    &format!("{}/{}/key.pk", test_dir, example_name),
+                    &srs_path,
+                ])
+                .status()
+                .expect("failed to execute process");
+            assert!(status.success());
+
+            let calldata_path = format!("{}/{}/tampered_calldata.hex", test_dir, example_name);
+            let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+                .args([
+                    "encode-evm-calldata",
+                    "--proof-path",
+                    &tampered_pf_path,
+                    "--calldata-path",
+                    &calldata_path,
+                    "--calldata-type",
+                    "data-attestation",
+                    deployed_addr_verifier_arg.as_str(),
+                ])
+                .status()
+                .expect("failed to execute process");
+            assert!(status.success());
+            let calldata =
+                std::fs::read_to_string(&calldata_path).expect("failed to read calldata");
+            let output = Command::new("cast")
+                .args([
+                    "call",
+                    addr_da.trim(),
+                    calldata.trim(),
+                    "--rpc-url",
+                    LIMITLESS_ANVIL_URL.as_str(),
+                ])
+                .output()
+                .expect("failed to execute process");
+            assert!(!output.status.success());
+            let error = String::from_utf8(output.stderr).unwrap();
+            assert!(error.contains("Invalid block hash"), "{}", error);
+        }
+
+        // Create a new set of test on chain data
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "setup-test-evm-data",
+                "-D",
+                data_path.as_str(),
+                "-M",
+                &model_path,
+                "--test-data",
+                test_on_chain_data_path.as_str(),
+                rpc_arg.as_str(),
+                test_input_source.as_str(),
+                test_output_source.as_str(),
+            ])
+            .status()
+            .expect("failed to execute process");
+
+        assert!(status.success());
+
+        let deployed_addr_arg = format!("--addr={}", addr_da);
+
+        let args = vec![
+            "test-update-account-calls",
+            deployed_addr_arg.as_str(),
+            "-D",
+            test_on_chain_data_path.as_str(),
+            rpc_arg.as_str(),
         ];
-
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
             .args(&args)
             .status()
             .expect("failed to execute process");
+
         assert!(status.success());
         // As sanity check, add example that should fail.
-        args[2] = PF_FAILURE;
+        let args = vec![
+            "verify-evm",
+            "--proof-path",
+            PF_FAILURE,
+            deployed_addr_verifier_arg.as_str(),
+            deployed_addr_da_arg.as_str(),
+            rpc_arg.as_str(),
+        ];
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
             .args(args)
             .status()
@@ -1919,40 +3485,18 @@ mod native_tests {
         assert!(!status.success());
     }
 
-    // run js browser evm verify tests for a given example
-    fn run_js_tests(test_dir: &str, example_name: String, js_test: &str) {
-        let status = Command::new("pnpm")
-            .args([
-                "run",
-                "test",
-                js_test,
-                &format!("--example={}", example_name),
-                &format!("--dir={}", test_dir),
-            ])
-            .status()
-            .expect("failed to execute process");
-        assert!(status.success());
-    }
-
-    fn kzg_evm_on_chain_input_prove_and_verify(
-        test_dir: &str,
-        example_name: String,
-        input_source: &str,
-        output_source: &str,
-        input_visibility: &str,
-        output_visibility: &str,
-    ) {
+    fn kzg_evm_signed_input_prove_and_verify(test_dir: &str, example_name: String) {
         gen_circuit_settings_and_witness(
             test_dir,
             example_name.clone(),
-            input_visibility,
+            "public",
+            "private",
             "private",
-            output_visibility,
             1,
             "resources",
-            // we need the accuracy
             Some(vec![7, 8]),
             1,
+            ProofSystem::default(),
         );
 
         let model_path = format!("{}/{}/network.compiled", test_dir, example_name);
@@ -1962,68 +3506,94 @@ mod native_tests {
         let srs_path = format!("--srs-path={}", srs_path);
 
         let data_path = format!("{}/{}/input.json", test_dir, example_name);
+        let signed_data_path = format!("{}/{}/signed_input.json", test_dir, example_name);
         let witness_path = format!("{}/{}/witness.json", test_dir, example_name);
-        let test_on_chain_data_path = format!("{}/{}/on_chain_input.json", test_dir, example_name);
         let rpc_arg = format!("--rpc-url={}", LIMITLESS_ANVIL_URL.as_str());
         let private_key = format!("--private-key={}", *ANVIL_DEFAULT_PRIVATE_KEY);
 
-        let test_input_source = format!("--input-source={}", input_source);
-        let test_output_source = format!("--output-source={}", output_source);
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "setup",
+                "-M",
+                &model_path,
+                "--pk-path",
+                &format!("{}/{}/key.pk", test_dir, example_name),
+                "--vk-path",
+                &format!("{}/{}/key.vk", test_dir, example_name),
+                &srs_path,
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
 
-        // load witness
-        let witness: GraphWitness = GraphWitness::from_path(witness_path.clone().into()).unwrap();
-        let mut input: GraphData = GraphData::from_path(data_path.clone().into()).unwrap();
+        let vk_arg = format!("{}/{}/key.vk", test_dir, example_name);
+        let settings_arg = format!("--settings-path={}", settings_path);
+        let sol_arg = format!("{}/{}/kzg.sol", test_dir, example_name);
 
-        if input_visibility == "hashed" {
-            let hashes = witness.processed_inputs.unwrap().poseidon_hash.unwrap();
-            input.input_data = DataSource::File(
-                hashes
-                    .iter()
-                    .map(|h| vec![FileSourceInner::Field(*h)])
-                    .collect(),
-            );
-        }
-        if output_visibility == "hashed" {
-            let hashes = witness.processed_outputs.unwrap().poseidon_hash.unwrap();
-            input.output_data = Some(DataSource::File(
-                hashes
-                    .iter()
-                    .map(|h| vec![FileSourceInner::Field(*h)])
-                    .collect(),
-            ));
-        }
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "create-evm-verifier",
+                &srs_path,
+                "--vk-path",
+                &vk_arg,
+                &settings_arg,
+                "--sol-code-path",
+                sol_arg.as_str(),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
 
-        println!("input is {:?}", input);
+        let addr_path_verifier_arg = format!(
+            "--addr-path={}/{}/addr_verifier.txt",
+            test_dir, example_name
+        );
 
-        input.save(data_path.clone().into()).unwrap();
+        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .args([
+                "deploy-evm-verifier",
+                rpc_arg.as_str(),
+                addr_path_verifier_arg.as_str(),
+                "--sol-code-path",
+                sol_arg.as_str(),
+            ])
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+
+        let addr_verifier =
+            std::fs::read_to_string(format!("{}/{}/addr_verifier.txt", test_dir, example_name))
+                .expect("failed to read address file");
+        let deployed_addr_verifier_arg = format!("--addr-verifier={}", addr_verifier);
 
+        // the data is signed for the deployed verifier
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
             .args([
-                "setup-test-evm-data",
+                "sign-data",
                 "-D",
                 data_path.as_str(),
                 "-M",
                 &model_path,
-                "--test-data",
-                test_on_chain_data_path.as_str(),
-                rpc_arg.as_str(),
-                test_input_source.as_str(),
-                test_output_source.as_str(),
+                private_key.as_str(),
+                deployed_addr_verifier_arg.as_str(),
+                "--chain-id=31337",
+                "-O",
+                signed_data_path.as_str(),
             ])
             .status()
             .expect("failed to execute process");
         assert!(status.success());
 
+        // the signature is checked when generating the witness
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
             .args([
-                "setup",
+                "gen-witness",
+                "-D",
+                signed_data_path.as_str(),
                 "-M",
                 &model_path,
-                "--pk-path",
-                &format!("{}/{}/key.pk", test_dir, example_name),
-                "--vk-path",
-                &format!("{}/{}/key.vk", test_dir, example_name),
-                &srs_path,
+                "-O",
+                &witness_path,
             ])
             .status()
             .expect("failed to execute process");
@@ -2046,64 +3616,19 @@ mod native_tests {
             .expect("failed to execute process");
         assert!(status.success());
 
-        let vk_arg = format!("{}/{}/key.vk", test_dir, example_name);
-
-        let settings_arg = format!("--settings-path={}", settings_path);
-
-        // create the verifier
-        let mut args = vec![
-            "create-evm-verifier",
-            &srs_path,
-            "--vk-path",
-            &vk_arg,
-            &settings_arg,
-        ];
-
-        let sol_arg = format!("{}/{}/kzg.sol", test_dir, example_name);
-
-        args.push("--sol-code-path");
-        args.push(sol_arg.as_str());
-
-        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
-            .args(&args)
-            .status()
-            .expect("failed to execute process");
-        assert!(status.success());
-
-        let addr_path_verifier_arg = format!(
-            "--addr-path={}/{}/addr_verifier.txt",
-            test_dir, example_name
-        );
-
-        // deploy the verifier
-        let mut args = vec![
-            "deploy-evm-verifier",
-            rpc_arg.as_str(),
-            addr_path_verifier_arg.as_str(),
-        ];
-
-        args.push("--sol-code-path");
-        args.push(sol_arg.as_str());
-
-        let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
-            .args(&args)
-            .status()
-            .expect("failed to execute process");
-        assert!(status.success());
-
-        let sol_arg = format!("{}/{}/kzg.sol", test_dir, example_name);
+        let sol_da_arg = format!("{}/{}/kzg_da.sol", test_dir, example_name);
 
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
             .args([
                 "create-evm-da",
                 &settings_arg,
                 "--sol-code-path",
-                sol_arg.as_str(),
+                sol_da_arg.as_str(),
                 &srs_path,
                 "--vk-path",
                 &vk_arg,
                 "-D",
-                test_on_chain_data_path.as_str(),
+                signed_data_path.as_str(),
             ])
             .status()
             .expect("failed to execute process");
@@ -2113,11 +3638,11 @@ mod native_tests {
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
             .args([
                 "deploy-evm-da",
-                format!("--settings-path={}", settings_path).as_str(),
+                &settings_arg,
                 "-D",
-                test_on_chain_data_path.as_str(),
+                signed_data_path.as_str(),
                 "--sol-code-path",
-                sol_arg.as_str(),
+                sol_da_arg.as_str(),
                 rpc_arg.as_str(),
                 addr_path_da_arg.as_str(),
                 private_key.as_str(),
@@ -2127,77 +3652,79 @@ mod native_tests {
         assert!(status.success());
 
         let pf_arg = format!("{}/{}/proof.pf", test_dir, example_name);
-        // read in the verifier address
-        let addr_verifier =
-            std::fs::read_to_string(format!("{}/{}/addr_verifier.txt", test_dir, example_name))
-                .expect("failed to read address file");
-
-        let deployed_addr_verifier_arg = format!("--addr-verifier={}", addr_verifier);
-
-        // read in the da address
         let addr_da = std::fs::read_to_string(format!("{}/{}/addr_da.txt", test_dir, example_name))
             .expect("failed to read address file");
-
         let deployed_addr_da_arg = format!("--addr-da={}", addr_da);
 
-        let args = vec![
-            "verify-evm",
-            "--proof-path",
-            pf_arg.as_str(),
-            deployed_addr_verifier_arg.as_str(),
-            deployed_addr_da_arg.as_str(),
-            rpc_arg.as_str(),
-        ];
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
-            .args(&args)
+            .args([
+                "verify-evm",
+                "--proof-path",
+                pf_arg.as_str(),
+                deployed_addr_verifier_arg.as_str(),
+                deployed_addr_da_arg.as_str(),
+                "-D",
+                signed_data_path.as_str(),
+                rpc_arg.as_str(),
+            ])
             .status()
             .expect("failed to execute process");
         assert!(status.success());
-        // Create a new set of test on chain data
+
+        // a forged signature should be rejected
+        let mut data: GraphData = GraphData::from_path(signed_data_path.clone().into()).unwrap();
+        if let DataSource::Signed(source) = &mut data.input_data {
+            source.signature = "00".repeat(65);
+        }
+        let forged_data_path = format!("{}/{}/forged_input.json", test_dir, example_name);
+        data.save(forged_data_path.clone().into()).unwrap();
+
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
             .args([
-                "setup-test-evm-data",
+                "verify-evm",
+                "--proof-path",
+                pf_arg.as_str(),
+                deployed_addr_verifier_arg.as_str(),
+                deployed_addr_da_arg.as_str(),
                 "-D",
-                data_path.as_str(),
-                "-M",
-                &model_path,
-                "--test-data",
-                test_on_chain_data_path.as_str(),
+                forged_data_path.as_str(),
                 rpc_arg.as_str(),
-                test_input_source.as_str(),
-                test_output_source.as_str(),
             ])
             .status()
             .expect("failed to execute process");
+        assert!(!status.success());
 
-        assert!(status.success());
-
-        let deployed_addr_arg = format!("--addr={}", addr_da);
-
-        let args = vec![
-            "test-update-account-calls",
-            deployed_addr_arg.as_str(),
-            "-D",
-            test_on_chain_data_path.as_str(),
-            rpc_arg.as_str(),
-        ];
+        // a signature over a nonce the contract hasn't reached yet should be rejected
+        let replayed_data_path = format!("{}/{}/replayed_input.json", test_dir, example_name);
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
-            .args(&args)
+            .args([
+                "sign-data",
+                "-D",
+                data_path.as_str(),
+                "-M",
+                &model_path,
+                private_key.as_str(),
+                deployed_addr_verifier_arg.as_str(),
+                "--chain-id=31337",
+                "--nonce=1",
+                "-O",
+                replayed_data_path.as_str(),
+            ])
             .status()
             .expect("failed to execute process");
-
         assert!(status.success());
-        // As sanity check, add example that should fail.
-        let args = vec![
-            "verify-evm",
-            "--proof-path",
-            PF_FAILURE,
-            deployed_addr_verifier_arg.as_str(),
-            deployed_addr_da_arg.as_str(),
-            rpc_arg.as_str(),
-        ];
+
         let status = Command::new(format!("{}/release/ezkl", *CARGO_TARGET_DIR))
-            .args(args)
+            .args([
+                "verify-evm",
+                "--proof-path",
+                pf_arg.as_str(),
+                deployed_addr_verifier_arg.as_str(),
+                deployed_addr_da_arg.as_str(),
+                "-D",
+                replayed_data_path.as_str(),
+                rpc_arg.as_str(),
+            ])
             .status()
             .expect("failed to execute process");
         assert!(!status.success());
@@ -3,7 +3,9 @@ import os
 import pytest
 import json
 import subprocess
+import shutil
 import time
+import urllib.request
 
 folder_path = os.path.abspath(
     os.path.join(
@@ -51,6 +53,18 @@ def test_py_run_args():
     run_args.tolerance = 1.5
 
 
+def test_py_run_args_bad_visibility():
+    """
+    Test that malformed visibilities raise rather than panic or drop their suffixes
+    """
+    run_args = ezkl.PyRunArgs()
+    run_args.input_visibility = "hashed/private/0,1@t5+salted"
+    assert run_args.input_visibility == "hashed/private/0,1@t5+salted"
+    for visibility in ["hashed/private/x", "public@t5", "private+salted", "merkle/4+salted"]:
+        with pytest.raises(ValueError):
+            run_args.input_visibility = visibility
+
+
 def test_poseidon_hash():
     """
     Test for poseidon_hash
@@ -62,6 +76,67 @@ def test_poseidon_hash():
         res[0]) == "0x0da7e5e5c8877242fa699f586baf770d731defd54f952d4adeb85047a0e32f45"
 
 
+def test_poseidon_hash_params():
+    """
+    Test for poseidon_hash with a named parameter set
+    """
+    message = [1.0, 2.0, 3.0, 4.0]
+    message = [ezkl.float_to_vecu64(x, 7) for x in message]
+    assert ezkl.poseidon_hash(message, "t2") == ezkl.poseidon_hash(message)
+    assert ezkl.poseidon_hash(message, "t5") != ezkl.poseidon_hash(message)
+    with pytest.raises(ValueError):
+        ezkl.poseidon_hash(message, "t3")
+    with pytest.raises(ValueError):
+        ezkl.poseidon_hash(message, "t4")
+
+
+def test_merkle_proof():
+    """
+    Test for merkle_root and merkle_proof
+    """
+    records = [[ezkl.float_to_vecu64(float(i + j), 0) for j in range(3)] for i in range(5)]
+    root = ezkl.merkle_root(records)
+    proof = json.loads(ezkl.merkle_proof(records, 3))
+    assert proof["index"] == 3
+    # 5 records are padded to a tree of 8 leaves
+    assert len(proof["siblings"]) == 3
+    # changing a record changes the root
+    records[0][0] = ezkl.float_to_vecu64(42.0, 0)
+    assert ezkl.merkle_root(records) != root
+    with pytest.raises(ValueError):
+        ezkl.merkle_proof(records, 8)
+
+
+def test_merkle_commit():
+    """
+    Test for merkle_commit
+    """
+    # a message of one leaf commits to the root of a single leaf tree
+    message = [ezkl.float_to_vecu64(float(i), 0) for i in range(3)]
+    assert ezkl.merkle_commit(message) == ezkl.merkle_root([message])
+    # longer messages are split into leaves of 32 elements
+    message = [ezkl.float_to_vecu64(float(i), 0) for i in range(70)]
+    leaves = [message[i:i + 32] for i in range(0, 70, 32)]
+    assert ezkl.merkle_commit(message) == ezkl.merkle_root(leaves)
+
+
+def test_eddsa_sign():
+    """
+    Test for eddsa_public_key and eddsa_sign
+    """
+    secret_key = ezkl.float_to_vecu64(1234.0, 0)
+    message = [ezkl.float_to_vecu64(float(i), 0) for i in range(10)]
+    public_key = ezkl.eddsa_public_key(secret_key)
+    assert len(public_key) == 2
+
+    signature = json.loads(ezkl.eddsa_sign(message, secret_key))
+    other = json.loads(ezkl.eddsa_sign(message, secret_key))
+    assert set(signature.keys()) == {"public_key", "r", "s"}
+    # the key is the same, the nonce is fresh
+    assert signature["public_key"] == other["public_key"]
+    assert signature["r"] != other["r"]
+
+
 def test_elgamal():
     """
     Test for elgamal encryption and decryption
@@ -223,6 +298,90 @@ def test_calibrate():
     assert os.path.isfile(output_path)
 
 
+def test_calibrate_sampled():
+    """
+    Calibrates on a random subset of the batches of a multi-batch dataset, streamed from a csv,
+    a candidate at a time
+    """
+    model_path = os.path.join(
+        examples_path,
+        'onnx',
+        '1l_average',
+        'network.onnx'
+    )
+    csv_path = os.path.join(
+        folder_path,
+        'sampled_calibration.csv'
+    )
+    data_path = os.path.join(
+        folder_path,
+        'sampled_calibration.json'
+    )
+    output_path = os.path.join(
+        folder_path,
+        'sampled_settings.json'
+    )
+
+    # 100 batches of the model's 12 inputs, more rows than are read from the csv at a time
+    with open(csv_path, 'w') as f:
+        f.write('x\n')
+        for i in range(100 * 12):
+            f.write('{}\n'.format((i % 97) / 97))
+    with open(data_path, 'w') as f:
+        json.dump({'input_data': {'csv': csv_path}}, f)
+
+    res = ezkl.gen_settings(model_path, output_path)
+    assert res == True
+
+    # a 1MB limit fits a single candidate at a time
+    res = ezkl.calibrate_settings(
+        data_path, model_path, output_path, "resources", num_samples=10, max_memory=1)
+    assert res == True
+    with open(output_path) as f:
+        settings = json.load(f)
+    assert settings['run_args']['logrows'] > 0
+
+
+def test_gen_settings_preprocessing():
+    """
+    Test that the declared input preprocessing is stored in the settings
+    and survives calibration
+    """
+    data_path = os.path.join(
+        examples_path,
+        'onnx',
+        '1l_average',
+        'input.json'
+    )
+    model_path = os.path.join(
+        examples_path,
+        'onnx',
+        '1l_average',
+        'network.onnx'
+    )
+    preprocessing_path = os.path.join(folder_path, 'preprocessing.json')
+    output_path = os.path.join(folder_path, 'preprocessed_settings.json')
+
+    preprocessing = [{"steps": [
+        {"op": "normalize", "mean": [0.5], "std": [2.0]},
+        {"op": "clip", "min": -1.0, "max": 1.0},
+    ]}]
+    with open(preprocessing_path, 'w') as f:
+        json.dump(preprocessing, f)
+
+    res = ezkl.gen_settings(
+        model_path, output_path, preprocessing=preprocessing_path)
+    assert res == True
+
+    res = ezkl.calibrate_settings(
+        data_path, model_path, output_path, "resources")
+    assert res == True
+
+    with open(output_path, 'r') as f:
+        settings = json.load(f)
+    assert settings['preprocessing'][0]['steps'] == preprocessing[0]['steps']
+
+
 def test_model_compile():
     """
    Test for model compilation/serialization
@@ -276,6 +435,60 @@ def test_forward():
     assert data["processed_outputs"]["poseidon_hash"] == res["processed_outputs"]["poseidon_hash"]
 
 
+def test_sign_data():
+    """
+    Test for signing data for signed data attestation
+    """
+    data_path = os.path.join(
+        examples_path,
+        'onnx',
+        '1l_average',
+        'input.json'
+    )
+    model_path = os.path.join(
+        folder_path,
+        'model.compiled'
+    )
+    signed_data_path = os.path.join(
+        folder_path,
+        'signed_input.json'
+    )
+    # anvil's first default account
+    private_key = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+
+    # the data is signed for a verifier on anvil's chain
+    addr_verifier = "0x5fbdb2315678afecb367f032d93f642f64180aa3"
+    res = ezkl.sign_data(
+        data_path,
+        model_path,
+        private_key,
+        addr_verifier,
+        31337,
+        signed_data_path,
+    )
+
+    with open(signed_data_path, "r") as f:
+        data = json.load(f)
+
+    # the signed data is returned as it is saved
+    assert res["input_data"] == data["input_data"]
+    assert res["output_data"] == data["output_data"]
+
+    assert data["input_data"]["signer"] == "f39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+    assert len(data["input_data"]["signature"]) == 130
+    assert data["input_data"]["domain"] == {
+        "chain_id": 31337,
+        "verifier": addr_verifier[2:],
+        "nonce": 0,
+    }
+
+    # the signature is checked when loading the signed data
+    witness_path = os.path.join(folder_path, 'signed_witness.json')
+    res = ezkl.gen_witness(signed_data_path, model_path, witness_path)
+    with open(os.path.join(folder_path, 'witness.json'), "r") as f:
+        assert json.load(f)["inputs"] == res["inputs"]
+
+
 def test_get_srs():
     """
     Test for get_srs
@@ -466,6 +679,42 @@ def test_create_evm_verifier():
     assert os.path.isfile(sol_code_path)
 
 
+def test_create_evm_verifier_separate_vk():
+    """
+    Create EVM verifier and a separate verifying key contract with solidity code
+    In order to run this test you will need to install solc in your environment
+    """
+    vk_path = os.path.join(folder_path, 'test_evm.vk')
+    settings_path = os.path.join(folder_path, 'settings.json')
+    sol_code_path = os.path.join(folder_path, 'test_separate.sol')
+    vk_code_path = os.path.join(folder_path, 'test_vk.sol')
+    abi_path = os.path.join(folder_path, 'test_separate.abi')
+    vk_abi_path = os.path.join(folder_path, 'test_vk.abi')
+
+    res = ezkl.create_evm_verifier(
+        vk_path,
+        srs_path,
+        settings_path,
+        sol_code_path,
+        abi_path,
+        render_vk_separately=True
+    )
+
+    assert res == True
+    assert os.path.isfile(sol_code_path)
+
+    res = ezkl.create_evm_vk(
+        vk_path,
+        srs_path,
+        settings_path,
+        vk_code_path,
+        vk_abi_path
+    )
+
+    assert res == True
+    assert os.path.isfile(vk_code_path)
+
+
 def test_deploy_evm():
     """
     Test deployment of the verifier smart contract
@@ -519,6 +768,32 @@ def test_deploy_evm_with_private_key():
         )
 
 
+def test_deploy_evm_create2():
+    """
+    Test deployment of the verifier smart contract through a CREATE2 factory,
+    at the address computed offline
+    In order to run this you will need to install solc in your environment
+    """
+    addr_path = os.path.join(folder_path, 'address_create2.json')
+    sol_code_path = os.path.join(folder_path, 'test.sol')
+
+    expected_addr = ezkl.create2_address(sol_code_path)
+
+    # deploying the same verifier again reuses the already deployed contract
+    for _ in range(2):
+        res = ezkl.deploy_evm(
+            addr_path,
+            sol_code_path,
+            rpc_url=anvil_url,
+            create2=True,
+        )
+        assert res == True
+
+        with open(addr_path, 'r') as file:
+            addr = file.read().rstrip()
+        assert addr == expected_addr
+
+
 def test_verify_evm():
     """
     Verifies an evm proof
@@ -546,6 +821,106 @@ def test_verify_evm():
     assert res == True
 
 
+@pytest.mark.skipif(shutil.which("anvil") is None, reason="requires an anvil install")
+def test_encode_evm_calldata():
+    """
+    Encodes the calldata to verify an evm proof and executes it against the deployed verifier
+    """
+    proof_path = os.path.join(folder_path, 'test_evm.pf')
+    addr_path = os.path.join(folder_path, 'address.json')
+    calldata_path = os.path.join(folder_path, 'calldata.hex')
+
+    with open(addr_path, 'r') as file:
+        addr = file.read().rstrip()
+
+    res = ezkl.encode_evm_calldata(proof_path, calldata_path=calldata_path)
+
+    assert res.startswith("0x")
+    with open(calldata_path, 'r') as file:
+        assert file.read() == res
+
+    request = json.dumps({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_call",
+        "params": [{"to": addr, "data": res}, "latest"],
+    }).encode()
+    response = urllib.request.urlopen(
+        urllib.request.Request(
+            anvil_url,
+            data=request,
+            headers={"Content-Type": "application/json"},
+        )
+    )
+    assert int(json.loads(response.read())["result"], 16) == 1
+
+
+def test_decode_evm_outputs():
+    """
+    Decodes the public outputs of an evm proof with the generated output decoder library
+    In order to run this you will need to install solc in your environment
+    """
+    data_path = os.path.join(
+        examples_path,
+        'onnx',
+        '1l_average',
+        'input.json'
+    )
+    model_path = os.path.join(
+        examples_path,
+        'onnx',
+        '1l_average',
+        'network.onnx'
+    )
+    # the outputs have to be public to be decoded from the instances
+    settings_path = os.path.join(folder_path, 'decoder_settings.json')
+    compiled_model_path = os.path.join(folder_path, 'decoder_model.compiled')
+    pk_path = os.path.join(folder_path, 'decoder.pk')
+    vk_path = os.path.join(folder_path, 'decoder.vk')
+    witness_path = os.path.join(folder_path, 'decoder_witness.json')
+    proof_path = os.path.join(folder_path, 'decoder.pf')
+    sol_code_path = os.path.join(folder_path, 'output_decoder.sol')
+
+    res = ezkl.gen_settings(model_path, settings_path)
+    assert res == True
+    res = ezkl.calibrate_settings(
+        data_path, model_path, settings_path, "resources")
+    assert res == True
+    res = ezkl.compile_circuit(model_path, compiled_model_path, settings_path)
+    assert res == True
+    res = ezkl.setup(compiled_model_path, vk_path, pk_path, srs_path)
+    assert res == True
+    witness = ezkl.gen_witness(data_path, compiled_model_path, witness_path)
+    res = ezkl.prove(
+        witness_path,
+        compiled_model_path,
+        pk_path,
+        proof_path,
+        srs_path,
+        "single",
+    )
+    assert os.path.isfile(proof_path)
+
+    res = ezkl.create_evm_output_decoder(settings_path, sol_code_path)
+    assert res == True
+    assert os.path.isfile(sol_code_path)
+
+    outputs = ezkl.decode_evm_outputs(
+        proof_path,
+        sol_code_path,
+        rpc_url=anvil_url,
+    )
+
+    with open(settings_path, 'r') as f:
+        scales = json.load(f)["model_output_scales"]
+
+    assert len(outputs) == len(witness["outputs"])
+    for output, witness_output, scale in zip(outputs, witness["outputs"], scales):
+        assert len(output) == len(witness_output)
+        for value, felt in zip(output, witness_output):
+            assert value / 2**scale == ezkl.vecu64_to_float(felt, scale)
+
+
 def test_aggregate_and_verify_aggr():
     data_path = os.path.join(
         examples_path,
@@ -758,6 +1133,38 @@ def test_evm_aggregate_and_verify_aggr():
     assert res == True
     assert os.path.isfile(sol_code_path)
 
+    split_sol_code_path = os.path.join(folder_path, 'aggr_evm_1l_relu_split.sol')
+    split_abi_path = os.path.join(folder_path, 'aggr_evm_1l_relu_split.abi')
+    layout_path = os.path.join(folder_path, 'aggr_evm_1l_relu_layout.json')
+
+    res = ezkl.create_evm_verifier_aggr(
+        aggregate_vk_path,
+        params_k20_path,
+        split_sol_code_path,
+        split_abi_path,
+        [settings_path],
+        split_proofs=True,
+        layout_path=layout_path,
+    )
+
+    assert res == True
+    assert os.path.isfile(split_sol_code_path)
+
+    with open(layout_path, 'r') as f:
+        layout = json.load(f)
+    # the accumulator limbs come first, followed by the single snark's instances
+    assert layout['accumulator']['offset'] == 0
+    assert layout['accumulator']['len'] == 16
+    assert len(layout['segments']) == 1
+    assert layout['segments'][0]['offset'] == 16
+    assert layout['num_instances'] == 16 + layout['segments'][0]['len']
+    assert [r['name'] for r in layout['outputs']] == ['output_0']
+
+    with open(split_sol_code_path, 'r') as f:
+        split_sol = f.read()
+    assert 'contract Halo2Verifier is Halo2VerifierBase' in split_sol
+    assert 'function verifySplitProof' in split_sol
+
     addr_path = os.path.join(folder_path, 'address_aggr.json')
 
     res = ezkl.deploy_evm(
@@ -190,7 +190,7 @@ mod wasm32 {
         .map_err(|_| "failed")
         .unwrap();
 
-        let sk = serde_json::to_vec(&var.sk).unwrap();
+        let sk = serde_json::to_vec(&var.sk.unwrap()).unwrap();
 
         let decrypted_message =
             elgamalDecrypt(wasm_bindgen::Clamped(cipher), wasm_bindgen::Clamped(sk))
@@ -260,6 +260,7 @@ mod wasm32 {
         .unwrap();
 
         assert!(pk.len() > 0);
+        assert!(pk.starts_with(&pfsys::container::MAGIC));
     }
 
     #[wasm_bindgen_test]
@@ -272,6 +273,8 @@ mod wasm32 {
         .unwrap();
 
         assert!(vk.len() > 0);
+        // keys carry the same container header as the cli's
+        assert!(vk.starts_with(&pfsys::container::MAGIC));
     }
 
     #[wasm_bindgen_test]